@@ -6,6 +6,7 @@ use std::{
 };
 
 use triomphe::Arc;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Eq)]
 /// A copy on write smart pointer shared value without deep clone .
@@ -58,7 +59,62 @@ impl Substr {
 
     Substr { str: self.str.clone(), rg: start..end }
   }
+
+  /// Returns the byte range of the first case-insensitive match of `needle`,
+  /// or `None` if it's not found.
+  ///
+  /// The comparison folds the case of both strings by their lowercase, and
+  /// folds `ß` to `ss`, so the matched range may have a different length from
+  /// the `needle`. A match always starts and ends at a char boundary.
+  pub fn find_ci(&self, needle: &str) -> Option<Range<usize>> {
+    let needle: Vec<char> = needle.chars().flat_map(case_fold).collect();
+    if needle.is_empty() {
+      return Some(0..0);
+    }
+
+    // Every folded char with the byte range of the char it folded from.
+    let folded: Vec<(char, Range<usize>)> = self
+      .char_indices()
+      .flat_map(|(idx, c)| {
+        let rg = idx..idx + c.len_utf8();
+        case_fold(c).map(move |f| (f, rg.clone()))
+      })
+      .collect();
+
+    folded
+      .windows(needle.len())
+      .enumerate()
+      .find(|(i, w)| {
+        let start_at_boundary = *i == 0 || folded[i - 1].1 != w[0].1;
+        let end = i + needle.len();
+        let end_at_boundary = end == folded.len() || folded[end].1 != w[needle.len() - 1].1;
+        start_at_boundary && end_at_boundary && w.iter().map(|(c, _)| c).eq(needle.iter())
+      })
+      .map(|(_, w)| w[0].1.start..w[needle.len() - 1].1.end)
+  }
+
+  /// Splits the string into the extended grapheme clusters, so a combining
+  /// sequence, such as an emoji with a skin tone modifier, is not split. Every
+  /// grapheme shares the same buffer with this string.
+  pub fn split_graphemes(&self) -> impl Iterator<Item = Substr> + '_ {
+    self
+      .grapheme_indices(true)
+      .map(|(idx, g)| self.substr(idx..idx + g.len()))
+  }
+}
+
+fn case_fold(c: char) -> impl Iterator<Item = char> {
+  // `ß` is lowercase already, its full case folding is `ss`.
+  let (lower, ss) = match c {
+    'ß' | 'ẞ' => (None, Some(['s', 's'])),
+    _ => (Some(c.to_lowercase()), None),
+  };
+  lower
+    .into_iter()
+    .flatten()
+    .chain(ss.into_iter().flatten())
 }
+
 impl std::ops::Deref for Substr {
   type Target = str;
 
@@ -186,6 +242,10 @@ impl<B: ?Sized + ToOwned> Borrow<B> for CowArc<B> {
 
 impl<B: ?Sized + ToOwned + PartialEq> PartialEq for CowArc<B> {
   fn eq(&self, other: &Self) -> bool {
+    // The shared values, such as the interned strings, are equal by pointer.
+    if self.ptr_eq(other) {
+      return true;
+    }
     let a: &B = self.borrow();
     let b = other.borrow();
     a == b
@@ -248,3 +308,28 @@ fn smoke() {
   assert_eq!(&*cow, &2);
   assert_eq!(&*c_cow, &1);
 }
+
+#[test]
+fn find_case_insensitive() {
+  let s = Substr::from("ÄBC");
+  assert_eq!(s.find_ci("äb"), Some(0..3));
+  assert_eq!(s.find_ci("bc"), Some(2..4));
+  assert_eq!(s.find_ci("cd"), None);
+
+  let s = Substr::from("Straße");
+  assert_eq!(s.find_ci("STRASSE"), Some(0..7));
+  assert_eq!(s.find_ci("ss"), Some(4..6));
+  // Can't match a part of the folded `ß`.
+  assert_eq!(s.find_ci("se"), None);
+}
+
+#[test]
+fn split_graphemes() {
+  // A thumbs up with a skin tone modifier.
+  let s = Substr::from("a\u{1F44D}\u{1F3FD}e\u{301}");
+  let graphemes: Vec<_> = s.split_graphemes().collect();
+  assert_eq!(graphemes.len(), 3);
+  assert_eq!(&*graphemes[0], "a");
+  assert_eq!(&*graphemes[1], "\u{1F44D}\u{1F3FD}");
+  assert_eq!(&*graphemes[2], "e\u{301}");
+}
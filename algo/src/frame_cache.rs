@@ -13,6 +13,7 @@ use std::{
   iter::FusedIterator,
   marker::PhantomData,
   mem::MaybeUninit,
+  num::NonZeroUsize,
   ptr::{self, NonNull},
 };
 
@@ -100,6 +101,11 @@ pub struct FrameCache<K, V> {
   // head and tail are sigil nodes to facilitate inserting entries
   head: *mut LruEntry<K, V>,
   tail: *mut LruEntry<K, V>,
+  // The entries not hit in this frame are linked after the `tail`, this is the
+  // last of them, the least recently used entry. Null if there is no such
+  // entry.
+  stale_tail: *mut LruEntry<K, V>,
+  cap: Option<NonZeroUsize>,
 }
 
 impl<K: Hash + Eq, V> FrameCache<K, V> {
@@ -109,6 +115,8 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
       map: HashMap::default(),
       head: Box::into_raw(Box::new(LruEntry::new_sigil())),
       tail: Box::into_raw(Box::new(LruEntry::new_sigil())),
+      stale_tail: ptr::null_mut(),
+      cap: None,
     };
 
     unsafe {
@@ -118,6 +126,35 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
 
     cache
   }
+
+  /// Creates a new Frame Cache that holds at most `cap` entries. Besides the
+  /// entries not hit by the last frame, the least recently used entry is
+  /// evicted when a new entry is inserted into a full cache, so it's suitable
+  /// for the caches that live across frames.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::num::NonZeroUsize;
+  ///
+  /// use ribir_algo::FrameCache;
+  ///
+  /// let mut cache = FrameCache::with_capacity(NonZeroUsize::new(2).unwrap());
+  ///
+  /// assert_eq!(None, cache.push(1, "a"));
+  /// assert_eq!(None, cache.push(2, "b"));
+  /// assert_eq!(Some((1, "a")), cache.push(3, "c"));
+  /// assert_eq!(cache.len(), 2);
+  /// ```
+  pub fn with_capacity(cap: NonZeroUsize) -> FrameCache<K, V> {
+    let mut cache = Self::new();
+    cache.cap = Some(cap);
+    cache
+  }
+
+  /// The maximum number of entries the cache can hold, `None` if it's
+  /// unbounded.
+  pub fn cap(&self) -> Option<NonZeroUsize> { self.cap }
 }
 
 impl<K: Hash + Eq, V> FrameCache<K, V> {
@@ -142,8 +179,9 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
   pub fn put(&mut self, k: K, v: V) -> Option<V> { self.push(k, v).map(|(_, v)| v) }
 
   /// Pushes a key-value pair into the cache. If an entry with key `k` already
-  /// exists in the cache, then it returns the old entry's key-value pair.
-  /// Otherwise, returns `None`.
+  /// exists in the cache, then it returns the old entry's key-value pair. If
+  /// the cache is full, then it evicts and returns the least recently used
+  /// entry. Otherwise, returns `None`.
   ///
   /// # Example
   ///
@@ -186,6 +224,7 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
         Some((k, v))
       }
       None => {
+        let evicted = self.evict_if_full();
         let node = self.create_node(k, v);
         let node_ptr: *mut LruEntry<K, V> = node.as_ptr();
 
@@ -194,7 +233,7 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
         let keyref = unsafe { (*node_ptr).key.as_ptr() };
         self.map.insert(KeyRef { k: keyref }, node);
 
-        None
+        evicted
       }
     }
   }
@@ -311,6 +350,7 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
       unsafe { &*(*node_ptr).val.as_ptr() }
     } else {
       let v = f();
+      self.evict_if_full();
       let node = self.create_node(k, v);
       let node_ptr: *mut LruEntry<K, V> = node.as_ptr();
 
@@ -356,6 +396,7 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
       unsafe { &mut *(*node_ptr).val.as_mut_ptr() }
     } else {
       let v = f();
+      self.evict_if_full();
       let node = self.create_node(k, v);
       let node_ptr: *mut LruEntry<K, V> = node.as_ptr();
 
@@ -704,6 +745,10 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
   /// An iterator visiting all entries in most-recently used order. The iterator
   /// element type is `(&K, &V)`.
   ///
+  /// The entries not hit in the current frame are visited too, after the ones
+  /// hit in it. Visiting an entry isn't a hit, so it doesn't keep the entry
+  /// from being removed by the [`end_frame`](FrameCache::end_frame).
+  ///
   /// # Examples
   ///
   /// ```
@@ -719,17 +764,16 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
   /// }
   /// ```
   pub fn iter(&self) -> Iter<'_, K, V> {
-    Iter {
-      len: self.len(),
-      ptr: unsafe { (*self.head).next },
-      end: unsafe { (*self.tail).prev },
-      phantom: PhantomData,
-    }
+    let (ptr, end) = self.iter_range();
+    Iter { len: self.len(), ptr, end, sigil: self.tail, phantom: PhantomData }
   }
 
   /// An iterator visiting all entries in most-recently-used order, giving a
   /// mutable reference on V.  The iterator element type is `(&K, &mut V)`.
   ///
+  /// Like [`iter`](FrameCache::iter), it visits the entries not hit in the
+  /// current frame too, and the visit isn't a hit.
+  ///
   /// # Examples
   ///
   /// ```
@@ -754,11 +798,26 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
   /// }
   /// ```
   pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
-    IterMut {
-      len: self.len(),
-      ptr: unsafe { (*self.head).next },
-      end: unsafe { (*self.tail).prev },
-      phantom: PhantomData,
+    let (ptr, end) = self.iter_range();
+    IterMut { len: self.len(), ptr, end, sigil: self.tail, phantom: PhantomData }
+  }
+
+  /// The first and the last entries of the list. The `tail` sigil is the frame
+  /// mark, the entries not hit in the current frame are linked after it.
+  fn iter_range(&self) -> (*mut LruEntry<K, V>, *mut LruEntry<K, V>) {
+    unsafe {
+      let mut end = self.tail;
+      while !(*end).next.is_null() {
+        end = (*end).next;
+      }
+      if end == self.tail {
+        end = (*end).prev;
+      }
+      let mut ptr = (*self.head).next;
+      if ptr == self.tail {
+        ptr = (*ptr).next;
+      }
+      (ptr, end)
     }
   }
 
@@ -772,6 +831,29 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
     unsafe { NonNull::new_unchecked(Box::into_raw(Box::new(LruEntry::new(k, v)))) }
   }
 
+  // Removes the least recently used entry if the cache is full.
+  fn evict_if_full(&mut self) -> Option<(K, V)> {
+    let cap = self.cap?;
+    if self.len() < cap.get() {
+      return None;
+    }
+
+    let node = if !self.stale_tail.is_null() {
+      self.stale_tail
+    } else {
+      let prev = unsafe { (*self.tail).prev };
+      if prev == self.head {
+        return None;
+      }
+      prev
+    };
+    let key = KeyRef { k: unsafe { &(*(*node).key.as_ptr()) } };
+    self.map.remove(&key).unwrap();
+    self.detach(node);
+    let LruEntry { key, val, .. } = unsafe { *Box::from_raw(node) };
+    unsafe { Some((key.assume_init(), val.assume_init())) }
+  }
+
   fn remove_last(&mut self) -> Option<Box<LruEntry<K, V>>> {
     let prev;
     unsafe { prev = (*self.tail).prev }
@@ -788,6 +870,10 @@ impl<K: Hash + Eq, V> FrameCache<K, V> {
 
   fn detach(&mut self, node: *mut LruEntry<K, V>) {
     unsafe {
+      if node == self.stale_tail {
+        let prev = (*node).prev;
+        self.stale_tail = if prev == self.tail { ptr::null_mut() } else { prev };
+      }
       (*(*node).prev).next = (*node).next;
       // node has at least one predecessor (head), but not necessarily a successor
       if !(*node).next.is_null() {
@@ -839,6 +925,9 @@ impl<'a, K: Hash + Eq, V> FrameDrain<'a, K, V> {
     let tail = cache.tail;
 
     unsafe {
+      // The entries hit in this frame will be the stale entries of the next frame.
+      let last = (*tail).prev;
+      cache.stale_tail = if last == cache.head { ptr::null_mut() } else { last };
       cache.break_at(tail);
       let cursor = (*tail).next;
       if !cursor.is_null() {
@@ -943,6 +1032,7 @@ pub struct Iter<'a, K: 'a, V: 'a> {
 
   ptr: *const LruEntry<K, V>,
   end: *const LruEntry<K, V>,
+  sigil: *const LruEntry<K, V>,
 
   phantom: PhantomData<&'a K>,
 }
@@ -960,6 +1050,9 @@ impl<'a, K, V> Iterator for Iter<'a, K, V> {
 
     self.len -= 1;
     self.ptr = unsafe { (*self.ptr).next };
+    if self.ptr == self.sigil {
+      self.ptr = unsafe { (*self.ptr).next };
+    }
 
     Some((key, val))
   }
@@ -980,6 +1073,9 @@ impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
 
     self.len -= 1;
     self.end = unsafe { (*self.end).prev };
+    if self.end == self.sigil {
+      self.end = unsafe { (*self.end).prev };
+    }
 
     Some((key, val))
   }
@@ -990,7 +1086,7 @@ impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
 
 impl<'a, K, V> Clone for Iter<'a, K, V> {
   fn clone(&self) -> Iter<'a, K, V> {
-    Iter { len: self.len, ptr: self.ptr, end: self.end, phantom: PhantomData }
+    Iter { len: self.len, ptr: self.ptr, end: self.end, sigil: self.sigil, phantom: PhantomData }
   }
 }
 
@@ -1011,6 +1107,7 @@ pub struct IterMut<'a, K: 'a, V: 'a> {
 
   ptr: *mut LruEntry<K, V>,
   end: *mut LruEntry<K, V>,
+  sigil: *mut LruEntry<K, V>,
 
   phantom: PhantomData<&'a K>,
 }
@@ -1028,6 +1125,9 @@ impl<'a, K, V> Iterator for IterMut<'a, K, V> {
 
     self.len -= 1;
     self.ptr = unsafe { (*self.ptr).next };
+    if self.ptr == self.sigil {
+      self.ptr = unsafe { (*self.ptr).next };
+    }
 
     Some((key, val))
   }
@@ -1048,6 +1148,9 @@ impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
 
     self.len -= 1;
     self.end = unsafe { (*self.end).prev };
+    if self.end == self.sigil {
+      self.end = unsafe { (*self.end).prev };
+    }
 
     Some((key, val))
   }
@@ -1116,7 +1219,10 @@ impl<K: Hash + Eq, V> Default for FrameCache<K, V> {
 #[cfg(test)]
 mod tests {
   use core::fmt::Debug;
-  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+  };
 
   use scoped_threadpool::Pool;
 
@@ -1272,6 +1378,38 @@ mod tests {
     assert_opt_eq(cache.get(&"tomato"), "red");
   }
 
+  #[test]
+  fn test_capacity_evicts_lru() {
+    let mut cache = FrameCache::with_capacity(NonZeroUsize::new(2).unwrap());
+
+    assert_eq!(cache.push("apple", "red"), None);
+    assert_eq!(cache.push("banana", "yellow"), None);
+    cache.get(&"apple");
+    assert_eq!(cache.push("pear", "green"), Some(("banana", "yellow")));
+
+    assert_eq!(cache.len(), 2);
+    assert!(!cache.contains(&"banana"));
+    assert_opt_eq(cache.get(&"apple"), "red");
+    assert_opt_eq(cache.get(&"pear"), "green");
+  }
+
+  #[test]
+  fn test_capacity_evicts_stale_first() {
+    let mut cache = FrameCache::with_capacity(NonZeroUsize::new(3).unwrap());
+
+    cache.put("apple", "red");
+    cache.put("banana", "yellow");
+    cache.put("pear", "green");
+    cache.end_frame("");
+
+    // Only "pear" is hit in this frame, the least recently used is "apple".
+    cache.get(&"pear");
+    assert_eq!(cache.push("lemon", "yellow"), Some(("apple", "red")));
+    assert_eq!(cache.push("tomato", "red"), Some(("banana", "yellow")));
+    assert_eq!(cache.push("grape", "purple"), Some(("pear", "green")));
+    assert_eq!(cache.len(), 3);
+  }
+
   #[test]
   fn test_peek() {
     let mut cache = FrameCache::new();
@@ -1764,6 +1902,45 @@ mod tests {
     assert_eq!(cache.pop_lru(), None);
   }
 
+  #[test]
+  fn iter_across_frame_mark() {
+    let mut cache = FrameCache::new();
+    cache.put(1, 1);
+    cache.put(2, 2);
+    cache.end_frame("");
+    cache.put(3, 3);
+
+    let keys: Vec<_> = cache.iter().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![3, 2, 1]);
+    let keys: Vec<_> = cache.iter().rev().map(|(k, _)| *k).collect();
+    assert_eq!(keys, vec![1, 2, 3]);
+
+    cache.iter_mut().for_each(|(_, v)| *v *= 10);
+    assert_eq!(cache.peek(&1), Some(&10));
+    assert_eq!(cache.peek(&3), Some(&30));
+  }
+
+  #[test]
+  fn iter_is_not_hit() {
+    let frame = |iter: bool| {
+      let mut cache = FrameCache::new();
+      cache.put(1, 1);
+      cache.put(2, 2);
+      cache.end_frame("").for_each(drop);
+      assert_eq!(cache.get(&1), Some(&1));
+      if iter {
+        assert_eq!(cache.iter_mut().count(), 2);
+        assert_eq!(cache.iter().count(), 2);
+      }
+      let removed: Vec<_> = cache.end_frame("").collect();
+      let kept: Vec<_> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+      (removed, kept)
+    };
+
+    assert_eq!(frame(false), (vec![2], vec![(1, 1)]));
+    assert_eq!(frame(true), frame(false));
+  }
+
   #[test]
   fn end_frame_remove_none() {
     let mut cache = FrameCache::new();
@@ -0,0 +1,129 @@
+use std::{
+  collections::HashSet,
+  sync::{LazyLock, Mutex},
+};
+
+use triomphe::Arc;
+
+use crate::{CowArc, Substr};
+
+/// The minimum size of the interner to purge the unused strings.
+const MIN_PURGE_SIZE: usize = 64;
+
+/// A string interner shares one allocation among the equal strings, so the
+/// repeated strings, such as the class names and the font family names, are
+/// allocated once and compared by the pointer first.
+///
+/// The interner is thread-safe. It holds a reference of every string it
+/// interned, and releases the strings that are no longer referenced outside
+/// when it grows, so it doesn't keep the unused strings forever.
+///
+/// # Example
+///
+/// ```
+/// use ribir_algo::Interner;
+///
+/// let interner = Interner::default();
+/// let a = interner.intern("primary");
+/// let b = interner.intern(&String::from("primary"));
+/// assert!(a.ptr_eq(&b));
+/// ```
+pub struct Interner {
+  inner: Mutex<InternerInner>,
+}
+
+struct InternerInner {
+  strs: HashSet<CowArc<str>, ahash::RandomState>,
+  purge_at: usize,
+}
+
+impl Interner {
+  pub fn new() -> Self {
+    let inner = InternerInner { strs: HashSet::default(), purge_at: MIN_PURGE_SIZE };
+    Self { inner: Mutex::new(inner) }
+  }
+
+  /// Return the shared string equal to `s`, the string is allocated only if
+  /// it's not interned yet.
+  pub fn intern(&self, s: &str) -> CowArc<str> {
+    let mut inner = self.inner.lock().unwrap();
+    if let Some(str) = inner.strs.get(s) {
+      return str.clone();
+    }
+
+    if inner.strs.len() >= inner.purge_at {
+      inner.purge();
+      inner.purge_at = (inner.strs.len() * 2).max(MIN_PURGE_SIZE);
+    }
+    let str: CowArc<str> = CowArc::owned(s.to_owned());
+    inner.strs.insert(str.clone());
+    str
+  }
+
+  /// Return the shared string equal to `s` as a [`Substr`].
+  pub fn intern_substr(&self, s: &str) -> Substr { self.intern(s).substr(..) }
+
+  /// Release the interned strings that are no longer referenced outside.
+  pub fn purge(&self) { self.inner.lock().unwrap().purge(); }
+
+  /// The count of the strings in the interner.
+  pub fn len(&self) -> usize { self.inner.lock().unwrap().strs.len() }
+
+  pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+impl InternerInner {
+  fn purge(&mut self) {
+    self.strs.retain(|s| match s {
+      CowArc::Borrowed(_) => true,
+      CowArc::Owned(arc) => !Arc::is_unique(arc),
+    });
+  }
+}
+
+impl Default for Interner {
+  fn default() -> Self { Self::new() }
+}
+
+static INTERNER: LazyLock<Interner> = LazyLock::new(Interner::new);
+
+/// Intern the string by the global [`Interner`].
+pub fn intern(s: &str) -> CowArc<str> { INTERNER.intern(s) }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn share_allocation() {
+    let interner = Interner::new();
+    let a = interner.intern("primary");
+    let b = interner.intern(&["pri", "mary"].concat());
+    assert!(a.ptr_eq(&b));
+    assert_eq!(a.as_ptr(), b.as_ptr());
+    assert_eq!(interner.len(), 1);
+
+    assert!(!a.ptr_eq(&interner.intern("secondary")));
+  }
+
+  #[test]
+  fn purge_unused() {
+    let interner = Interner::new();
+    let used = interner.intern("used");
+    interner.intern("unused");
+    assert_eq!(interner.len(), 2);
+
+    interner.purge();
+    assert_eq!(interner.len(), 1);
+    assert!(used.ptr_eq(&interner.intern("used")));
+  }
+
+  #[test]
+  fn share_across_threads() {
+    let a = intern("thread");
+    let b = std::thread::spawn(|| intern("thread"))
+      .join()
+      .unwrap();
+    assert!(a.ptr_eq(&b));
+  }
+}
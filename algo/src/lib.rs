@@ -4,6 +4,8 @@ mod cow_rc;
 mod frame_cache;
 pub use cow_rc::{CowArc, Substr};
 pub use frame_cache::*;
+mod interner;
+pub use interner::*;
 mod resource;
 pub use resource::*;
 mod sc;
@@ -2,6 +2,7 @@ use std::{
   any::Any,
   hash::{Hash, Hasher},
   ops::Deref,
+  sync::{Arc, Weak},
 };
 
 use serde::{Deserialize, Serialize};
@@ -13,26 +14,51 @@ use serde::{Deserialize, Serialize};
 /// Compare two `Resource` just compare if it come form same resource not
 /// compare its content.
 #[derive(Debug, Deserialize)]
-pub struct Resource<T: ?Sized>(triomphe::Arc<T>);
+pub struct Resource<T: ?Sized>(Arc<T>);
+
+/// A weak handle of a [`Resource`], it doesn't keep the resource alive. It's
+/// useful to track the resources without preventing them from being released,
+/// such as a cache that evicts the resources no longer used.
+#[derive(Debug)]
+pub struct WeakResource<T: ?Sized>(Weak<T>);
 
 impl<T: Sized> Resource<T> {
   #[inline]
-  pub fn new(v: T) -> Self { Resource(triomphe::Arc::new(v)) }
+  pub fn new(v: T) -> Self { Resource(Arc::new(v)) }
 
   #[inline]
   pub fn into_any(self) -> Resource<dyn Any>
   where
     T: Sized + Any,
   {
-    let ptr = triomphe::Arc::into_raw(self.0) as *const dyn Any;
-    let ptr: triomphe::Arc<dyn Any> = unsafe { triomphe::Arc::from_raw(ptr) };
-    Resource(ptr)
+    Resource(self.0)
   }
 }
 
 impl<T: ?Sized> Resource<T> {
   #[inline]
-  pub fn as_ptr(this: &Self) -> *const () { triomphe::Arc::as_ptr(&this.0) as *const () }
+  pub fn as_ptr(this: &Self) -> *const () { Arc::as_ptr(&this.0) as *const () }
+
+  /// Create a weak handle of the resource.
+  #[inline]
+  pub fn downgrade(this: &Self) -> WeakResource<T> { WeakResource(Arc::downgrade(&this.0)) }
+
+  /// The count of the live `Resource` of the same resource, the weak handles
+  /// are not counted.
+  #[inline]
+  pub fn ref_count(this: &Self) -> usize { Arc::strong_count(&this.0) }
+}
+
+impl<T: ?Sized> WeakResource<T> {
+  /// Return the resource if it's still alive, or `None` if all its `Resource`
+  /// are dropped.
+  #[inline]
+  pub fn upgrade(&self) -> Option<Resource<T>> { self.0.upgrade().map(Resource) }
+}
+
+impl<T: ?Sized> Clone for WeakResource<T> {
+  #[inline]
+  fn clone(&self) -> Self { Self(self.0.clone()) }
 }
 
 impl<T> From<T> for Resource<T> {
@@ -53,14 +79,14 @@ impl<T: ?Sized> Deref for Resource<T> {
 
 impl<T: ?Sized> PartialEq for Resource<T> {
   #[inline]
-  fn eq(&self, other: &Self) -> bool { triomphe::Arc::ptr_eq(&self.0, &other.0) }
+  fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.0, &other.0) }
 }
 
 impl<T: ?Sized> Eq for Resource<T> {}
 
 impl<T: ?Sized> Hash for Resource<T> {
   #[inline]
-  fn hash<H: Hasher>(&self, state: &mut H) { triomphe::Arc::as_ptr(&self.0).hash(state); }
+  fn hash<H: Hasher>(&self, state: &mut H) { Arc::as_ptr(&self.0).hash(state); }
 }
 
 impl<T: Serialize> Serialize for Resource<T> {
@@ -101,4 +127,22 @@ mod tests {
     let b = Resource::new(1);
     assert_ne!(b, a);
   }
+
+  #[test]
+  fn upgrade_after_dropped() {
+    let a = Resource::new(1);
+    let a2 = a.clone();
+    assert_eq!(Resource::ref_count(&a), 2);
+
+    let weak = Resource::downgrade(&a);
+    assert_eq!(Resource::ref_count(&a), 2);
+    assert_eq!(weak.upgrade(), Some(a.clone()));
+
+    drop(a);
+    assert_eq!(Resource::ref_count(&a2), 1);
+    assert!(weak.upgrade().is_some());
+
+    drop(a2);
+    assert!(weak.upgrade().is_none());
+  }
 }
@@ -0,0 +1,697 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  str::FromStr,
+};
+
+use anyhow::{Context, Result, bail};
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
+use ribir_painter::PixelImage;
+
+use crate::{
+  CliCommand,
+  program_check::{Program, check_all_programs},
+};
+
+pub fn bundle() -> Box<dyn CliCommand> { Box::new(BundleCmd {}) }
+
+struct BundleCmd {}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+  Deb,
+  AppImage,
+  /// The macOS application bundle.
+  App,
+  /// The macOS disk image that contains the application bundle.
+  Dmg,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "bundle")]
+/// bundle an application as a Linux package or a macOS application
+struct Bundle {
+  /// Package of which to bundle
+  #[arg(short, long)]
+  package: String,
+
+  /// The format of the package
+  #[arg(short, long, value_enum)]
+  format: Format,
+
+  /// Direction path to output, default to the `bundle` directory in the target
+  /// directory
+  #[arg(short, long)]
+  out_dir: Option<PathBuf>,
+
+  /// Build release, default build to debug
+  #[arg(short, long)]
+  release: bool,
+
+  /// The target triple to build for, default to the host
+  #[arg(long)]
+  target: Option<String>,
+}
+
+/// The metadata of the application to bundle, it's read from the
+/// `[package]` and `[package.metadata.bundle]` of its `Cargo.toml`.
+#[derive(Debug, Clone, PartialEq)]
+struct BundleMeta {
+  /// The name of the binary.
+  bin: String,
+  /// The reverse domain name of the application, such as `org.ribir.counter`.
+  identifier: String,
+  version: String,
+  /// The name shown in the application menu, default to the binary name.
+  name: String,
+  description: String,
+  /// The categories of the desktop entry.
+  categories: Vec<String>,
+  /// The image to generate the icons of the application.
+  icon: Option<PathBuf>,
+  /// The maintainer of the Debian package, it's read from the `maintainer` of
+  /// the metadata or the first of the `package.authors`.
+  maintainer: Option<String>,
+  /// The identity to sign the macOS application bundle, it's read from the
+  /// `signing_identity` of the metadata or the `RIBIR_SIGNING_IDENTITY`
+  /// environment variable.
+  signing_identity: Option<String>,
+}
+
+impl BundleMeta {
+  /// Read the metadata from the `manifest` of the package, the fields
+  /// inherited from the workspace are read from the `workspace` manifest.
+  fn from_manifest(manifest: &Path, workspace: &Path) -> Result<Self> {
+    let toml = read_toml(manifest)?;
+    let package = toml.get("package").and_then(|p| p.as_table());
+    let bundle = package
+      .and_then(|p| p.get("metadata")?.get("bundle"))
+      .and_then(|b| b.as_table());
+    let package_str =
+      |key: &str| package.and_then(|p| p.get(key)?.as_str().map(str::to_string));
+    let bundle_str = |key: &str| bundle.and_then(|b| b.get(key)?.as_str().map(str::to_string));
+    // The value of `key` in the `[package]`, it's read from the
+    // `[workspace.package]` if it's inherited from the workspace.
+    let package_value = |key: &str| -> Result<Option<toml::Value>> {
+      let value = package.and_then(|p| p.get(key));
+      if value.and_then(|v| v.get("workspace")?.as_bool()) == Some(true) {
+        let workspace = read_toml(workspace)?;
+        Ok(
+          workspace
+            .get("workspace")
+            .and_then(|w| w.get("package")?.get(key))
+            .cloned(),
+        )
+      } else {
+        Ok(value.cloned())
+      }
+    };
+
+    let mut missing = vec![];
+    let bin = package_str("name");
+    if bin.is_none() {
+      missing.push("package.name");
+    }
+    let identifier = bundle_str("identifier");
+    if identifier.is_none() {
+      missing.push("package.metadata.bundle.identifier");
+    }
+    let version = package_value("version")?.and_then(|v| v.as_str().map(str::to_string));
+    if version.is_none() {
+      missing.push("package.version");
+    }
+    if !missing.is_empty() {
+      bail!("missing the required metadata `{}` in {manifest:?}", missing.join("`, `"));
+    }
+
+    let bin = bin.unwrap();
+    let categories = bundle
+      .and_then(|b| b.get("categories")?.as_array())
+      .map(|arr| {
+        arr
+          .iter()
+          .filter_map(|c| c.as_str().map(str::to_string))
+          .collect()
+      })
+      .unwrap_or_else(|| vec!["Utility".to_string()]);
+    let icon = bundle_str("icon").map(|icon| manifest.parent().unwrap().join(icon));
+    if let Some(icon) = icon.as_ref().filter(|icon| !icon.is_file()) {
+      bail!("the icon {icon:?} of `package.metadata.bundle.icon` doesn't exist");
+    }
+    let maintainer = bundle_str("maintainer").or(
+      package_value("authors")?.and_then(|authors| authors.get(0)?.as_str().map(str::to_string)),
+    );
+    let signing_identity = bundle_str("signing_identity")
+      .or_else(|| std::env::var("RIBIR_SIGNING_IDENTITY").ok())
+      .filter(|id| !id.is_empty());
+    Ok(BundleMeta {
+      name: bundle_str("name").unwrap_or_else(|| bin.clone()),
+      icon,
+      maintainer,
+      signing_identity,
+      description: package_str("description").unwrap_or_default(),
+      identifier: identifier.unwrap(),
+      version: version.unwrap(),
+      categories,
+      bin,
+    })
+  }
+
+  fn info_plist(&self) -> String {
+    let BundleMeta { bin, identifier, version, name, .. } = self;
+    let icon = if self.icon.is_some() {
+      format!("  <key>CFBundleIconFile</key>\n  <string>{bin}.icns</string>\n")
+    } else {
+      String::new()
+    };
+    format!(
+      r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>CFBundleDevelopmentRegion</key>
+  <string>en</string>
+  <key>CFBundleDisplayName</key>
+  <string>{name}</string>
+  <key>CFBundleExecutable</key>
+  <string>{bin}</string>
+{icon}  <key>CFBundleIdentifier</key>
+  <string>{identifier}</string>
+  <key>CFBundleInfoDictionaryVersion</key>
+  <string>6.0</string>
+  <key>CFBundleName</key>
+  <string>{name}</string>
+  <key>CFBundlePackageType</key>
+  <string>APPL</string>
+  <key>CFBundleShortVersionString</key>
+  <string>{version}</string>
+  <key>CFBundleVersion</key>
+  <string>{version}</string>
+  <key>NSHighResolutionCapable</key>
+  <true/>
+</dict>
+</plist>
+"#
+    )
+  }
+
+  /// The maintainer of the Debian package, it must be in the form of
+  /// `Name <email>`.
+  fn deb_maintainer(&self) -> Result<&str> {
+    let maintainer = self.maintainer.as_deref().context(
+      "missing the maintainer of the Debian package, add `package.authors` or \
+       `package.metadata.bundle.maintainer`",
+    )?;
+    let valid = maintainer
+      .strip_suffix('>')
+      .and_then(|m| m.split_once('<'))
+      .is_some_and(|(name, email)| !name.trim().is_empty() && email.contains('@'));
+    if !valid {
+      bail!(
+        "the maintainer `{maintainer}` of the Debian package isn't in the form of `Name <email>`"
+      );
+    }
+    Ok(maintainer)
+  }
+
+  fn desktop_entry(&self, exec: &str) -> String {
+    format!(
+      "[Desktop Entry]\nType=Application\nName={}\nComment={}\nExec={exec}\nIcon={}\n\
+       Categories={};\nTerminal=false\n",
+      self.name,
+      self.description,
+      self.bin,
+      self.categories.join(";")
+    )
+  }
+}
+
+fn read_toml(path: &Path) -> Result<toml::Table> {
+  let content = fs::read_to_string(path).with_context(|| format!("failed to read {path:?}"))?;
+  toml::Table::from_str(&content).with_context(|| format!("invalid {path:?}"))
+}
+
+/// The architecture names of the target `triple` used by Debian and AppImage.
+fn linux_arch(triple: &str) -> Result<(&'static str, &'static str)> {
+  let arch = triple.split('-').next().unwrap_or_default();
+  let names = match arch {
+    "x86_64" => ("amd64", "x86_64"),
+    "aarch64" => ("arm64", "aarch64"),
+    "i586" | "i686" => ("i386", "i686"),
+    "armv7" => ("armhf", "armhf"),
+    "riscv64gc" => ("riscv64", "riscv64"),
+    _ => bail!("unsupported architecture `{arch}` of the target `{triple}`"),
+  };
+  Ok(names)
+}
+
+/// Stage the files of the package built for the target `triple` in `out_dir`,
+/// and return the staging directory. The `assets` directory is bundled next to
+/// the binary, so the application finds it relative to its executable.
+fn stage(
+  meta: &BundleMeta, binary: &Path, assets: Option<&Path>, format: Format, triple: &str,
+  out_dir: &Path,
+) -> Result<PathBuf> {
+  let BundleMeta { bin, identifier, version, .. } = meta;
+  let root = match format {
+    Format::Deb => out_dir.join(format!("{bin}_{version}_{}", linux_arch(triple)?.0)),
+    Format::AppImage => out_dir.join(format!("{}.AppDir", meta.name)),
+    Format::App | Format::Dmg => out_dir.join(format!("{}.app", meta.name)),
+  };
+  if root.exists() {
+    fs::remove_dir_all(&root)?;
+  }
+  let app_dir = match format {
+    Format::Deb | Format::AppImage => root.join("usr/lib").join(bin),
+    Format::App | Format::Dmg => root.join("Contents/MacOS"),
+  };
+  fs::create_dir_all(&app_dir)?;
+  fs::copy(binary, app_dir.join(bin)).with_context(|| format!("failed to copy {binary:?}"))?;
+  if let Some(assets) = assets {
+    let options = fs_extra::dir::CopyOptions::new().overwrite(true);
+    fs_extra::dir::copy(assets, &app_dir, &options)?;
+  }
+
+  if matches!(format, Format::App | Format::Dmg) {
+    let contents = root.join("Contents");
+    fs::write(contents.join("Info.plist"), meta.info_plist())?;
+    if let Some(icon) = &meta.icon {
+      let resources = contents.join("Resources");
+      fs::create_dir_all(&resources)?;
+      fs::write(resources.join(format!("{bin}.icns")), icns(icon)?)?;
+    }
+    return Ok(root);
+  }
+
+  let applications = root.join("usr/share/applications");
+  fs::create_dir_all(&applications)?;
+  let desktop_file = format!("{identifier}.desktop");
+  match format {
+    Format::Deb => {
+      let exec = format!("/usr/lib/{bin}/{bin}");
+      fs::write(applications.join(&desktop_file), meta.desktop_entry(&exec))?;
+      if let Some(icon) = &meta.icon {
+        let icons = root.join(format!("usr/share/icons/hicolor/{ICON_SIZE}x{ICON_SIZE}/apps"));
+        fs::create_dir_all(&icons)?;
+        fs::write(icons.join(format!("{bin}.png")), png_icon(icon)?)?;
+      }
+      let control = root.join("DEBIAN");
+      fs::create_dir_all(&control)?;
+      let description = if meta.description.is_empty() { &meta.name } else { &meta.description };
+      let (arch, _) = linux_arch(triple)?;
+      let maintainer = meta.deb_maintainer()?;
+      fs::write(
+        control.join("control"),
+        format!(
+          "Package: {bin}\nVersion: {version}\nArchitecture: {arch}\n\
+           Maintainer: {maintainer}\nDescription: {description}\n"
+        ),
+      )?;
+    }
+    Format::AppImage => {
+      let entry = meta.desktop_entry(bin);
+      fs::write(applications.join(&desktop_file), &entry)?;
+      // The `appimagetool` requires the desktop entry and its icon in the root.
+      fs::write(root.join(&desktop_file), &entry)?;
+      if let Some(icon) = &meta.icon {
+        fs::write(root.join(format!("{bin}.png")), png_icon(icon)?)?;
+      }
+      let app_run = root.join("AppRun");
+      let script = format!(
+        "#!/bin/sh\nHERE=\"$(dirname \"$(readlink -f \"$0\")\")\"\n\
+         exec \"$HERE/usr/lib/{bin}/{bin}\" \"$@\"\n"
+      );
+      fs::write(&app_run, script)?;
+      #[cfg(unix)]
+      {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&app_run, fs::Permissions::from_mode(0o755))?;
+      }
+    }
+    Format::App | Format::Dmg => unreachable!(),
+  }
+  Ok(root)
+}
+
+/// The size of the icon installed for the Linux desktop entry.
+const ICON_SIZE: u32 = 256;
+
+fn read_icon(icon: &Path) -> Result<PixelImage> {
+  let bytes = fs::read(icon).with_context(|| format!("failed to read {icon:?}"))?;
+  PixelImage::from_bytes(&bytes).with_context(|| format!("invalid icon {icon:?}"))
+}
+
+/// Generate the PNG icon of the Linux desktop entry from the `icon` image.
+fn png_icon(icon: &Path) -> Result<Vec<u8>> {
+  let mut png = vec![];
+  read_icon(icon)?
+    .resize(ICON_SIZE, ICON_SIZE)
+    .write_as_png(&mut png)
+    .map_err(|err| anyhow::anyhow!("{err}"))?;
+  Ok(png)
+}
+
+/// Generate the macOS icon file that contains the PNG icons of every size
+/// from the `icon` image.
+fn icns(icon: &Path) -> Result<Vec<u8>> {
+  const ICON_TYPES: [(u32, &[u8; 4]); 7] = [
+    (16, b"icp4"),
+    (32, b"icp5"),
+    (64, b"icp6"),
+    (128, b"ic07"),
+    (256, b"ic08"),
+    (512, b"ic09"),
+    (1024, b"ic10"),
+  ];
+
+  let image = read_icon(icon)?;
+  let sizes = ICON_TYPES.map(|(size, _)| size);
+  let mut entries = vec![];
+  for (img, (_, ty)) in image.icon_set(&sizes).iter().zip(ICON_TYPES) {
+    let mut png = vec![];
+    img
+      .write_as_png(&mut png)
+      .map_err(|err| anyhow::anyhow!("{err}"))?;
+    entries.extend_from_slice(ty);
+    entries.extend_from_slice(&(png.len() as u32 + 8).to_be_bytes());
+    entries.extend_from_slice(&png);
+  }
+
+  let mut icns = b"icns".to_vec();
+  icns.extend_from_slice(&(entries.len() as u32 + 8).to_be_bytes());
+  icns.extend_from_slice(&entries);
+  Ok(icns)
+}
+
+/// Sign the macOS application bundle, it's skipped if no signing identity is
+/// configured. Return if the bundle is signed.
+fn sign_app(shell: &xshell::Shell, app: &Path, identity: Option<&str>) -> Result<bool> {
+  let Some(identity) = identity else {
+    println!("No signing identity is configured, skip signing {app:?}");
+    return Ok(false);
+  };
+  check_all_programs(&[Program { crate_name: "codesign", binary_name: "codesign" }])?;
+  xshell::cmd!(shell, "codesign --force --deep --options runtime --sign {identity} {app}")
+    .quiet()
+    .run()?;
+  Ok(true)
+}
+
+impl Bundle {
+  fn root_path(&self) -> Result<PathBuf> {
+    let root = PathBuf::from_str(env!("CARGO_WORKSPACE_DIR"))?;
+    Ok(root)
+  }
+
+  fn out_dir(&self, target_dir: &Path) -> Result<PathBuf> {
+    match self.out_dir.clone() {
+      Some(out_dir) if out_dir.is_relative() => Ok(self.root_path()?.join(out_dir)),
+      Some(out_dir) => Ok(out_dir),
+      None => Ok(target_dir.join("bundle")),
+    }
+  }
+
+  /// Return the manifest path of the package and the target directory of the
+  /// workspace.
+  fn cargo_metadata(&self, shell: &xshell::Shell) -> Result<(PathBuf, PathBuf)> {
+    let metadata = xshell::cmd!(shell, "cargo metadata --no-deps --format-version 1")
+      .quiet()
+      .read()?;
+    let metadata: serde_json::Value = serde_json::from_str(&metadata)?;
+    let package = &self.package;
+    let manifest = metadata["packages"]
+      .as_array()
+      .and_then(|packages| packages.iter().find(|p| p["name"] == package.as_str()))
+      .and_then(|p| p["manifest_path"].as_str())
+      .map(PathBuf::from)
+      .with_context(|| format!("no package named `{package}` in the workspace"))?;
+    let target_dir = metadata["target_directory"]
+      .as_str()
+      .map(PathBuf::from)
+      .context("no target directory in the cargo metadata")?;
+    Ok((manifest, target_dir))
+  }
+
+  /// The target triple to build for.
+  fn target_triple(&self, shell: &xshell::Shell) -> Result<String> {
+    if let Some(target) = &self.target {
+      return Ok(target.clone());
+    }
+    let version = xshell::cmd!(shell, "rustc -vV").quiet().read()?;
+    version
+      .lines()
+      .find_map(|line| line.strip_prefix("host: "))
+      .map(str::to_string)
+      .context("failed to read the host triple from `rustc -vV`")
+  }
+
+  fn bundle(&self) -> Result<PathBuf> {
+    let shell = xshell::Shell::new()?;
+    shell.change_dir(self.root_path()?);
+    let (manifest, target_dir) = self.cargo_metadata(&shell)?;
+    let triple = self.target_triple(&shell)?;
+    // Check the metadata before building.
+    let workspace = self.root_path()?.join("Cargo.toml");
+    let meta = BundleMeta::from_manifest(&manifest, &workspace)?;
+    match self.format {
+      Format::Deb => {
+        linux_arch(&triple)?;
+        meta.deb_maintainer()?;
+      }
+      Format::AppImage => {
+        linux_arch(&triple)?;
+      }
+      Format::App | Format::Dmg => {}
+    }
+
+    let package = &self.package;
+    let release_flg = if self.release { Some("--release") } else { None };
+    let target_flg = self
+      .target
+      .iter()
+      .flat_map(|target| ["--target", target]);
+    xshell::cmd!(shell, "cargo build -p {package} {release_flg...} {target_flg...}")
+      .quiet()
+      .run()?;
+    let profile = if self.release { "release" } else { "debug" };
+    let mut binary = target_dir.clone();
+    if let Some(target) = &self.target {
+      binary.push(target);
+    }
+    binary.push(profile);
+    binary.push(&meta.bin);
+
+    let assets = manifest.parent().unwrap().join("assets");
+    let assets = assets.is_dir().then_some(assets.as_path());
+    let out_dir = self.out_dir(&target_dir)?;
+    let staging = stage(&meta, &binary, assets, self.format, &triple, &out_dir)?;
+
+    let BundleMeta { bin, version, .. } = &meta;
+    let output = match self.format {
+      Format::Deb => {
+        let (arch, _) = linux_arch(&triple)?;
+        let output = out_dir.join(format!("{bin}_{version}_{arch}.deb"));
+        xshell::cmd!(shell, "dpkg-deb --build --root-owner-group {staging} {output}")
+          .quiet()
+          .run()?;
+        output
+      }
+      Format::AppImage => {
+        let (_, arch) = linux_arch(&triple)?;
+        let output = out_dir.join(format!("{}-{version}-{arch}.AppImage", meta.name));
+        xshell::cmd!(shell, "appimagetool {staging} {output}")
+          .env("ARCH", arch)
+          .quiet()
+          .run()?;
+        output
+      }
+      Format::App => {
+        sign_app(&shell, &staging, meta.signing_identity.as_deref())?;
+        staging
+      }
+      Format::Dmg => {
+        sign_app(&shell, &staging, meta.signing_identity.as_deref())?;
+        let name = &meta.name;
+        let output = out_dir.join(format!("{name}-{version}.dmg"));
+        xshell::cmd!(
+          shell,
+          "hdiutil create -volname {name} -srcfolder {staging} -ov -format UDZO {output}"
+        )
+        .quiet()
+        .run()?;
+        output
+      }
+    };
+    println!("Bundled {output:?}");
+    Ok(output)
+  }
+}
+
+impl CliCommand for BundleCmd {
+  fn name(&self) -> &str { "bundle" }
+
+  fn command(&self) -> clap::Command { Bundle::command() }
+
+  fn exec(&self, args: &clap::ArgMatches) -> Result<()> {
+    let args = Bundle::from_arg_matches(args)?;
+    let program = match args.format {
+      Format::Deb => Some(Program { crate_name: "dpkg-deb", binary_name: "dpkg-deb" }),
+      Format::AppImage => Some(Program { crate_name: "appimagetool", binary_name: "appimagetool" }),
+      Format::App => None,
+      Format::Dmg => Some(Program { crate_name: "hdiutil", binary_name: "hdiutil" }),
+    };
+    check_all_programs(program.as_slice())?;
+    args.bundle()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn fixture() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/bundle_app")
+  }
+
+  fn out_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ribir_cli_bundle_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+  }
+
+  fn read_meta(manifest: &str) -> Result<BundleMeta> {
+    BundleMeta::from_manifest(&fixture().join(manifest), &fixture().join("workspace.toml"))
+  }
+
+  fn meta() -> BundleMeta { read_meta("Cargo.toml").unwrap() }
+
+  const X86_64: &str = "x86_64-unknown-linux-gnu";
+
+  #[test]
+  fn read_metadata() {
+    let meta = meta();
+    assert_eq!(meta.bin, "bundle_app");
+    assert_eq!(meta.identifier, "org.ribir.bundle_app");
+    assert_eq!(meta.version, "0.1.0");
+    assert_eq!(meta.name, "Bundle App");
+
+    assert_eq!(meta.maintainer.as_deref(), Some("Ribir Team <ribir@example.com>"));
+
+    let inherited = read_meta("inherit_version.toml").unwrap();
+    assert_eq!(inherited.version, "0.2.0");
+    assert_eq!(inherited.name, "bundle_app");
+    assert_eq!(inherited.maintainer.as_deref(), Some("Workspace Team <team@example.com>"));
+
+    let err = read_meta("missing_meta.toml").unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      format!(
+        "missing the required metadata `package.metadata.bundle.identifier`, `package.version` \
+         in {:?}",
+        fixture().join("missing_meta.toml")
+      )
+    );
+  }
+
+  #[test]
+  fn deb_layout() {
+    let out = out_dir("deb");
+    let binary = fixture().join("bundle_app.bin");
+    let assets = fixture().join("assets");
+    let root = stage(&meta(), &binary, Some(&assets), Format::Deb, X86_64, &out).unwrap();
+
+    assert_eq!(root, out.join("bundle_app_0.1.0_amd64"));
+    assert!(root.join("usr/lib/bundle_app/bundle_app").is_file());
+    assert!(root.join("usr/lib/bundle_app/assets/hello.txt").is_file());
+    assert!(
+      root
+        .join("usr/share/icons/hicolor/256x256/apps/bundle_app.png")
+        .is_file()
+    );
+    let control = fs::read_to_string(root.join("DEBIAN/control")).unwrap();
+    assert!(control.contains(
+      "Package: bundle_app\nVersion: 0.1.0\nArchitecture: amd64\n\
+       Maintainer: Ribir Team <ribir@example.com>\n"
+    ));
+    let entry =
+      fs::read_to_string(root.join("usr/share/applications/org.ribir.bundle_app.desktop")).unwrap();
+    assert!(entry.contains("Name=Bundle App\n"));
+    assert!(entry.contains("Exec=/usr/lib/bundle_app/bundle_app\n"));
+
+    // The architecture is derived from the target triple.
+    let root =
+      stage(&meta(), &binary, None, Format::Deb, "aarch64-unknown-linux-gnu", &out).unwrap();
+    assert_eq!(root, out.join("bundle_app_0.1.0_arm64"));
+    let control = fs::read_to_string(root.join("DEBIAN/control")).unwrap();
+    assert!(control.contains("Architecture: arm64\n"));
+    let err = stage(&meta(), &binary, None, Format::Deb, "wasm32-unknown-unknown", &out);
+    assert_eq!(
+      err.unwrap_err().to_string(),
+      "unsupported architecture `wasm32` of the target `wasm32-unknown-unknown`"
+    );
+
+    let meta = BundleMeta { maintainer: Some("org.ribir.bundle_app".into()), ..meta() };
+    let err = stage(&meta, &binary, None, Format::Deb, X86_64, &out).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "the maintainer `org.ribir.bundle_app` of the Debian package isn't in the form of \
+       `Name <email>`"
+    );
+
+    fs::remove_dir_all(out).unwrap();
+  }
+
+  #[test]
+  fn app_image_layout() {
+    let out = out_dir("app_image");
+    let binary = fixture().join("bundle_app.bin");
+    let assets = fixture().join("assets");
+    let root = stage(&meta(), &binary, Some(&assets), Format::AppImage, X86_64, &out).unwrap();
+
+    assert_eq!(root, out.join("Bundle App.AppDir"));
+    assert!(root.join("AppRun").is_file());
+    assert!(root.join("usr/lib/bundle_app/bundle_app").is_file());
+    assert!(root.join("usr/lib/bundle_app/assets/hello.txt").is_file());
+    assert!(root.join("org.ribir.bundle_app.desktop").is_file());
+    let icon = PixelImage::from_bytes(&fs::read(root.join("bundle_app.png")).unwrap()).unwrap();
+    assert_eq!((icon.width(), icon.height()), (256, 256));
+    assert!(
+      root
+        .join("usr/share/applications/org.ribir.bundle_app.desktop")
+        .is_file()
+    );
+
+    fs::remove_dir_all(out).unwrap();
+  }
+
+  #[test]
+  fn app_layout() {
+    let out = out_dir("app");
+    let binary = fixture().join("bundle_app.bin");
+    let meta = meta();
+    let assets = fixture().join("assets");
+    let root = stage(&meta, &binary, Some(&assets), Format::App, X86_64, &out).unwrap();
+
+    assert_eq!(root, out.join("Bundle App.app"));
+    assert!(root.join("Contents/MacOS/bundle_app").is_file());
+    assert!(root.join("Contents/MacOS/assets/hello.txt").is_file());
+    let plist = fs::read_to_string(root.join("Contents/Info.plist")).unwrap();
+    assert!(plist.contains("<key>CFBundleExecutable</key>\n  <string>bundle_app</string>"));
+    assert!(
+      plist.contains("<key>CFBundleIdentifier</key>\n  <string>org.ribir.bundle_app</string>")
+    );
+    assert!(plist.contains("<key>CFBundleIconFile</key>\n  <string>bundle_app.icns</string>"));
+
+    let icns = fs::read(root.join("Contents/Resources/bundle_app.icns")).unwrap();
+    assert_eq!(&icns[..4], b"icns");
+    assert_eq!(u32::from_be_bytes(icns[4..8].try_into().unwrap()) as usize, icns.len());
+    assert_eq!(&icns[8..12], b"icp4");
+
+    // Without a signing identity, the signing is skipped.
+    let shell = xshell::Shell::new().unwrap();
+    assert!(!sign_app(&shell, &root, None).unwrap());
+
+    fs::remove_dir_all(out).unwrap();
+  }
+}
@@ -1,8 +1,12 @@
+mod bundle;
+mod new;
 mod program_check;
 mod run_wasm;
 
 use anyhow::Result;
+use bundle::bundle;
 use clap::ArgMatches;
+use new::new_project;
 use run_wasm::run_wasm;
 
 trait CliCommand {
@@ -14,7 +18,7 @@ trait CliCommand {
 fn main() {
   let mut cli = clap::Command::new("cli").bin_name("cli");
 
-  let commands = [run_wasm()];
+  let commands = [run_wasm(), bundle(), new_project()];
 
   for cmd in &commands {
     cli = cli.subcommand(cmd.command());
@@ -23,7 +27,10 @@ fn main() {
 
   if let Some((sub_cmd, matches)) = matches.subcommand() {
     if let Some(cmd) = commands.iter().find(|cmd| cmd.name() == sub_cmd) {
-      let _ = cmd.exec(matches);
+      if let Err(err) = cmd.exec(matches) {
+        eprintln!("{err:#}");
+        std::process::exit(1);
+      }
     }
   }
 }
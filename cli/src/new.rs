@@ -0,0 +1,223 @@
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::{Result, bail};
+use clap::{CommandFactory, FromArgMatches, Parser, ValueEnum};
+
+use crate::CliCommand;
+
+pub fn new_project() -> Box<dyn CliCommand> { Box::new(NewCmd {}) }
+
+struct NewCmd {}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Theme {
+  Material,
+  Slim,
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "new")]
+/// create a new Ribir application
+struct New {
+  /// Name of the application package
+  name: String,
+
+  /// Direction path to create the application, default to ./<name>
+  #[arg(short, long)]
+  path: Option<PathBuf>,
+
+  /// The theme of the application
+  #[arg(short, long, value_enum, default_value_t = Theme::Material)]
+  theme: Theme,
+
+  /// Depend on the Ribir crates of a local Ribir repository, instead of the
+  /// published ones
+  #[arg(long)]
+  ribir_path: Option<PathBuf>,
+}
+
+/// The repository of Ribir, the crates not published are depended from it.
+const RIBIR_GIT: &str = "https://github.com/RibirX/Ribir";
+
+/// Create the files of a minimal Ribir application in `dir`, the `dir` must be
+/// empty or not exist. The Ribir crates are depended from `ribir_path` if it's
+/// given.
+fn scaffold(dir: &Path, name: &str, theme: Theme, ribir_path: Option<&Path>) -> Result<()> {
+  let valid_name = name
+    .chars()
+    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+  if name.is_empty() || !valid_name || name.starts_with(|c: char| c.is_ascii_digit()) {
+    bail!("`{name}` isn't a valid package name, use letters, digits, `-` and `_` only");
+  }
+  if dir.exists() && (!dir.is_dir() || fs::read_dir(dir)?.next().is_some()) {
+    bail!("the directory {dir:?} is not empty, refuse to overwrite it");
+  }
+
+  fs::create_dir_all(dir.join("src"))?;
+  fs::create_dir_all(dir.join("assets"))?;
+  fs::write(dir.join("assets/.gitkeep"), "")?;
+  fs::write(dir.join(".gitignore"), "/target\n")?;
+  fs::write(dir.join("Cargo.toml"), cargo_toml(name, theme, ribir_path))?;
+  fs::write(dir.join("src/main.rs"), main_rs(name, theme))?;
+  Ok(())
+}
+
+fn cargo_toml(name: &str, theme: Theme, ribir_path: Option<&Path>) -> String {
+  // `ribir_slim` isn't published, so the slim theme depends on the Ribir
+  // repository, and `ribir` must come from the same source as it.
+  let source = |dir: &str| match ribir_path {
+    Some(root) => {
+      format!("path = {}", toml::Value::from(root.join(dir).to_string_lossy().as_ref()))
+    }
+    None if theme == Theme::Slim => format!("git = \"{RIBIR_GIT}\""),
+    None => format!("version = \"{}\"", env!("CARGO_PKG_VERSION")),
+  };
+  let ribir = source("ribir");
+  let dependencies = match theme {
+    Theme::Material => format!("ribir = {{ {ribir}, features = [\"material\"] }}\n"),
+    Theme::Slim => format!(
+      "ribir = {{ {ribir}, default-features = false, features = [\"wgpu\", \"widgets\", \"png\"] \
+       }}\nribir_slim = {{ {} }}\n",
+      source("themes/ribir_slim")
+    ),
+  };
+  format!(
+    "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+     [dependencies]\n{dependencies}\n[package.metadata.bundle]\nidentifier = \"com.example.{}\"\n",
+    name.replace('-', "_")
+  )
+}
+
+fn main_rs(name: &str, theme: Theme) -> String {
+  let theme = match theme {
+    Theme::Material => "material::purple::light()",
+    Theme::Slim => "ribir_slim::purple()",
+  };
+  format!(
+    r#"use ribir::prelude::*;
+
+fn app() -> Widget<'static> {{
+  let cnt = Stateful::new(0);
+  button! {{
+    h_align: HAlign::Center,
+    v_align: VAlign::Center,
+    on_tap: move |_| *$cnt.write() += 1,
+    @pipe!($cnt.to_string())
+  }}
+  .into_widget()
+}}
+
+fn main() {{
+  App::run(app)
+    .with_app_theme({theme})
+    .with_size(Size::new(400., 300.))
+    .with_title("{name}");
+}}
+"#
+  )
+}
+
+impl CliCommand for NewCmd {
+  fn name(&self) -> &str { "new" }
+
+  fn command(&self) -> clap::Command { New::command() }
+
+  fn exec(&self, args: &clap::ArgMatches) -> Result<()> {
+    let args = New::from_arg_matches(args)?;
+    let dir = args
+      .path
+      .clone()
+      .unwrap_or_else(|| PathBuf::from(&args.name));
+    scaffold(&dir, &args.name, args.theme, args.ribir_path.as_deref())?;
+    println!("Created the Ribir application `{}` in {dir:?}", args.name);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ribir_cli_new_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+  }
+
+  #[test]
+  fn scaffold_app() {
+    let dir = temp_dir("app");
+    scaffold(&dir, "hello-ribir", Theme::Material, None).unwrap();
+
+    assert!(dir.join("assets").is_dir());
+    assert!(dir.join(".gitignore").is_file());
+    let cargo = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    let toml: toml::Table = cargo.parse().unwrap();
+    assert_eq!(toml["package"]["name"].as_str(), Some("hello-ribir"));
+    assert_eq!(toml["dependencies"]["ribir"]["version"].as_str(), Some(env!("CARGO_PKG_VERSION")));
+    let main = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+    assert!(main.contains("App::run("));
+    assert!(main.contains(".with_app_theme(material::purple::light())"));
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn slim_theme() {
+    let dir = temp_dir("slim");
+    scaffold(&dir, "slim_app", Theme::Slim, None).unwrap();
+
+    let toml: toml::Table = fs::read_to_string(dir.join("Cargo.toml"))
+      .unwrap()
+      .parse()
+      .unwrap();
+    // `ribir_slim` isn't published, both crates come from the repository.
+    let deps = &toml["dependencies"];
+    assert_eq!(deps["ribir_slim"]["git"].as_str(), Some(RIBIR_GIT));
+    assert_eq!(deps["ribir"]["git"].as_str(), Some(RIBIR_GIT));
+    let main = fs::read_to_string(dir.join("src/main.rs")).unwrap();
+    assert!(main.contains(".with_app_theme(ribir_slim::purple())"));
+
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn resolve_local_ribir() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+      .parent()
+      .unwrap();
+    for (theme, name) in [(Theme::Material, "material_app"), (Theme::Slim, "slim_app")] {
+      let dir = temp_dir(name);
+      scaffold(&dir, name, theme, Some(root)).unwrap();
+
+      let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+      let output = std::process::Command::new(cargo)
+        .args(["metadata", "--offline", "--format-version", "1", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .output()
+        .unwrap();
+      assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+      fs::remove_dir_all(dir).unwrap();
+    }
+  }
+
+  #[test]
+  fn refuse_non_empty_dir() {
+    let dir = temp_dir("non_empty");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("keep.txt"), "keep").unwrap();
+
+    let err = scaffold(&dir, "app", Theme::Material, None).unwrap_err();
+    let msg = format!("the directory {dir:?} is not empty, refuse to overwrite it");
+    assert_eq!(err.to_string(), msg);
+    assert_eq!(fs::read_to_string(dir.join("keep.txt")).unwrap(), "keep");
+    assert!(!dir.join("Cargo.toml").exists());
+
+    assert!(scaffold(&temp_dir("invalid"), "1app", Theme::Material, None).is_err());
+    fs::remove_dir_all(dir).unwrap();
+  }
+}
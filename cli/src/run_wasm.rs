@@ -1,13 +1,15 @@
 use std::{
+  fs,
   path::{Path, PathBuf},
   str::FromStr,
+  sync::mpsc::{Receiver, RecvTimeoutError},
   thread,
-  time::Duration,
+  time::{Duration, SystemTime},
 };
 
 use anyhow::Result;
-use clap::{CommandFactory, FromArgMatches, Parser, command};
-use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer, notify::*};
+use clap::{CommandFactory, FromArgMatches, Parser};
+use notify_debouncer_mini::notify::{self, RecommendedWatcher, RecursiveMode, Watcher};
 
 use crate::{
   CliCommand,
@@ -15,6 +17,52 @@ use crate::{
 };
 
 const WATCH_DEBOUNCE_GAP: Duration = Duration::from_secs(2);
+/// The file changed after every rebuild, the page reloads when it changes.
+const RELOAD_TOKEN: &str = "reload_token.txt";
+/// The script injected to the `index.html` to reload the page after a rebuild.
+const RELOAD_SCRIPT: &str = r#"<script data-ribir-reload>
+  // Injected by `cli run-wasm` to reload the page after a rebuild.
+  (() => {
+    let token;
+    setInterval(async () => {
+      try {
+        const res = await fetch("reload_token.txt", { cache: "no-store" });
+        const latest = await res.text();
+        if (token !== undefined && latest !== token) location.reload();
+        token = latest;
+      } catch (_) {}
+    }, 1000);
+  })();
+</script>
+"#;
+
+/// Receive the changed paths from the `changes`, and call `rebuild` once the
+/// changes are quiet for the `gap`, so the rapid successive saves trigger only
+/// one rebuild. The paths that `need_rebuild` rejects are ignored. It returns
+/// when the sender of the `changes` is dropped.
+fn debounce_changes(
+  changes: Receiver<PathBuf>, gap: Duration, need_rebuild: impl Fn(&Path) -> bool,
+  mut rebuild: impl FnMut(),
+) {
+  while let Ok(path) = changes.recv() {
+    let mut dirty = need_rebuild(&path);
+    loop {
+      match changes.recv_timeout(gap) {
+        Ok(path) => dirty |= need_rebuild(&path),
+        Err(RecvTimeoutError::Timeout) => break,
+        Err(RecvTimeoutError::Disconnected) => {
+          if dirty {
+            rebuild();
+          }
+          return;
+        }
+      }
+    }
+    if dirty {
+      rebuild();
+    }
+  }
+}
 
 pub fn run_wasm() -> Box<dyn CliCommand> { Box::new(RunWasm {}) }
 
@@ -75,35 +123,63 @@ impl Wasm {
       .unwrap_or("web_wasm".to_string())
   }
 
-  fn auto_rebuild(&self) -> Debouncer<RecommendedWatcher> {
+  fn auto_rebuild(&self) -> RecommendedWatcher {
     let root_path = self.root_path().unwrap();
-    let ignore_file = root_path.join(".gitignore");
-    let this = self.clone();
-    let mut debouncer =
-      new_debouncer(WATCH_DEBOUNCE_GAP, move |res: DebounceEventResult| match res {
-        Ok(events) => {
-          let ignore = gitignore::File::new(Path::new(&ignore_file));
-          let need_rebuild = events.iter().any(|e| {
-            if let Ok(ignore) = &ignore {
-              return !ignore.is_excluded(&e.path).unwrap_or(false);
-            }
-            true
-          });
-          if need_rebuild {
-            let _ = this.wasm_build();
-          }
-        }
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher =
+      notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+        Ok(event) => event.paths.into_iter().for_each(|p| {
+          let _ = tx.send(p);
+        }),
         Err(e) => println!("Error {:?}", e),
       })
       .unwrap();
 
     // Add a path to be watched. All files and directories at that path and
     // below will be monitored for changes.
-    debouncer
-      .watcher()
+    watcher
       .watch(&root_path, RecursiveMode::Recursive)
       .unwrap();
-    debouncer
+
+    let this = self.clone();
+    thread::spawn(move || {
+      let ignore_file = root_path.join(".gitignore");
+      let ignore = gitignore::File::new(&ignore_file);
+      let need_rebuild = |path: &Path| match &ignore {
+        Ok(ignore) => !ignore.is_excluded(path).unwrap_or(false),
+        Err(_) => true,
+      };
+      debounce_changes(rx, WATCH_DEBOUNCE_GAP, need_rebuild, || {
+        if this.wasm_build().is_ok() {
+          let _ = this.notify_reload();
+        }
+      });
+    });
+    watcher
+  }
+
+  /// Inject the reload script into the `index.html` of the output, so the
+  /// page reloads after a rebuild.
+  fn inject_reload_script(&self) -> Result<()> {
+    let index = self.out_dir().join("index.html");
+    let Ok(html) = fs::read_to_string(&index) else { return Ok(()) };
+    if html.contains("data-ribir-reload") {
+      return Ok(());
+    }
+    let html = match html.rfind("</body>") {
+      Some(idx) => format!("{}{RELOAD_SCRIPT}{}", &html[..idx], &html[idx..]),
+      None => format!("{html}{RELOAD_SCRIPT}"),
+    };
+    fs::write(index, html)?;
+    Ok(())
+  }
+
+  fn notify_reload(&self) -> Result<()> {
+    let token = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)?
+      .as_millis();
+    fs::write(self.out_dir().join(RELOAD_TOKEN), token.to_string())?;
+    Ok(())
   }
 
   fn wasm_build(&self) -> Result<()> {
@@ -158,6 +234,9 @@ impl Wasm {
         )?;
       }
     }
+    if !self.no_server {
+      self.inject_reload_script()?;
+    }
     Ok(())
   }
 
@@ -200,9 +279,46 @@ impl CliCommand for RunWasm {
 
     args.wasm_build()?;
     if !args.no_server {
+      args.notify_reload()?;
       args.server()?;
     }
 
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::sync::mpsc::channel;
+
+  use super::*;
+
+  #[test]
+  fn debounce_rapid_changes() {
+    let gap = Duration::from_millis(100);
+    let (tx, rx) = channel();
+    let watcher = thread::spawn(move || {
+      // Rapid successive saves in the debounce gap.
+      for _ in 0..5 {
+        tx.send(PathBuf::from("src/main.rs")).unwrap();
+        thread::sleep(Duration::from_millis(10));
+      }
+      thread::sleep(gap * 3);
+      // The changes of the ignored files don't trigger a rebuild.
+      tx.send(PathBuf::from("target/debug/main"))
+        .unwrap();
+      thread::sleep(gap * 3);
+      tx.send(PathBuf::from("src/lib.rs")).unwrap();
+    });
+
+    let mut rebuilds = vec![];
+    let start = std::time::Instant::now();
+    debounce_changes(rx, gap, |p| !p.starts_with("target"), || rebuilds.push(start.elapsed()));
+    watcher.join().unwrap();
+
+    // One rebuild for the rapid saves, and one for the last change.
+    assert_eq!(rebuilds.len(), 2);
+    // The first rebuild happens after the last change of the rapid saves.
+    assert!(rebuilds[0] >= Duration::from_millis(40) + gap);
+  }
+}
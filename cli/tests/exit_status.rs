@@ -0,0 +1,19 @@
+use std::{fs, process::Command};
+
+#[test]
+fn exit_with_error() {
+  let dir = std::env::temp_dir().join(format!("ribir_cli_exit_{}", std::process::id()));
+  fs::create_dir_all(&dir).unwrap();
+  fs::write(dir.join("keep.txt"), "keep").unwrap();
+
+  let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+    .args(["new", "app", "--path"])
+    .arg(&dir)
+    .output()
+    .unwrap();
+  assert_eq!(output.status.code(), Some(1));
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.contains("is not empty, refuse to overwrite it"), "{stderr}");
+
+  fs::remove_dir_all(dir).unwrap();
+}
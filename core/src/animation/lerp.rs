@@ -155,6 +155,9 @@ impl Lerp for Measure {
     match (self, to) {
       (Measure::Pixel(from), Measure::Pixel(to)) => Measure::Pixel(from.lerp(to, factor)),
       (Measure::Percent(from), Measure::Percent(to)) => Measure::Percent(from.lerp(to, factor)),
+      (Measure::Vw(from), Measure::Vw(to)) => Measure::Vw(from.lerp(to, factor)),
+      (Measure::Vh(from), Measure::Vh(to)) => Measure::Vh(from.lerp(to, factor)),
+      (Measure::Em(from), Measure::Em(to)) => Measure::Em(from.lerp(to, factor)),
       _ => *to,
     }
   }
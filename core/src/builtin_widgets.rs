@@ -9,9 +9,10 @@ pub mod key;
 mod painting_style;
 use std::ops::DerefMut;
 
-pub use key::{Key, KeyWidget};
+pub use key::{ForKeys, Key, KeyWidget};
 pub use painting_style::*;
 pub mod image_widget;
+pub use image_widget::*;
 pub mod keep_alive;
 pub use keep_alive::*;
 mod theme;
@@ -26,6 +27,10 @@ mod foreground;
 mod padding;
 pub use foreground::*;
 pub use padding::*;
+mod safe_area;
+pub use safe_area::*;
+mod keyboard_avoider;
+pub use keyboard_avoider::*;
 mod scrollable;
 pub use scrollable::*;
 mod transform_widget;
@@ -36,6 +41,10 @@ mod ignore_pointer;
 pub use ignore_pointer::*;
 mod void;
 pub use void::Void;
+mod error_boundary;
+pub use error_boundary::*;
+mod memo;
+pub use memo::*;
 mod unconstrained_box;
 pub use unconstrained_box::*;
 mod opacity;
@@ -67,6 +76,8 @@ pub mod container;
 pub use container::*;
 mod class;
 pub use class::*;
+mod disabled;
+pub use disabled::*;
 mod constrained_box;
 pub use constrained_box::*;
 mod text_style;
@@ -80,6 +91,10 @@ mod text;
 pub use text::*;
 mod tooltips;
 pub use tooltips::*;
+mod semantics;
+pub use semantics::*;
+mod pixel_snap;
+pub use pixel_snap::*;
 mod providers;
 pub use providers::*;
 mod border;
@@ -148,6 +163,8 @@ pub struct FatObj<T> {
   keep_alive: Option<State<KeepAlive>>,
   keep_alive_unsubscribe_handle: Option<Box<dyn Any>>,
   tooltips: Option<State<Tooltips>>,
+  semantics: Option<State<SemanticsWidget>>,
+  pixel_snap: Option<State<PixelSnap>>,
   clip_boundary: Option<State<ClipBoundary>>,
   providers: Option<SmallVec<[Provider; 1]>>,
 }
@@ -197,6 +214,8 @@ impl<T> FatObj<T> {
       visibility: self.visibility,
       opacity: self.opacity,
       tooltips: self.tooltips,
+      semantics: self.semantics,
+      pixel_snap: self.pixel_snap,
       clip_boundary: self.clip_boundary,
       keep_alive: self.keep_alive,
       keep_alive_unsubscribe_handle: self.keep_alive_unsubscribe_handle,
@@ -232,6 +251,8 @@ impl<T> FatObj<T> {
       && self.opacity.is_none()
       && self.keep_alive.is_none()
       && self.tooltips.is_none()
+      && self.semantics.is_none()
+      && self.pixel_snap.is_none()
       && self.clip_boundary.is_none()
   }
 
@@ -476,6 +497,22 @@ impl<T> FatObj<T> {
       .get_or_insert_with(|| State::value(<_>::default()))
   }
 
+  /// Returns the `State<SemanticsWidget>` widget from the FatObj. If it
+  /// doesn't exist, a new one is created.
+  pub fn get_semantics_widget(&mut self) -> &State<SemanticsWidget> {
+    self
+      .semantics
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
+  /// Returns the `State<PixelSnap>` widget from the FatObj. If it doesn't
+  /// exist, a new one is created.
+  pub fn get_pixel_snap_widget(&mut self) -> &State<PixelSnap> {
+    self
+      .pixel_snap
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
   /// Returns the `State<ClipBoundary>` widget from the FatObj. If it doesn't
   /// exist, a new one is created.
   pub fn get_clip_boundary_widget(&mut self) -> &State<ClipBoundary> {
@@ -578,6 +615,22 @@ impl<T> FatObj<T> {
     on_mixin!(self, on_pointer_leave, f)
   }
 
+  /// Attaches a handler to the widget that is triggered when the hover status
+  /// changes, it receives `true` when a pointer device enters the widget and
+  /// `false` when it leaves.
+  pub fn on_hover_changed(mut self, f: impl FnMut(bool) + 'static) -> Self {
+    on_mixin!(self, on_hover_changed, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a pointer device
+  /// rests over the widget for a while, it receives `true` then, and `false`
+  /// when the pointer leaves after that. Unlike `on_hover_changed`, passing
+  /// over the widget quickly doesn't trigger it, so it's suitable for the
+  /// hover-triggered UI, like tooltips and previews.
+  pub fn on_hover_intent(mut self, f: impl FnMut(bool) + 'static) -> Self {
+    on_mixin!(self, on_hover_intent, f)
+  }
+
   /// Attaches a handler to the widget that is triggered when a tap(click)
   /// occurs.
   pub fn on_tap(mut self, f: impl FnMut(&mut PointerEvent) + 'static) -> Self {
@@ -875,7 +928,7 @@ impl<T> FatObj<T> {
   }
 
   /// Initializes the extra space within the widget.
-  pub fn padding<const M: usize>(self, v: impl DeclareInto<EdgeInsets, M>) -> Self {
+  pub fn padding<const M: usize>(self, v: impl DeclareInto<MeasureInsets, M>) -> Self {
     self.declare_builtin_init(v, Self::get_padding_widget, |m, v| m.padding = v)
   }
 
@@ -899,6 +952,17 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_scrollable_widget, |m, v| m.scrollable = v)
   }
 
+  /// Initializes whether the scroll locks to the dominant axis once a scroll
+  /// gesture begins.
+  pub fn scroll_direction_lock<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_scrollable_widget, |m, v| m.scroll_direction_lock = v)
+  }
+
+  /// Initializes the multiplier of the wheel delta when the widget scrolls.
+  pub fn scroll_sensitivity<const M: usize>(self, v: impl DeclareInto<f32, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_scrollable_widget, |m, v| m.scroll_sensitivity = v)
+  }
+
   /// Initializes the transformation of the widget.
   pub fn transform<const M: usize>(self, v: impl DeclareInto<Transform, M>) -> Self {
     self.declare_builtin_init(v, Self::get_transform_widget, |m, v| m.transform = v)
@@ -944,6 +1008,17 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_tooltips_widget, |m, v| m.tooltips = v)
   }
 
+  /// Initializes the semantics of the widget for the assistive technologies.
+  pub fn semantics<const M: usize>(self, v: impl DeclareInto<Semantics, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_semantics_widget, |m, v| m.semantics = v)
+  }
+
+  /// Initializes whether to snap the paint origin of the widget to the device
+  /// pixel grid, for crisp borders and dividers.
+  pub fn pixel_snap<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_pixel_snap_widget, |m, v| m.pixel_snap = v)
+  }
+
   /// Initializes the clip_boundary of the widget.
   pub fn clip_boundary<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
     self.declare_builtin_init(v, Self::get_clip_boundary_widget, |m, v| m.clip_boundary = v)
@@ -1077,7 +1152,8 @@ impl<'a> FatObj<Widget<'a>> {
           clip_boundary,
           radius,
           scrollable,
-          layout_box
+          layout_box,
+          pixel_snap
         ]
     );
     if let Some(providers) = self.providers {
@@ -1090,6 +1166,7 @@ impl<'a> FatObj<Widget<'a>> {
           class,
           constrained_box,
           tooltips,
+          semantics,
           margin,
           cursor,
           mix_builtin,
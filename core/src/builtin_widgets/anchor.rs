@@ -170,7 +170,10 @@ impl WrapRender for RelativeAnchor {
     ctx.update_position(ctx.widget_id(), Point::zero());
     let child_size = host.perform_layout(clamp, ctx);
 
-    let offset = self.anchor.into_pixel(child_size, clamp.max);
+    let base = MeasureBase::of(ctx, &ctx.window());
+    let offset = self
+      .anchor
+      .into_pixel(child_size, clamp.max, &base);
     let pos = ctx.box_pos().unwrap_or_default();
     ctx.update_position(ctx.widget_id(), pos + Size::new(offset.x, offset.y));
     child_size
@@ -178,30 +181,30 @@ impl WrapRender for RelativeAnchor {
 }
 
 impl HAnchor {
-  pub fn into_pixel(self, width: f32, parent: f32) -> f32 {
+  pub fn into_pixel(self, width: f32, parent: f32, base: &MeasureBase) -> f32 {
     match self {
-      HAnchor::Left(x) => x.into_pixel(parent),
-      HAnchor::Right(x) => parent - width - x.into_pixel(parent),
+      HAnchor::Left(x) => x.into_pixel(parent, base),
+      HAnchor::Right(x) => parent - width - x.into_pixel(parent, base),
     }
   }
 }
 
 impl VAnchor {
-  pub fn into_pixel(self, height: f32, parent: f32) -> f32 {
+  pub fn into_pixel(self, height: f32, parent: f32, base: &MeasureBase) -> f32 {
     match self {
-      VAnchor::Top(y) => y.into_pixel(parent),
-      VAnchor::Bottom(y) => parent - height - y.into_pixel(parent),
+      VAnchor::Top(y) => y.into_pixel(parent, base),
+      VAnchor::Bottom(y) => parent - height - y.into_pixel(parent, base),
     }
   }
 }
 
 impl Anchor {
-  pub fn into_pixel(self, size: Size, parent: Size) -> Point {
+  pub fn into_pixel(self, size: Size, parent: Size, base: &MeasureBase) -> Point {
     let Self { x, y } = self;
     Point::new(
-      x.map(|x| x.into_pixel(size.width, parent.width))
+      x.map(|x| x.into_pixel(size.width, parent.width, base))
         .unwrap_or_default(),
-      y.map(|y| y.into_pixel(size.height, parent.height))
+      y.map(|y| y.into_pixel(size.height, parent.height, base))
         .unwrap_or_default(),
     )
   }
@@ -292,4 +295,23 @@ mod test {
     .with_wnd_size(Size::new(500., 500.)),
     LayoutCase::new(&[0]).with_rect(ribir_geom::rect(40., 30., 100., 100.))
   );
+
+  widget_layout_test!(
+    viewport_left_top,
+    WidgetTester::new(fn_widget! {
+      @MockBox {
+        size: CHILD_SIZE,
+        anchor: Anchor::left_top(Measure::Vw(50.), Measure::Vh(10.)),
+      }
+    })
+    .with_wnd_size(Size::new(800., 600.)),
+    LayoutCase::default().with_pos(Point::new(400., 60.))
+  );
+
+  #[test]
+  fn viewport_measure_in_zero_size_window() {
+    let base = MeasureBase { viewport: Size::zero(), font_size: 14. };
+    assert_eq!(Measure::Vw(50.).into_pixel(100., &base), 0.);
+    assert_eq!(Measure::Vh(50.).into_pixel(100., &base), 0.);
+  }
 }
@@ -17,9 +17,12 @@
 //!
 //! let mut theme = Theme::default();
 //! // Define how `RED_BORDER` transforms a widget.
-//! theme.classes.insert(RED_BORDER, style_class! {
-//!   border: Border::all(BorderSide::new(2., Color::RED.into()))
-//! });
+//! theme.classes.insert(
+//!   RED_BORDER,
+//!   style_class! {
+//!     border: Border::all(BorderSide::new(2., Color::RED.into()))
+//!   },
+//! );
 //!
 //! let w = fn_widget! {
 //!   @Container {
@@ -38,6 +41,7 @@ use ops::box_it::CloneableBoxOp;
 use pipe::PipeNode;
 use smallvec::{SmallVec, smallvec};
 
+use super::disabled::apply_disabled_style;
 use crate::{pipe::GenRange, prelude::*, window::WindowId};
 
 /// A collection of class implementations that are part of the `Theme`.
@@ -135,6 +139,8 @@ macro_rules! class_names {
 impl ClassName {
   pub const fn new(name: &'static str) -> Self { ClassName(name) }
 
+  pub(crate) fn name(&self) -> &'static str { self.0 }
+
   fn type_info(&self) -> TypeInfo {
     const LAYOUT: std::alloc::Layout = std::alloc::Layout::new::<ClassImpl>();
     // Tricky: We disregard the package version since the type remains stable.
@@ -232,12 +238,18 @@ impl<'c> ComposeChild<'c> for Class {
 
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     let f = move || match this.try_into_value() {
-      Ok(c) => c.apply_style(child),
+      Ok(c) => {
+        let child = child.attach_data(Box::new(Queryable(Class { class: c.class })));
+        c.apply_style(child)
+      }
       Err(this) => {
         let this2 = this.clone_watcher();
         let cls_child = ClassNode::empty_node();
         // Reapply the class when it is updated.
         let cls_child2 = cls_child.clone();
+        // Attach the class before the original child is wrapped, so it's kept
+        // by the child when the class is reapplied.
+        let child = child.attach_data(Box::new(this.clone_boxed_reader()));
         let child = child.on_build(move |orig_id| {
           let orig_child = ClassNode::empty_node();
           orig_child.init_for_single(orig_id);
@@ -288,8 +300,17 @@ impl Class {
     Provider::Setup(Box::new(setup))
   }
 
+  /// Return the implementation of the class `name` in the current build
+  /// context.
+  pub fn impl_of(name: ClassName) -> Option<ClassImpl> { Class { class: Some(name) }.class_impl() }
+
   fn apply_style<'a>(&self, w: Widget<'a>) -> Widget<'a> {
-    if let Some(cls_impl) = self.class_impl() { cls_impl(w) } else { w }
+    match self.class_impl() {
+      // The `DISABLED` class is applied by `apply_disabled_style` itself.
+      Some(cls_impl) if self.class == Some(DISABLED) => cls_impl(w),
+      Some(cls_impl) => apply_disabled_style(cls_impl(w)),
+      None => w,
+    }
   }
 
   fn class_impl(&self) -> Option<ClassImpl> {
@@ -428,9 +449,12 @@ mod tests {
       }
       .into_widget()
     });
-    classes.insert(CLAMP_50, style_class! {
-      clamp: BoxClamp::fixed_size(Size::new(50., 50.))
-    });
+    classes.insert(
+      CLAMP_50,
+      style_class! {
+        clamp: BoxClamp::fixed_size(Size::new(50., 50.))
+      },
+    );
     classes
   }
 
@@ -0,0 +1,196 @@
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "The class applied on top of the class of every themed widget within a \
+  disabled `Disabled`, the theme uses it to give them a disabled appearance."]
+  DISABLED
+}
+
+/// A widget that disables its descendants.
+///
+/// The descendants can't be hit by the pointer and can't get the focus, and
+/// every descendant widget with a class will also apply the `DISABLED` class,
+/// so the theme can give them a disabled appearance, such as reducing the
+/// opacity or muting the colors, without each widget reimplementing it.
+///
+/// The `DISABLED` class is applied only once along a branch of the tree, so the
+/// nested disabled scopes or themed widgets will not dim again.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @Disabled {
+///     @FilledButton { @{ "Disabled" } }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone, Copy)]
+pub struct Disabled {
+  #[declare(default = true)]
+  pub disabled: bool,
+}
+
+/// The state provided by the disabled scope to its descendants.
+struct DisabledScope {
+  /// Whether the scope is disabled, it's disabled by an outer scope too.
+  disabled: Stateful<bool>,
+  /// Whether the `DISABLED` class has been applied by an ancestor.
+  dimmed: Stateful<bool>,
+}
+
+impl<'c> ComposeChild<'c> for Disabled {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let (outer, dimmed) = match Provider::of::<DisabledScope>(BuildCtx::get()) {
+      Some(s) => (s.disabled.clone_writer(), s.dimmed.clone_writer()),
+      None => (Stateful::new(false), Stateful::new(false)),
+    };
+    let disabled = Stateful::new(*outer.read() || this.read().disabled);
+    let c_disabled = disabled.clone_writer();
+    let u = watch!(*$outer || $this.disabled)
+      .distinct_until_changed()
+      .subscribe(move |v| *c_disabled.write() = v);
+    let scope = DisabledScope { disabled: disabled.clone_writer(), dimmed };
+
+    fn_widget! {
+      @FocusScope {
+        // The scope shares its host with the child, keep the host focusable
+        // while enabled.
+        skip_host: pipe!(*$disabled),
+        skip_descendants: pipe!(*$disabled),
+        on_disposed: move |_| u.unsubscribe(),
+        @IgnorePointer {
+          ignore: pipe!(if *$disabled { IgnoreScope::Subtree } else { IgnoreScope::None }),
+          @ { Providers::new([Provider::new(scope)]).with_child(child) }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+impl Disabled {
+  /// Return whether the widget is within a disabled scope.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>) -> bool {
+    Provider::of::<DisabledScope>(ctx).is_some_and(|s| *s.disabled.read())
+  }
+}
+
+/// Apply the `DISABLED` class to the themed widget when its scope is disabled
+/// and no ancestor has applied it.
+pub(crate) fn apply_disabled_style<'a>(w: Widget<'a>) -> Widget<'a> {
+  let Some((disabled, dimmed)) = Provider::of::<DisabledScope>(BuildCtx::get())
+    .map(|s| (s.disabled.clone_writer(), s.dimmed.clone_writer()))
+  else {
+    return w;
+  };
+
+  let dim = |disabled: bool, dimmed: bool| (disabled && !dimmed).then_some(DISABLED);
+  let class = Stateful::new(Class { class: dim(*disabled.read(), *dimmed.read()) });
+  let c_class = class.clone_writer();
+  let u1 = watch!(dim(*$disabled, *$dimmed))
+    .distinct_until_changed()
+    .subscribe(move |v| c_class.write().class = v);
+
+  // The descendants are dimmed with this widget.
+  let inner_dimmed = Stateful::new(*disabled.read() || *dimmed.read());
+  let c_inner_dimmed = inner_dimmed.clone_writer();
+  let u2 = watch!(*$disabled || *$dimmed)
+    .distinct_until_changed()
+    .subscribe(move |v| *c_inner_dimmed.write() = v);
+
+  let w = Class::compose_child(class, w);
+  let scope = DisabledScope { disabled, dimmed: inner_dimmed };
+  let w = Providers::new([Provider::new(scope)]).with_child(w);
+  FatObj::new(w)
+    .on_disposed(move |_| {
+      u1.unsubscribe();
+      u2.unsubscribe();
+    })
+    .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  class_names!(BTN);
+
+  #[test]
+  fn propagate_disabled_class() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      let mut classes = Classes::default();
+      classes.insert(BTN, empty_cls);
+      classes.insert(DISABLED, style_class! { padding: EdgeInsets::all(5.) });
+      let size = Size::new(10., 10.);
+      @Providers {
+        providers: [Provider::new(classes)],
+        @MockMulti {
+          @Disabled {
+            @Disabled {
+              @Class {
+                class: BTN,
+                @MockBox { size, class: BTN }
+              }
+            }
+          }
+          @MockBox { size, class: BTN }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    // The nested scopes and the nested themed widgets only dim once.
+    LayoutCase::expect_size(&wnd, &[0, 0], Size::new(20., 20.));
+    LayoutCase::expect_size(&wnd, &[0, 1], Size::new(10., 10.));
+  }
+
+  #[test]
+  fn toggle_disabled() {
+    reset_test_env!();
+
+    let (disabled, w_disabled) = split_value(true);
+    let (taps, w_taps) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      let mut classes = Classes::default();
+      classes.insert(BTN, empty_cls);
+      classes.insert(DISABLED, style_class! { padding: EdgeInsets::all(5.) });
+      @Providers {
+        providers: [Provider::new(classes)],
+        @Disabled {
+          disabled: pipe!(*$disabled),
+          @MockBox {
+            size: Size::new(10., 10.),
+            class: BTN,
+            tab_index: 0i16,
+            on_tap: move |_| *$w_taps.write() += 1,
+          }
+        }
+      }
+    });
+    wnd.draw_frame();
+    // The disabled widget is dimmed, can't be tapped or focused.
+    LayoutCase::expect_size(&wnd, &[0], Size::new(20., 20.));
+    wnd.tap_at(Point::new(10., 10.));
+    wnd.request_next_focus();
+    wnd.draw_frame();
+    assert_eq!(*taps.read(), 0);
+    assert!(wnd.focusing().is_none());
+
+    *w_disabled.write() = false;
+    wnd.draw_frame();
+    LayoutCase::expect_size(&wnd, &[0], Size::new(10., 10.));
+    wnd.request_next_focus();
+    assert!(wnd.focusing().is_some());
+    wnd.tap_at(Point::new(5., 5.));
+    wnd.draw_frame();
+    assert_eq!(*taps.read(), 1);
+  }
+}
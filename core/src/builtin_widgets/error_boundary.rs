@@ -0,0 +1,223 @@
+use std::{any::Any, panic::resume_unwind};
+
+use crate::prelude::*;
+
+/// A widget catches the panics of its child, and shows the `fallback` widget in
+/// place of the child, so the rest of the UI keeps working.
+///
+/// The panics in building the child are caught immediately, and the build
+/// context is restored, so the providers and the pending children set up by the
+/// failed build don't leak to the other widgets. The panics in painting the
+/// subtree are caught when the frame is drawn, the failed subtree is skipped
+/// and the fallback is shown from the next frame.
+///
+/// Once the child panics, the boundary keeps showing the fallback.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @ErrorBoundary {
+///     fallback: |err: &str| @Text { text: format!("Oops: {err}") }.into_widget(),
+///     @ { fn_widget! { @Text { text: "Hello" } } }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct ErrorBoundary {
+  /// Build the widget shown in place of the failed child with the panic
+  /// message, an empty widget is shown if it's not set.
+  #[declare(custom, default)]
+  pub fallback: Option<FallbackBuilder>,
+  #[declare(skip)]
+  error: Option<String>,
+}
+
+/// Build the fallback widget of an [`ErrorBoundary`] with the panic message.
+pub type FallbackBuilder = Box<dyn Fn(&str) -> Widget<'static>>;
+
+pub trait ErrorBoundaryDeclarerExtend {
+  /// Initialize the builder of the widget shown in place of the failed child.
+  fn fallback(self, f: impl Fn(&str) -> Widget<'static> + 'static) -> Self;
+}
+
+impl ErrorBoundaryDeclarerExtend for FatObj<ErrorBoundaryDeclarer> {
+  fn fallback(mut self, f: impl Fn(&str) -> Widget<'static> + 'static) -> Self {
+    let f: FallbackBuilder = Box::new(f);
+    self.fallback = Some(DeclareInit::Value(Some(f)));
+    self
+  }
+}
+
+impl ErrorBoundary {
+  /// The message of the panic caught by the boundary, `None` if the child
+  /// never panics.
+  pub fn error(&self) -> Option<&str> { self.error.as_deref() }
+
+  /// Handle the panic of painting the widget `id`. The nearest boundary of the
+  /// widget switches to the fallback, or the panic is resumed if the widget
+  /// isn't in a boundary.
+  pub(crate) fn catch_paint_panic(id: WidgetId, tree: &WidgetTree, err: Box<dyn Any + Send>) {
+    let boundary = id
+      .ancestors(tree)
+      .find_map(|id| id.query_write::<ErrorBoundary>(tree));
+    match boundary {
+      Some(mut boundary) => boundary.error = Some(panic_message(&*err)),
+      None => resume_unwind(err),
+    }
+  }
+
+  fn fallback_widget(&self) -> Widget<'static> {
+    match (&self.fallback, &self.error) {
+      (Some(fallback), Some(error)) => fallback(error),
+      _ => Void.into_widget(),
+    }
+  }
+}
+
+impl ComposeChild<'static> for ErrorBoundary {
+  type Child = Widget<'static>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    let mut child = Some(child);
+    let boundary = this.clone_writer();
+    let content = pipe!($this.error.is_some()).map(move |_| {
+      let Some(child) = child.take() else {
+        return boundary.read().fallback_widget();
+      };
+      let boundary = boundary.clone_writer();
+      Widget::from_fn(move |ctx| match ctx.try_build(child) {
+        Ok(id) => id,
+        Err(err) => {
+          // The pipe is building, so don't notify it.
+          boundary.silent().error = Some(panic_message(&*err));
+          let fallback = boundary.read().fallback_widget();
+          ctx.build(fallback)
+        }
+      })
+    });
+
+    content
+      .into_widget()
+      .try_unwrap_state_and_attach(this)
+  }
+}
+
+fn panic_message(err: &(dyn Any + Send)) -> String {
+  if let Some(msg) = err.downcast_ref::<&str>() {
+    msg.to_string()
+  } else if let Some(msg) = err.downcast_ref::<String>() {
+    msg.clone()
+  } else {
+    "Unknown panic".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  struct PaintPanic;
+
+  impl Render for PaintPanic {
+    fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.max }
+
+    fn paint(&self, _: &mut PaintingCtx) { panic!("paint failed") }
+  }
+
+  #[test]
+  fn build_panic_shows_fallback() {
+    reset_test_env!();
+
+    let count = Stateful::new(0);
+    let c_count = count.clone_writer();
+    let error = Stateful::new(String::new());
+    let c_error = error.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @ErrorBoundary {
+            fallback: move |err: &str| {
+              *$c_error.write() = err.to_string();
+              @MockBox { size: Size::new(30., 30.) }.into_widget()
+            },
+            @ {
+              FnWidget::new(|| -> Widget<'static> { panic!("build failed") })
+            }
+          }
+          @MockBox {
+            size: Size::new(50., 50.),
+            on_tap: move |_| *$c_count.write() += 1,
+          }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    assert_eq!(*error.read(), "build failed");
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().size, Some(Size::new(30., 30.)));
+    wnd
+      .tap_at(Point::new(40., 10.))
+      .expect_state(&count, 1);
+  }
+
+  #[test]
+  fn dispose_partially_built_child() {
+    reset_test_env!();
+
+    let (size, w_size) = split_value(10.);
+    let (updates, w_updates) = split_value(0);
+    let (disposed, w_disposed) = split_value(false);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @ErrorBoundary {
+        fallback: |_: &str| @MockBox { size: Size::new(30., 30.) }.into_widget(),
+        @MockMulti {
+          @MockBox {
+            size: pipe!(*$size).map(move |v| {
+              *$w_updates.write() += 1;
+              Size::splat(v)
+            }),
+            on_disposed: move |_| *$w_disposed.write() = true,
+          }
+          @ { FnWidget::new(|| -> Widget<'static> { panic!("build failed") }) }
+        }
+      }
+    });
+    wnd.draw_frame();
+    assert!(*disposed.read());
+
+    let before = *updates.read();
+    *w_size.write() = 20.;
+    wnd.draw_frame();
+    // The pipe of the disposed child is unsubscribed.
+    assert_eq!(*updates.read(), before);
+    assert_eq!(wnd.layout_info_by_path(&[0]).unwrap().size, Some(Size::new(30., 30.)));
+  }
+
+  #[test]
+  fn paint_panic_shows_fallback() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockMulti {
+          @ErrorBoundary {
+            fallback: |_: &str| @MockBox { size: Size::new(30., 30.) }.into_widget(),
+            @ { @MockBox { size: Size::new(20., 20.), @ { PaintPanic } } }
+          }
+          @MockBox { size: Size::new(50., 50.) }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    wnd.draw_frame();
+
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().size, Some(Size::new(30., 30.)));
+    assert_eq!(wnd.layout_info_by_path(&[0, 1]).unwrap().size, Some(Size::new(50., 50.)));
+  }
+}
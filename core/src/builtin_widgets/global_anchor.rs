@@ -105,8 +105,8 @@ impl GlobalAnchorX {
         return Err(());
       }
       let size = wnd.widget_size(wid).unwrap();
-      let wnd_size = wnd.size();
-      Ok(x.into_pixel(size.width, wnd_size.width))
+      let base = MeasureBase::of_window(wnd);
+      Ok(x.into_pixel(size.width, base.viewport.width, &base))
     }))
   }
 
@@ -192,8 +192,8 @@ impl GlobalAnchorY {
         return Err(());
       }
       let size = wnd.widget_size(wid).unwrap();
-      let wnd_size = wnd.size();
-      Ok(y.into_pixel(size.height, wnd_size.height))
+      let base = MeasureBase::of_window(wnd);
+      Ok(y.into_pixel(size.height, base.viewport.height, &base))
     }))
   }
 
@@ -15,4 +15,122 @@ impl Render for Resource<PixelImage> {
       painter.draw_img(self.clone(), &rc, &Some(rc));
     }
   }
+
+  fn semantics(&self) -> Option<Semantics> { Some(Semantics::new(Role::Image)) }
+}
+
+/// An image source that refers to a region of an image, so the regions of an
+/// image, like the sprites of a sprite sheet, share one texture of the image
+/// without slicing its pixels.
+#[derive(Clone)]
+pub struct ImageSource {
+  image: Resource<PixelImage>,
+  region: Rect,
+}
+
+impl ImageSource {
+  /// The whole image as the source.
+  pub fn new(image: Resource<PixelImage>) -> Self {
+    let region = Rect::from_size(Size::new(image.width() as f32, image.height() as f32));
+    Self { image, region }
+  }
+
+  /// The `region` of the image as the source. The part of the `region` outside
+  /// the image is clamped, so the region is empty if it's entirely outside the
+  /// image.
+  pub fn region(image: Resource<PixelImage>, region: Rect) -> Self {
+    let bounds = Rect::from_size(Size::new(image.width() as f32, image.height() as f32));
+    let region = bounds
+      .intersection(&region)
+      .unwrap_or_else(|| Rect::new(region.origin.clamp(bounds.min(), bounds.max()), Size::zero()));
+    Self { image, region }
+  }
+
+  /// The image that the source refers to.
+  pub fn image(&self) -> &Resource<PixelImage> { &self.image }
+
+  /// The region of the image that the source refers to.
+  pub fn source_rect(&self) -> Rect { self.region }
+}
+
+impl From<Resource<PixelImage>> for ImageSource {
+  fn from(image: Resource<PixelImage>) -> Self { Self::new(image) }
+}
+
+impl From<Sprite> for ImageSource {
+  fn from(sprite: Sprite) -> Self {
+    Self::region(sprite.atlas().into(), sprite.rect().to_f32().cast_unit())
+  }
+}
+
+impl Render for ImageSource {
+  fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size {
+    clamp.clamp(self.region.size)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    let box_rect = Rect::from_size(size);
+    let dst_rect = Rect::from_size(self.region.size);
+    if let Some(dst) = dst_rect.intersection(&box_rect) {
+      let src = Rect::new(self.region.origin, dst.size);
+      ctx
+        .painter()
+        .draw_img(self.image.clone(), &dst, &Some(src));
+    }
+  }
+
+  fn semantics(&self) -> Option<Semantics> { Some(Semantics::new(Role::Image)) }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_painter::image::ColorFormat;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn regions_share_texture() {
+    reset_test_env!();
+
+    // A sprite sheet with two 2x2 sprites side by side.
+    let sheet = Resource::new(PixelImage::new(vec![0; 4 * 4 * 2].into(), 4, 2, ColorFormat::Rgba8));
+    let left = ImageSource::region(sheet.clone(), Rect::new(Point::zero(), Size::splat(2.)));
+    let right = ImageSource::region(sheet.clone(), Rect::new(Point::new(2., 0.), Size::splat(2.)));
+    // The part outside the image is clamped.
+    let clamped =
+      ImageSource::region(sheet.clone(), Rect::new(Point::new(3., 1.), Size::splat(2.)));
+    assert_eq!(clamped.source_rect(), Rect::new(Point::new(3., 1.), Size::splat(1.)));
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @ { left.clone() }
+        @ { right.clone() }
+      }
+    });
+    wnd.draw_frame();
+
+    let frame = wnd.take_last_frame().unwrap();
+    let imgs: Vec<_> = frame
+      .commands
+      .iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand {
+          transform,
+          action: PaintPathAction::Paint { brush: CommandBrush::Image { img, .. }, .. },
+          ..
+        }) => Some((img.clone(), *transform)),
+        _ => None,
+      })
+      .collect();
+
+    assert_eq!(imgs.len(), 2);
+    assert!(imgs.iter().all(|(img, _)| img == &sheet));
+    // Every image maps its region to the position of its widget.
+    let (_, left_ts) = imgs[0];
+    let (_, right_ts) = imgs[1];
+    assert_eq!(left_ts.transform_point(Point::zero()), Point::zero());
+    assert_eq!(right_ts.transform_point(Point::new(2., 0.)), Point::new(2., 0.));
+  }
 }
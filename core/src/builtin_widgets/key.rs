@@ -1,6 +1,7 @@
 use std::{
   cmp::{Eq, Ord, PartialOrd},
   fmt::Debug,
+  hash::{DefaultHasher, Hash, Hasher},
 };
 
 use crate::prelude::*;
@@ -161,6 +162,37 @@ where
   fn record_before_value(&mut self, value: V) { self.before_value = Some(value); }
 }
 
+/// The keys of the children of a reactive `@for` list. Every child is keyed by
+/// its item, so the children of the same item are matched when the list is
+/// regenerated. The equal items are distinguished by their occurrences.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct ForKeys(ahash::HashMap<u64, usize>);
+
+impl ForKeys {
+  pub fn key_of(&mut self, item: &impl Hash) -> Key {
+    let mut hasher = DefaultHasher::new();
+    item.hash(&mut hasher);
+    let hash = hasher.finish();
+    let nth = self.0.entry(hash).or_default();
+    let key = complex_key!(hash, *nth);
+    *nth += 1;
+    key.into()
+  }
+
+  /// Key the `child` by `key`, unless it declares a `KeyWidget` of its own.
+  pub fn attach<'c>(key: Key, child: Widget<'c>) -> Widget<'c> {
+    child.on_build(move |id| {
+      let tree = BuildCtx::get_mut().tree_mut();
+      if id.query_ref::<Box<dyn AnyKey>>(tree).is_none() {
+        let key = KeyWidget { key, value: (), before_value: None, has_successor: false };
+        let key: Box<dyn AnyKey> = Box::new(Stateful::new(key));
+        id.attach_data(Box::new(Queryable(key)), tree);
+      }
+    })
+  }
+}
+
 macro_rules! from_key_impl {
   ($($ty: ty : $name: ident)*) => {
     $(
@@ -272,3 +304,44 @@ fn key_detect() {
   assert!(k3 != k1);
   assert!(ck1 == ck2);
 }
+
+#[test]
+fn for_list_keys() {
+  use crate::{reset_test_env, test_helper::*};
+  reset_test_env!();
+
+  let (list, w_list) = split_value(vec!['a', 'b', 'a']);
+  let mut wnd = TestWindow::new(fn_widget! {
+    @MockMulti {
+      @for c in $list {
+        @MockBox { size: Size::new(if c == 'a' { 10. } else { 20. }, 10.) }
+      }
+    }
+  });
+  let entered = |wnd: &TestWindow| {
+    let tree = wnd.tree();
+    let multi = tree.root().first_child(tree).unwrap();
+    multi
+      .children(tree)
+      .map(|c| {
+        let key = c.query_ref::<Box<dyn AnyKey>>(tree).unwrap();
+        let key = key
+          .as_any()
+          .downcast_ref::<Stateful<KeyWidget<()>>>();
+        let enter = key.unwrap().read().is_enter();
+        enter
+      })
+      .collect::<Vec<_>>()
+  };
+
+  wnd.draw_frame();
+  wnd.assert_root_size(Size::new(40., 10.));
+  assert_eq!(entered(&wnd), [true, true, true]);
+
+  *w_list.write() = vec!['a', 'a', 'c'];
+  wnd.draw_frame();
+  wnd.assert_root_size(Size::new(40., 10.));
+  // The children of the same items are reused, and the equal items are matched
+  // by their occurrences.
+  assert_eq!(entered(&wnd), [false, false, true]);
+}
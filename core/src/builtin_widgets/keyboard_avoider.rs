@@ -0,0 +1,158 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::prelude::*;
+
+/// A widget keeps the focused widget visible above the on-screen keyboard.
+///
+/// When the keyboard shows, it pads the bottom of its child by the part of it
+/// covered by the keyboard, then scrolls the nearest scroll view of the
+/// focused widget to show it. When the keyboard is dismissed, the padding is
+/// removed and the scroll view goes back to where it was.
+///
+/// The avoider should be sized by its parent, usually filling the window, so
+/// the padding doesn't change the covered part of it.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _ = fn_widget! {
+///   @KeyboardAvoider {
+///     @Container {
+///       size: Size::new(f32::INFINITY, 2000.),
+///       scrollable: Scrollable::Y,
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct KeyboardAvoider;
+
+impl<'c> ComposeChild<'c> for KeyboardAvoider {
+  type Child = Widget<'c>;
+
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let mut child = FatObj::new(child);
+    let avoider = child.get_track_id_widget().read().track_id();
+    fn_widget! {
+      let wnd = BuildCtx::get().window();
+      let keyboard = wnd.keyboard_rect_watcher();
+
+      let c_wnd = wnd.clone();
+      let c_avoider = avoider.clone();
+      let padding = pipe!(*$keyboard).map(move |rect| {
+        let overlap = rect
+          .zip(c_avoider.get())
+          .map_or(0., |(rect, id)| covered_height(&c_wnd, id, &rect));
+        EdgeInsets::only_bottom(overlap)
+      });
+
+      // The focused widget and the scroll position before it's scrolled to
+      // avoid the keyboard.
+      let scrolled: Rc<Cell<Option<(WidgetId, Point)>>> = <_>::default();
+      let u = watch!($keyboard.is_some())
+        .distinct_until_changed()
+        .subscribe(move |showing| {
+          let wnd = wnd.clone();
+          let scrolled = scrolled.clone();
+          let avoider = avoider.clone();
+          let c_wnd = wnd.clone();
+          wnd.once_layout_ready(move || {
+            if showing {
+              let focused = c_wnd.focusing().zip(avoider.get());
+              let Some((focused, avoider)) = focused else { return };
+              if avoider.ancestor_of(focused, c_wnd.tree()) {
+                with_scroll_view(focused, &c_wnd, |scroll| {
+                  scrolled.set(Some((focused, scroll.get_scroll_pos())));
+                  scroll.visible_widget(focused, Anchor::default(), &c_wnd);
+                });
+              }
+            } else if let Some((focused, pos)) = scrolled.take() {
+              if !focused.is_dropped(c_wnd.tree()) {
+                with_scroll_view(focused, &c_wnd, |scroll| scroll.jump_to(pos));
+              }
+            }
+          });
+        });
+
+      @ $child {
+        padding,
+        on_disposed: move |_| u.unsubscribe(),
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// The height of the bottom part of the widget covered by the keyboard.
+fn covered_height(wnd: &Window, id: WidgetId, keyboard: &Rect) -> f32 {
+  let Some(size) = wnd.widget_size(id) else { return 0. };
+  let bottom = wnd.map_to_global(Point::zero(), id).y + size.height;
+  (bottom - keyboard.min_y()).clamp(0., size.height)
+}
+
+/// Call `f` with the nearest scroll view of the widget, if there is one.
+fn with_scroll_view(id: WidgetId, wnd: &Window, f: impl FnOnce(&mut ScrollableWidget)) {
+  let ctx = ProviderCtx::collect_from(id, wnd.tree());
+  let Some(mut scroll) = ctx.get_provider_write::<ScrollableWidget>() else { return };
+  f(&mut scroll);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn set_keyboard_rect(wnd: &TestWindow, rect: Option<Rect>) {
+    let mut shell_wnd = wnd.shell_wnd().borrow_mut();
+    shell_wnd
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .keyboard_rect = rect;
+  }
+
+  #[test]
+  fn focused_above_keyboard() {
+    reset_test_env!();
+
+    let (input, w_input) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let w_input = w_input.clone_writer();
+        @KeyboardAvoider {
+          @MockBox {
+            size: INFINITY_SIZE,
+            @MockBox {
+              size: Size::new(200., 350.),
+              scrollable: Scrollable::Y,
+              @MockBox {
+                size: Size::new(200., 50.),
+                anchor: Anchor::top(300.),
+                auto_focus: true,
+                on_performed_layout: move |e| *w_input.write() = Some(e.current_target()),
+              }
+            }
+          }
+        }
+      },
+      Size::new(200., 400.),
+    );
+    wnd.draw_frame();
+    let input = input.read().unwrap();
+    let input_y = |wnd: &TestWindow| wnd.map_to_global(Point::zero(), input).y;
+    assert_eq!(wnd.focusing(), Some(input));
+    assert_eq!(input_y(&wnd), 300.);
+
+    let keyboard = Rect::new(Point::new(0., 250.), Size::new(200., 150.));
+    set_keyboard_rect(&wnd, Some(keyboard));
+    wnd.draw_frame();
+    assert_eq!(wnd.keyboard_rect(), Some(keyboard));
+    assert!(input_y(&wnd) + 50. <= keyboard.min_y());
+
+    set_keyboard_rect(&wnd, None);
+    wnd.draw_frame();
+    assert_eq!(input_y(&wnd), 300.);
+  }
+}
@@ -0,0 +1,99 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::prelude::*;
+
+/// A widget rebuilds its content only when the `key` changes, otherwise it
+/// keeps the subtree it built before.
+///
+/// Unlike `distinct_pipe!`, the key only needs to be `PartialEq + Clone`, and
+/// the build function receives the key. It's useful to skip rebuilding an
+/// expensive subtree when its inputs are modified but stay the same.
+///
+/// The memoized subtree lives in the place of the `Memo`, so if its parent is
+/// rebuilt, for example the theme or a provider of the context is changed, the
+/// content is rebuilt with the new context too.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let name = Stateful::new("Ribir".to_string());
+/// let _w = fn_widget! {
+///   Memo::new(pipe!($name.clone()), |name: &String| {
+///     @Text { text: format!("Hello, {name}!") }
+///   })
+/// };
+/// ```
+pub struct Memo(Widget<'static>);
+
+impl Memo {
+  /// Create a memo calls `build` with the value of `key` to build the content,
+  /// and calls it again only when the value of `key` is not equal to the last
+  /// one.
+  pub fn new<K, W, const M: usize>(
+    key: impl Pipe<Value = K>, mut build: impl FnMut(&K) -> W + 'static,
+  ) -> Self
+  where
+    K: PartialEq + Clone + 'static,
+    W: IntoWidget<'static, M>,
+  {
+    let last: Rc<RefCell<Option<K>>> = <_>::default();
+    let c_last = last.clone();
+    let content = key
+      .value_chain(move |s| {
+        s.filter(move |(_, key)| {
+          let mut last = c_last.borrow_mut();
+          let changed = last.as_ref() != Some(key);
+          if changed {
+            *last = Some(key.clone());
+          }
+          changed
+        })
+        .box_it()
+      })
+      .map(move |key| {
+        // The initial key isn't emitted by the stream, record it here.
+        *last.borrow_mut() = Some(key.clone());
+        build(&key).into_widget()
+      });
+    Self(content.into_widget())
+  }
+}
+
+impl IntoWidgetStrict<'static, FN> for Memo {
+  fn into_widget_strict(self) -> Widget<'static> { self.0 }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn rebuild_only_when_key_changed() {
+    reset_test_env!();
+
+    let (key, c_key) = split_value(1);
+    let (built, c_built) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      Memo::new(pipe!(*$key), move |key: &i32| {
+        *$c_built.write() += 1;
+        @MockBox { size: Size::new(*key as f32, 10.) }
+      })
+    });
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(*built.read(), 1);
+
+    // Modified but the same key.
+    *c_key.write() = 1;
+    wnd.draw_frame();
+    assert_eq!(*built.read(), 1);
+
+    *c_key.write() = 2;
+    wnd.draw_frame();
+    assert_eq!(*built.read(), 2);
+    wnd.assert_root_size(Size::new(2., 10.));
+  }
+}
@@ -1,4 +1,8 @@
-use std::{cell::RefCell, convert::Infallible};
+use std::{
+  cell::{Cell, RefCell},
+  convert::Infallible,
+  rc::Rc,
+};
 
 use rxrust::prelude::*;
 
@@ -6,6 +10,7 @@ use self::focus_mgr::FocusType;
 use crate::prelude::*;
 
 const MULTI_TAP_DURATION: Duration = Duration::from_millis(250);
+const HOVER_INTENT_DURATION: Duration = Duration::from_millis(300);
 
 bitflags! {
   #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
@@ -51,6 +56,8 @@ bitflags! {
     const TracePointerPressed = 1 << 20;
     #[doc="Indicates whether the pointer is pressed on this widget."]
     const PointerPressed = 1 << 21;
+    #[doc="Indicates whether the focus of this widget is gained by the keyboard."]
+    const FocusVisible = 1 << 22;
 
     #[doc="Indicates whether this widget has auto-focus functionality."]
     const AutoFocus = 1 << 47;
@@ -102,6 +109,13 @@ impl MixFlags {
   /// has focus.
   pub fn has_focus(&self) -> bool { self.contains(MixFlags::Focused) }
 
+  /// Indicates whether the focus of this widget (including its children) is
+  /// gained by the keyboard navigation, it's used to decide whether to show
+  /// the focus indicator.
+  ///
+  /// Like `has_focus`, it requires `MixBuiltin::trace_focus` to be called.
+  pub fn is_focus_visible(&self) -> bool { self.contains(MixFlags::FocusVisible) }
+
   /// Indicates whether the mouse is hovering over this widget (including its
   /// children).
   ///
@@ -152,8 +166,7 @@ impl MixBuiltin {
   /// Listen to all events
   pub fn on_event(&self, handler: impl FnMut(&mut Event) + 'static) -> &Self {
     self.silent_mark(MixFlags::AllListeners);
-    let _ = self.subject().subscribe(handler);
-    self
+    self.on_event_impl(handler)
   }
 
   pub fn on_mounted(&self, handler: impl FnOnce(&mut LifecycleEvent) + 'static) -> &Self {
@@ -218,6 +231,38 @@ impl MixBuiltin {
     impl_event_callback!(self, Pointer, PointerLeave, PointerEvent, handler)
   }
 
+  /// Listen to the hover status changes, the handler receives `true` when the
+  /// pointer enters this widget and `false` when it leaves.
+  pub fn on_hover_changed(&self, handler: impl FnMut(bool) + 'static) -> &Self {
+    self.silent_mark(MixFlags::Pointer);
+    let _ = self
+      .subject()
+      .filter_map(
+        (|e| match e {
+          Event::PointerEnter(_) => Some(true),
+          Event::PointerLeave(_) => Some(false),
+          _ => None,
+        }) as fn(&mut Event) -> Option<bool>,
+      )
+      .subscribe(handler);
+    self
+  }
+
+  /// Listen to the hover intent, the handler receives `true` only after the
+  /// pointer rests over this widget for a while, and `false` when the pointer
+  /// leaves after that. Passing over the widget quickly doesn't trigger it.
+  pub fn on_hover_intent(&self, handler: impl FnMut(bool) + 'static) -> &Self {
+    self.silent_mark(MixFlags::Pointer | MixFlags::Lifecycle);
+    let mut intent =
+      HoverIntent { handler: Rc::new(RefCell::new(handler)), fired: <_>::default(), timer: None };
+    self.on_event_impl(move |e| match e {
+      Event::PointerEnter(_) => intent.restart(),
+      Event::PointerMove(_) => intent.rest_again(),
+      Event::PointerLeave(_) | Event::Disposed(_) => intent.leave(),
+      _ => {}
+    })
+  }
+
   pub fn on_tap(&self, handler: impl FnMut(&mut PointerEvent) + 'static) -> &Self {
     impl_event_callback!(self, Pointer, Tap, PointerEvent, handler)
   }
@@ -350,9 +395,18 @@ impl MixBuiltin {
     if !self.contain_flag(MixFlags::TraceFocus) {
       self.silent_mark(MixFlags::TraceFocus);
       let flags = self.flags.clone_writer();
-      self.on_focus_in(move |_| flags.write().insert(MixFlags::Focused));
+      self.on_focus_in(move |e| {
+        let mut flags = flags.write();
+        flags.insert(MixFlags::Focused);
+        let visible = e.window().focus_reason() == FocusReason::Keyboard;
+        flags.set(MixFlags::FocusVisible, visible);
+      });
       let flags = self.flags.clone_writer();
-      self.on_focus_out(move |_| flags.write().remove(MixFlags::Focused));
+      self.on_focus_out(move |_| {
+        flags
+          .write()
+          .remove(MixFlags::Focused | MixFlags::FocusVisible)
+      });
     }
   }
 
@@ -380,6 +434,11 @@ impl MixBuiltin {
 
   fn subject(&self) -> EventSubject { self.subject.clone() }
 
+  fn on_event_impl(&self, handler: impl FnMut(&mut Event) + 'static) -> &Self {
+    let _ = self.subject().subscribe(handler);
+    self
+  }
+
   pub(crate) fn contain_flag(&self, t: MixFlags) -> bool { self.flags.read().contains(t) }
 
   fn silent_mark(&self, t: MixFlags) {
@@ -456,6 +515,47 @@ impl<'c> ComposeChild<'c> for MixBuiltin {
   }
 }
 
+/// The state of a hover intent, it waits for the pointer to rest by a timer
+/// that restarts whenever the pointer moves.
+struct HoverIntent<H> {
+  handler: Rc<RefCell<H>>,
+  fired: Rc<Cell<bool>>,
+  timer: Option<BoxSubscription<'static>>,
+}
+
+impl<H: FnMut(bool) + 'static> HoverIntent<H> {
+  fn restart(&mut self) {
+    self.cancel_timer();
+    let handler = self.handler.clone();
+    let fired = self.fired.clone();
+    let timer =
+      observable::timer((), HOVER_INTENT_DURATION, AppCtx::scheduler()).subscribe(move |_| {
+        fired.set(true);
+        (handler.borrow_mut())(true);
+      });
+    self.timer = Some(BoxSubscription::new(timer));
+  }
+
+  fn rest_again(&mut self) {
+    if self.timer.is_some() && !self.fired.get() {
+      self.restart();
+    }
+  }
+
+  fn leave(&mut self) {
+    self.cancel_timer();
+    if self.fired.replace(false) {
+      (self.handler.borrow_mut())(false);
+    }
+  }
+
+  fn cancel_timer(&mut self) {
+    if let Some(timer) = self.timer.take() {
+      timer.unsubscribe();
+    }
+  }
+}
+
 fn x_times_tap_map_filter(
   x: usize, dur: Duration, capture: bool,
 ) -> impl FnMut(&mut Event) -> Option<&mut PointerEvent> {
@@ -544,4 +644,89 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*outer_layout.read(), 2);
   }
+
+  #[test]
+  fn hover_intent_after_resting() {
+    reset_test_env!();
+
+    let (changes, w_changes) = split_value(vec![]);
+    let (intents, w_intents) = split_value(vec![]);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_hover_changed: move |hovered| $w_changes.write().push(hovered),
+          on_hover_intent: move |hovered| $w_intents.write().push(hovered),
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    // Passing over the widget quickly doesn't fire the intent.
+    wnd.cursor_move(Point::new(25., 25.));
+    wnd.draw_frame();
+    wnd.cursor_move(Point::new(75., 75.));
+    wnd.draw_frame();
+    assert_eq!(&*changes.read(), &[true, false]);
+
+    // Resting over the widget fires the intent once.
+    wnd.cursor_move(Point::new(25., 25.));
+    wnd.draw_frame();
+    assert!(intents.read().is_empty());
+    std::thread::sleep(HOVER_INTENT_DURATION);
+    wnd.draw_frame();
+    assert_eq!(&*intents.read(), &[true]);
+
+    wnd.cursor_move(Point::new(75., 75.));
+    wnd.draw_frame();
+    assert_eq!(&*changes.read(), &[true, false, true, false]);
+    assert_eq!(&*intents.read(), &[true, false]);
+  }
+
+  #[test]
+  fn focus_visible_only_by_keyboard() {
+    reset_test_env!();
+
+    let flags = Rc::new(RefCell::new(vec![]));
+    let c_flags = flags.clone();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut flags = c_flags.borrow_mut();
+        flags.clear();
+        let boxes = (0..2)
+          .map(|_| {
+            let mut w = @MockBox { size: Size::new(50., 50.), tab_index: 0i16 };
+            w.trace_focus();
+            flags.push(w.get_mix_flags_widget().clone_reader());
+            w
+          })
+          .collect::<Vec<_>>();
+        @MockMulti { @ { boxes } }
+      },
+      Size::new(100., 50.),
+    );
+    wnd.draw_frame();
+    let state = |idx: usize| {
+      let flags = *flags.borrow()[idx].read();
+      (flags.has_focus(), flags.is_focus_visible())
+    };
+
+    wnd.tap_at(Point::new(25., 25.));
+    wnd.draw_frame();
+    assert_eq!(wnd.focus_reason(), FocusReason::Pointer);
+    assert_eq!(state(0), (true, false));
+
+    wnd.key_press(PhysicalKey::Code(KeyCode::Tab), VirtualKey::Named(NamedKey::Tab));
+    wnd.draw_frame();
+    assert_eq!(wnd.focus_reason(), FocusReason::Keyboard);
+    assert_eq!(state(0), (false, false));
+    assert_eq!(state(1), (true, true));
+
+    // Clicking again hides the focus ring.
+    wnd.tap_at(Point::new(25., 25.));
+    wnd.draw_frame();
+    assert_eq!(state(0), (true, false));
+    assert_eq!(state(1), (false, false));
+  }
 }
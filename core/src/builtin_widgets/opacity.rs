@@ -24,9 +24,16 @@ impl WrapRender for Opacity {
   }
 
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
-    ctx.painter().apply_alpha(self.opacity);
+    // The opacity applies to the whole subtree as a group, so the overlapping
+    // descendants will not show through each other.
+    ctx.painter().apply_group_alpha(self.opacity);
     if self.opacity > 0. {
       host.paint(ctx)
     }
   }
+
+  fn debug_properties(&self, host: &dyn Render, props: &mut Vec<String>) {
+    props.push(format!("opacity: {}", self.opacity));
+    host.debug_properties(props)
+  }
 }
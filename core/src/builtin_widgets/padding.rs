@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use wrap_render::WrapRender;
 
 use crate::prelude::*;
@@ -19,10 +21,32 @@ use crate::prelude::*;
 ///   margin: EdgeInsets::all(10.),
 ///   background: Color::GREEN,
 /// };
+///
+/// The padding can also be relative, e.g. to the font size:
+/// ```
+/// use ribir::prelude::*;
+///
+/// let _em_padding = text! {
+///   text: "Padding of half the font size",
+///   padding: MeasureInsets::all(Measure::Em(0.5)),
+/// };
 /// ```
 #[derive(Default)]
 pub struct Padding {
-  pub padding: EdgeInsets,
+  pub padding: MeasureInsets,
+  /// The padding resolved to pixels by the last layout.
+  resolved: Cell<EdgeInsets>,
+}
+
+/// The insets of the four edges in [`Measure`]s, the `Percent` of the left and
+/// right edges is relative to the maximum width of the parent clamp, and the
+/// top and bottom edges to the maximum height.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Lerp)]
+pub struct MeasureInsets {
+  pub left: Measure,
+  pub right: Measure,
+  pub bottom: Measure,
+  pub top: Measure,
 }
 
 impl Declare for Padding {
@@ -35,7 +59,10 @@ impl_compose_child_for_wrap_render!(Padding, DirtyPhase::Layout);
 
 impl WrapRender for Padding {
   fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
-    let thickness = self.padding.thickness();
+    let base = MeasureBase::of(ctx, &ctx.window());
+    let padding = self.padding.into_pixel(clamp.max, &base);
+    self.resolved.set(padding);
+    let thickness = padding.thickness();
 
     let min = (clamp.min - thickness).max(ZERO_SIZE);
     let max = (clamp.max - thickness).max(ZERO_SIZE);
@@ -46,12 +73,14 @@ impl WrapRender for Padding {
   }
 
   fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
-    ctx.content_only_transform_apply(&Transform::translation(self.padding.left, self.padding.top));
+    let padding = self.resolved.get();
+    ctx.content_only_transform_apply(&Transform::translation(padding.left, padding.top));
     host.paint(ctx);
   }
 
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> {
-    let padding_matrix = Transform::translation(self.padding.left, self.padding.top);
+    let padding = self.resolved.get();
+    let padding_matrix = Transform::translation(padding.left, padding.top);
 
     let ts = host
       .get_transform()
@@ -59,11 +88,52 @@ impl WrapRender for Padding {
 
     Some(ts)
   }
+
+  fn baseline(&self, host: &dyn Render) -> Option<f32> {
+    host
+      .baseline()
+      .map(|b| b + self.resolved.get().top)
+  }
 }
 
 impl Padding {
   #[inline]
-  pub fn new(padding: EdgeInsets) -> Self { Self { padding } }
+  pub fn new(padding: impl Into<MeasureInsets>) -> Self {
+    Self { padding: padding.into(), resolved: Cell::default() }
+  }
+}
+
+impl MeasureInsets {
+  #[inline]
+  pub fn all(value: impl Into<Measure>) -> Self {
+    let value = value.into();
+    Self { left: value, right: value, bottom: value, top: value }
+  }
+
+  #[inline]
+  pub fn symmetrical(vertical: impl Into<Measure>, horizontal: impl Into<Measure>) -> Self {
+    let (vertical, horizontal) = (vertical.into(), horizontal.into());
+    Self { top: vertical, bottom: vertical, left: horizontal, right: horizontal }
+  }
+
+  /// Resolve the insets to pixels, the `max_clamp` is the maximum size of the
+  /// parent clamp that `Percent` relative to.
+  pub fn into_pixel(self, max_clamp: Size, base: &MeasureBase) -> EdgeInsets {
+    let Self { left, right, bottom, top } = self;
+    EdgeInsets {
+      left: left.into_pixel(max_clamp.width, base),
+      right: right.into_pixel(max_clamp.width, base),
+      bottom: bottom.into_pixel(max_clamp.height, base),
+      top: top.into_pixel(max_clamp.height, base),
+    }
+  }
+}
+
+impl From<EdgeInsets> for MeasureInsets {
+  fn from(value: EdgeInsets) -> Self {
+    let EdgeInsets { left, right, bottom, top } = value;
+    Self { left: left.into(), right: right.into(), bottom: bottom.into(), top: top.into() }
+  }
 }
 
 #[cfg(test)]
@@ -93,6 +163,29 @@ mod tests {
     LayoutCase::new(&[0, 0]).with_size(Size::new(100., 100.))
   );
 
+  #[test]
+  fn em_padding() {
+    crate::reset_test_env!();
+
+    let font_size = Stateful::new(16.);
+    let c_font_size = font_size.clone_watcher();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        font_size: pipe!(*$c_font_size),
+        @MockBox {
+          size: ZERO_SIZE,
+          padding: MeasureInsets::all(Measure::Em(1.5)),
+        }
+      }
+    });
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::splat(48.));
+
+    *font_size.write() = 24.;
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::splat(72.));
+  }
+
   #[test]
   #[cfg(not(target_arch = "wasm32"))]
   fn fix_padding_draw() {
@@ -0,0 +1,134 @@
+use wrap_render::WrapRender;
+
+use crate::prelude::*;
+
+/// This widget snaps the paint origin of the host widget to the device pixel
+/// grid, so the thin borders and dividers are drawn crisply rather than
+/// blurred across two device pixels.
+///
+/// Only the host widget itself is painted at the snapped origin, its children
+/// are positioned from the unsnapped origin and snap by themselves if they
+/// enable it, so the rounding never accumulates in nested widgets. The size is
+/// not snapped, a size that isn't a whole number of device pixels still has an
+/// anti-aliased edge.
+#[derive(Default, Clone)]
+pub struct PixelSnap {
+  /// If true, snap the paint origin of the host widget to the device pixel.
+  pub pixel_snap: bool,
+}
+
+impl Declare for PixelSnap {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl_compose_child_for_wrap_render!(PixelSnap, DirtyPhase::Paint);
+
+impl WrapRender for PixelSnap {
+  fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
+    host.perform_layout(clamp, ctx)
+  }
+
+  fn only_sized_by_parent(&self, host: &dyn Render) -> bool { host.only_sized_by_parent() }
+
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if !self.pixel_snap {
+      return host.paint(ctx);
+    }
+
+    let ratio = ctx.window().device_pixel_ratio();
+    let painter = ctx.painter();
+    let ts = *painter.transform();
+    let snap = |v: f32| (v * ratio).round() / ratio - v;
+    let offset = Vector::new(snap(ts.m31), snap(ts.m32));
+    painter.set_transform(ts.then_translate(offset));
+
+    host.paint(ctx);
+
+    // Back to the unsnapped origin for the children.
+    let painter = ctx.painter();
+    let ts = painter.transform().then_translate(-offset);
+    painter.set_transform(ts);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn painted_rects(wnd: &mut TestWindow) -> Vec<Rect> {
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame().unwrap();
+    frame
+      .commands
+      .iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(cmd) => Some(cmd.paint_bounds),
+        _ => None,
+      })
+      .collect()
+  }
+
+  fn is_aligned(rect: &Rect) -> bool {
+    let aligned = |v: f32| (v - v.round()).abs() < 1e-4;
+    aligned(rect.min_x()) && aligned(rect.min_y()) && aligned(rect.max_x()) && aligned(rect.max_y())
+  }
+
+  #[test]
+  fn snap_divider() {
+    reset_test_env!();
+
+    let divider = |snap: bool| {
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 1.),
+          margin: EdgeInsets::new(10.3, 0., 0., 5.6),
+          background: Color::BLACK,
+          pixel_snap: snap,
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(divider(false), Size::new(200., 200.));
+    let rects = painted_rects(&mut wnd);
+    assert_eq!(rects.len(), 1);
+    assert!(!is_aligned(&rects[0]));
+
+    let mut wnd = TestWindow::new_with_size(divider(true), Size::new(200., 200.));
+    let rects = painted_rects(&mut wnd);
+    assert_eq!(rects.len(), 1);
+    assert!(is_aligned(&rects[0]), "{:?}", rects[0]);
+    assert_eq!(rects[0].origin, Point::new(6., 10.));
+  }
+
+  #[test]
+  fn nested_snap_not_drift() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 10.),
+          margin: EdgeInsets::only_top(0.4),
+          background: Color::BLACK,
+          pixel_snap: true,
+          @MockBox {
+            size: Size::new(100., 1.),
+            margin: EdgeInsets::only_top(0.4),
+            background: Color::RED,
+            pixel_snap: true,
+          }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    let rects = painted_rects(&mut wnd);
+    assert_eq!(rects.len(), 2);
+    assert_eq!(rects[0].origin.y, 0.);
+    // The child is snapped from its real position 0.8, not from the snapped
+    // position of its parent.
+    assert_eq!(rects[1].origin.y, 1.);
+  }
+}
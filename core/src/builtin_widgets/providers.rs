@@ -487,6 +487,9 @@ impl ProviderCtx {
     self.setup_providers.push((id, providers));
   }
 
+  /// The number of the providers set up in the stack.
+  pub(crate) fn providers_depth(&self) -> usize { self.setup_providers.len() }
+
   /// Pop the providers from the stack and restore it.
   pub(crate) fn pop_providers(&mut self) -> Option<(WidgetId, *const Providers)> {
     self.setup_providers.pop().inspect(|(_, p)| {
@@ -672,9 +675,8 @@ impl Render for ProvidersRender {
   fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
     let Self { render, providers } = self;
     providers.setup_providers(ctx.as_mut());
-    let size = render.perform_layout(clamp, ctx);
-    providers.restore_providers(ctx.as_mut());
-    size
+    let guard = RestoreGuard { providers, ctx };
+    render.perform_layout(clamp, guard.ctx)
   }
 
   fn paint(&self, ctx: &mut PaintingCtx) {
@@ -699,6 +701,29 @@ impl Render for ProvidersRender {
   fn only_sized_by_parent(&self) -> bool { self.render.only_sized_by_parent() }
 
   fn get_transform(&self) -> Option<Transform> { self.render.get_transform() }
+
+  fn semantics(&self) -> Option<Semantics> { self.render.semantics() }
+
+  fn baseline(&self) -> Option<f32> { self.render.baseline() }
+
+  fn type_name(&self) -> &'static str { self.render.type_name() }
+
+  fn debug_properties(&self, props: &mut Vec<String>) { self.render.debug_properties(props) }
+}
+
+/// Restores the providers when it's dropped, so the context is clean even if
+/// the layout panics.
+struct RestoreGuard<'a, 'c> {
+  providers: &'a Providers,
+  ctx: &'a mut LayoutCtx<'c>,
+}
+
+impl Drop for RestoreGuard<'_, '_> {
+  fn drop(&mut self) {
+    self
+      .providers
+      .restore_providers(self.ctx.as_mut());
+  }
 }
 
 pub(crate) struct Setup {
@@ -0,0 +1,109 @@
+use crate::prelude::*;
+
+/// The insets of the window that are obscured by the notches, the rounded
+/// corners or the system bars of the display, such as on the mobile devices or
+/// in the mobile browsers. The desktop platforms report zero insets.
+///
+/// It's provided at the root of every window and updated when the insets of
+/// the window change. Obtain it with [`Provider::of`].
+///
+/// A [`SafeArea`] provides zero insets to its descendants, because its content
+/// is already kept out of the unsafe area.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SafeAreaInsets(pub EdgeInsets);
+
+/// A widget pads its child by the safe area insets of the window, so the
+/// child isn't obscured by the notches or the rounded corners of the display.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _ = fn_widget! {
+///   @SafeArea {
+///     @Text { text: "Never hidden by the notch." }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct SafeArea;
+
+impl<'c> ComposeChild<'c> for SafeArea {
+  type Child = Widget<'c>;
+
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    // Read the insets when it's building, so a nested safe area obtains the
+    // zero insets provided by its ancestor.
+    fn_widget! {
+      let ctx = BuildCtx::get();
+      let padding: DeclareInit<MeasureInsets> =
+        match Provider::state_of::<Watcher<Reader<SafeAreaInsets>>>(ctx) {
+          Some(insets) => {
+            let insets = insets.clone_watcher();
+            pipe!($insets.0).declare_into()
+          }
+          None => Provider::of::<SafeAreaInsets>(ctx)
+            .map(|insets| insets.0)
+            .unwrap_or_default()
+            .declare_into(),
+        };
+
+      let child = Providers::new([Provider::new(SafeAreaInsets::default())]).with_child(child);
+      FatObj::new(child).padding(padding)
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn set_safe_area(wnd: &TestWindow, insets: EdgeInsets) {
+    let mut shell_wnd = wnd.shell_wnd().borrow_mut();
+    shell_wnd
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .safe_area = insets;
+  }
+
+  #[test]
+  fn pad_by_safe_area() {
+    reset_test_env!();
+
+    let (layout, w_layout) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let w_layout = w_layout.clone_writer();
+        @SafeArea {
+          @SafeArea {
+            @MockMulti {
+              @MockBox {
+                size: INFINITY_SIZE,
+                on_performed_layout: move |e| {
+                  *w_layout.write() = Some((e.current_target(), e.box_size().unwrap()));
+                },
+              }
+            }
+          }
+        }
+      },
+      Size::new(200., 400.),
+    );
+    wnd.draw_frame();
+    let (id, size) = layout.read().unwrap();
+    assert_eq!(size, Size::new(200., 400.));
+    assert_eq!(wnd.map_to_global(Point::zero(), id), Point::zero());
+
+    set_safe_area(&wnd, EdgeInsets::only_top(44.));
+    wnd.draw_frame();
+    assert_eq!(wnd.safe_area_insets(), EdgeInsets::only_top(44.));
+    // The nested safe area doesn't pad the child again.
+    let (id, size) = layout.read().unwrap();
+    assert_eq!(size, Size::new(200., 356.));
+    assert_eq!(wnd.map_to_global(Point::zero(), id), Point::new(0., 44.));
+  }
+}
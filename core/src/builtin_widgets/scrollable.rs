@@ -1,4 +1,4 @@
-use std::cell::Cell;
+use std::{cell::Cell, rc::Rc};
 
 use crate::prelude::*;
 /// Enumerate to describe which direction allow widget to scroll.
@@ -16,17 +16,44 @@ pub enum Scrollable {
   Both,
 }
 
+/// The wheel events within this duration are considered as the same scroll
+/// gesture.
+const SCROLL_GESTURE_TIMEOUT: Duration = Duration::from_millis(200);
+
 /// Helper struct for builtin scrollable field.
-#[derive(Default)]
 pub struct ScrollableWidget {
   pub scrollable: Scrollable,
+  /// Whether to lock the scroll to the dominant axis once a scroll gesture
+  /// begins, until the gesture ends. It avoids the wobbly diagonal scroll of a
+  /// trackpad when the widget is scrollable in both directions.
+  pub scroll_direction_lock: bool,
+  /// The multiplier of the wheel delta.
+  pub scroll_sensitivity: f32,
   scroll_pos: Point,
   page: Size,
   content_size: Size,
+  /// The axis locked by the current scroll gesture and the time of its last
+  /// scroll.
+  gesture: Option<(Scrollable, Instant)>,
 
   view_id: Option<TrackId>,
 }
 
+impl Default for ScrollableWidget {
+  fn default() -> Self {
+    Self {
+      scrollable: <_>::default(),
+      scroll_direction_lock: false,
+      scroll_sensitivity: 1.,
+      scroll_pos: <_>::default(),
+      page: <_>::default(),
+      content_size: <_>::default(),
+      gesture: None,
+      view_id: None,
+    }
+  }
+}
+
 /// The provider of `ScrollableWidget` providers the descendant widgets to use
 /// it.
 ///
@@ -48,12 +75,35 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
   type Child = Widget<'c>;
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
+      // The pointer position and the scroll position when the drag starts.
+      let drag_start = Rc::new(Cell::new((Point::zero(), Point::zero())));
+      let c_drag_start = drag_start.clone();
       let mut view = @Viewport {
         scroll_dir: distinct_pipe!{
           let this = $this;
           this.scrollable
         },
-        on_wheel: move |e| $this.write().scroll(-e.delta_x, -e.delta_y),
+        on_wheel: move |e| $this
+          .write()
+          .wheel_scroll(-e.delta_x, -e.delta_y, Instant::now()),
+        on_pointer_down: move |e| {
+          let mut this = $this.silent();
+          if (this.is_x_scrollable() || this.is_y_scrollable()) && e.join_drag_gesture() {
+            c_drag_start.set((e.global_pos(), this.get_scroll_pos()));
+            // A drag begins a new scroll gesture.
+            this.end_scroll_gesture();
+          }
+        },
+        on_pointer_move: move |e| {
+          if e.is_gesture_winner() {
+            let (pos, scroll_pos) = drag_start.get();
+            let offset = pos - e.global_pos();
+            let mut this = $this.write();
+            let (x, y) = this.lock_direction(offset.x, offset.y, Instant::now());
+            this.jump_to(scroll_pos);
+            this.scroll(x, y);
+          }
+        },
       };
 
       let child = FatObj::new(child);
@@ -114,7 +164,9 @@ impl ScrollableWidget {
   /// view in horizontal direction, when the widget is out of the view.
   /// If Anchor.y is None, it will anchor the widget to the closest edge of the
   /// view in vertical direction, when the widget is out of the view.
-  pub fn visible_content_box(&mut self, rect: Rect, anchor: Anchor) {
+  ///
+  /// The `base` resolves the relative measures of the anchor.
+  pub fn visible_content_box(&mut self, rect: Rect, anchor: Anchor, base: &MeasureBase) {
     let view_size = self.scroll_view_size();
 
     let offset_x = anchor
@@ -128,7 +180,9 @@ impl ScrollableWidget {
           None
         }
       })
-      .map_or(self.scroll_pos.x, |x| rect.min_x() - x.into_pixel(rect.width(), view_size.width));
+      .map_or(self.scroll_pos.x, |x| {
+        rect.min_x() - x.into_pixel(rect.width(), view_size.width, base)
+      });
 
     let offset_y = anchor
       .y
@@ -141,7 +195,9 @@ impl ScrollableWidget {
           None
         }
       })
-      .map_or(self.scroll_pos.y, |y| rect.min_y() - y.into_pixel(rect.height(), view_size.height));
+      .map_or(self.scroll_pos.y, |y| {
+        rect.min_y() - y.into_pixel(rect.height(), view_size.height, base)
+      });
 
     self.jump_to(Point::new(offset_x, offset_y));
   }
@@ -156,7 +212,8 @@ impl ScrollableWidget {
     let Some(pos) = self.map_to_content(Point::zero(), child, wnd) else { return };
     let Some(size) = wnd.widget_size(child) else { return };
     let show_box = Rect::new(pos, size);
-    self.visible_content_box(show_box, anchor);
+    let base = MeasureBase::of_window(wnd);
+    self.visible_content_box(show_box, anchor, &base);
   }
 
   pub fn scroll(&mut self, x: f32, y: f32) {
@@ -170,6 +227,38 @@ impl ScrollableWidget {
     self.jump_to(new);
   }
 
+  /// Scroll by the delta of a wheel at `now`, the delta is scaled by the
+  /// `scroll_sensitivity` and locked to the dominant axis if
+  /// `scroll_direction_lock` is enabled.
+  pub fn wheel_scroll(&mut self, x: f32, y: f32, now: Instant) {
+    let gesture_ended = self
+      .gesture
+      .is_some_and(|(_, last)| now.saturating_duration_since(last) > SCROLL_GESTURE_TIMEOUT);
+    if gesture_ended {
+      self.end_scroll_gesture();
+    }
+    let sensitivity = self.scroll_sensitivity;
+    let (x, y) = self.lock_direction(x * sensitivity, y * sensitivity, now);
+    self.scroll(x, y);
+  }
+
+  /// End the current scroll gesture, release the locked direction.
+  pub fn end_scroll_gesture(&mut self) { self.gesture = None; }
+
+  fn lock_direction(&mut self, x: f32, y: f32, now: Instant) -> (f32, f32) {
+    if !self.scroll_direction_lock || self.scrollable != Scrollable::Both {
+      return (x, y);
+    }
+    let axis = match self.gesture {
+      Some((axis, _)) => axis,
+      None if x == 0. && y == 0. => return (x, y),
+      None if x.abs() > y.abs() => Scrollable::X,
+      None => Scrollable::Y,
+    };
+    self.gesture = Some((axis, now));
+    if axis == Scrollable::X { (x, 0.) } else { (0., y) }
+  }
+
   pub fn jump_to(&mut self, top_left: Point) {
     let max = self.max_scrollable();
     self.scroll_pos = top_left.clamp(Point::zero(), max.to_vector().to_point());
@@ -318,6 +407,45 @@ mod tests {
     test_assert(Scrollable::Both, 100., 100., 0., 0.);
   }
 
+  #[test]
+  fn direction_lock() {
+    reset_test_env!();
+
+    let (scroll, w_scroll) = split_value(None);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let mut w = @MockBox {
+          size: Size::new(1000., 1000.),
+          scrollable: Scrollable::Both,
+        };
+        *$w_scroll.write() = Some(w.get_scrollable_widget().clone_writer());
+        @ $w {
+          scroll_direction_lock: true,
+          scroll_sensitivity: 2.,
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    // A mostly-vertical but slightly diagonal scroll is locked to vertical.
+    wnd.cursor_move(Point::new(50., 50.));
+    wnd.wheel(Vector::new(-1., -10.));
+    wnd.wheel(Vector::new(-3., -10.));
+    wnd.wheel(Vector::new(-20., -10.));
+    wnd.draw_frame();
+    let pos = wnd.layout_info_by_path(&[0, 0]).unwrap().pos;
+    assert_eq!(pos, Point::new(0., -60.));
+
+    // The lock is released when the gesture ends.
+    let scroll = scroll.read().as_ref().unwrap().clone_writer();
+    let later = Instant::now() + SCROLL_GESTURE_TIMEOUT * 2;
+    scroll.write().wheel_scroll(10., 1., later);
+    wnd.draw_frame();
+    let pos = wnd.layout_info_by_path(&[0, 0]).unwrap().pos;
+    assert_eq!(pos, Point::new(-20., -60.));
+  }
+
   #[derive(SingleChild, Declare, Clone)]
   pub struct FixedBox {
     pub size: Size,
@@ -0,0 +1,259 @@
+use crate::{prelude::*, widget_tree::WidgetTree, wrap_render::*};
+
+/// The role of a widget, it tells the assistive technologies what the widget
+/// is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Role {
+  /// A widget without a specific role, it's usually a container.
+  #[default]
+  Generic,
+  /// The root of the window.
+  Window,
+  Button,
+  Checkbox,
+  Radio,
+  Switch,
+  Link,
+  TextBox,
+  Text,
+  Image,
+  List,
+  ListItem,
+  Tab,
+  Slider,
+  ProgressBar,
+}
+
+/// The semantics of a widget that describes it to the assistive technologies,
+/// such as the screen readers.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Semantics {
+  pub role: Role,
+  /// The accessible name of the widget. If it's not given, the widgets that
+  /// are named by their content, like a button, use the text of their
+  /// descendants.
+  pub label: Option<CowArc<str>>,
+  /// The checked state of a checkbox, a radio or a switch. `None` if the
+  /// widget is not checkable.
+  pub checked: Option<bool>,
+  pub disabled: bool,
+}
+
+/// A widget that declares the semantics of its child.
+///
+/// ### Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Text {
+///     text: "OK",
+///     semantics: Semantics::new(Role::Button),
+///   }
+/// };
+/// ```
+#[derive(Default, Clone)]
+pub struct SemanticsWidget {
+  pub semantics: Semantics,
+}
+
+/// A wrapper that gives the default semantics to a composed widget, like a
+/// button, which has no render object of its own to describe it. The explicit
+/// `semantics` declared on the widget is merged over it.
+///
+/// The semantics is evaluated when the semantics tree is exported, so it
+/// follows the latest state of the widget.
+pub struct DefaultSemantics(Box<dyn Fn() -> Semantics>);
+
+/// A node of the semantics tree, the semantics tree mirrors the widget tree
+/// but only keeps the widgets that have semantics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticsNode {
+  pub id: WidgetId,
+  pub semantics: Semantics,
+  pub children: Vec<SemanticsNode>,
+}
+
+impl Semantics {
+  pub fn new(role: Role) -> Self { Self { role, ..Default::default() } }
+
+  pub fn with_label(mut self, label: impl Into<CowArc<str>>) -> Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  pub fn with_checked(mut self, checked: bool) -> Self {
+    self.checked = Some(checked);
+    self
+  }
+
+  pub fn with_disabled(mut self, disabled: bool) -> Self {
+    self.disabled = disabled;
+    self
+  }
+
+  /// Return if the widget of this role takes its name from its content.
+  fn named_by_content(&self) -> bool {
+    matches!(
+      self.role,
+      Role::Button
+        | Role::Checkbox
+        | Role::Radio
+        | Role::Switch
+        | Role::Link
+        | Role::ListItem
+        | Role::Tab
+    )
+  }
+}
+
+impl Declare for SemanticsWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl<'c> ComposeChild<'c> for SemanticsWidget {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    child.attach_data(Box::new(this))
+  }
+}
+
+impl DefaultSemantics {
+  pub fn new(semantics: impl Fn() -> Semantics + 'static) -> Self { Self(Box::new(semantics)) }
+}
+
+impl WrapRender for DefaultSemantics {
+  fn semantics(&self, host: &dyn Render) -> Option<Semantics> {
+    let mut semantics = (self.0)();
+    // A label-only widget is named by its host, like the text of a button.
+    if semantics.label.is_none() {
+      semantics.label = host.semantics().and_then(|s| s.label);
+    }
+    Some(semantics)
+  }
+}
+
+impl_compose_child_for_wrap_render!(DefaultSemantics, DirtyPhase::Paint);
+
+impl SemanticsNode {
+  /// Find the first node in this subtree that matches the `role`, including
+  /// itself.
+  pub fn find(&self, role: Role) -> Option<&SemanticsNode> {
+    if self.semantics.role == role {
+      Some(self)
+    } else {
+      self.children.iter().find_map(|c| c.find(role))
+    }
+  }
+
+  fn content_text(&self, text: &mut String) {
+    if let Some(label) = self.semantics.label.as_ref() {
+      if !text.is_empty() {
+        text.push(' ');
+      }
+      text.push_str(label);
+    } else {
+      self
+        .children
+        .iter()
+        .for_each(|c| c.content_text(text));
+    }
+  }
+}
+
+impl Window {
+  /// Export the semantics tree of the window for the assistive technologies.
+  ///
+  /// A widget with the explicit semantics declared by `semantics` field uses
+  /// it, otherwise it uses the default semantics of its render object, for
+  /// example, a `Text` is a `Role::Text` labeled with its text.
+  pub fn semantics_tree(&self) -> SemanticsNode {
+    let tree = self.tree();
+    let root = tree.root();
+    let mut children = vec![];
+    collect_semantics(root, tree, &mut children);
+    SemanticsNode { id: root, semantics: Semantics::new(Role::Window), children }
+  }
+}
+
+fn collect_semantics(id: WidgetId, tree: &WidgetTree, out: &mut Vec<SemanticsNode>) {
+  let default = id.assert_get(tree).semantics();
+  let explicit = id
+    .query_ref::<SemanticsWidget>(tree)
+    .map(|w| w.semantics.clone());
+  let semantics = match (explicit, default) {
+    (Some(mut explicit), Some(default)) => {
+      if explicit.label.is_none() {
+        explicit.label = default.label;
+      }
+      Some(explicit)
+    }
+    (explicit, default) => explicit.or(default),
+  };
+
+  if let Some(mut semantics) = semantics {
+    let mut children = vec![];
+    for c in id.children(tree) {
+      collect_semantics(c, tree, &mut children);
+    }
+    if semantics.label.is_none() && semantics.named_by_content() {
+      let mut text = String::new();
+      children
+        .iter()
+        .for_each(|c| c.content_text(&mut text));
+      if !text.is_empty() {
+        semantics.label = Some(text.into());
+      }
+    }
+    out.push(SemanticsNode { id, semantics, children });
+  } else {
+    for c in id.children(tree) {
+      collect_semantics(c, tree, out);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn button_semantics() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(100., 30.),
+          semantics: Semantics::new(Role::Button).with_label("Submit"),
+        }
+        @MockBox {
+          size: Size::new(100., 30.),
+          semantics: Semantics::new(Role::Checkbox).with_checked(true),
+          @Text { text: "Remember me" }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.semantics_tree();
+    assert_eq!(tree.semantics.role, Role::Window);
+    assert_eq!(tree.children.len(), 2);
+
+    let btn = tree.find(Role::Button).unwrap();
+    assert_eq!(btn.semantics.label.as_deref(), Some("Submit"));
+    assert!(btn.children.is_empty());
+
+    // The checkbox is named by its content, and the text has a default
+    // semantics.
+    let checkbox = tree.find(Role::Checkbox).unwrap();
+    assert_eq!(checkbox.semantics.label.as_deref(), Some("Remember me"));
+    assert_eq!(checkbox.semantics.checked, Some(true));
+    let text = checkbox.find(Role::Text).unwrap();
+    assert_eq!(text.semantics.label.as_deref(), Some("Remember me"));
+  }
+}
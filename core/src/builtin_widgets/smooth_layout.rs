@@ -172,7 +172,7 @@ macro_rules! smooth_size_widget_impl {
           return host.perform_layout(clamp, ctx);
         }
 
-        self.switch_init_to_value(clamp.max);
+        self.switch_init_to_value(clamp.max, &MeasureBase::of(ctx, &ctx.window()));
 
         let SmoothImpl { force_layout, running, .. } = *self.0.read();
 
@@ -241,7 +241,7 @@ macro_rules! smooth_pos_widget_impl {
         }
 
         let size = host.perform_layout(clamp, ctx);
-        self.switch_init_to_value(size, clamp.max);
+        self.switch_init_to_value(size, clamp.max, &MeasureBase::of(ctx, &ctx.window()));
         size
       }
 
@@ -431,9 +431,9 @@ impl SmoothValue<VAnchor, f32> {
 }
 
 impl SmoothPos {
-  fn switch_init_to_value(&self, size: Size, max_clamp: Size) {
+  fn switch_init_to_value(&self, size: Size, max_clamp: Size, base: &MeasureBase) {
     let SmoothValue::Init(Some(v)) = self.0.read().value else { return };
-    let pos = v.into_pixel(size, max_clamp);
+    let pos = v.into_pixel(size, max_clamp, base);
     self.0.write().value = SmoothValue::Value(pos);
   }
 
@@ -447,9 +447,9 @@ impl SmoothPos {
 }
 
 impl SmoothX {
-  fn switch_init_to_value(&self, size: Size, max_clamp: Size) {
+  fn switch_init_to_value(&self, size: Size, max_clamp: Size, base: &MeasureBase) {
     let SmoothValue::Init(Some(v)) = self.0.read().value else { return };
-    let x = v.into_pixel(size.width, max_clamp.width);
+    let x = v.into_pixel(size.width, max_clamp.width, base);
     self.0.write().value = SmoothValue::Value(x);
   }
 
@@ -463,9 +463,9 @@ impl SmoothX {
 }
 
 impl SmoothY {
-  fn switch_init_to_value(&self, size: Size, max_clamp: Size) {
+  fn switch_init_to_value(&self, size: Size, max_clamp: Size, base: &MeasureBase) {
     let SmoothValue::Init(Some(v)) = self.0.read().value else { return };
-    let y = v.into_pixel(size.height, max_clamp.height);
+    let y = v.into_pixel(size.height, max_clamp.height, base);
     self.0.write().value = SmoothValue::Value(y);
   }
 
@@ -479,10 +479,12 @@ impl SmoothY {
 }
 
 impl SmoothSize {
-  fn switch_init_to_value(&self, max_clamp: Size) {
+  fn switch_init_to_value(&self, max_clamp: Size, base: &MeasureBase) {
     let SmoothValue::Init(Some(v)) = self.0.read().value else { return };
-    let value =
-      Size::new(v.width.into_pixel(max_clamp.width), v.height.into_pixel(max_clamp.height));
+    let value = Size::new(
+      v.width.into_pixel(max_clamp.width, base),
+      v.height.into_pixel(max_clamp.height, base),
+    );
     self.0.write().value = SmoothValue::Value(value);
   }
 
@@ -502,9 +504,9 @@ impl SmoothSize {
 }
 
 impl SmoothWidth {
-  fn switch_init_to_value(&self, max_clamp: Size) {
+  fn switch_init_to_value(&self, max_clamp: Size, base: &MeasureBase) {
     let SmoothValue::Init(Some(v)) = self.0.read().value else { return };
-    let width = v.into_pixel(max_clamp.width);
+    let width = v.into_pixel(max_clamp.width, base);
     self.0.write().value = SmoothValue::Value(width);
   }
 
@@ -524,9 +526,9 @@ impl SmoothWidth {
 }
 
 impl SmoothHeight {
-  fn switch_init_to_value(&self, max_clamp: Size) {
+  fn switch_init_to_value(&self, max_clamp: Size, base: &MeasureBase) {
     let SmoothValue::Init(Some(v)) = self.0.read().value else { return };
-    let height = v.into_pixel(max_clamp.height);
+    let height = v.into_pixel(max_clamp.height, base);
     self.0.write().value = SmoothValue::Value(height);
   }
 
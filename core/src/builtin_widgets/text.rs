@@ -1,4 +1,7 @@
-use std::cell::{Ref, RefCell};
+use std::{
+  cell::{Ref, RefCell},
+  ops::Range,
+};
 
 use font_db::GlyphBaseline;
 use typography::PlaceLineDirection;
@@ -12,6 +15,13 @@ pub struct Text {
   pub text: CowArc<str>,
   #[declare(default = TextAlign::Start)]
   pub text_align: TextAlign,
+  /// The byte ranges of the text to highlight, such as the matches of a
+  /// search. A range that spans the wrapped lines is highlighted on every line.
+  #[declare(default)]
+  pub highlights: Vec<Range<usize>>,
+  /// The brush to paint the background of the highlights.
+  #[declare(default = Color::YELLOW.with_alpha(0.4))]
+  pub highlight_brush: Brush,
   #[declare(skip)]
   glyphs: RefCell<Option<VisualGlyphs>>,
 }
@@ -71,6 +81,12 @@ impl Render for Text {
   #[inline]
   fn only_sized_by_parent(&self) -> bool { false }
 
+  fn semantics(&self) -> Option<Semantics> {
+    Some(Semantics::new(Role::Text).with_label(self.text.clone()))
+  }
+
+  fn baseline(&self) -> Option<f32> { self.glyphs()?.first_baseline() }
+
   fn paint(&self, ctx: &mut PaintingCtx) {
     let box_rect = Rect::from_size(ctx.box_size().unwrap());
     if ctx
@@ -81,6 +97,18 @@ impl Render for Text {
       return;
     };
 
+    let highlights = self.highlight_rects();
+    if !highlights.is_empty() {
+      let painter = ctx.painter();
+      let old_brush = painter.fill_brush().clone();
+      painter.set_fill_brush(self.highlight_brush.clone());
+      highlights.iter().for_each(|rect| {
+        painter.rect(rect);
+      });
+      painter.fill();
+      painter.set_fill_brush(old_brush);
+    }
+
     let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
     let visual_glyphs = self.glyphs().unwrap();
     let rect = visual_glyphs.visual_rect();
@@ -90,11 +118,28 @@ impl Render for Text {
 
 impl Text {
   pub fn new<const M: u8>(text: impl Into<CowArc<str>>) -> Self {
-    Self { text: text.into(), text_align: TextAlign::Start, glyphs: Default::default() }
+    Self {
+      text: text.into(),
+      text_align: TextAlign::Start,
+      highlights: vec![],
+      highlight_brush: Color::YELLOW.with_alpha(0.4).into(),
+      glyphs: Default::default(),
+    }
   }
   pub fn glyphs(&self) -> Option<Ref<VisualGlyphs>> {
     Ref::filter_map(self.glyphs.borrow(), |v| v.as_ref()).ok()
   }
+
+  /// Return the rects behind the glyphs of the highlights, one rect for every
+  /// line a highlight spans. It's empty before the text is laid out.
+  pub fn highlight_rects(&self) -> Vec<Rect> {
+    let Some(glyphs) = self.glyphs() else { return vec![] };
+    self
+      .highlights
+      .iter()
+      .flat_map(|rg| glyphs.select_range(rg))
+      .collect()
+  }
 }
 
 macro_rules! define_text_with_theme_style {
@@ -127,7 +172,11 @@ define_text_with_theme_style!(H6, title_small);
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-  use ribir::{core::test_helper::*, material as ribir_material, prelude::*};
+  use ribir::{
+    core::{reset_test_env, test_helper::*},
+    material as ribir_material,
+    prelude::*,
+  };
   use ribir_dev_helper::*;
 
   const WND_SIZE: Size = Size::new(164., 64.);
@@ -184,4 +233,99 @@ mod tests {
     .with_wnd_size(WND_SIZE)
     .with_comparison(0.0001)
   );
+
+  widget_image_tests!(
+    highlight_wrapped_word,
+    WidgetTester::new(fn_widget! {
+      @Text {
+        text: "Hello ribir world!",
+        text_overflow: TextOverflow::AutoWrap,
+        highlights: vec![0..1, 6..17],
+      }
+    })
+    .with_wnd_size(Size::new(96., 64.))
+  );
+
+  widget_image_tests!(
+    generic_families,
+    WidgetTester::new(self::column! {
+      @Text {
+        text: "Serif ribir!",
+        font_face: FontFace { families: Box::new([FontFamily::Serif]), ..<_>::default() },
+      }
+      @Text {
+        text: "Monospace ribir!",
+        font_face: FontFace { families: Box::new([FontFamily::Monospace]), ..<_>::default() },
+      }
+    })
+    .with_wnd_size(Size::new(128., 64.))
+  );
+
+  #[test]
+  fn highlight_rects_across_lines() {
+    reset_test_env!();
+
+    let mut text = Text::new::<0>("Hello ribir world!");
+    text.highlights.push(6..17);
+    let text = Stateful::new(text);
+    let c_text = text.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let text = FatObj::new(c_text.clone_writer());
+        @ $text { text_overflow: TextOverflow::AutoWrap }
+      },
+      Size::new(96., 64.),
+    );
+    wnd.draw_frame();
+
+    let text = text.read();
+    let glyphs = text.glyphs().unwrap();
+    assert_eq!(glyphs.glyph_row_count(), 2);
+
+    let rects = text.highlight_rects();
+    assert_eq!(rects.len(), 2);
+    let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+
+    // The first line is highlighted from `ribir` to the end of the line.
+    let (row, col) = glyphs.position_by_cluster(6);
+    let start = glyphs.glyph_rect(row, col);
+    assert_eq!(row, 0);
+    assert!(close(rects[0].min_x(), start.min_x()));
+
+    // The second line is highlighted from its start to the end of `world`.
+    let (row, col) = glyphs.position_by_cluster(16);
+    let end = glyphs.glyph_rect(row, col);
+    assert_eq!(row, 1);
+    assert!(close(rects[1].min_x(), glyphs.glyph_rect(1, 0).min_x()));
+    assert!(close(rects[1].max_x(), end.max_x()));
+    assert!(rects[0].max_y() <= rects[1].min_y());
+  }
+
+  #[test]
+  fn generic_families_use_bundled_font() {
+    reset_test_env!();
+
+    let glyphs = |families: Box<[FontFamily]>| {
+      let style = TextStyle {
+        font_size: 14.,
+        font_face: FontFace { families, ..<_>::default() },
+        letter_space: 0.,
+        line_height: 16.,
+        overflow: TextOverflow::Overflow,
+        first_line_indent: Em(0.),
+        hanging_indent: Em(0.),
+      };
+      let glyphs = text_glyph("Hello ribir!".into(), &style, TextAlign::Start, Size::splat(100.));
+      glyphs
+        .glyphs()
+        .map(|g| (g.face_id, g.glyph_id, g.x_advance, g.x_offset))
+        .collect::<Vec<_>>()
+    };
+
+    let lato = glyphs(Box::new([FontFamily::Name("Lato".into())]));
+    assert!(!lato.is_empty());
+    for family in [FontFamily::Serif, FontFamily::SansSerif, FontFamily::Monospace] {
+      assert_eq!(glyphs(Box::new([family])), lato);
+    }
+  }
 }
@@ -237,7 +237,15 @@ fn typography_theme() -> TypographyTheme {
     };
     let overflow = TextOverflow::Overflow;
     TextTheme {
-      text: TextStyle { line_height, font_size, letter_space, font_face, overflow },
+      text: TextStyle {
+        line_height,
+        font_size,
+        letter_space,
+        font_face,
+        overflow,
+        first_line_indent: Em(0.),
+        hanging_indent: Em(0.),
+      },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
         decoration_color: Color::BLACK.with_alpha(0.87).into(),
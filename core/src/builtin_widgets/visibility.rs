@@ -56,6 +56,11 @@ impl WrapRender for VisibilityRender {
       HitTest { hit: false, can_hit_child: false }
     }
   }
+
+  fn debug_properties(&self, host: &dyn Render, props: &mut Vec<String>) {
+    props.push(format!("visible: {}", self.display));
+    host.debug_properties(props)
+  }
 }
 
 impl Visibility {
@@ -1,7 +1,14 @@
-use std::{borrow::Cow, io::Error};
+use std::{
+  borrow::Cow,
+  hash::{Hash, Hasher},
+  io::Error,
+};
 
 use log::warn;
 use ribir_painter::PixelImage;
+use rxrust::prelude::*;
+
+use crate::{context::AppCtx, ticker::Duration};
 
 pub trait Clipboard {
   // read the string from the clipboard
@@ -17,13 +24,38 @@ pub trait Clipboard {
   fn write_img(&mut self, img: &PixelImage) -> Result<(), Error>;
 
   // read the custom format from the clipboard
-  fn read(&mut self, format: &str) -> Result<Cow<[u8]>, Error>;
+  fn read(&mut self, format: &str) -> Result<Cow<'_, [u8]>, Error>;
 
   // write the custom format from the clipboard
   fn write(&mut self, format: &str, data: &[u8]) -> Result<(), Error>;
 
   // clear all content in the clipboard
   fn clear(&mut self) -> Result<(), Error>;
+
+  /// Return a token that changes when the content of the clipboard changes,
+  /// `None` if the clipboard can't be read. The default implementation hashes
+  /// the text of the clipboard, so the changes of the other formats may be
+  /// missed.
+  fn change_token(&mut self) -> Option<u64> {
+    let text = self.read_text().ok()?;
+    let mut hasher = ahash::AHasher::default();
+    text.hash(&mut hasher);
+    Some(hasher.finish())
+  }
+}
+
+/// Call `f` when the content of the application clipboard changes. The
+/// clipboard is polled every `interval` by its `change_token`, unsubscribe the
+/// returned subscription to stop it.
+pub fn subscribe_changes(interval: Duration, mut f: impl FnMut() + 'static) -> impl Subscription {
+  let mut last = AppCtx::clipboard().borrow_mut().change_token();
+  observable::interval(interval, AppCtx::scheduler()).subscribe(move |_| {
+    let token = AppCtx::clipboard().borrow_mut().change_token();
+    if token != last {
+      last = token;
+      f();
+    }
+  })
 }
 
 pub(crate) struct MockClipboard {}
@@ -49,7 +81,7 @@ impl Clipboard for MockClipboard {
     Err(Error::new(std::io::ErrorKind::Unsupported, "clipboard write_img"))
   }
 
-  fn read(&mut self, format: &str) -> Result<Cow<[u8]>, Error> {
+  fn read(&mut self, format: &str) -> Result<Cow<'_, [u8]>, Error> {
     warn!("read {format} data from clipboard");
     Err(Error::new(std::io::ErrorKind::Unsupported, "clipboard read format {format}"))
   }
@@ -63,4 +95,84 @@ impl Clipboard for MockClipboard {
     warn!("clear content of clipboard");
     Err(Error::new(std::io::ErrorKind::Unsupported, "clipboard clear"))
   }
+
+  fn change_token(&mut self) -> Option<u64> { None }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::Cell, rc::Rc};
+
+  use super::*;
+  use crate::{reset_test_env, timer::Timer};
+
+  #[derive(Default)]
+  struct TextClipboard(String);
+
+  impl Clipboard for TextClipboard {
+    fn read_text(&mut self) -> Result<String, Error> { Ok(self.0.clone()) }
+
+    fn write_text(&mut self, text: &str) -> Result<(), Error> {
+      self.0 = text.to_string();
+      Ok(())
+    }
+
+    fn read_img(&mut self) -> Result<PixelImage, Error> { Err(Error::other("unsupported")) }
+
+    fn write_img(&mut self, _: &PixelImage) -> Result<(), Error> {
+      Err(Error::other("unsupported"))
+    }
+
+    fn read(&mut self, _: &str) -> Result<Cow<'_, [u8]>, Error> { Err(Error::other("unsupported")) }
+
+    fn write(&mut self, _: &str, _: &[u8]) -> Result<(), Error> { Err(Error::other("unsupported")) }
+
+    fn clear(&mut self) -> Result<(), Error> {
+      self.0.clear();
+      Ok(())
+    }
+  }
+
+  fn poll() {
+    std::thread::sleep(Duration::from_millis(2));
+    Timer::wake_timeout_futures();
+    AppCtx::run_until_stalled();
+  }
+
+  #[test]
+  fn notify_clipboard_changes() {
+    reset_test_env!();
+
+    AppCtx::set_clipboard(Box::new(TextClipboard::default()));
+    let changes = Rc::new(Cell::new(0));
+    let c_changes = changes.clone();
+    let subscription =
+      subscribe_changes(Duration::from_millis(1), move || c_changes.set(c_changes.get() + 1));
+
+    poll();
+    assert_eq!(changes.get(), 0);
+
+    AppCtx::clipboard()
+      .borrow_mut()
+      .write_text("hello")
+      .unwrap();
+    poll();
+    assert_eq!(changes.get(), 1);
+
+    // Writing the same content is not a change.
+    AppCtx::clipboard()
+      .borrow_mut()
+      .write_text("hello")
+      .unwrap();
+    poll();
+    assert_eq!(changes.get(), 1);
+
+    subscription.unsubscribe();
+    AppCtx::clipboard()
+      .borrow_mut()
+      .write_text("world")
+      .unwrap();
+    poll();
+    assert_eq!(changes.get(), 1);
+  }
 }
@@ -145,6 +145,19 @@ impl AppCtx {
   #[track_caller]
   pub fn font_db() -> &'static Sc<RefCell<FontDB>> { &Self::shared().font_db }
 
+  /// Replace the font database with the one that only contains the fonts
+  /// bundled by Ribir, see [`FontDB::load_test_fonts`].
+  ///
+  /// Call it at the beginning of a test, after [`AppCtx::new_lock_scope`], so
+  /// the text of the test renders the same on every machine.
+  pub fn use_test_fonts() {
+    let mut font_db = FontDB::default();
+    font_db.load_test_fonts();
+    let ctx = Self::shared();
+    *ctx.font_db.borrow_mut() = font_db;
+    *ctx.typography_store.borrow_mut() = TypographyStore::new(ctx.font_db.clone());
+  }
+
   /// This function returns a stream of app ticks, where each frame of the app
   /// will emit a tick notification.
   pub fn frame_ticks() -> &'static Subject<'static, Instant, Infallible> {
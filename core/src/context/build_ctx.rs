@@ -1,7 +1,7 @@
 #![allow(static_mut_refs)]
 use std::ptr::NonNull;
 
-use crate::{local_sender::LocalSender, prelude::*};
+use crate::{local_sender::LocalSender, prelude::*, widget_tree::widget_id::RenderQueryable};
 
 /// A context provide during build the widget tree.
 pub struct BuildCtx {
@@ -12,6 +12,9 @@ pub struct BuildCtx {
   /// this process; hence, we use 'static to avoid introducing a lifetime for
   /// the BuildCtx.
   children: Vec<(WidgetId, Widget<'static>)>,
+  /// The widgets allocated by the running `try_build`, they're disposed if the
+  /// build panics.
+  allocated: Option<Vec<WidgetId>>,
 }
 
 impl BuildCtx {
@@ -46,6 +49,14 @@ impl BuildCtx {
 
   pub(crate) fn tree_ptr(&self) -> *mut WidgetTree { self.tree.as_ptr() }
 
+  pub(crate) fn alloc_node(&mut self, node: Box<dyn RenderQueryable>) -> WidgetId {
+    let id = self.tree_mut().alloc_node(node);
+    if let Some(allocated) = self.allocated.as_mut() {
+      allocated.push(id);
+    }
+    id
+  }
+
   pub(crate) fn build(&mut self, widget: Widget<'_>) -> WidgetId {
     let size = self.children.len();
     let root = widget.call(self);
@@ -62,6 +73,42 @@ impl BuildCtx {
     root
   }
 
+  /// Build the widget, if it panics, the context is restored to the state
+  /// before building, the partially built widgets are disposed and the panic
+  /// payload is returned.
+  pub(crate) fn try_build(&mut self, widget: Widget<'_>) -> std::thread::Result<WidgetId> {
+    let children = self.children.len();
+    let providers = self.provider_ctx.providers_depth();
+    let outer = self.allocated.replace(vec![]);
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.build(widget)));
+    let allocated = std::mem::replace(&mut self.allocated, outer).unwrap_or_default();
+    if res.is_err() {
+      self.children.truncate(children);
+      while self.provider_ctx.providers_depth() > providers {
+        self.provider_ctx.pop_providers();
+      }
+      self.dispose_partial(&allocated);
+    } else if let Some(outer) = self.allocated.as_mut() {
+      outer.extend(allocated);
+    }
+    res
+  }
+
+  /// Dispose the subtrees of the `allocated` widgets of a failed build, so
+  /// their subscriptions are released by their `Disposed` event.
+  fn dispose_partial(&mut self, allocated: &[WidgetId]) {
+    let tree = self.tree_mut();
+    let set: ahash::HashSet<_> = allocated.iter().copied().collect();
+    let roots: Vec<_> = allocated
+      .iter()
+      .filter(|id| id.parent(tree).is_none_or(|p| !set.contains(&p)))
+      .copied()
+      .collect();
+    roots
+      .into_iter()
+      .for_each(|id| id.dispose_subtree(tree));
+  }
+
   pub(crate) fn build_parent(&mut self, parent: Widget<'_>, children: Vec<Widget<'_>>) -> WidgetId {
     let root = self.build(parent);
     let p = root.single_leaf(self.tree_mut());
@@ -120,7 +167,7 @@ impl BuildCtx {
   pub(crate) fn set_for(startup: WidgetId, tree: NonNull<WidgetTree>) {
     let t = unsafe { tree.as_ref() };
     let provider_ctx = ProviderCtx::collect_from(startup, t);
-    let ctx = BuildCtx { tree, children: <_>::default(), provider_ctx };
+    let ctx = BuildCtx { tree, children: <_>::default(), provider_ctx, allocated: None };
 
     BuildCtx::set(ctx);
   }
@@ -135,7 +182,7 @@ impl BuildCtx {
   }
 
   pub(crate) fn empty(tree: NonNull<WidgetTree>) -> Self {
-    Self { tree, children: <_>::default(), provider_ctx: <_>::default() }
+    Self { tree, children: <_>::default(), provider_ctx: <_>::default(), allocated: None }
   }
 }
 
@@ -107,6 +107,10 @@ impl<'a> LayoutCtx<'a> {
     self.tree.store.layout_info_or_default(child).size = Some(size);
   }
 
+  /// Return the baseline of the widget that has been laid out, see
+  /// [`Render::baseline`](crate::prelude::Render::baseline).
+  pub fn widget_baseline(&self, id: WidgetId) -> Option<f32> { id.assert_get(self.tree).baseline() }
+
   /// Split a children iterator from the context, returning a tuple of `&mut
   /// LayoutCtx` and the iterator of the children.
   pub fn split_children(&mut self) -> (&mut Self, impl Iterator<Item = WidgetId> + '_) {
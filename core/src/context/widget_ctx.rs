@@ -28,9 +28,8 @@ pub trait WidgetCtx {
   fn single_child(&self) -> Option<WidgetId>;
   /// Return the single child of `widget`.
   /// # Panic
-  /// panic if widget have multi child.
-  #[inline]
-  fn assert_single_child(&self) -> WidgetId { self.single_child().expect("Must have one child.") }
+  /// panic if widget does not have exactly one child.
+  fn assert_single_child(&self) -> WidgetId;
   /// Return if `widget` have child.
   fn has_child(&self) -> bool { self.first_child().is_some() }
   /// Return the first child of widget.
@@ -126,6 +125,10 @@ impl<T: WidgetCtxImpl> WidgetCtx for T {
   #[track_caller]
   fn single_child(&self) -> Option<WidgetId> { self.id().single_child(self.tree()) }
 
+  #[inline]
+  #[track_caller]
+  fn assert_single_child(&self) -> WidgetId { self.id().assert_single_child(self.tree()) }
+
   #[inline]
   fn first_child(&self) -> Option<WidgetId> { self.id().first_child(self.tree()) }
 
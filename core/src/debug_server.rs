@@ -0,0 +1,515 @@
+//! A debug server lets the external tools inspect a running application, it's
+//! enabled by the `debug` feature.
+//!
+//! The server answers the HTTP requests:
+//!
+//! - `GET /status` returns `{"status": "ok"}`.
+//! - `GET /tree` returns the widget tree of every window in JSON, see
+//!   [`Window::json_tree`].
+//! - `GET /screenshot` returns a PNG of the last frame of a window, see
+//!   [`Window::capture`]. Pass `?window=<id>` to pick the window, otherwise the
+//!   first window is captured.
+//! - `POST /input` injects an input event described in JSON to a window, the
+//!   positions are in logical pixels relative to the window. The event is one
+//!   of `{"type": "tap", "x": 10, "y": 20}`, `{"type": "text", "text": "hi"}`
+//!   or `{"type": "key", "key": "Enter"}`, add a `"window": <id>` field to pick
+//!   the window, otherwise the event is sent to the first window.
+use std::{
+  io::{BufRead, BufReader, Read, Write},
+  net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+  sync::mpsc,
+  time::Duration,
+};
+
+use futures::{
+  StreamExt,
+  channel::mpsc::{UnboundedSender, unbounded},
+};
+use serde_json::{Value, json};
+use winit::keyboard::{Key as VirtualKey, KeyCode, NamedKey, NativeKeyCode, PhysicalKey};
+
+use crate::{prelude::*, window::WindowId};
+
+/// How long a request waits for the UI thread to reply.
+const UI_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long the server waits for a client to send its request, the requests
+/// are served one by one, so a stalled client can't block the others longer.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The environment variable to set the address the debug server of the
+/// application listens on, it's [`DEFAULT_ADDR`] if it's not set.
+pub const DEBUG_SERVER_ADDR_ENV: &str = "RIBIR_DEBUG_SERVER";
+/// The address the debug server of the application listens on by default.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:2333";
+
+/// The requests served by the UI thread, with the channel to reply.
+enum UiRequest {
+  Tree(mpsc::Sender<Value>),
+  Screenshot(Option<WindowId>, mpsc::Sender<Option<PixelImage>>),
+  /// Reply whether the window to receive the input exists.
+  Input(Option<WindowId>, InputEvent, mpsc::Sender<bool>),
+}
+
+/// An input event injected by `POST /input`.
+enum InputEvent {
+  /// Press and release the left mouse button at the position.
+  Tap(Point),
+  /// Type the text to the focused widget.
+  Text(String),
+  /// Press and release a key.
+  Key(PhysicalKey, VirtualKey),
+}
+
+/// Start the debug server listening on `addr`, and return the address it's
+/// bound to. Pass the port `0` to let the system pick a free port.
+///
+/// The connections are accepted on a background thread, but the widget trees
+/// are snapshotted and the windows are captured by a task running on the UI
+/// thread, so the server never reads a tree while it's being modified. It must
+/// be called on the UI thread.
+pub fn start_debug_server(addr: impl ToSocketAddrs) -> std::io::Result<SocketAddr> {
+  let listener = TcpListener::bind(addr)?;
+  let local_addr = listener.local_addr()?;
+
+  let (ui_tx, mut ui_rx) = unbounded::<UiRequest>();
+  AppCtx::spawn_local(async move {
+    while let Some(request) = ui_rx.next().await {
+      match request {
+        UiRequest::Tree(reply) => {
+          let _ = reply.send(windows_snapshot());
+        }
+        UiRequest::Screenshot(id, reply) => {
+          let wnd = find_window(id);
+          let capture = wnd.map(|wnd| wnd.capture());
+          // Don't block the other requests until the capture is done.
+          let _ = AppCtx::spawn_local(async move {
+            let img = match capture {
+              Some(capture) => capture.await,
+              None => None,
+            };
+            let _ = reply.send(img);
+          });
+        }
+        UiRequest::Input(id, event, reply) => {
+          let wnd = find_window(id);
+          if let Some(wnd) = &wnd {
+            inject_input(wnd, event);
+          }
+          let _ = reply.send(wnd.is_some());
+        }
+      }
+    }
+  })
+  .map_err(std::io::Error::other)?;
+
+  std::thread::spawn(move || {
+    for stream in listener.incoming().flatten() {
+      let served = stream
+        .set_read_timeout(Some(READ_TIMEOUT))
+        .and_then(|_| handle_request(stream, &ui_tx));
+      if let Err(err) = served {
+        log::warn!("Debug server failed to respond: {err}");
+      }
+    }
+  });
+
+  Ok(local_addr)
+}
+
+/// Start the debug server listening on the address of the
+/// [`DEBUG_SERVER_ADDR_ENV`] environment variable, or the [`DEFAULT_ADDR`] if
+/// it's not set. The application calls it when it runs with the `debug`
+/// feature.
+pub fn start_debug_server_from_env() -> std::io::Result<SocketAddr> {
+  let addr = std::env::var(DEBUG_SERVER_ADDR_ENV).unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+  start_debug_server(addr)
+}
+
+fn handle_request(
+  mut stream: TcpStream, ui_tx: &UnboundedSender<UiRequest>,
+) -> std::io::Result<()> {
+  let mut reader = BufReader::new(&stream);
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+  // The server only needs the length of the body from the headers.
+  let mut content_len = 0;
+  let mut header = String::new();
+  while reader.read_line(&mut header)? > 2 {
+    if let Some((name, value)) = header.split_once(':') {
+      if name.eq_ignore_ascii_case("content-length") {
+        content_len = value.trim().parse().unwrap_or(0);
+      }
+    }
+    header.clear();
+  }
+  let mut body = vec![0; content_len];
+  reader.read_exact(&mut body)?;
+
+  let mut parts = request_line.split_whitespace();
+  let method = parts.next().unwrap_or_default();
+  let target = parts.next().unwrap_or_default();
+  let (path, query) = target.split_once('?').unwrap_or((target, ""));
+  let response = match (method, path) {
+    ("GET", "/status") => Response::json("200 OK", json!({ "status": "ok" })),
+    ("GET", "/tree") => match ask_ui(ui_tx, UiRequest::Tree) {
+      Some(snapshot) => Response::json("200 OK", snapshot),
+      None => Response::ui_unavailable(),
+    },
+    ("GET", "/screenshot") => {
+      let id = query
+        .split('&')
+        .find_map(|kv| kv.strip_prefix("window="))
+        .map(|id| id.parse::<u64>().map(WindowId::from));
+      match id.transpose() {
+        Err(_) => Response::json("400 Bad Request", json!({ "error": "Invalid window id." })),
+        Ok(id) => match ask_ui(ui_tx, |reply| UiRequest::Screenshot(id, reply)) {
+          Some(Some(img)) => {
+            let mut png = vec![];
+            match img.write_as_png(&mut png) {
+              Ok(()) => Response { status: "200 OK", content_type: "image/png", body: png },
+              Err(err) => Response::json(
+                "500 Internal Server Error",
+                json!({ "error": format!("Failed to encode the screenshot: {err}") }),
+              ),
+            }
+          }
+          Some(None) => {
+            Response::json("404 Not Found", json!({ "error": "No window can be captured." }))
+          }
+          None => Response::ui_unavailable(),
+        },
+      }
+    }
+    ("POST", "/input") => match parse_input(&body) {
+      Err(err) => Response::json("400 Bad Request", json!({ "error": err })),
+      Ok((id, event)) => match ask_ui(ui_tx, |reply| UiRequest::Input(id, event, reply)) {
+        Some(true) => Response::json("200 OK", json!({ "status": "ok" })),
+        Some(false) => Response::json("404 Not Found", json!({ "error": "No window found." })),
+        None => Response::ui_unavailable(),
+      },
+    },
+    _ => Response::json(
+      "404 Not Found",
+      json!({ "error": format!("No endpoint for `{method} {path}`.") }),
+    ),
+  };
+
+  let Response { status, content_type, body } = response;
+  write!(
+    stream,
+    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: \
+     close\r\n\r\n",
+    body.len()
+  )?;
+  stream.write_all(&body)
+}
+
+struct Response {
+  status: &'static str,
+  content_type: &'static str,
+  body: Vec<u8>,
+}
+
+impl Response {
+  fn json(status: &'static str, body: Value) -> Self {
+    Response { status, content_type: "application/json", body: body.to_string().into_bytes() }
+  }
+
+  fn ui_unavailable() -> Self {
+    Response::json("503 Service Unavailable", json!({ "error": "The UI thread doesn't respond." }))
+  }
+}
+
+/// Send a request to the UI thread and wait for its reply, `None` if the UI
+/// thread doesn't respond in time.
+fn ask_ui<T>(
+  ui_tx: &UnboundedSender<UiRequest>, request: impl FnOnce(mpsc::Sender<T>) -> UiRequest,
+) -> Option<T> {
+  let (tx, rx) = mpsc::channel();
+  ui_tx.unbounded_send(request(tx)).ok()?;
+  rx.recv_timeout(UI_TIMEOUT).ok()
+}
+
+fn find_window(id: Option<WindowId>) -> Option<Sc<Window>> {
+  match id {
+    Some(id) => AppCtx::get_window(id),
+    None => AppCtx::windows()
+      .borrow()
+      .values()
+      .next()
+      .cloned(),
+  }
+}
+
+fn parse_input(body: &[u8]) -> Result<(Option<WindowId>, InputEvent), String> {
+  let json: Value = serde_json::from_slice(body).map_err(|err| format!("Invalid JSON: {err}"))?;
+  let id = match json.get("window") {
+    Some(id) => Some(id.as_u64().ok_or("Invalid window id.")?.into()),
+    None => None,
+  };
+  let number = |name: &str| {
+    json[name]
+      .as_f64()
+      .map(|v| v as f32)
+      .ok_or(format!("Missing the number field `{name}`."))
+  };
+  let string = |name: &str| {
+    json[name]
+      .as_str()
+      .ok_or(format!("Missing the string field `{name}`."))
+  };
+  let event = match string("type")? {
+    "tap" => InputEvent::Tap(Point::new(number("x")?, number("y")?)),
+    "text" => InputEvent::Text(string("text")?.to_string()),
+    "key" => {
+      let key = string("key")?;
+      parse_key(key).ok_or(format!("Unknown key `{key}`."))?
+    }
+    ty => return Err(format!("Unknown input type `{ty}`.")),
+  };
+  Ok((id, event))
+}
+
+/// Parse a single character or a named key, such as `Enter` or `ArrowLeft`.
+fn parse_key(key: &str) -> Option<InputEvent> {
+  let mut chars = key.chars();
+  if let (Some(c), None) = (chars.next(), chars.next()) {
+    let physical = PhysicalKey::Unidentified(NativeKeyCode::Unidentified);
+    return Some(InputEvent::Key(physical, VirtualKey::Character(c.to_string().into())));
+  }
+  let (code, named) = match key {
+    "Enter" => (KeyCode::Enter, NamedKey::Enter),
+    "Tab" => (KeyCode::Tab, NamedKey::Tab),
+    "Space" => (KeyCode::Space, NamedKey::Space),
+    "Backspace" => (KeyCode::Backspace, NamedKey::Backspace),
+    "Delete" => (KeyCode::Delete, NamedKey::Delete),
+    "Escape" => (KeyCode::Escape, NamedKey::Escape),
+    "Home" => (KeyCode::Home, NamedKey::Home),
+    "End" => (KeyCode::End, NamedKey::End),
+    "ArrowLeft" => (KeyCode::ArrowLeft, NamedKey::ArrowLeft),
+    "ArrowRight" => (KeyCode::ArrowRight, NamedKey::ArrowRight),
+    "ArrowUp" => (KeyCode::ArrowUp, NamedKey::ArrowUp),
+    "ArrowDown" => (KeyCode::ArrowDown, NamedKey::ArrowDown),
+    _ => return None,
+  };
+  Some(InputEvent::Key(PhysicalKey::Code(code), VirtualKey::Named(named)))
+}
+
+/// Inject the event the same way as the application dispatches the native
+/// events.
+fn inject_input(wnd: &Window, event: InputEvent) {
+  match event {
+    InputEvent::Tap(pos) => wnd.inject_tap(pos),
+    InputEvent::Text(text) => wnd.inject_text(&text),
+    InputEvent::Key(physical_key, key) => wnd.inject_key_press(physical_key, key),
+  }
+  wnd.emit_events();
+}
+
+fn windows_snapshot() -> Value {
+  let windows = AppCtx::windows()
+    .borrow()
+    .values()
+    .map(|wnd| json!({ "id": u64::from(wnd.id()), "tree": wnd.json_tree() }))
+    .collect::<Vec<_>>();
+  json!({ "windows": windows })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Read;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn get(addr: SocketAddr, path: &'static str) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+      let mut stream = TcpStream::connect(addr).unwrap();
+      write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+      let mut response = vec![];
+      stream.read_to_end(&mut response).unwrap();
+      response
+    })
+  }
+
+  /// Run the UI thread tasks until the request is responded.
+  fn wait_raw_response(request: std::thread::JoinHandle<Vec<u8>>) -> Vec<u8> {
+    while !request.is_finished() {
+      AppCtx::run_until_stalled();
+      std::thread::sleep(Duration::from_millis(1));
+    }
+    request.join().unwrap()
+  }
+
+  fn wait_response(request: std::thread::JoinHandle<Vec<u8>>) -> String {
+    String::from_utf8(wait_raw_response(request)).unwrap()
+  }
+
+  #[test]
+  fn query_tree() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @MockBox { size: Size::new(100., 50.) } },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+    let addr = start_debug_server("127.0.0.1:0").unwrap();
+
+    let response = wait_response(get(addr, "/tree"));
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"));
+    let json: Value = serde_json::from_str(body).unwrap();
+    let windows = json["windows"].as_array().unwrap();
+    assert_eq!(windows.len(), 1);
+    assert_eq!(windows[0]["id"], u64::from(wnd.id()));
+    let root = &windows[0]["tree"];
+    assert_eq!(root["type"], "Root");
+    let child = &root["children"][0];
+    assert_eq!(child["type"], "MockBox");
+    assert_eq!(child["rect"], json!({ "x": 0., "y": 0., "width": 100., "height": 50. }));
+
+    let response = wait_response(get(addr, "/status"));
+    assert!(response.ends_with(r#"{"status":"ok"}"#));
+    let response = wait_response(get(addr, "/unknown"));
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+  }
+
+  #[test]
+  fn stalled_client() {
+    reset_test_env!();
+
+    let addr = start_debug_server("127.0.0.1:0").unwrap();
+    // A client connects but never sends its request.
+    let _stalled = TcpStream::connect(addr).unwrap();
+
+    let response = wait_response(get(addr, "/status"));
+    assert!(response.ends_with(r#"{"status":"ok"}"#));
+  }
+
+  #[test]
+  fn screenshot() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { @MockBox { size: Size::new(100., 50.) } },
+      Size::new(200., 100.),
+    );
+    wnd
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .renderer = Some(Box::new(|frame: &Frame| {
+      let (w, h) = (frame.viewport.width() as u32, frame.viewport.height() as u32);
+      PixelImage::new(vec![255; (w * h * 4) as usize].into(), w, h, image::ColorFormat::Rgba8)
+    }));
+    let addr = start_debug_server("127.0.0.1:0").unwrap();
+
+    // Nothing to capture before the first frame.
+    let response = wait_response(get(addr, "/screenshot"));
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+    wnd.draw_frame();
+    let response = wait_raw_response(get(addr, "/screenshot"));
+    let split = response
+      .windows(4)
+      .position(|w| w == b"\r\n\r\n")
+      .unwrap();
+    let head = std::str::from_utf8(&response[..split]).unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK"));
+    assert!(head.contains("Content-Type: image/png"));
+    let img = PixelImage::from_bytes(&response[split + 4..]).unwrap();
+    assert_eq!(img.size(), DeviceSize::new(200, 100));
+
+    let response = wait_response(get(addr, "/screenshot?window=abc"));
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"));
+  }
+
+  fn post(
+    addr: SocketAddr, path: &'static str, body: &'static str,
+  ) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+      let mut stream = TcpStream::connect(addr).unwrap();
+      write!(
+        stream,
+        "POST {path} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+      )
+      .unwrap();
+      let mut response = vec![];
+      stream.read_to_end(&mut response).unwrap();
+      response
+    })
+  }
+
+  #[test]
+  fn inject_input() {
+    reset_test_env!();
+
+    let count = Stateful::new(0);
+    let c_count = count.clone_writer();
+    let chars = Stateful::new(String::new());
+    let c_chars = chars.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(50., 50.),
+          auto_focus: true,
+          on_tap: move |_| *$c_count.write() += 1,
+          on_chars: move |e| $c_chars.write().push_str(&e.chars),
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    let addr = start_debug_server("127.0.0.1:0").unwrap();
+
+    let response = wait_response(post(addr, "/input", r#"{"type": "tap", "x": 80, "y": 80}"#));
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert_eq!(*count.read(), 0);
+
+    let response = wait_response(post(addr, "/input", r#"{"type": "tap", "x": 25, "y": 25}"#));
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert_eq!(*count.read(), 1);
+
+    wait_response(post(addr, "/input", r#"{"type": "text", "text": "h"}"#));
+    wait_response(post(addr, "/input", r#"{"type": "key", "key": "i"}"#));
+    assert_eq!(&*chars.read(), "hi");
+
+    let response = wait_response(post(addr, "/input", r#"{"type": "swipe"}"#));
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request"));
+  }
+
+  #[test]
+  fn inject_tap_in_logical_pixels() {
+    reset_test_env!();
+
+    let count = Stateful::new(0);
+    let c_count = count.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(50., 50.),
+          on_tap: move |_| *$c_count.write() += 1,
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<TestShellWindow>()
+      .unwrap()
+      .device_pixel_ratio = 2.;
+    wnd.draw_frame();
+    let addr = start_debug_server("127.0.0.1:0").unwrap();
+
+    wait_response(post(addr, "/input", r#"{"type": "tap", "x": 80, "y": 80}"#));
+    assert_eq!(*count.read(), 0);
+    wait_response(post(addr, "/input", r#"{"type": "tap", "x": 40, "y": 40}"#));
+    assert_eq!(*count.read(), 1);
+  }
+}
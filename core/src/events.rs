@@ -11,6 +11,9 @@ use crate::{
 
 pub(crate) mod dispatcher;
 pub use dispatcher::GrabPointer;
+mod gesture_arena;
+pub(crate) use gesture_arena::GestureArena;
+pub use gesture_arena::DRAG_SLOP;
 pub mod custom_event;
 pub use custom_event::*;
 mod pointers;
@@ -29,6 +32,7 @@ mod lifecycle;
 pub use lifecycle::*;
 
 pub(crate) mod focus_mgr;
+pub use focus_mgr::FocusReason;
 mod listener_impl_helper;
 
 pub struct CommonEvent {
@@ -1,4 +1,7 @@
-use std::cell::RefCell;
+use std::{
+  cell::{Cell, RefCell},
+  collections::VecDeque,
+};
 
 use winit::event::{DeviceId, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
 
@@ -11,9 +14,19 @@ use crate::{
 ///
 /// The widget corresponding to the wid will receives all pointer events
 /// (on_pointer_down, on_pointer_move, and on_pointer_up) until the handle's
-/// release() is called or the GrabPointer is dropped; other widgets get no
-/// pointer events at all.
-pub struct GrabPointer(Sc<RefCell<Option<WidgetId>>>);
+/// release() is called, the GrabPointer is dropped or the widget is disposed;
+/// other widgets get no pointer events at all.
+pub struct GrabPointer {
+  slot: Sc<RefCell<Option<PointerGrabber>>>,
+  handle: usize,
+}
+
+/// The widget that grabs the pointer and the handle that owns the grab.
+#[derive(Clone, Copy)]
+struct PointerGrabber {
+  wid: WidgetId,
+  handle: usize,
+}
 
 impl GrabPointer {
   /// Grab the pointer input to the widget corresponding to the wid.
@@ -29,15 +42,26 @@ impl GrabPointer {
 }
 
 impl Drop for GrabPointer {
-  fn drop(&mut self) { self.0.borrow_mut().take(); }
+  fn drop(&mut self) {
+    let mut slot = self.slot.borrow_mut();
+    // The grab may already be released by the disposal of the widget, and the
+    // pointer may be grabbed by another one.
+    if slot.is_some_and(|g| g.handle == self.handle) {
+      slot.take();
+    }
+  }
 }
 
 pub(crate) struct Dispatcher {
   wnd_id: WindowId,
   pub(crate) info: DispatchInfo,
   pub(crate) entered_widgets: Vec<WidgetId>,
-  grab_mouse_wid: Sc<RefCell<Option<WidgetId>>>,
+  grab_mouse_wid: Sc<RefCell<Option<PointerGrabber>>>,
+  grab_handles: Cell<usize>,
+  /// The grab held by the window for `capture_pointer`.
+  captured: RefCell<Option<GrabPointer>>,
   pointer_down_wid: Option<WidgetId>,
+  pub(crate) arena: GestureArena,
 }
 
 impl Dispatcher {
@@ -47,19 +71,64 @@ impl Dispatcher {
       info: <_>::default(),
       entered_widgets: vec![],
       grab_mouse_wid: Sc::new(RefCell::new(None)),
+      grab_handles: Cell::new(0),
+      captured: RefCell::new(None),
       pointer_down_wid: None,
+      arena: <_>::default(),
     }
   }
 
   pub(crate) fn grab_pointer(&self, wid: WidgetId) -> Option<GrabPointer> {
     if self.grab_mouse_wid.borrow().is_none() {
-      *self.grab_mouse_wid.borrow_mut() = Some(wid);
-      Some(GrabPointer(self.grab_mouse_wid.clone()))
+      let handle = self.grab_handles.get() + 1;
+      self.grab_handles.set(handle);
+      *self.grab_mouse_wid.borrow_mut() = Some(PointerGrabber { wid, handle });
+      Some(GrabPointer { slot: self.grab_mouse_wid.clone(), handle })
     } else {
       None
     }
   }
 
+  /// Capture the pointer to the `wid`, return `false` if another widget has
+  /// already grabbed it.
+  pub(crate) fn capture_pointer(&self, wid: WidgetId) -> bool {
+    if let Some(grabbing) = self.grabbing_widget() {
+      return grabbing == wid;
+    }
+    let grab = self.grab_pointer(wid);
+    *self.captured.borrow_mut() = grab;
+    true
+  }
+
+  /// Release the pointer captured by `capture_pointer`, the pointer grabbed by
+  /// a `GrabPointer` handle is not affected.
+  pub(crate) fn release_pointer(&self) {
+    let captured = self.captured.borrow_mut().take();
+    drop(captured);
+  }
+
+  /// Release the pointer if it's captured by the `wid`.
+  pub(crate) fn release_pointer_of(&self, wid: WidgetId) {
+    let captured = self
+      .captured
+      .borrow_mut()
+      .take_if(|g| g.slot.borrow().is_some_and(|g| g.wid == wid));
+    drop(captured);
+  }
+
+  /// Release the pointer grabbed by the disposed widget `wid`, no matter who
+  /// owns the grab.
+  pub(crate) fn on_widget_disposed(&self, wid: WidgetId) {
+    let mut slot = self.grab_mouse_wid.borrow_mut();
+    if slot.is_some_and(|g| g.wid == wid) {
+      slot.take();
+    }
+  }
+
+  pub(crate) fn pointer_capture(&self) -> Option<WidgetId> { self.grabbing_widget() }
+
+  fn grabbing_widget(&self) -> Option<WidgetId> { self.grab_mouse_wid.borrow().map(|g| g.wid) }
+
   fn window(&self) -> Sc<Window> {
     AppCtx::get_window(self.wnd_id).expect("The window of the `Dispatcher` already dropped")
   }
@@ -72,8 +141,15 @@ pub(crate) struct DispatchInfo {
   cursor_pos: Point,
   /// The current state of the keyboard modifiers
   modifiers: ModifiersState,
+  /// The recent samples of the cursor, the oldest is the first.
+  history: VecDeque<PointerSample>,
 }
 
+/// The max count of the pointer samples kept in the history.
+const POINTER_HISTORY_SIZE: usize = 32;
+/// The duration of the recent pointer history used to compute the velocity.
+const VELOCITY_DURATION: Duration = Duration::from_millis(100);
+
 impl Dispatcher {
   pub fn dispatch(&mut self, event: WindowEvent, wnd_factor: f64) {
     match event {
@@ -94,6 +170,7 @@ impl Dispatcher {
   ) {
     let wnd = self.window();
     if let Some(id) = wnd.focusing() {
+      let is_repeat = is_repeat && state == ElementState::Pressed;
       let e = DelayEvent::KeyBoard { key, state, physical_key, is_repeat, location, id };
       wnd.add_delay_event(e);
     } else if key == VirtualKey::Named(NamedKey::Tab) && state == ElementState::Pressed {
@@ -118,13 +195,14 @@ impl Dispatcher {
   }
 
   fn cursor_press_down(&mut self, hit: Option<WidgetId>) {
-    let grab_pointer = *self.grab_mouse_wid.borrow();
+    let grab_pointer = self.grabbing_widget();
     if let Some(grab_pointer) = grab_pointer {
       self
         .window()
         .add_delay_event(DelayEvent::GrabPointerDown(grab_pointer));
     } else {
       self.pointer_down_wid = None;
+      self.arena.open(self.info.cursor_pos);
       if let Some(hit) = hit {
         self.pointer_down_wid = Some(hit);
         self
@@ -136,9 +214,14 @@ impl Dispatcher {
 
   fn cursor_press_up(&mut self, hit: Option<WidgetId>) {
     let wnd = self.window();
-    let grab_pointer = *self.grab_mouse_wid.borrow();
+    let grab_pointer = self.grabbing_widget();
+    let winner = self.arena.close();
     if let Some(grab_pointer) = grab_pointer {
       wnd.add_delay_event(DelayEvent::GrabPointerUp(grab_pointer));
+      // The winner of the gesture arena only captures the pointer until released.
+      if let Some(winner) = winner {
+        self.release_pointer_of(winner);
+      }
     } else {
       if let Some(hit) = hit {
         wnd.add_delay_event(DelayEvent::PointerUp(hit));
@@ -154,22 +237,51 @@ impl Dispatcher {
 
   pub fn cursor_move_to(&mut self, position: Point) {
     self.info.cursor_pos = position;
-    let grab_pointer = *self.grab_mouse_wid.borrow();
+    let sample = PointerSample { pos: position, time: Instant::now() };
+    if self.info.history.len() == POINTER_HISTORY_SIZE {
+      self.info.history.pop_front();
+    }
+    self.info.history.push_back(sample);
+    if let Some(winner) = self.arena.resolve(position) {
+      // The drag wins the gesture, so the pointer sequence is no longer a tap.
+      if !self.capture_pointer(winner) {
+        // Another widget has captured the pointer, it wins.
+        self.arena.close();
+      } else if let Some(down) = self.pointer_down_wid.take() {
+        self
+          .window()
+          .add_delay_event(DelayEvent::PointerCancel(down));
+      }
+    }
+    let grab_pointer = self.grabbing_widget();
     if let Some(grab_pointer) = grab_pointer {
-      self
-        .window()
-        .add_delay_event(DelayEvent::GrabPointerMove(grab_pointer));
+      self.add_pointer_move(grab_pointer, true, sample);
     } else {
       let new_hit = self.hit_widget();
       self.pointer_enter_leave_dispatch(new_hit);
       if let Some(hit) = new_hit {
-        self
-          .window()
-          .add_delay_event(DelayEvent::PointerMove(hit));
+        self.add_pointer_move(hit, false, sample);
       }
     }
   }
 
+  /// Queue a pointer move event, the moves to the same target before the
+  /// events are emitted are coalesced into one event with all their samples.
+  fn add_pointer_move(&self, wid: WidgetId, grab: bool, sample: PointerSample) {
+    let wnd = self.window();
+    let mut queue = wnd.delay_emitter.borrow_mut();
+    match (queue.back_mut(), grab) {
+      (Some(DelayEvent::PointerMove(id, samples)), false)
+      | (Some(DelayEvent::GrabPointerMove(id, samples)), true)
+        if *id == wid =>
+      {
+        samples.push(sample)
+      }
+      _ if grab => queue.push_back(DelayEvent::GrabPointerMove(wid, vec![sample])),
+      _ => queue.push_back(DelayEvent::PointerMove(wid, vec![sample])),
+    }
+  }
+
   pub fn on_cursor_left(&mut self) {
     if self.grab_mouse_wid.borrow().is_none() {
       self.info.cursor_pos = Point::new(-1., -1.);
@@ -198,7 +310,10 @@ impl Dispatcher {
               })
             });
             if let Some(focus_id) = nearest_focus {
-              wnd.focus_mgr.borrow_mut().focus(focus_id, tree);
+              wnd
+                .focus_mgr
+                .borrow_mut()
+                .focus(focus_id, FocusReason::Pointer, tree);
             } else {
               wnd.focus_mgr.borrow_mut().blur(tree);
             }
@@ -246,14 +361,18 @@ impl Dispatcher {
       .find(|wid| !(*wid).is_dropped(tree))
       .copied();
 
-    if let Some(old) = old {
-      let ancestor = new_hit.and_then(|w| w.lowest_common_ancestor(old, tree));
-      wnd.add_delay_event(DelayEvent::PointerLeave { bottom: old, up: ancestor });
-    };
+    // Nothing entered or left if the hit widget is not changed, don't interrupt
+    // the coalescing of the pointer moves.
+    if old != new_hit {
+      if let Some(old) = old {
+        let ancestor = new_hit.and_then(|w| w.lowest_common_ancestor(old, tree));
+        wnd.add_delay_event(DelayEvent::PointerLeave { bottom: old, up: ancestor });
+      };
 
-    if let Some(new) = new_hit {
-      let ancestor = old.and_then(|o| o.lowest_common_ancestor(new, tree));
-      wnd.add_delay_event(DelayEvent::PointerEnter { bottom: new, up: ancestor });
+      if let Some(new) = new_hit {
+        let ancestor = old.and_then(|o| o.lowest_common_ancestor(new, tree));
+        wnd.add_delay_event(DelayEvent::PointerEnter { bottom: new, up: ancestor });
+      }
     }
 
     self.entered_widgets = new_hit.map_or(vec![], |wid| wid.ancestors(tree).collect::<Vec<_>>());
@@ -323,6 +442,32 @@ impl DispatchInfo {
 
   #[inline]
   pub fn mouse_buttons(&self) -> MouseButtons { self.mouse_button.1 }
+
+  pub fn pointer_history(&self) -> impl Iterator<Item = &PointerSample> { self.history.iter() }
+
+  /// The last sample of the cursor, or the current position if it's not
+  /// sampled, such as the cursor left the window.
+  pub fn last_sample(&self) -> PointerSample {
+    self
+      .history
+      .back()
+      .filter(|s| s.pos == self.cursor_pos)
+      .copied()
+      .unwrap_or(PointerSample { pos: self.cursor_pos, time: Instant::now() })
+  }
+
+  /// The velocity of the cursor at `now` by the samples in the recent
+  /// [`VELOCITY_DURATION`].
+  pub fn pointer_velocity(&self, now: Instant) -> Vector {
+    let recent = |s: &&PointerSample| now.saturating_duration_since(s.time) <= VELOCITY_DURATION;
+    let first = self.history.iter().find(recent);
+    match (first, self.history.back()) {
+      (Some(first), Some(last)) if last.time > first.time => {
+        (last.pos - first.pos) / (last.time - first.time).as_secs_f32()
+      }
+      _ => Vector::zero(),
+    }
+  }
 }
 
 #[cfg(test)]
@@ -810,4 +955,94 @@ mod tests {
 
     assert_ne!(w.unwrap(), wnd.tree().root());
   }
+
+  #[test]
+  fn capture_pointer_while_drag() {
+    reset_test_env!();
+
+    let (moves, w_moves) = split_value(vec![]);
+    let (mounted, w_mounted) = split_value(true);
+    let w = fn_widget! {
+      @MockMulti {
+        @ {
+          pipe!(*$mounted).map(move |v| v.then(move || fn_widget! {
+            @MockBox {
+              size: Size::new(100., 30.),
+              on_pointer_down: move |e| { e.capture_pointer(e.current_target()); },
+              on_pointer_move: move |e| $w_moves.write().push(e.position()),
+              on_pointer_up: move |e| e.release_pointer(),
+            }
+          }))
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(400., 400.));
+    wnd.draw_frame();
+
+    wnd
+      .cursor_move(Point::new(10., 10.))
+      .mouse_press()
+      .cursor_move(Point::new(200., 200.));
+    wnd.run_frame_tasks();
+    assert_eq!(*moves.read(), [Point::new(10., 10.), Point::new(200., 200.)]);
+    assert!(wnd.pointer_capture().is_some());
+
+    // The widget doesn't receive the events after the pointer is released.
+    wnd
+      .mouse_release()
+      .cursor_move(Point::new(300., 300.));
+    wnd.run_frame_tasks();
+    assert_eq!(moves.read().len(), 2);
+    assert_eq!(wnd.pointer_capture(), None);
+
+    // Disposing the capturing widget releases the capture.
+    wnd
+      .cursor_move(Point::new(10., 10.))
+      .mouse_press();
+    wnd.run_frame_tasks();
+    assert!(wnd.pointer_capture().is_some());
+    *w_mounted.write() = false;
+    wnd.draw_frame();
+    assert_eq!(wnd.pointer_capture(), None);
+  }
+
+  #[test]
+  fn capture_not_release_grab_handle() {
+    reset_test_env!();
+
+    let (mounted, w_mounted) = split_value(true);
+    let w = fn_widget! {
+      @MockMulti {
+        @ {
+          pipe!(*$mounted).map(move |v| v.then_some(fn_widget! {
+            @MockBox { size: Size::new(100., 30.) }
+          }))
+        }
+        @MockBox { size: Size::new(100., 30.) }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(400., 400.));
+    wnd.draw_frame();
+    let tree = wnd.tree();
+    let content = tree.root().first_child(tree).unwrap();
+    let first = content.first_child(tree).unwrap();
+    let second = content.last_child(tree).unwrap();
+
+    // The capture API can't release the pointer grabbed by a handle.
+    let grab = GrabPointer::grab(first, &wnd).unwrap();
+    assert!(!wnd.capture_pointer(second));
+    wnd.release_pointer();
+    assert_eq!(wnd.pointer_capture(), Some(first));
+
+    // The disposal releases the grab, and the stale handle doesn't release the
+    // capture of others.
+    *w_mounted.write() = false;
+    wnd.draw_frame();
+    assert_eq!(wnd.pointer_capture(), None);
+    assert!(wnd.capture_pointer(second));
+    drop(grab);
+    assert_eq!(wnd.pointer_capture(), Some(second));
+    wnd.release_pointer();
+    assert_eq!(wnd.pointer_capture(), None);
+  }
 }
@@ -18,6 +18,24 @@ pub(crate) struct FocusManager {
   arena: Arena<FocusNodeInfo>,
   root: NodeId,
   wnd_id: WindowId,
+  /// The reason of the current focus.
+  reason: FocusReason,
+  /// The reason of the focus requested, it becomes the reason of the focus
+  /// when the focus is refreshed.
+  request_reason: FocusReason,
+}
+
+/// The reason why a widget gains the focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FocusReason {
+  /// The focus is gained by a pointer, like clicking the widget.
+  Pointer,
+  /// The focus is gained by the keyboard navigation, like pressing `Tab`.
+  Keyboard,
+  /// The focus is gained by others, like the auto focus or requested by the
+  /// code.
+  #[default]
+  Other,
 }
 
 pub struct FocusHandle {
@@ -92,6 +110,8 @@ impl FocusManager {
       node_ids: ahash::HashMap::default(),
       arena,
       root,
+      reason: FocusReason::Other,
+      request_reason: FocusReason::Other,
     }
   }
 
@@ -186,6 +206,7 @@ impl FocusManager {
       wid = self.focus_step(wid, backward);
     }
     self.request_focus_to(wid);
+    self.request_reason = FocusReason::Keyboard;
   }
 
   fn focus_step(&mut self, focusing: Option<WidgetId>, backward: bool) -> Option<WidgetId> {
@@ -480,8 +501,9 @@ impl FocusManager {
     self.refresh_focus(tree);
   }
 
-  pub fn focus(&mut self, wid: WidgetId, tree: &WidgetTree) {
+  pub fn focus(&mut self, wid: WidgetId, reason: FocusReason, tree: &WidgetTree) {
     self.request_focus_to(Some(wid));
+    self.request_reason = reason;
     self.refresh_focus(tree);
   }
 
@@ -495,9 +517,14 @@ impl FocusManager {
   /// return the focusing widget.
   pub fn focusing(&self) -> Option<WidgetId> { self.focusing }
 
+  /// Return the reason of the current focus.
+  pub fn focus_reason(&self) -> FocusReason { self.reason }
+
   pub fn refresh_focus(&mut self, tree: &WidgetTree) {
     let new_focus = self.next_focus(tree);
+    let reason = std::mem::take(&mut self.request_reason);
     if self.focus_widgets.first() != new_focus.as_ref() {
+      self.reason = reason;
       self.change_focusing_to(new_focus);
     }
   }
@@ -510,6 +537,7 @@ impl FocusManager {
   // will be removed soon.
   fn request_focus_to(&mut self, focus_to: Option<WidgetId>) {
     self.request_focusing = Some(focus_to);
+    self.request_reason = FocusReason::Other;
   }
 
   fn change_focusing_to(&mut self, node: Option<WidgetId>) -> Option<WidgetId> {
@@ -727,7 +755,7 @@ mod tests {
     wnd
       .focus_mgr
       .borrow_mut()
-      .focus(child, wnd.tree());
+      .focus(child, FocusReason::Other, wnd.tree());
     wnd.draw_frame();
     assert_eq!(&*log.borrow(), &["focus child", "focusin child", "focusin parent"]);
     log.borrow_mut().clear();
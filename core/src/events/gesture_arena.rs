@@ -0,0 +1,95 @@
+use crate::prelude::*;
+
+/// The distance the pointer can move from where it was pressed and still be a
+/// tap. Once the pointer moves farther, a drag recognizer wins the gesture.
+pub const DRAG_SLOP: f32 = 8.;
+
+/// The arena resolves the competing gestures of a pointer sequence, from the
+/// press to the release.
+///
+/// The tap is the default gesture of the sequence, and the widgets recognizing
+/// a drag, like a scrollable widget, join the arena when the pointer is
+/// pressed. If the pointer moves within [`DRAG_SLOP`], the sequence is still a
+/// tap. Once it exceeds the slop, the first joined drag recognizer, that is the
+/// innermost one, wins: it captures the pointer and the tap is canceled.
+#[derive(Default)]
+pub(crate) struct GestureArena {
+  down_pos: Option<Point>,
+  drags: Vec<WidgetId>,
+  winner: Option<WidgetId>,
+}
+
+impl GestureArena {
+  /// Open the arena for a new pointer sequence pressed at `pos`.
+  pub(crate) fn open(&mut self, pos: Point) {
+    *self = Self { down_pos: Some(pos), ..<_>::default() }
+  }
+
+  /// Close the arena when the pointer sequence ends.
+  pub(crate) fn close(&mut self) -> Option<WidgetId> { std::mem::take(self).winner }
+
+  pub(crate) fn join_drag(&mut self, wid: WidgetId) -> bool {
+    let open = self.down_pos.is_some() && self.winner.is_none();
+    if open && !self.drags.contains(&wid) {
+      self.drags.push(wid);
+    }
+    open
+  }
+
+  pub(crate) fn winner(&self) -> Option<WidgetId> { self.winner }
+
+  /// Resolve the arena by the pointer moving to `pos`, return the winner if
+  /// a drag recognizer just wins.
+  pub(crate) fn resolve(&mut self, pos: Point) -> Option<WidgetId> {
+    let down_pos = self.down_pos?;
+    if self.winner.is_some() || (pos - down_pos).length() <= DRAG_SLOP {
+      return None;
+    }
+    self.winner = self.drags.first().copied();
+    if self.winner.is_none() {
+      // No drag recognizer, the sequence keeps being a tap.
+      self.down_pos = None;
+    }
+    self.winner
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{prelude::*, reset_test_env, test_helper::*};
+
+  #[test]
+  fn drag_cancels_tap_and_scrolls() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let (canceled, w_canceled) = split_value(0);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(1000., 1000.),
+          scrollable: Scrollable::Y,
+          on_tap: move |_| *$w_taps.write() += 1,
+          on_pointer_cancel: move |_| *$w_canceled.write() += 1,
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    // A small movement within the slop is still a tap.
+    wnd.drag(Point::new(50., 50.), Point::new(53., 52.));
+    wnd.expect_state(&taps, 1);
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::zero());
+
+    // Exceeding the slop cancels the tap and scrolls the parent.
+    wnd.drag(Point::new(50., 50.), Point::new(50., 20.));
+    wnd
+      .expect_state(&taps, 1)
+      .expect_state(&canceled, 1);
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::new(0., -30.));
+    assert_eq!(wnd.pointer_capture(), None);
+  }
+}
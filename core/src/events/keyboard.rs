@@ -20,6 +20,8 @@ impl KeyboardEvent {
   #[inline]
   pub fn key(&self) -> &VirtualKey { &self.key }
 
+  /// Return if the key down event is generated by the auto-repeat of the
+  /// platform when the key is held. It's always `false` for the key up event.
   #[inline]
   pub fn is_repeat(&self) -> bool { self.is_repeat }
 
@@ -134,4 +136,37 @@ mod tests {
       "key up Character(\"1\")"
     ]);
   }
+
+  #[test]
+  fn repeat_key_down() {
+    reset_test_env!();
+
+    let (repeats, w_repeats) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::zero(),
+        auto_focus: true,
+        on_key_down: move |e| $w_repeats.write().push(("down", e.is_repeat())),
+        on_key_up: move |e| $w_repeats.write().push(("up", e.is_repeat())),
+      }
+    });
+    wnd.draw_frame();
+
+    let key = |wnd: &TestWindow, is_repeat, state| {
+      wnd.processes_keyboard_event(
+        PhysicalKey::Code(KeyCode::KeyA),
+        VirtualKey::Character("a".into()),
+        is_repeat,
+        KeyLocation::Standard,
+        state,
+      );
+    };
+    key(&wnd, false, ElementState::Pressed);
+    key(&wnd, true, ElementState::Pressed);
+    // A release is never a repeat, even if the platform reports it.
+    key(&wnd, true, ElementState::Released);
+    wnd.run_frame_tasks();
+
+    assert_eq!(*repeats.read(), [("down", false), ("down", true), ("up", false)]);
+  }
 }
@@ -1,5 +1,5 @@
 use super::CommonEvent;
-use crate::impl_common_event_deref;
+use crate::{impl_common_event_deref, prelude::*};
 mod from_mouse;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PointerId(usize);
@@ -45,10 +45,21 @@ pub struct PointerEvent {
   /// Indicates if the pointer represents the primary pointer of this pointer
   /// type.
   pub is_primary: bool,
+  /// The samples of the pointer coalesced into this event, the last one is the
+  /// position of this event.
+  pub coalesced: Vec<PointerSample>,
 
   pub common: CommonEvent,
 }
 
+/// A position of the pointer sampled at a time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerSample {
+  /// The position in global (window) coordinates.
+  pub pos: Point,
+  pub time: Instant,
+}
+
 bitflags! {
   #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
   pub struct MouseButtons: u8 {
@@ -76,6 +87,46 @@ pub enum PointerType {
 }
 
 impl_common_event_deref!(PointerEvent);
+
+impl PointerEvent {
+  /// Capture the pointer to the widget `wid`, see [`Window::capture_pointer`].
+  pub fn capture_pointer(&self, wid: WidgetId) -> bool { self.window().capture_pointer(wid) }
+
+  /// Release the captured pointer, see [`Window::release_pointer`].
+  pub fn release_pointer(&self) { self.window().release_pointer(); }
+
+  /// Join the current target to the gesture arena as a drag recognizer, see
+  /// [`Window::join_drag_gesture`].
+  pub fn join_drag_gesture(&self) -> bool {
+    self
+      .window()
+      .join_drag_gesture(self.current_target())
+  }
+
+  /// Whether the current target wins the gesture arena of the pointer.
+  pub fn is_gesture_winner(&self) -> bool {
+    self.window().gesture_winner() == Some(self.current_target())
+  }
+
+  /// The samples of the pointer coalesced into this event, in the order they
+  /// happened.
+  ///
+  /// The platform may deliver the pointer moves more frequently than the
+  /// frame rate, the moves to the same target before the events are emitted
+  /// are coalesced into one pointer move event, use this to get all of them,
+  /// for example, to draw a smooth stroke. If the platform doesn't, it's only
+  /// the sample of this event.
+  pub fn coalesced_samples(&self) -> &[PointerSample] { &self.coalesced }
+
+  /// The recent samples of the pointer, in the order they happened.
+  pub fn pointer_history(&self) -> Vec<PointerSample> {
+    self.pick_info(|info| info.pointer_history().cloned().collect())
+  }
+
+  /// The velocity of the pointer in logical pixels per second, computed from
+  /// the recent pointer history.
+  pub fn velocity(&self) -> Vector { self.pick_info(|info| info.pointer_velocity(Instant::now())) }
+}
 #[cfg(test)]
 mod tests {
 
@@ -137,4 +188,50 @@ mod tests {
     assert_eq!(*tap.read(), 2);
     assert!(*focused.read());
   }
+
+  #[test]
+  fn coalesced_pointer_moves() {
+    reset_test_env!();
+
+    let (moves, w_moves) = split_value(vec![]);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_pointer_move: move |e| {
+            let samples = e.coalesced_samples().iter().map(|s| s.pos).collect::<Vec<_>>();
+            $w_moves.write().push(samples);
+          },
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    // The platform delivers several moves in a frame.
+    let device_id = unsafe { DeviceId::dummy() };
+    for x in [10., 20., 30.] {
+      let position = LogicalPosition::new(x, 10.).to_physical(1.);
+      #[allow(deprecated)]
+      wnd.processes_native_event(WindowEvent::CursorMoved { device_id, position });
+    }
+    wnd.draw_frame();
+    assert_eq!(
+      &*moves.read(),
+      &[vec![Point::new(10., 10.), Point::new(20., 10.), Point::new(30., 10.)]]
+    );
+    assert_eq!(
+      wnd
+        .dispatcher
+        .borrow()
+        .info
+        .pointer_history()
+        .count(),
+      3
+    );
+
+    // Only a single sample without coalesced moves.
+    wnd.cursor_move(Point::new(40., 10.));
+    assert_eq!(moves.read().last().unwrap(), &[Point::new(40., 10.)]);
+  }
 }
@@ -5,12 +5,9 @@ use crate::prelude::*;
 
 impl PointerEvent {
   pub(crate) fn from_mouse(target: WidgetId, wnd: &Window) -> Self {
-    let no_button = wnd
-      .dispatcher
-      .borrow()
-      .info
-      .mouse_buttons()
-      .is_empty();
+    let dispatcher = wnd.dispatcher.borrow();
+    let no_button = dispatcher.info.mouse_buttons().is_empty();
+    let sample = dispatcher.info.last_sample();
     PointerEvent {
       // todo: we need to trace the pressed pointer, how to generate pointer id, by device + button?
       id: PointerId(0),
@@ -22,9 +19,17 @@ impl PointerEvent {
       twist: 0.,
       point_type: PointerType::Mouse,
       is_primary: true,
+      coalesced: vec![sample],
       common: CommonEvent::new(target, wnd.tree),
     }
   }
+
+  pub(crate) fn with_coalesced(mut self, samples: Vec<PointerSample>) -> Self {
+    if !samples.is_empty() {
+      self.coalesced = samples;
+    }
+    self
+  }
 }
 
 impl From<MouseButton> for MouseButtons {
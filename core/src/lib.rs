@@ -8,9 +8,12 @@ pub mod builtin_widgets;
 pub mod clipboard;
 mod context;
 pub mod data_widget;
+#[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+pub mod debug_server;
 pub mod declare;
 pub mod events;
 pub mod local_sender;
+pub mod locale;
 pub mod pipe;
 pub(crate) mod render_helper;
 mod state;
@@ -35,6 +38,28 @@ pub enum Measure {
   /// finite parent clamp, corresponding to the parent's size if the parent is a
   /// fixed-size container. A value of 1.0 corresponds to 100%.
   Percent(f32),
+
+  /// The value represents a percentage of the window width, a value of 1.0
+  /// corresponds to 1% of the width, so `Vw(50.)` is half of the window width.
+  Vw(f32),
+
+  /// The value represents a percentage of the window height, a value of 1.0
+  /// corresponds to 1% of the height, so `Vh(50.)` is half of the window
+  /// height.
+  Vh(f32),
+
+  /// The value represents a multiple of the font size of the ambient
+  /// `TextStyle`, so `Em(1.5)` is 24 pixels under a 16 pixels font.
+  Em(f32),
+}
+
+/// The references that the relative [`Measure`]s are resolved against.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeasureBase {
+  /// The window size that `Vw` and `Vh` are relative to.
+  pub viewport: Size,
+  /// The font size that `Em` is relative to.
+  pub font_size: f32,
 }
 
 pub mod prelude {
@@ -55,6 +80,7 @@ pub mod prelude {
     context::*,
     declare::*,
     events::*,
+    locale::{Locale, NumberFormat, PluralCategory, Translations},
     multi_class,
     overlay::{AutoClosePolicy, Overlay, OverlayStyle},
     pipe::{BoxPipe, FinalChain, MapPipe, ModifiesPipe, Pipe},
@@ -66,13 +92,16 @@ pub mod prelude {
     widget::*,
     widget_children::*,
     widget_tree::{BoxClamp, DirtyPhase, LayoutInfo, TrackId, WidgetId},
-    window::Window,
+    window::{Window, WindowLevel},
   };
   pub use crate::{timer, *};
 }
 
 pub mod test_helper;
 
+use prelude::{AppCtx, Provider, ProviderCtx, StateReader, TextStyle, Window};
+use ribir_geom::Size;
+
 impl From<f32> for Measure {
   fn from(value: f32) -> Self { Measure::Pixel(value) }
 }
@@ -82,16 +111,41 @@ impl Default for Measure {
 }
 
 impl Measure {
-  pub fn into_pixel(self, max_clamp: f32) -> f32 {
+  /// Resolve the measure to logical pixels, the `max_clamp` is the maximum
+  /// size of the parent clamp that `Percent` relative to, and the `base`
+  /// provides the references of the other relative measures. The viewport
+  /// relative measures resolve to zero in a zero-size window.
+  pub fn into_pixel(self, max_clamp: f32, base: &MeasureBase) -> f32 {
+    let percent_of = |x: f32, base: f32| if x.is_finite() { x * base } else { 0. };
     match self {
       Measure::Pixel(x) => x,
-      Measure::Percent(x) => {
-        if x.is_finite() {
-          x * max_clamp
-        } else {
-          0.
-        }
-      }
+      Measure::Percent(x) => percent_of(x, max_clamp),
+      Measure::Vw(x) => percent_of(x, base.viewport.width) / 100.,
+      Measure::Vh(x) => percent_of(x, base.viewport.height) / 100.,
+      Measure::Em(x) => percent_of(x, base.font_size),
     }
   }
 }
+
+impl MeasureBase {
+  /// The base of the measures in the context, the font size is from the
+  /// ambient `TextStyle`, or the default font size if there isn't one.
+  pub fn of(ctx: &impl AsRef<ProviderCtx>, wnd: &Window) -> Self {
+    let font_size = Provider::of::<TextStyle>(ctx)
+      .map_or_else(|| TextStyle::default().font_size, |style| style.font_size);
+    Self { viewport: wnd.size(), font_size }
+  }
+
+  /// The base of the measures at the root of the window, for resolving the
+  /// measures out of a widget context, the font size is from the body text
+  /// style of the application theme.
+  pub fn of_window(wnd: &Window) -> Self {
+    let font_size = AppCtx::app_theme()
+      .read()
+      .typography_theme
+      .body_medium
+      .text
+      .font_size;
+    Self { viewport: wnd.size(), font_size }
+  }
+}
@@ -0,0 +1,487 @@
+//! The localized strings of the application.
+//!
+//! The [`Translations`] of a language map the keys to the localized strings,
+//! they're usually embedded by the `include_crate_translations!` macro. The
+//! [`Locale`] provider decides which translations its descendants use, and the
+//! [`tr!`](crate::tr) macro looks up a key in it. The [`Locale`] also selects
+//! the [`PluralCategory`] of a count and formats the numbers and dates by the
+//! conventions of the language.
+//!
+//! ```
+//! use ribir_core::prelude::*;
+//!
+//! let en = Translations::new("en")
+//!   .with("greet", "Hello, {name}!")
+//!   .with("bye", "Goodbye!");
+//! let zh = Translations::new("zh").with("greet", "你好，{name}！");
+//!
+//! let _w = providers! {
+//!   providers: [Provider::new(Locale::new(zh).with_fallback(en))],
+//!   @ {
+//!     // "你好，Ribir！"
+//!     let greet = tr!("greet", name = "Ribir");
+//!     // "Goodbye!", from the fallback translations.
+//!     let bye = tr!("bye");
+//!     @Text { text: format!("{greet} {bye}") }
+//!   }
+//! };
+//! ```
+use std::{fmt::Display, rc::Rc};
+
+use crate::prelude::*;
+
+/// Look up the localized string of a key in the [`Locale`] provided to the
+/// building widget, and replace the `{name}` placeholders in it with the
+/// arguments.
+///
+/// The key itself is used as the string if it's not found, so `tr!` still
+/// works without a `Locale` provider.
+#[macro_export]
+macro_rules! tr {
+  ($key: expr $(, $name: ident = $value: expr)* $(,)?) => {
+    $crate::prelude::Locale::tr(
+      $crate::prelude::BuildCtx::get(),
+      $key,
+      &[$((stringify!($name), &$value as &dyn std::fmt::Display)),*],
+    )
+  };
+}
+
+/// The plural category of a count, it's a small subset of the CLDR plural
+/// rules that covers the integers of the common languages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+  Zero,
+  One,
+  Two,
+  Few,
+  Many,
+  Other,
+}
+
+/// How to format the numbers of a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+  /// The separator between every three integer digits.
+  pub group: char,
+  /// The mark between the integer and the fraction digits.
+  pub decimal: char,
+}
+
+/// How to format the dates of a language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateFormat {
+  pub order: DateOrder,
+  /// The separator between the year, month and day.
+  pub separator: char,
+  /// Pad the month and the day to two digits.
+  pub zero_pad: bool,
+}
+
+/// The order of the year, month and day in a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+  YearMonthDay,
+  DayMonthYear,
+  MonthDayYear,
+}
+
+/// The translation table of a language, maps the keys to the localized
+/// strings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Translations {
+  lang: String,
+  strings: ahash::HashMap<String, String>,
+}
+
+/// The provider decides the translations used by its descendants.
+///
+/// A key missing in the active translations is looked up in the fallback
+/// translations, which is usually the default language of the application.
+#[derive(Debug, Clone)]
+pub struct Locale {
+  active: Rc<Translations>,
+  fallback: Option<Rc<Translations>>,
+}
+
+impl Translations {
+  pub fn new(lang: impl Into<String>) -> Self {
+    Translations { lang: lang.into(), strings: <_>::default() }
+  }
+
+  /// Create the translations of `lang` from the pairs of key and string.
+  pub fn from_entries(lang: impl Into<String>, entries: &[(&str, &str)]) -> Self {
+    entries
+      .iter()
+      .fold(Self::new(lang), |t, (key, value)| t.with(*key, *value))
+  }
+
+  /// Add the localized string of the `key`.
+  pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+    self.strings.insert(key.into(), value.into());
+    self
+  }
+
+  /// The language of the translations, such as `en` or `zh-CN`.
+  pub fn lang(&self) -> &str { &self.lang }
+
+  pub fn get(&self, key: &str) -> Option<&str> { self.strings.get(key).map(String::as_str) }
+
+  pub fn plural_category(&self, count: u64) -> PluralCategory {
+    PluralCategory::select(&self.lang, count)
+  }
+
+  pub fn number_format(&self) -> NumberFormat { NumberFormat::of_lang(&self.lang) }
+
+  pub fn date_format(&self) -> DateFormat { DateFormat::of_lang(&self.lang) }
+
+  /// Return the plural string of the `key` for the `count`, it's stored by the
+  /// key suffixed with the plural category, such as `apple.one`, and the
+  /// `other` category is used if the category of the count is missing.
+  pub fn get_plural(&self, key: &str, count: u64) -> Option<&str> {
+    let category = self.plural_category(count);
+    self
+      .get(&format!("{key}.{}", category.as_str()))
+      .or_else(|| self.get(&format!("{key}.{}", PluralCategory::Other.as_str())))
+  }
+}
+
+impl Locale {
+  pub fn new(active: Translations) -> Self { Locale { active: Rc::new(active), fallback: None } }
+
+  /// Use the `fallback` translations for the keys missing in the active one.
+  pub fn with_fallback(mut self, fallback: Translations) -> Self {
+    self.fallback = Some(Rc::new(fallback));
+    self
+  }
+
+  pub fn active(&self) -> &Translations { &self.active }
+
+  pub fn fallback(&self) -> Option<&Translations> { self.fallback.as_deref() }
+
+  /// Return the localized string of the `key`, the fallback translations are
+  /// used if the active one misses it.
+  pub fn lookup(&self, key: &str) -> Option<&str> {
+    self
+      .active
+      .get(key)
+      .or_else(|| self.fallback.as_ref()?.get(key))
+  }
+
+  /// Return the localized string of the `key` with the `{name}` placeholders
+  /// replaced by the `args`, the key itself is used if it's not found.
+  pub fn translate(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    interpolate(self.lookup(key).unwrap_or(key), args)
+  }
+
+  /// Return the plural string of the `key` for the `count` with the `{count}`
+  /// and the other placeholders replaced, see [`Translations::get_plural`].
+  pub fn translate_plural(&self, key: &str, count: u64, args: &[(&str, &dyn Display)]) -> String {
+    let template = self
+      .active
+      .get_plural(key, count)
+      .or_else(|| self.fallback.as_ref()?.get_plural(key, count))
+      .unwrap_or(key);
+    let count = self.format_number(count as f64, 0);
+    let mut all_args: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+    all_args.extend_from_slice(args);
+    interpolate(template, &all_args)
+  }
+
+  /// The plural category of the `count` in the active language.
+  pub fn plural_category(&self, count: u64) -> PluralCategory { self.active.plural_category(count) }
+
+  /// Format the `value` with `fraction_digits` digits after the decimal mark
+  /// by the conventions of the active language.
+  pub fn format_number(&self, value: f64, fraction_digits: usize) -> String {
+    self
+      .active
+      .number_format()
+      .format(value, fraction_digits)
+  }
+
+  /// Format the date by the conventions of the active language, the `month`
+  /// and `day` start from 1.
+  pub fn format_date(&self, year: i32, month: u32, day: u32) -> String {
+    self.active.date_format().format(year, month, day)
+  }
+
+  /// Translate the `key` by the `Locale` provided in the `ctx`, it's what the
+  /// [`tr!`](crate::tr) macro calls.
+  pub fn tr(ctx: &impl AsRef<ProviderCtx>, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    match Provider::of::<Locale>(ctx) {
+      Some(locale) => locale.translate(key, args),
+      None => interpolate(key, args),
+    }
+  }
+}
+
+impl PluralCategory {
+  /// Select the plural category of the `count` in the language `lang`, such
+  /// as `en` or `pl-PL`. The languages without known rules use the English
+  /// rules.
+  pub fn select(lang: &str, count: u64) -> Self {
+    let primary = lang.split(['-', '_']).next().unwrap_or(lang);
+    let (n10, n100) = (count % 10, count % 100);
+    let few = (2..=4).contains(&n10) && !(12..=14).contains(&n100);
+    match primary.to_ascii_lowercase().as_str() {
+      "zh" | "ja" | "ko" | "vi" | "th" | "id" | "ms" => PluralCategory::Other,
+      "fr" | "pt" if count <= 1 => PluralCategory::One,
+      "fr" | "pt" => PluralCategory::Other,
+      "ru" | "uk" | "be" => match count {
+        _ if n10 == 1 && n100 != 11 => PluralCategory::One,
+        _ if few => PluralCategory::Few,
+        _ => PluralCategory::Many,
+      },
+      "pl" => match count {
+        1 => PluralCategory::One,
+        _ if few => PluralCategory::Few,
+        _ => PluralCategory::Many,
+      },
+      "cs" | "sk" => match count {
+        1 => PluralCategory::One,
+        2..=4 => PluralCategory::Few,
+        _ => PluralCategory::Other,
+      },
+      "ar" => match count {
+        0 => PluralCategory::Zero,
+        1 => PluralCategory::One,
+        2 => PluralCategory::Two,
+        _ if (3..=10).contains(&n100) => PluralCategory::Few,
+        _ if (11..=99).contains(&n100) => PluralCategory::Many,
+        _ => PluralCategory::Other,
+      },
+      _ if count == 1 => PluralCategory::One,
+      _ => PluralCategory::Other,
+    }
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      PluralCategory::Zero => "zero",
+      PluralCategory::One => "one",
+      PluralCategory::Two => "two",
+      PluralCategory::Few => "few",
+      PluralCategory::Many => "many",
+      PluralCategory::Other => "other",
+    }
+  }
+}
+
+impl NumberFormat {
+  /// The number format of the language `lang`, the languages without known
+  /// conventions use the English format.
+  pub fn of_lang(lang: &str) -> Self {
+    let primary = lang.split(['-', '_']).next().unwrap_or(lang);
+    let (group, decimal) = match primary.to_ascii_lowercase().as_str() {
+      "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" => ('.', ','),
+      "fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" => ('\u{a0}', ','),
+      _ => (',', '.'),
+    };
+    NumberFormat { group, decimal }
+  }
+
+  /// Format the `value` with `fraction_digits` digits after the decimal mark.
+  pub fn format(&self, value: f64, fraction_digits: usize) -> String {
+    let digits = format!("{:.*}", fraction_digits, value.abs());
+    let (int, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+
+    let mut res = String::with_capacity(digits.len() + int.len() / 3 + 1);
+    if value.is_sign_negative()
+      && digits
+        .bytes()
+        .any(|b| b.is_ascii_digit() && b != b'0')
+    {
+      res.push('-');
+    }
+    for (i, c) in int.chars().enumerate() {
+      if i > 0 && (int.len() - i) % 3 == 0 {
+        res.push(self.group);
+      }
+      res.push(c);
+    }
+    if !fraction.is_empty() {
+      res.push(self.decimal);
+      res.push_str(fraction);
+    }
+    res
+  }
+}
+
+impl DateFormat {
+  /// The date format of the language `lang`, such as `en-GB` or `de`. The
+  /// languages without known conventions use the ISO 8601 format.
+  pub fn of_lang(lang: &str) -> Self {
+    let lang = lang.to_ascii_lowercase().replace('_', "-");
+    let primary = lang.split('-').next().unwrap_or(&lang);
+    let (order, separator, zero_pad) = match primary {
+      "en" if lang == "en" || lang == "en-us" => (DateOrder::MonthDayYear, '/', false),
+      "en" => (DateOrder::DayMonthYear, '/', true),
+      "fr" | "es" | "it" | "pt" | "id" => (DateOrder::DayMonthYear, '/', true),
+      "de" | "ru" | "uk" | "pl" | "cs" | "sk" | "fi" | "nb" | "da" | "tr" => {
+        (DateOrder::DayMonthYear, '.', true)
+      }
+      "nl" => (DateOrder::DayMonthYear, '-', true),
+      "zh" | "ja" => (DateOrder::YearMonthDay, '/', false),
+      "ko" => (DateOrder::YearMonthDay, '.', false),
+      _ => (DateOrder::YearMonthDay, '-', true),
+    };
+    DateFormat { order, separator, zero_pad }
+  }
+
+  /// Format the date, the `month` and `day` start from 1.
+  pub fn format(&self, year: i32, month: u32, day: u32) -> String {
+    let field = |v: u32| if self.zero_pad { format!("{v:02}") } else { v.to_string() };
+    let (year, month, day) = (year.to_string(), field(month), field(day));
+    let fields = match self.order {
+      DateOrder::YearMonthDay => [year, month, day],
+      DateOrder::DayMonthYear => [day, month, year],
+      DateOrder::MonthDayYear => [month, day, year],
+    };
+    fields.join(&self.separator.to_string())
+  }
+}
+
+/// Replace the `{name}` placeholders in the `template` with the `args`, the
+/// `{{` and `}}` are escaped braces. A placeholder without an argument is kept
+/// as it is.
+fn interpolate(template: &str, args: &[(&str, &dyn Display)]) -> String {
+  use std::fmt::Write;
+
+  let mut res = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(idx) = rest.find(['{', '}']) {
+    res.push_str(&rest[..idx]);
+    rest = &rest[idx..];
+    if rest.starts_with("{{") || rest.starts_with("}}") {
+      res.push_str(&rest[..1]);
+      rest = &rest[2..];
+      continue;
+    }
+
+    let placeholder = rest
+      .strip_prefix('{')
+      .and_then(|s| Some(&s[..s.find('}')?]));
+    let arg = placeholder.and_then(|name| args.iter().find(|(n, _)| *n == name));
+    match (placeholder, arg) {
+      (Some(name), Some((_, value))) => {
+        let _ = write!(res, "{value}");
+        rest = &rest[name.len() + 2..];
+      }
+      _ => {
+        res.push_str(&rest[..1]);
+        rest = &rest[1..];
+      }
+    }
+  }
+  res.push_str(rest);
+  res
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn locale() -> Locale {
+    let en = Translations::new("en")
+      .with("greet", "Hello, {name}!")
+      .with("bye", "Goodbye, {name}!");
+    let zh = Translations::from_entries("zh", &[("greet", "你好，{name}！")]);
+    Locale::new(zh).with_fallback(en)
+  }
+
+  #[test]
+  fn lookup_with_fallback() {
+    reset_test_env!();
+
+    let (strings, w_strings) = split_value(vec![]);
+    let w = fn_widget! {
+      let w_strings = w_strings.clone_writer();
+      @Providers {
+        providers: [Provider::new(locale())],
+        @ {
+          *w_strings.write() = vec![
+            tr!("greet", name = "Ribir"),
+            tr!("bye", name = 2),
+            tr!("missing {name}", name = "key"),
+          ];
+          @Void {}
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    assert_eq!(*strings.read(), ["你好，Ribir！", "Goodbye, 2!", "missing key"]);
+  }
+
+  #[test]
+  fn resolve_by_active_locale() {
+    let en = Translations::new("en").with("greet", "Hello, {name}!");
+    let zh = locale();
+    let en = Locale::new(en);
+    assert_eq!(zh.translate("greet", &[("name", &"Ribir")]), "你好，Ribir！");
+    assert_eq!(en.translate("greet", &[("name", &"Ribir")]), "Hello, Ribir!");
+  }
+
+  #[test]
+  fn plural_category() {
+    let categories = |lang| {
+      [1, 2, 5]
+        .map(|n| PluralCategory::select(lang, n).as_str())
+        .to_vec()
+    };
+    assert_eq!(categories("en"), ["one", "other", "other"]);
+    assert_eq!(categories("pl"), ["one", "few", "many"]);
+    assert_eq!(PluralCategory::select("pl-PL", 22), PluralCategory::Few);
+    assert_eq!(PluralCategory::select("pl", 12), PluralCategory::Many);
+    assert_eq!(PluralCategory::select("ru", 21), PluralCategory::One);
+    assert_eq!(PluralCategory::select("zh", 1), PluralCategory::Other);
+
+    let pl = Translations::new("pl")
+      .with("file.one", "{count} plik")
+      .with("file.few", "{count} pliki")
+      .with("file.many", "{count} plików");
+    let en = Translations::new("en")
+      .with("file.one", "{count} file")
+      .with("file.other", "{count} files");
+    let pl = Locale::new(pl);
+    assert_eq!(pl.translate_plural("file", 2, &[]), "2 pliki");
+    assert_eq!(pl.translate_plural("file", 5, &[]), "5 plików");
+    let en = Locale::new(en);
+    assert_eq!(en.translate_plural("file", 1, &[]), "1 file");
+    assert_eq!(en.translate_plural("file", 1200, &[]), "1,200 files");
+  }
+
+  #[test]
+  fn format_number() {
+    assert_eq!(NumberFormat::of_lang("en").format(1234567.891, 2), "1,234,567.89");
+    assert_eq!(NumberFormat::of_lang("de-DE").format(-1234.5, 1), "-1.234,5");
+    assert_eq!(NumberFormat::of_lang("fr").format(1234.0, 0), "1\u{a0}234");
+    assert_eq!(NumberFormat::of_lang("en").format(999.0, 0), "999");
+    assert_eq!(NumberFormat::of_lang("en").format(-0.001, 2), "0.00");
+  }
+
+  #[test]
+  fn format_date() {
+    let date = |lang| DateFormat::of_lang(lang).format(2024, 3, 9);
+    assert_eq!(date("en"), "3/9/2024");
+    assert_eq!(date("en-GB"), "09/03/2024");
+    assert_eq!(date("de-DE"), "09.03.2024");
+    assert_eq!(date("fr"), "09/03/2024");
+    assert_eq!(date("zh_CN"), "2024/3/9");
+    assert_eq!(date("sv"), "2024-03-09");
+
+    let pl = Locale::new(Translations::new("pl"));
+    assert_eq!(pl.format_date(2024, 12, 31), "31.12.2024");
+  }
+
+  #[test]
+  fn interpolation() {
+    let args: &[(&str, &dyn Display)] = &[("a", &1), ("b", &"two")];
+    assert_eq!(interpolate("{a} and {b}", args), "1 and two");
+    assert_eq!(interpolate("{{a}} {c} {a", args), "{a} {c} {a");
+    assert_eq!(interpolate("}{b}{", args), "}two{");
+  }
+}
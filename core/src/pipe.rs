@@ -43,6 +43,24 @@ pub trait Pipe: 'static {
     FinalChain { source: self, f, _marker: PhantomData }
   }
 
+  /// Converts the pipe into an observable that emits the current value
+  /// synchronously when it's subscribed, and then emits the new value once for
+  /// every modification.
+  ///
+  /// The value is evaluated at subscription, so subscribing it during the build
+  /// of a widget delivers the current value before the first frame.
+  fn start_with_current(self) -> BoxOp<'static, Self::Value, Infallible>
+  where
+    Self: Sized,
+    Self::Value: 'static,
+  {
+    observable::defer(move || {
+      let (v, stream) = self.unzip(ModifyScope::DATA, None);
+      stream.map(|(_, v)| v).start_with(vec![v])
+    })
+    .box_it()
+  }
+
   /// Unzip the `Pipe` into its inner value and the stream of changes for that
   /// value.
   ///
@@ -225,8 +243,9 @@ pub(crate) trait InnerPipe: Pipe + Sized {
         if set_pos_of_multi(id, idx + 1, tree) {
           // We need to associate the parent information with the children pipe so that
           // when the child pipe is regenerated, it can update the parent pipe information
-          // accordingly.
-          id.attach_data(Box::new(pipe_node), tree);
+          // accordingly. Only the pipe node itself is exposed, the data it hosts
+          // belongs to the first widget.
+          id.attach_data(Box::new(Queryable(pipe_node)), tree);
         }
       });
 
@@ -255,7 +274,7 @@ pub(crate) trait InnerPipe: Pipe + Sized {
         // when the leaf pipe is regenerated, it can update the parent pipe information
         // accordingly.
         if leaf.contain_type::<PipeNode>(tree) {
-          leaf.attach_data(Box::new(node.clone()), tree);
+          leaf.attach_data(Box::new(Queryable(node.clone())), tree);
         };
 
         let pipe_node = node.clone();
@@ -866,6 +885,14 @@ impl Render for PipeNode {
   }
 
   fn get_transform(&self) -> Option<Transform> { self.as_ref().data.get_transform() }
+
+  fn semantics(&self) -> Option<Semantics> { self.as_ref().data.semantics() }
+
+  fn baseline(&self) -> Option<f32> { self.as_ref().data.baseline() }
+
+  fn type_name(&self) -> &'static str { self.as_ref().data.type_name() }
+
+  fn debug_properties(&self, props: &mut Vec<String>) { self.as_ref().data.debug_properties(props) }
 }
 
 #[derive(Clone)]
@@ -1083,6 +1110,43 @@ mod tests {
     );
   }
 
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn multi_pipe_item_query_own_data() {
+    reset_test_env!();
+    class_names!(ITEM);
+
+    let trigger = Stateful::new(0);
+    let c_trigger = trigger.clone_watcher();
+    let w = fn_widget! {
+      let c_trigger = c_trigger.clone_watcher();
+      @MockMulti {
+        @ {
+          pipe!($c_trigger;).map(move |_| {
+            (0..3).map(|i| {
+              // The class pipe makes every item a pipe widget.
+              @MockBox {
+                size: Size::zero(),
+                class: pipe!($c_trigger;).map(|_| ITEM),
+                semantics: Semantics::new(Role::Button).with_label(i.to_string()),
+              }
+            }).collect::<Vec<_>>()
+          })
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    let tree = wnd.semantics_tree();
+    let labels: Vec<_> = tree
+      .children
+      .iter()
+      .map(|c| c.semantics.label.as_deref().unwrap())
+      .collect();
+    assert_eq!(labels, ["0", "1", "2"]);
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn pipe_widget_mounted_new() {
@@ -1826,4 +1890,58 @@ mod tests {
     *m_writer.write() += 1;
     wnd.draw_frame();
   }
+
+  #[test]
+  fn start_with_current() {
+    reset_test_env!();
+
+    let (value, c_value) = split_value(1);
+    let (emitted, c_emitted) = split_value(vec![]);
+    let widget = fn_widget! {
+      pipe!(*$value)
+        .start_with_current()
+        .subscribe(move |v| $c_emitted.write().push(v));
+      @MockBox { size: Size::zero() }
+    };
+
+    let mut wnd = TestWindow::new(widget);
+    // The current value is delivered during the build.
+    assert_eq!(*emitted.read(), [1]);
+
+    wnd.draw_frame();
+    assert_eq!(*emitted.read(), [1]);
+
+    *c_value.write() = 2;
+    wnd.draw_frame();
+    assert_eq!(*emitted.read(), [1, 2]);
+
+    *c_value.write() = 3;
+    wnd.draw_frame();
+    assert_eq!(*emitted.read(), [1, 2, 3]);
+  }
+
+  #[test]
+  fn distinct_pipe_widget_first_change() {
+    reset_test_env!();
+
+    let (value, c_value) = split_value(0);
+    let (built, c_built) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      distinct_pipe!(*$value > 0).map(move |_| {
+        *$c_built.write() += 1;
+        @MockBox { size: Size::zero() }
+      })
+    });
+    wnd.draw_frame();
+    assert_eq!(*built.read(), 1);
+
+    // The first modification is not skipped as the initial notification.
+    *c_value.write() = 1;
+    wnd.draw_frame();
+    assert_eq!(*built.read(), 2);
+
+    *c_value.write() = 2;
+    wnd.draw_frame();
+    assert_eq!(*built.read(), 2);
+  }
 }
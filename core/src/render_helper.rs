@@ -49,6 +49,16 @@ where
 
   #[inline]
   fn get_transform(&self) -> Option<Transform> { self.proxy().get_transform() }
+
+  #[inline]
+  fn semantics(&self) -> Option<Semantics> { self.proxy().semantics() }
+
+  #[inline]
+  fn baseline(&self) -> Option<f32> { self.proxy().baseline() }
+
+  fn type_name(&self) -> &'static str { self.proxy().type_name() }
+
+  fn debug_properties(&self, props: &mut Vec<String>) { self.proxy().debug_properties(props) }
 }
 
 impl<R: Render> RenderProxy for RefCell<R> {
@@ -1,11 +1,14 @@
+mod effect;
 mod map_state;
 mod prior_op;
 mod splitted_state;
 mod stateful;
 mod watcher;
+mod while_mounted;
 use std::{cell::UnsafeCell, convert::Infallible, mem::MaybeUninit, ops::DerefMut};
 pub mod state_cell;
 
+pub use effect::*;
 pub use map_state::*;
 pub use prior_op::*;
 use ribir_algo::Sc;
@@ -15,6 +18,7 @@ pub use state_cell::*;
 use state_cell::{StateCell, ValueMutRef};
 pub use stateful::*;
 pub use watcher::*;
+pub use while_mounted::*;
 
 use crate::{prelude::*, render_helper::RenderProxy};
 
@@ -373,12 +377,10 @@ impl<'a, V: ?Sized> WriteRef<'a, V> {
     let borrow = orig.value.borrow.clone();
     let a = ValueMutRef { inner: a, borrow: borrow.clone() };
     let b = ValueMutRef { inner: b, borrow };
-    (WriteRef { value: a, modified, modify_scope, info }, WriteRef {
-      value: b,
-      modified,
-      modify_scope,
-      info,
-    })
+    (
+      WriteRef { value: a, modified, modify_scope, info },
+      WriteRef { value: b, modified, modify_scope, info },
+    )
   }
 
   /// Forget all modifies of this reference. So all the modifies occurred on
@@ -795,4 +797,50 @@ mod tests {
     let v: ReadRef<dyn Any> = s.read();
     assert_eq!(*v.downcast_ref::<i32>().unwrap(), 0);
   }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn part_writer_by_index() {
+    reset_test_env!();
+
+    struct List {
+      items: Vec<i32>,
+      named: ahash::HashMap<String, i32>,
+    }
+    let list = Stateful::new(List {
+      items: vec![1, 2, 3],
+      named: [("a".to_string(), 1)].into_iter().collect(),
+    });
+    let item = part_writer!(&mut list.items[1]);
+    *item.write() = 20;
+    assert_eq!(list.read().items, [1, 20, 3]);
+
+    let named = part_writer!(&mut list.named["a"]);
+    *named.write() += 1;
+    assert_eq!(list.read().named["a"], 2);
+
+    let vec = Stateful::new(vec![(1, 2)]);
+    let second = part_reader!(&vec[0].1);
+    vec.write()[0].1 = 3;
+    assert_eq!(*second.read(), 3);
+
+    // The names used by the index never conflict with the macro.
+    fn v() -> usize { 2 }
+    let third = part_writer!(&mut list.items[v()]);
+    *third.write() = 30;
+    assert_eq!(list.read().items, [1, 20, 30]);
+  }
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  #[should_panic(expected = "the part index is out of bounds: the len is 1 but the index is 1")]
+  fn part_index_out_of_bounds() {
+    reset_test_env!();
+
+    let vec = Stateful::new(vec![1, 2]);
+    let last = part_writer!(&mut vec[1]);
+    vec.write().pop();
+    // The index is evaluated when the part is accessed.
+    let _ = *last.read();
+  }
 }
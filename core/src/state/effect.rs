@@ -0,0 +1,141 @@
+use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+use rxrust::ops::box_it::BoxOp;
+
+use crate::prelude::*;
+
+type Cleanup = Rc<RefCell<Option<Box<dyn FnOnce()>>>>;
+
+/// A side effect run by the [`effect!`] macro, it runs again when its
+/// dependencies are modified, and cleans up the previous run before that.
+///
+/// Unsubscribe it to stop the effect and clean up its last run, or use
+/// [`Effect::while_mounted`] to do it when a widget is disposed.
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let cnt = Stateful::new(0);
+///   let mut w = @Text { text: "effect" };
+///   effect! {
+///     let v = *$cnt;
+///     println!("start with {v}");
+///     move || println!("clean up {v}")
+///   }
+///   .while_mounted(&mut w);
+///   w
+/// };
+/// ```
+pub struct Effect {
+  subscription: BoxSubscription<'static>,
+  cleanup: Cleanup,
+}
+
+impl Effect {
+  /// Run `effect` when the `upstream` emits, the cleanup returned by the last
+  /// run is called before it.
+  pub fn new<C: FnOnce() + 'static>(
+    upstream: BoxOp<'static, ModifyScope, Infallible>,
+    mut effect: impl FnMut(ModifyScope) -> C + 'static,
+  ) -> Self {
+    let cleanup: Cleanup = <_>::default();
+    let c_cleanup = cleanup.clone();
+    let subscription = upstream.subscribe(move |scope| {
+      let prev = c_cleanup.borrow_mut().take();
+      if let Some(prev) = prev {
+        prev();
+      }
+      let next = effect(scope);
+      *c_cleanup.borrow_mut() = Some(Box::new(next));
+    });
+
+    Self { subscription: BoxSubscription::new(subscription), cleanup }
+  }
+
+  /// Stop the effect and clean up its last run when the `host` widget is
+  /// disposed.
+  pub fn while_mounted<T>(self, host: &mut FatObj<T>) {
+    let mut this = Some(self);
+    host
+      .get_mix_builtin_widget()
+      .on_disposed(move |_| {
+        if let Some(this) = this.take() {
+          this.unsubscribe();
+        }
+      });
+  }
+}
+
+impl Subscription for Effect {
+  fn unsubscribe(self) {
+    self.subscription.unsubscribe();
+    let cleanup = self.cleanup.borrow_mut().take();
+    if let Some(cleanup) = cleanup {
+      cleanup();
+    }
+  }
+
+  fn is_closed(&self) -> bool { self.subscription.is_closed() }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  #[cfg(target_arch = "wasm32")]
+  use crate::test_helper::wasm_bindgen_test;
+  use crate::{prelude::*, reset_test_env, test_helper::*};
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn cleanup_before_rerun() {
+    reset_test_env!();
+
+    let src = Stateful::new(0);
+    let logs = Rc::new(RefCell::new(vec![]));
+    let (mounted, w_mounted) = split_value(true);
+    let c_src = src.clone_writer();
+    let c_logs = logs.clone();
+
+    let w = fn_widget! {
+      let logs = logs.clone();
+      @MockBox {
+        size: Size::zero(),
+        @ {
+          pipe!(*$mounted).map(move |v| {
+            let logs = logs.clone();
+            v.then(move || fn_widget! {
+              let mut w = @MockBox { size: Size::zero() };
+              let logs = logs.clone();
+              effect! {
+                let v = *$src;
+                logs.borrow_mut().push(format!("run {v}"));
+                let logs = logs.clone();
+                move || logs.borrow_mut().push(format!("clean {v}"))
+              }
+              .while_mounted(&mut w);
+              w
+            })
+          })
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    assert_eq!(*c_logs.borrow(), ["run 0"]);
+
+    *c_src.write() += 1;
+    wnd.draw_frame();
+    assert_eq!(*c_logs.borrow(), ["run 0", "clean 0", "run 1"]);
+
+    *w_mounted.write() = false;
+    wnd.draw_frame();
+    assert_eq!(*c_logs.borrow(), ["run 0", "clean 0", "run 1", "clean 1"]);
+
+    *c_src.write() += 1;
+    wnd.draw_frame();
+    assert_eq!(c_logs.borrow().len(), 4);
+  }
+}
@@ -1,7 +1,11 @@
 //! This implementation is a fork from `std::cell::RefCell`, allowing us to
 //! manage the borrow flag.
 use std::{
+  borrow::Borrow,
   cell::{Cell, UnsafeCell},
+  collections::{BTreeMap, HashMap, VecDeque},
+  fmt::Debug,
+  hash::{BuildHasher, Hash},
   marker::PhantomData,
   ops::{Deref, DerefMut},
   ptr::NonNull,
@@ -232,6 +236,111 @@ impl<'a, T> PartMut<'a, T> {
   }
 }
 
+/// Index a collection by the `part_writer!` and `part_reader!` macros, such as
+/// `part_writer!(&mut writer.items[3])`.
+///
+/// The index is evaluated every time the part is accessed, and it panics with
+/// the index and the collection length or the missing key if the element
+/// doesn't exist.
+pub trait PartIndex<Idx> {
+  type Output: ?Sized;
+
+  fn part_index(&self, idx: Idx) -> &Self::Output;
+
+  fn part_index_mut(&mut self, idx: Idx) -> &mut Self::Output;
+}
+
+#[track_caller]
+fn out_of_bounds(idx: usize, len: usize) -> ! {
+  panic!("the part index is out of bounds: the len is {len} but the index is {idx}")
+}
+
+#[track_caller]
+fn key_not_found(key: &dyn Debug) -> ! { panic!("the part key {key:?} isn't found") }
+
+impl<T> PartIndex<usize> for [T] {
+  type Output = T;
+
+  #[track_caller]
+  fn part_index(&self, idx: usize) -> &T {
+    self
+      .get(idx)
+      .unwrap_or_else(|| out_of_bounds(idx, self.len()))
+  }
+
+  #[track_caller]
+  fn part_index_mut(&mut self, idx: usize) -> &mut T {
+    let len = self.len();
+    self
+      .get_mut(idx)
+      .unwrap_or_else(|| out_of_bounds(idx, len))
+  }
+}
+
+impl<T> PartIndex<usize> for VecDeque<T> {
+  type Output = T;
+
+  #[track_caller]
+  fn part_index(&self, idx: usize) -> &T {
+    self
+      .get(idx)
+      .unwrap_or_else(|| out_of_bounds(idx, self.len()))
+  }
+
+  #[track_caller]
+  fn part_index_mut(&mut self, idx: usize) -> &mut T {
+    let len = self.len();
+    self
+      .get_mut(idx)
+      .unwrap_or_else(|| out_of_bounds(idx, len))
+  }
+}
+
+impl<K, V, S, Q> PartIndex<&Q> for HashMap<K, V, S>
+where
+  K: Eq + Hash + Borrow<Q>,
+  Q: Eq + Hash + Debug + ?Sized,
+  S: BuildHasher,
+{
+  type Output = V;
+
+  #[track_caller]
+  fn part_index(&self, key: &Q) -> &V {
+    self
+      .get(key)
+      .unwrap_or_else(|| key_not_found(&key))
+  }
+
+  #[track_caller]
+  fn part_index_mut(&mut self, key: &Q) -> &mut V {
+    self
+      .get_mut(key)
+      .unwrap_or_else(|| key_not_found(&key))
+  }
+}
+
+impl<K, V, Q> PartIndex<&Q> for BTreeMap<K, V>
+where
+  K: Ord + Borrow<Q>,
+  Q: Ord + Debug + ?Sized,
+{
+  type Output = V;
+
+  #[track_caller]
+  fn part_index(&self, key: &Q) -> &V {
+    self
+      .get(key)
+      .unwrap_or_else(|| key_not_found(&key))
+  }
+
+  #[track_caller]
+  fn part_index_mut(&mut self, key: &Q) -> &mut V {
+    self
+      .get_mut(key)
+      .unwrap_or_else(|| key_not_found(&key))
+  }
+}
+
 pub struct ReadRef<'a, T: ?Sized> {
   pub(crate) inner: InnerPart<T>,
   pub(crate) borrow: BorrowRef<'a>,
@@ -0,0 +1,90 @@
+use std::convert::Infallible;
+
+use rxrust::{ops::take_until::TakeUntilOp, prelude::*};
+
+use crate::prelude::FatObj;
+
+pub type DisposedNotifier = Subject<'static, (), Infallible>;
+
+/// This trait limits an Observable to emit values only until a widget is
+/// disposed, so the subscription will not outlive the widget and needn't be
+/// unsubscribed manually.
+pub trait WhileMounted<Item, Err>: ObservableExt<Item, Err> {
+  /// Completes the Observable and unsubscribes its source when the `host`
+  /// widget is disposed.
+  ///
+  /// ```
+  /// use ribir::prelude::*;
+  ///
+  /// let _w = fn_widget! {
+  ///   let cnt = Stateful::new(0);
+  ///   let mut text = @Text { text: "0" };
+  ///   watch!(*$cnt)
+  ///     .while_mounted(&mut text)
+  ///     .subscribe(move |v| $text.write().text = v.to_string().into());
+  ///   text
+  /// };
+  /// ```
+  fn while_mounted<T>(
+    self, host: &mut FatObj<T>,
+  ) -> TakeUntilOp<Self, DisposedNotifier, (), Infallible> {
+    let disposed = DisposedNotifier::default();
+    let mut notifier = disposed.clone();
+    host
+      .get_mix_builtin_widget()
+      .on_disposed(move |_| notifier.next(()));
+    self.take_until(disposed)
+  }
+}
+
+impl<Item, Err, T> WhileMounted<Item, Err> for T where T: ObservableExt<Item, Err> {}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(target_arch = "wasm32")]
+  use crate::test_helper::wasm_bindgen_test;
+  use crate::{prelude::*, reset_test_env, test_helper::*};
+
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn unsubscribe_when_disposed() {
+    reset_test_env!();
+
+    let src = Stateful::new(0);
+    let hits = Stateful::new(vec![]);
+    let (mounted, c_mounted) = split_value(true);
+    let c_src = src.clone_writer();
+    let c_hits = hits.clone_reader();
+
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::zero(),
+        @ {
+          pipe!(*$mounted).map(move |v| {
+            v.then(move || fn_widget! {
+              let mut w = @MockBox { size: Size::zero() };
+              watch!(*$src)
+                .while_mounted(&mut w)
+                .subscribe(move |v| $hits.write().push(v));
+              w
+            })
+          })
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+
+    *c_src.write() += 1;
+    wnd.draw_frame();
+    assert_eq!(*c_hits.read(), [0, 1]);
+
+    *c_mounted.write() = false;
+    wnd.draw_frame();
+
+    *c_src.write() += 1;
+    wnd.draw_frame();
+    assert_eq!(*c_hits.read(), [0, 1]);
+  }
+}
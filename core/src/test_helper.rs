@@ -8,10 +8,12 @@ pub use wasm_bindgen_test::wasm_bindgen_test;
 #[cfg(target_family = "wasm")]
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
+use winit::event::{ElementState, MouseButton, MouseScrollDelta};
+
 pub use crate::timer::Timer;
 use crate::{
   prelude::*,
-  window::{ShellWindow, WindowFlags, WindowId},
+  window::{CaptureFuture, ShellWindow, WindowFlags, WindowId},
 };
 
 pub struct Frame {
@@ -20,6 +22,9 @@ pub struct Frame {
   pub surface: Color,
 }
 
+/// Renders a frame to an image, used by [`TestShellWindow`] to capture itself.
+pub type FrameRenderer = Box<dyn Fn(&Frame) -> PixelImage>;
+
 pub fn split_value<T: 'static>(v: T) -> (Watcher<Reader<T>>, Stateful<T>) {
   let src = Stateful::new(v);
   (src.clone_watcher(), src.clone_writer())
@@ -35,6 +40,7 @@ macro_rules! reset_test_env {
   () => {
     let _ = $crate::prelude::NEW_TIMER_FN.set($crate::timer::Timer::new_timer_future);
     let _guard = $crate::prelude::AppCtx::new_lock_scope();
+    $crate::prelude::AppCtx::use_test_fonts();
   };
 }
 
@@ -107,6 +113,88 @@ impl TestWindow {
     AppCtx::frame_ticks().clone().next(Instant::now());
     self.0.draw_frame();
   }
+
+  /// Move the cursor to `pos`, the position is relative to the window.
+  pub fn cursor_move(&mut self, pos: Point) -> &mut Self {
+    self.layout_if_need();
+    self.inject_cursor_move(pos);
+    self.run_frame_tasks();
+    self
+  }
+
+  /// Press the left mouse button at the current cursor position.
+  pub fn mouse_press(&mut self) -> &mut Self { self.mouse_input(ElementState::Pressed) }
+
+  /// Release the left mouse button at the current cursor position.
+  pub fn mouse_release(&mut self) -> &mut Self { self.mouse_input(ElementState::Released) }
+
+  /// Move the cursor to `pos`, then press and release the left mouse button.
+  pub fn tap_at(&mut self, pos: Point) -> &mut Self {
+    self.layout_if_need();
+    self.inject_tap(pos);
+    self.run_frame_tasks();
+    self
+  }
+
+  /// Drag from `from` to `to` with the left mouse button.
+  pub fn drag(&mut self, from: Point, to: Point) -> &mut Self {
+    self
+      .cursor_move(from)
+      .mouse_press()
+      .cursor_move(to)
+      .mouse_release()
+  }
+
+  /// Scroll the mouse wheel by `delta` pixels at the current cursor position.
+  pub fn wheel(&mut self, delta: Vector) -> &mut Self {
+    self.layout_if_need();
+    let delta = MouseScrollDelta::PixelDelta((delta.x as f64, delta.y as f64).into());
+    let ratio = self.device_pixel_ratio() as f64;
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_wheel(delta, ratio);
+    self.run_frame_tasks();
+    self
+  }
+
+  /// Press and release a key, the event is sent to the focused widget.
+  pub fn key_press(&mut self, physical_key: PhysicalKey, key: VirtualKey) -> &mut Self {
+    self.inject_key_press(physical_key, key);
+    self.run_frame_tasks();
+    self
+  }
+
+  /// Type the `text` to the focused widget.
+  pub fn type_text(&mut self, text: &str) -> &mut Self {
+    self.inject_text(text);
+    self.run_frame_tasks();
+    self
+  }
+
+  /// Assert the value of the `state` is equal to `expected`.
+  #[track_caller]
+  pub fn expect_state<V: PartialEq + std::fmt::Debug>(
+    &mut self, state: &impl StateReader<Value = V>, expected: V,
+  ) -> &mut Self {
+    self.run_frame_tasks();
+    assert_eq!(*state.read(), expected);
+    self
+  }
+
+  fn mouse_input(&mut self, state: ElementState) -> &mut Self {
+    self.layout_if_need();
+    self.inject_mouse_input(state, MouseButton::Left);
+    self.run_frame_tasks();
+    self
+  }
+
+  /// The events hit test by the layout, so make sure the layout is up to date.
+  fn layout_if_need(&mut self) {
+    if self.need_draw() {
+      self.draw_frame();
+    }
+  }
 }
 
 impl std::ops::Deref for TestWindow {
@@ -121,6 +209,21 @@ pub struct TestShellWindow {
   pub id: WindowId,
   pub surface_color: Color,
   pub last_frame: Option<Frame>,
+  /// The levels the window is set to, in order.
+  pub level_changes: Vec<WindowLevel>,
+  pub maximized: bool,
+  /// How many times the window is dragged by `drag_window`.
+  pub drag_times: usize,
+  /// The safe area insets the window reports, zero by default.
+  pub safe_area: EdgeInsets,
+  /// The rect covered by the on-screen keyboard the window reports, `None` by
+  /// default.
+  pub keyboard_rect: Option<Rect>,
+  /// Renders the last frame when the window is captured, `None` by default,
+  /// so the window can't be captured.
+  pub renderer: Option<FrameRenderer>,
+  /// The device pixel ratio the window reports, `1.` by default.
+  pub device_pixel_ratio: f32,
 }
 
 impl ShellWindow for TestShellWindow {
@@ -137,6 +240,10 @@ impl ShellWindow for TestShellWindow {
 
   fn set_min_size(&mut self, _: Size) {}
 
+  fn set_max_size(&mut self, _: Size) {}
+
+  fn set_outer_position(&mut self, _: Point) {}
+
   fn set_cursor(&mut self, cursor: CursorIcon) { self.cursor = cursor; }
 
   fn cursor(&self) -> CursorIcon { self.cursor }
@@ -159,10 +266,30 @@ impl ShellWindow for TestShellWindow {
 
   fn set_decorations(&mut self, _: bool) {}
 
+  fn window_level(&self) -> WindowLevel {
+    self
+      .level_changes
+      .last()
+      .copied()
+      .unwrap_or(WindowLevel::Normal)
+  }
+
+  fn set_window_level(&mut self, level: WindowLevel) { self.level_changes.push(level); }
+
   fn is_minimized(&self) -> bool { false }
 
   fn set_minimized(&mut self, _: bool) {}
 
+  fn is_maximized(&self) -> bool { self.maximized }
+
+  fn set_maximized(&mut self, maximized: bool) { self.maximized = maximized; }
+
+  fn drag_window(&mut self) { self.drag_times += 1; }
+
+  fn safe_area_insets(&self) -> EdgeInsets { self.safe_area }
+
+  fn keyboard_rect(&self) -> Option<Rect> { self.keyboard_rect }
+
   fn set_ime_allowed(&mut self, _: bool) {}
 
   fn as_any(&self) -> &dyn Any { self }
@@ -178,9 +305,14 @@ impl ShellWindow for TestShellWindow {
 
   fn end_frame(&mut self) {}
 
+  fn capture(&mut self) -> Option<CaptureFuture> {
+    let img = (self.renderer.as_ref()?)(self.last_frame.as_ref()?);
+    Some(Box::pin(std::future::ready(Some(img))))
+  }
+
   fn id(&self) -> WindowId { self.id }
 
-  fn device_pixel_ratio(&self) -> f32 { 1. }
+  fn device_pixel_ratio(&self) -> f32 { self.device_pixel_ratio }
 }
 
 impl TestShellWindow {
@@ -193,6 +325,13 @@ impl TestShellWindow {
       id: ID.fetch_add(1, Ordering::Relaxed).into(),
       last_frame: None,
       surface_color: Color::WHITE,
+      level_changes: vec![],
+      maximized: false,
+      drag_times: 0,
+      safe_area: EdgeInsets::default(),
+      keyboard_rect: None,
+      renderer: None,
+      device_pixel_ratio: 1.,
     }
   }
 }
@@ -360,13 +499,21 @@ pub struct WidgetTester {
   pub env_init: Option<Box<dyn FnOnce()>>,
   pub on_initd: Option<InitdFn>,
   pub comparison: Option<f64>,
+  pub perceptual: bool,
 }
 
 type InitdFn = Box<dyn FnOnce(&mut TestWindow)>;
 
 impl WidgetTester {
   pub fn new(widget: impl Into<GenWidget>) -> Self {
-    Self { wnd_size: None, widget: widget.into(), on_initd: None, env_init: None, comparison: None }
+    Self {
+      wnd_size: None,
+      widget: widget.into(),
+      on_initd: None,
+      env_init: None,
+      comparison: None,
+      perceptual: false,
+    }
   }
 
   pub fn with_env_init(mut self, env_init: impl Fn() + 'static) -> Self {
@@ -390,6 +537,14 @@ impl WidgetTester {
     self
   }
 
+  /// Compare the rendered image with the reference image perceptually, so
+  /// the anti-aliasing differences across GPUs don't fail the test.
+  pub fn with_perceptual_comparison(mut self, threshold: f64) -> Self {
+    self.comparison = Some(threshold);
+    self.perceptual = true;
+    self
+  }
+
   pub fn create_wnd(&mut self) -> TestWindow {
     if let Some(env_init) = self.env_init.take() {
       env_init();
@@ -414,3 +569,39 @@ impl WidgetTester {
 impl Default for LayoutCase {
   fn default() -> Self { Self { path: &[0], x: None, y: None, width: None, height: None } }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn synthetic_input() {
+    reset_test_env!();
+
+    let count = Stateful::new(0);
+    let c_count = count.clone_writer();
+    let chars = Stateful::new(String::new());
+    let c_chars = chars.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(50., 50.),
+          auto_focus: true,
+          on_tap: move |_| *$c_count.write() += 1,
+          on_chars: move |e| $c_chars.write().push_str(&e.chars),
+        }
+      },
+      Size::new(100., 100.),
+    );
+
+    wnd
+      .tap_at(Point::new(10., 10.))
+      .expect_state(&count, 1)
+      .tap_at(Point::new(80., 80.))
+      .expect_state(&count, 1)
+      .tap_at(Point::new(40., 40.))
+      .expect_state(&count, 2)
+      .type_text("hi")
+      .expect_state(&chars, "hi".to_string());
+  }
+}
@@ -1,11 +1,14 @@
-use std::convert::Infallible;
 #[cfg(not(target_family = "wasm"))]
 pub use std::time::{Duration, Instant};
+use std::{cell::Cell, convert::Infallible, rc::Rc};
 
-use rxrust::prelude::Subject;
+use ribir_algo::Sc;
+use rxrust::prelude::*;
 #[cfg(target_family = "wasm")]
 pub use web_time::{Duration, Instant};
 
+use crate::{state::StateWriter, window::Window};
+
 /// Frame ticker emit message when new frame need to draw.
 pub type FrameTicker = Subject<'static, FrameMsg, Infallible>;
 
@@ -36,3 +39,317 @@ pub enum FrameMsg {
   /// receive this message.
   Finish(Instant),
 }
+
+/// A limiter to cap the frame rate of the continuous frames, such as the
+/// frames of a running animation.
+///
+/// A frame is never delayed more than one frame interval, so the limiter does
+/// not introduce an input lag beyond one frame.
+#[derive(Debug, Clone, Default)]
+pub struct FrameLimiter {
+  max_fps: Option<u32>,
+  last_frame: Option<Instant>,
+}
+
+impl FrameLimiter {
+  pub fn new(max_fps: Option<u32>) -> Self { Self { max_fps, last_frame: None } }
+
+  /// The maximum frames per second, `None` means no limit.
+  pub fn max_fps(&self) -> Option<u32> { self.max_fps }
+
+  pub fn set_max_fps(&mut self, max_fps: Option<u32>) { self.max_fps = max_fps; }
+
+  /// Return the time when the next frame is allowed to draw, or `None` if it
+  /// can draw at `now`.
+  pub fn next_frame_time(&self, now: Instant) -> Option<Instant> {
+    let fps = self.max_fps.filter(|fps| *fps > 0)?;
+    let next = self.last_frame? + Duration::from_secs(1) / fps;
+    (next > now).then_some(next)
+  }
+
+  /// Record a frame is drawn at `at`.
+  pub fn frame_drawn(&mut self, at: Instant) { self.last_frame = Some(at); }
+}
+
+/// The mode of a [`Timer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerMode {
+  /// Count down from the duration to zero, the timer stops at zero.
+  Countdown(Duration),
+  /// Count up from zero without an end.
+  Stopwatch,
+}
+
+/// The durations of a [`Timer`] emitted to its tick callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerTick {
+  /// The running time, the paused time is not included.
+  pub elapsed: Duration,
+  /// The time left to zero of a countdown, `None` for a stopwatch.
+  pub remaining: Option<Duration>,
+}
+
+/// A countdown or stopwatch timer that can be started, paused and reset.
+///
+/// The timer only counts the time it's running, a pause keeps the time counted
+/// so far and a start resumes from it. It's driven by the frames of a window
+/// with [`Timer::run_in`], which calls the tick callback once every frame while
+/// it's running.
+///
+/// The methods take the current time as an argument, so the timer can be
+/// advanced by any clock, pass `Instant::now()` to use the real time.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::{prelude::*, ticker::*};
+///
+/// let _ = fn_widget! {
+///   let timer = Stateful::new(Timer::new(TimerMode::Countdown(Duration::from_secs(25 * 60))));
+///   let handle = Timer::run_in(timer.clone_writer(), BuildCtx::get().window());
+///   timer.write().start(Instant::now());
+///   @Text {
+///     text: pipe!($timer.remaining(Instant::now()).unwrap().as_secs().to_string()),
+///     on_disposed: move |_| handle.unsubscribe(),
+///   }
+/// };
+/// ```
+pub struct Timer {
+  mode: TimerMode,
+  /// The time counted before the current run.
+  counted: Duration,
+  /// The time the current run started at, `None` if the timer is not running.
+  started_at: Option<Instant>,
+  on_tick: Option<Box<dyn FnMut(TimerTick)>>,
+}
+
+impl Timer {
+  pub fn new(mode: TimerMode) -> Self {
+    Self { mode, counted: Duration::ZERO, started_at: None, on_tick: None }
+  }
+
+  /// Set the callback called with the durations of the timer on every tick.
+  pub fn with_on_tick(mut self, f: impl FnMut(TimerTick) + 'static) -> Self {
+    self.on_tick = Some(Box::new(f));
+    self
+  }
+
+  pub fn mode(&self) -> TimerMode { self.mode }
+
+  pub fn is_running(&self) -> bool { self.started_at.is_some() }
+
+  /// Start or resume the timer at `now`, do nothing if it's running or it's a
+  /// finished countdown.
+  pub fn start(&mut self, now: Instant) {
+    if !self.is_running() && !self.is_finished(now) {
+      self.started_at = Some(now);
+    }
+  }
+
+  /// Pause the timer at `now`, the time counted so far is kept.
+  pub fn pause(&mut self, now: Instant) {
+    if self.is_running() {
+      self.counted = self.elapsed(now);
+      self.started_at = None;
+    }
+  }
+
+  /// Stop the timer and clear the time counted.
+  pub fn reset(&mut self) {
+    self.counted = Duration::ZERO;
+    self.started_at = None;
+  }
+
+  /// The running time at `now`, a countdown never exceeds its duration.
+  pub fn elapsed(&self, now: Instant) -> Duration {
+    let running = self
+      .started_at
+      .map_or(Duration::ZERO, |at| now.saturating_duration_since(at));
+    let elapsed = self.counted + running;
+    match self.mode {
+      TimerMode::Countdown(total) => elapsed.min(total),
+      TimerMode::Stopwatch => elapsed,
+    }
+  }
+
+  /// The time left to zero at `now`, `None` for a stopwatch.
+  pub fn remaining(&self, now: Instant) -> Option<Duration> {
+    match self.mode {
+      TimerMode::Countdown(total) => Some(total - self.elapsed(now)),
+      TimerMode::Stopwatch => None,
+    }
+  }
+
+  /// Return if the timer is a countdown that reached zero at `now`.
+  pub fn is_finished(&self, now: Instant) -> bool { self.remaining(now) == Some(Duration::ZERO) }
+
+  /// Call the tick callback with the durations at `now`, a countdown reached
+  /// zero stops.
+  pub fn tick(&mut self, now: Instant) {
+    let tick = TimerTick { elapsed: self.elapsed(now), remaining: self.remaining(now) };
+    if self.is_finished(now) {
+      self.pause(now);
+    }
+    if let Some(on_tick) = self.on_tick.as_mut() {
+      on_tick(tick);
+    }
+  }
+
+  /// Drive the timer by the frames of the window, the timer ticks once every
+  /// frame before the layout while it's running, and the window keeps drawing
+  /// frames until it's paused.
+  ///
+  /// Unsubscribe the returned handle to stop driving the timer.
+  pub fn run_in(this: impl StateWriter<Value = Self> + 'static, wnd: Sc<Window>) -> TimerHandle {
+    let animating = Rc::new(Cell::new(false));
+    let c_animating = animating.clone();
+    let c_wnd = wnd.clone();
+    let sync = move |running: bool| {
+      if c_animating.get() != running {
+        c_animating.set(running);
+        if running { c_wnd.inc_running_animate() } else { c_wnd.dec_running_animate() }
+      }
+    };
+
+    let frames = wnd
+      .frame_tick_stream()
+      .filter_map(|msg| match msg {
+        FrameMsg::BeforeLayout(at) => Some(Some(at)),
+        _ => None,
+      });
+    let modifies = this.modifies().map(|_| None);
+    sync(this.read().is_running());
+    let subscription = frames
+      .merge(modifies)
+      .subscribe(move |at: Option<Instant>| {
+        if let Some(at) = at.filter(|_| this.read().is_running()) {
+          this.write().tick(at);
+        }
+        sync(this.read().is_running());
+      });
+    let subscription = BoxSubscription::new(subscription);
+
+    TimerHandle { subscription, animating, wnd }
+  }
+}
+
+/// The handle of a [`Timer`] driven by a window, returned by
+/// [`Timer::run_in`].
+pub struct TimerHandle {
+  subscription: BoxSubscription<'static>,
+  animating: Rc<Cell<bool>>,
+  wnd: Sc<Window>,
+}
+
+impl Subscription for TimerHandle {
+  fn unsubscribe(self) {
+    self.subscription.unsubscribe();
+    if self.animating.get() {
+      self.wnd.dec_running_animate();
+    }
+  }
+
+  fn is_closed(&self) -> bool { self.subscription.is_closed() }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{prelude::*, reset_test_env, test_helper::TestWindow};
+
+  #[test]
+  fn limit_frame_rate() {
+    let start = Instant::now();
+    let simulate = |limiter: &mut FrameLimiter| {
+      // Request a frame every millisecond in one second.
+      let mut frames = 0;
+      for ms in 0..1000 {
+        let now = start + Duration::from_millis(ms);
+        if limiter.next_frame_time(now).is_none() {
+          limiter.frame_drawn(now);
+          frames += 1;
+        }
+      }
+      frames
+    };
+
+    let frames = simulate(&mut FrameLimiter::new(Some(60)));
+    assert!((55..=60).contains(&frames), "{frames}");
+    let frames = simulate(&mut FrameLimiter::new(Some(30)));
+    assert!((28..=30).contains(&frames), "{frames}");
+    assert_eq!(simulate(&mut FrameLimiter::new(None)), 1000);
+  }
+
+  #[test]
+  fn delay_at_most_one_frame() {
+    let mut limiter = FrameLimiter::new(Some(50));
+    let start = Instant::now();
+    limiter.frame_drawn(start);
+
+    let now = start + Duration::from_millis(1);
+    let next = limiter.next_frame_time(now).unwrap();
+    assert_eq!(next, start + Duration::from_millis(20));
+    assert!(limiter.next_frame_time(next).is_none());
+  }
+
+  #[test]
+  fn countdown_pause_and_resume() {
+    let start = Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let ticks = Rc::new(std::cell::RefCell::new(vec![]));
+    let c_ticks = ticks.clone();
+    let mut timer = Timer::new(TimerMode::Countdown(Duration::from_secs(10)))
+      .with_on_tick(move |tick| c_ticks.borrow_mut().push(tick));
+
+    timer.start(start);
+    timer.tick(at(3));
+    timer.pause(at(3));
+    // The paused time is not counted.
+    assert_eq!(timer.remaining(at(8)), Some(Duration::from_secs(7)));
+
+    timer.start(at(8));
+    timer.tick(at(10));
+    assert_eq!(&*ticks.borrow(), &[
+      TimerTick { elapsed: Duration::from_secs(3), remaining: Some(Duration::from_secs(7)) },
+      TimerTick { elapsed: Duration::from_secs(5), remaining: Some(Duration::from_secs(5)) },
+    ]);
+
+    // The countdown stops at zero and can't start again until reset.
+    timer.tick(at(20));
+    assert_eq!(timer.remaining(at(20)), Some(Duration::ZERO));
+    assert!(!timer.is_running());
+    timer.start(at(21));
+    assert!(!timer.is_running());
+
+    timer.reset();
+    assert_eq!(timer.remaining(at(22)), Some(Duration::from_secs(10)));
+  }
+
+  #[test]
+  fn stopwatch_driven_by_frames() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget!(Void));
+    let ticks = Rc::new(Cell::new(0));
+    let c_ticks = ticks.clone();
+    let timer = Stateful::new(
+      Timer::new(TimerMode::Stopwatch).with_on_tick(move |_| c_ticks.set(c_ticks.get() + 1)),
+    );
+    let handle = Timer::run_in(timer.clone_writer(), wnd.0.clone());
+    wnd.draw_frame();
+    assert_eq!(ticks.get(), 0);
+
+    timer.write().start(Instant::now());
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(ticks.get(), 2);
+    assert!(timer.read().elapsed(Instant::now()) > Duration::ZERO);
+
+    timer.write().pause(Instant::now());
+    wnd.draw_frame();
+    assert!(!wnd.need_draw());
+    assert_eq!(ticks.get(), 2);
+
+    handle.unsubscribe();
+  }
+}
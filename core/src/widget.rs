@@ -71,6 +71,23 @@ pub trait Render: 'static {
 
   /// Return a transform to map the coordinate to parent coordinate.
   fn get_transform(&self) -> Option<Transform> { None }
+
+  /// Return the default semantics of the widget for the assistive
+  /// technologies, it's used if the widget has no explicit semantics.
+  fn semantics(&self) -> Option<Semantics> { None }
+
+  /// Return the distance from the top of the widget to the baseline of its
+  /// first line of text after it is laid out, or `None` if the widget has no
+  /// baseline. It's used to align the widgets by their baselines.
+  fn baseline(&self) -> Option<f32> { None }
+
+  /// The type name of the render widget, it's used to describe the widget in
+  /// the diagnostic messages.
+  fn type_name(&self) -> &'static str { std::any::type_name::<Self>() }
+
+  /// Push the properties worth showing in the diagnostic messages, like
+  /// `opacity: 0.5`, into `props`.
+  fn debug_properties(&self, _props: &mut Vec<String>) {}
 }
 
 /// The common type of all widget can convert to.
@@ -215,7 +232,7 @@ impl<'w> Widget<'w> {
   }
 
   pub(crate) fn from_render(r: Box<dyn RenderQueryable>) -> Widget<'static> {
-    Widget::from_fn(|_| BuildCtx::get_mut().tree_mut().alloc_node(r))
+    Widget::from_fn(|ctx| ctx.alloc_node(r))
   }
 
   /// Attach anonymous data to a widget and user can't query it.
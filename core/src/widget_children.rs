@@ -226,6 +226,48 @@ pub trait ChildOfCompose {}
 /// This template outlines two child components for its parent: a mandatory
 /// `Leading<Widget>` and an optional `Trailing<Widget>`.
 ///
+/// A field that is not an `Option` is required, omitting it is a compile-time
+/// error. A required field can be made optional by giving it a default value
+/// with `#[template(default)]` or `#[template(default = expr)]`, then it uses
+/// the default value if it's omitted.
+///
+/// ```rust
+/// use ribir::prelude::*;
+///
+/// #[derive(Template)]
+/// struct CardTml<'w> {
+///   header: Option<Leading<Widget<'w>>>,
+///   body: Widget<'w>,
+///   #[template(default = Trailing::new(Void))]
+///   footer: Trailing<Widget<'w>>,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use ribir::prelude::*;
+///
+/// #[derive(Declare)]
+/// struct Card;
+///
+/// #[derive(Template)]
+/// struct CardTml<'w> {
+///   header: Option<Leading<Widget<'w>>>,
+///   body: Widget<'w>,
+/// }
+///
+/// impl<'c> ComposeChild<'c> for Card {
+///   type Child = CardTml<'c>;
+///   fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+///     child.body
+///   }
+/// }
+///
+/// // The required `body` is omitted.
+/// let _card = fn_widget! {
+///   @Card { @Leading::new(@Text { text: "header" }) }
+/// };
+/// ```
+///
 /// ```rust
 /// use ribir::prelude::*;
 ///
@@ -241,7 +283,10 @@ pub trait ChildOfCompose {}
 ///
 /// Refer to the [`ComposeChild`] documentation for further information.
 pub trait Template: Sized {
-  type Builder: TemplateBuilder;
+  /// The builder that collects the children. It may change its type as the
+  /// children are filled, and can only build the template after all the
+  /// required children are filled.
+  type Builder;
   fn builder() -> Self::Builder;
 }
 
@@ -517,4 +562,37 @@ mod tests {
     WidgetTester::new(fn_widget! { @Host { @{ Field } }}),
     LayoutCase::default().with_size(FIX_OPTION_TEMPLATE_EXPECT_SIZE)
   );
+
+  #[test]
+  #[should_panic(expected = "`ribir_core::test_helper::MockBox` is a single child widget, it \
+                             expects at most 1 child but got 2 children.")]
+  fn single_child_with_two_children() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      let parent = MockBox { size: Size::new(100., 100.) }.into_widget();
+      Widget::new(parent, vec![Void.into_widget(), Void.into_widget()])
+    });
+    wnd.draw_frame();
+  }
+
+  #[test]
+  #[should_panic(expected = "expects exactly 1 child but got none.")]
+  fn single_child_without_child() {
+    reset_test_env!();
+
+    #[derive(Declare, SingleChild)]
+    struct OnlyOne;
+
+    impl Render for OnlyOne {
+      fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+        ctx.assert_perform_single_child_layout(clamp)
+      }
+
+      fn paint(&self, _: &mut PaintingCtx) {}
+    }
+
+    let mut wnd = TestWindow::new(fn_widget! { OnlyOne });
+    wnd.draw_frame();
+  }
 }
@@ -56,6 +56,7 @@ impl WidgetTree {
 
     let (mut providers, child) = Theme::preprocess_before_compose(theme, child.into());
     providers.push(Provider::new(ShowingOverlays::default()));
+    providers.push(Provider::value_of_watcher(wnd.safe_area_watcher()));
 
     let root = Providers::new(providers).with_child(child);
     let root = BuildCtx::get_mut().build(root);
@@ -82,26 +83,74 @@ impl WidgetTree {
 
   /// Do the work of computing the layout for all node which need, Return if any
   /// node has really computing the layout.
+  ///
+  /// Only the dirty widgets and their parents are laid out again with their
+  /// last constraints, the change propagates to the further ancestors only if
+  /// the size changed. The clean siblings reuse their last layout.
   pub(crate) fn layout(&mut self, win_size: Size) {
     loop {
-      let Some(mut needs_layout) = self.layout_list() else {
+      let Some(needs_layout) = self.layout_list() else {
         break;
       };
-      while let Some(wid) = needs_layout.pop() {
-        if wid.is_dropped(self) {
-          continue;
-        }
-        if self.store.layout_box_size(wid).is_none() {
-          let clamp = self
+      // Clear the size of all the dirty widgets first, so a dirty widget is laid
+      // out by its dirty ancestor, not twice.
+      let mut needs_layout: Vec<_> = needs_layout
+        .into_iter()
+        .map(|id| {
+          let old_size = self
             .store
-            .layout_info(wid)
-            .map(|info| info.clamp)
-            .unwrap_or_else(|| BoxClamp { min: Size::zero(), max: win_size });
+            .get_mut(&id)
+            .and_then(|info| info.size.take());
+          (id, old_size)
+        })
+        .collect();
+      while let Some((wid, old_size)) = needs_layout.pop() {
+        self.relayout_until_absorbed(wid, old_size, win_size);
+      }
+    }
+  }
 
-          let mut ctx = LayoutCtx::new(wid, self);
-          ctx.perform_layout(clamp);
+  /// Lay out the dirty widget with its last constraints and its parent, then
+  /// lay out the ancestors one by one while their size changed, until an
+  /// ancestor absorbs the change or is only sized by its parent.
+  fn relayout_until_absorbed(
+    &mut self, mut wid: WidgetId, mut old_size: Option<Size>, win_size: Size,
+  ) {
+    // The parent of the dirty widget always lays out again, because it may
+    // depend on more than the size of the widget, e.g. the flex of `Expanded`.
+    let mut dirty = true;
+    loop {
+      if wid.is_dropped(self) {
+        return;
+      }
+      let parent = wid.parent(self);
+      let clamp = match self.store.layout_info(wid) {
+        // Already laid out by its ancestor.
+        Some(LayoutInfo { size: Some(_), .. }) => return,
+        Some(info) => Some(info.clamp),
+        None if parent.is_none() => Some(BoxClamp { min: Size::zero(), max: win_size }),
+        // A new widget, its parent lays it out with the right constraints.
+        None => None,
+      };
+      if let Some(clamp) = clamp {
+        let size = LayoutCtx::new(wid, self).perform_layout(clamp);
+        if !dirty && (Some(size) == old_size || wid.assert_get(self).only_sized_by_parent()) {
+          return;
         }
       }
+      dirty = false;
+
+      let Some(p) = parent else { return };
+      // If the parent has no size, it's waiting to be laid out.
+      let Some(p_size) = self
+        .store
+        .get_mut(&p)
+        .and_then(|info| info.size.take())
+      else {
+        return;
+      };
+      wid = p;
+      old_size = Some(p_size);
     }
   }
 
@@ -121,7 +170,12 @@ impl WidgetTree {
     AppCtx::get_window(self.wnd_id).expect("Must initialize the widget tree before use it.")
   }
 
-  #[allow(unused)]
+  /// Describe the sub-tree in text, one widget a line with its type, id,
+  /// layout rect relative to its parent, the clamp it was laid out with and
+  /// its builtin properties like `opacity`, `visible` and `class`.
+  ///
+  /// Only the widget tree is walked, the states held by the widgets are not
+  /// visited, so the shared states never lead to a recursion.
   pub fn display_tree(&self, sub_tree: WidgetId) -> String {
     let mut display = String::new();
     self.display_node("".to_string(), sub_tree, &mut display);
@@ -129,7 +183,29 @@ impl WidgetTree {
   }
 
   fn display_node(&self, mut prefix: String, id: WidgetId, display: &mut String) {
-    display.push_str(&format!("{prefix}{:?}\n", id.0));
+    let node = id.assert_get(self);
+    let name = short_type_name(node.type_name());
+    let index = usize::from(id.0);
+    let layout = match self.store.layout_info(id) {
+      Some(LayoutInfo { clamp, size: Some(size), pos }) => format!(
+        "({}, {}, {}x{}) clamp: {}x{}..{}x{}",
+        pos.x, pos.y, size.width, size.height, clamp.min.width, clamp.min.height, clamp.max.width,
+        clamp.max.height
+      ),
+      _ => "not laid out".to_string(),
+    };
+    let mut props = vec![];
+    node.debug_properties(&mut props);
+    props.extend(
+      id.query_all_iter::<Class>(self)
+        .filter_map(|c| c.class)
+        .map(|c| format!("class: {}", c.name())),
+    );
+    display.push_str(&format!("{prefix}{name} #{index} {layout}"));
+    if !props.is_empty() {
+      display.push_str(&format!(" [{}]", props.join(", ")));
+    }
+    display.push('\n');
 
     prefix.pop();
     match prefix.pop() {
@@ -145,6 +221,29 @@ impl WidgetTree {
       self.display_node(prefix, c, display)
     });
   }
+
+  /// Describe the sub-tree in JSON, every node is an object with its `type`,
+  /// `id`, layout `rect` relative to its parent and `children`.
+  #[cfg(feature = "debug")]
+  pub fn json_tree(&self, id: WidgetId) -> serde_json::Value {
+    let rect = self.store.layout_info(id).and_then(|info| {
+      let size = info.size?;
+      Some(serde_json::json!({
+        "x": info.pos.x, "y": info.pos.y, "width": size.width, "height": size.height
+      }))
+    });
+    let children = id
+      .children(self)
+      .map(|c| self.json_tree(c))
+      .collect::<Vec<_>>();
+    serde_json::json!({
+      "type": short_type_name(id.assert_get(self).type_name()),
+      "id": usize::from(id.0),
+      "rect": rect,
+      "children": children,
+    })
+  }
+
   pub(crate) fn layout_list(&mut self) -> Option<Vec<WidgetId>> {
     if self.dirty_set.borrow().is_empty() {
       return None;
@@ -158,36 +257,14 @@ impl WidgetTree {
       }
 
       if dirty == DirtyPhase::LayoutSubtree {
-        for w in id.0.descendants(&self.arena).map(WidgetId) {
+        let descendants = id.0.descendants(&self.arena).skip(1);
+        for w in descendants.map(WidgetId) {
           if let Some(info) = self.store.get_mut(&w) {
             info.size.take();
           }
         }
-      } else if let Some(info) = self.store.get_mut(&id) {
-        info.size.take();
       }
-
-      let mut relayout_root = id;
-      // All ancestors of this render widget should relayout until the one which only
-      // sized by parent.
-      for p in id.0.ancestors(&self.arena).skip(1).map(WidgetId) {
-        // The first one may be a pipe that is newly generated. Otherwise, if there
-        // isn't layout information, it indicates that the ancestor marked for relayout
-        // already.
-        if self.store.layout_box_size(p).is_none() {
-          break;
-        }
-
-        relayout_root = p;
-        if let Some(info) = self.store.get_mut(&p) {
-          info.size.take();
-        }
-
-        if p.assert_get(self).only_sized_by_parent() {
-          break;
-        }
-      }
-      needs_layout.push(relayout_root);
+      needs_layout.push(id);
     }
 
     (!needs_layout.is_empty()).then(|| {
@@ -282,6 +359,22 @@ impl Render for Root {
   }
 }
 
+/// Strip the module paths of a type name, `a::b::Foo<c::Bar>` to `Foo<Bar>`.
+fn short_type_name(name: &str) -> String {
+  let mut short = String::with_capacity(name.len());
+  let mut start = 0;
+  for (i, c) in name.char_indices() {
+    if c == ':' {
+      start = i + 1;
+    } else if !(c.is_alphanumeric() || c == '_') {
+      short.push_str(&name[start..=i]);
+      start = i + 1;
+    }
+  }
+  short.push_str(&name[start..]);
+  short
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -533,4 +626,89 @@ mod tests {
     assert_eq!(*layout_cnt.read(), 1);
     assert_eq!(c_paint_cnt.read().paint_cnt.get(), 2);
   }
+
+  #[test]
+  fn paint_only_builtin_no_relayout() {
+    reset_test_env!();
+
+    struct LayoutCnt(std::rc::Rc<std::cell::Cell<usize>>);
+
+    impl Render for LayoutCnt {
+      fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size {
+        self.0.set(self.0.get() + 1);
+        clamp.max
+      }
+    }
+
+    let layout_cnt = std::rc::Rc::new(std::cell::Cell::new(0));
+    let c_layout_cnt = layout_cnt.clone();
+    let (opacity, w_opacity) = split_value(1.);
+    let (background, w_background) = split_value(Color::RED);
+    let (foreground, w_foreground) = split_value(Color::RED);
+    let (transform, w_transform) = split_value(Transform::identity());
+
+    let mut wnd = TestWindow::new(fat_obj! {
+      opacity: pipe!(*$opacity),
+      background: pipe!(*$background),
+      foreground: pipe!(*$foreground),
+      transform: pipe!(*$transform),
+      @ { LayoutCnt(c_layout_cnt.clone()) }
+    });
+    wnd.draw_frame();
+    assert_eq!(layout_cnt.get(), 1);
+
+    let mut assert_paint_only = |modify: &mut dyn FnMut()| {
+      modify();
+      AppCtx::run_until_stalled();
+      assert!(wnd.need_draw());
+      wnd.draw_frame();
+      assert_eq!(layout_cnt.get(), 1);
+    };
+    assert_paint_only(&mut || *w_opacity.write() = 0.5);
+    assert_paint_only(&mut || *w_background.write() = Color::BLUE);
+    assert_paint_only(&mut || *w_foreground.write() = Color::BLUE);
+    assert_paint_only(&mut || *w_transform.write() = Transform::translation(10., 10.));
+  }
+
+  #[test]
+  fn dump_tree() {
+    reset_test_env!();
+    class_names!(MULTI);
+
+    // The two boxes share the same state.
+    let size = Stateful::new(Size::new(10., 10.));
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        @MockMulti {
+          opacity: 0.5,
+          class: MULTI,
+          @MockBox { size: pipe!(*$size), visible: false }
+          @MockBox { size: pipe!(*$size * 2.) }
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    // The ids depend on the build order, only the structure is checked.
+    let tree = wnd.dump_tree();
+    let lines = tree
+      .lines()
+      .map(|l| {
+        let (name, rest) = l.split_once(" #").unwrap();
+        let (id, rest) = rest.split_once(' ').unwrap();
+        assert!(id.parse::<usize>().is_ok());
+        format!("{name} {rest}")
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(lines, [
+      "Root (0, 0, 200x200) clamp: 0x0..200x200",
+      "└─MockBox (0, 0, 100x100) clamp: 0x0..200x200",
+      "  └─MockMulti (0, 0, 20x20) clamp: 0x0..100x100 [opacity: 0.5, class: MULTI]",
+      "    ├─MockBox (0, 0, 0x0) clamp: 0x0..100x100 [visible: false]",
+      "    └─MockBox (0, 0, 20x20) clamp: 0x0..100x100",
+    ]);
+    assert_eq!(short_type_name("a::b::Foo<c::Bar, d::Baz>"), "Foo<Bar, Baz>");
+  }
 }
@@ -365,4 +365,41 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*cnt.read(), 2);
   }
+
+  #[test]
+  fn relayout_only_dirty_chain() {
+    reset_test_env!();
+
+    let (laid_out, w_laid_out) = split_value(vec![]);
+    let c_laid_out = w_laid_out.clone_writer();
+    let (size, w_size) = split_value(Size::new(10., 10.));
+    let record = move |name: &'static str| {
+      let w_laid_out = w_laid_out.clone_writer();
+      move |_: &mut LifecycleEvent| w_laid_out.write().push(name)
+    };
+    let w = fn_widget! {
+      @MockMulti {
+        on_performed_layout: record("root"),
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_performed_layout: record("boundary"),
+          @MockMulti {
+            on_performed_layout: record("parent"),
+            @MockBox { size: pipe!(*$size), on_performed_layout: record("leaf") }
+            @MockBox { size: Size::new(10., 10.), on_performed_layout: record("sibling") }
+          }
+        }
+        @MockBox { size: Size::new(10., 10.), on_performed_layout: record("other") }
+      }
+    };
+
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+    c_laid_out.write().clear();
+
+    *w_size.write() = Size::new(20., 20.);
+    wnd.draw_frame();
+    assert_eq!(&*laid_out.read(), &["leaf", "parent", "boundary"]);
+    wnd.assert_root_size(Size::new(110., 100.));
+  }
 }
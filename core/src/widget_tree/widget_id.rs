@@ -234,8 +234,24 @@ impl WidgetId {
   /// Return the single child of `widget`, panic if have more than once child.
   #[track_caller]
   pub(crate) fn single_child(&self, tree: &WidgetTree) -> Option<WidgetId> {
-    assert_eq!(self.first_child(tree), self.last_child(tree), "Have more than one child.");
-    self.first_child(tree)
+    let first = self.first_child(tree);
+    if first != self.last_child(tree) {
+      panic!(
+        "`{}` is a single child widget, it expects at most 1 child but got {} children.",
+        self.assert_get(tree).type_name(),
+        self.children(tree).count()
+      );
+    }
+    first
+  }
+
+  /// Return the single child of `widget`, panic if it does not have exactly
+  /// one child.
+  #[track_caller]
+  pub(crate) fn assert_single_child(&self, tree: &WidgetTree) -> WidgetId {
+    self.single_child(tree).unwrap_or_else(|| {
+      panic!("`{}` expects exactly 1 child but got none.", self.assert_get(tree).type_name())
+    })
   }
 
   fn node_feature(
@@ -289,9 +305,16 @@ impl WidgetId {
             .painter()
             .save()
             .translate(layout_box.min_x(), layout_box.min_y());
-          render.paint(&mut ctx);
-
-          if let Some(c) = id.first_child(tree) {
+          let depth = ctx.painter().state_depth();
+          let painted =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| render.paint(&mut ctx)));
+          if let Err(err) = painted {
+            // Skip the failed subtree, the boundary shows its fallback later.
+            ErrorBoundary::catch_paint_panic(id, tree, err);
+            while ctx.painter().state_depth() > depth {
+              ctx.painter().restore();
+            }
+          } else if let Some(c) = id.first_child(tree) {
             ctx.switch_to(c);
             continue;
           }
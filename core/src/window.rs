@@ -2,6 +2,7 @@ use std::{
   cell::{Cell, RefCell},
   collections::VecDeque,
   convert::Infallible,
+  pin::Pin,
   ptr::NonNull,
 };
 
@@ -10,7 +11,7 @@ use ribir_algo::Sc;
 use smallvec::SmallVec;
 use widget_id::TrackId;
 use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
-pub use winit::window::CursorIcon;
+pub use winit::window::{CursorIcon, WindowLevel};
 
 use crate::{
   events::{
@@ -18,7 +19,7 @@ use crate::{
     focus_mgr::{FocusManager, FocusType},
   },
   prelude::*,
-  ticker::{FrameMsg, FrameTicker},
+  ticker::{FrameLimiter, FrameMsg, FrameTicker},
 };
 
 /// Window is the root to represent.
@@ -35,6 +36,7 @@ pub struct Window {
   pub(crate) frame_ticker: FrameTicker,
   pub(crate) focus_mgr: RefCell<FocusManager>,
   pub(crate) running_animates: Sc<Cell<u32>>,
+  frame_limiter: RefCell<FrameLimiter>,
   pre_edit: RefCell<Option<String>>,
   /// This vector store the task to emit events. When perform layout, dispatch
   /// event and so on, some part of window may be already mutable borrowed and
@@ -49,6 +51,12 @@ pub struct Window {
   /// executed first.
   priority_task_queue: PriorityTaskQueue,
   shell_wnd: RefCell<Box<dyn ShellWindow>>,
+  /// The minimum and maximum inner size of the window.
+  size_limit: Cell<BoxClamp>,
+  /// The safe area insets of the window, provided to the whole widget tree.
+  safe_area: Stateful<SafeAreaInsets>,
+  /// The rect of the window covered by the on-screen keyboard.
+  keyboard_rect: Stateful<Option<Rect>>,
   /// A vector store the widget id pair of (parent, child). The child need to
   /// drop after its `KeepAlive::keep_alive` be false or its parent
   /// is dropped.
@@ -57,6 +65,8 @@ pub struct Window {
   pub(crate) delay_drop_widgets: RefCell<Vec<(Option<WidgetId>, TrackId)>>,
 
   flags: Cell<WindowFlags>,
+  /// Whether the window is between `begin_frame` and `end_frame`.
+  drawing: Cell<bool>,
 }
 
 bitflags! {
@@ -84,6 +94,8 @@ pub trait ShellWindow {
   fn request_resize(&mut self, size: Size);
   fn on_resize(&mut self, size: Size);
   fn set_min_size(&mut self, size: Size);
+  fn set_max_size(&mut self, size: Size);
+  fn set_outer_position(&mut self, pos: Point);
   fn cursor(&self) -> CursorIcon;
   fn set_cursor(&mut self, cursor: CursorIcon);
   fn set_title(&mut self, str: &str);
@@ -94,24 +106,46 @@ pub trait ShellWindow {
   fn set_resizable(&mut self, resizable: bool);
   fn is_minimized(&self) -> bool;
   fn set_minimized(&mut self, minimized: bool);
+  fn is_maximized(&self) -> bool;
+  fn set_maximized(&mut self, maximized: bool);
+  /// Start moving the window with the pressed primary button, it should be
+  /// called when the button is pressed.
+  fn drag_window(&mut self);
   fn focus_window(&mut self);
   fn set_decorations(&mut self, decorations: bool);
+  fn window_level(&self) -> WindowLevel;
+  fn set_window_level(&mut self, level: WindowLevel);
   fn as_any(&self) -> &dyn Any;
   fn as_any_mut(&mut self) -> &mut dyn Any;
   /// The device pixel ratio of Window interface returns the ratio of the
   /// resolution in physical pixels to the logic pixels for the current display
   /// device.
   fn device_pixel_ratio(&self) -> f32;
+  /// The insets of the window that are obscured by the notches, the rounded
+  /// corners or the system bars, zero on the desktop platforms.
+  fn safe_area_insets(&self) -> EdgeInsets;
+  /// The rect of the window covered by the on-screen keyboard, `None` if the
+  /// keyboard is hidden or the platform has no on-screen keyboard.
+  fn keyboard_rect(&self) -> Option<Rect>;
   fn begin_frame(&mut self, surface_color: Color);
   fn draw_commands(&mut self, viewport: Rect, commands: &[PaintCommand]);
   fn end_frame(&mut self);
+  /// Render the last completed frame of the window to an image, return `None`
+  /// if the shell can't read back its frames. It's never called within a
+  /// frame.
+  fn capture(&mut self) -> Option<CaptureFuture> { None }
 }
 
+/// The image of a window captured by [`Window::capture`], resolves to `None`
+/// if the window can't be captured.
+pub type CaptureFuture = Pin<Box<dyn Future<Output = Option<PixelImage>>>>;
+
 impl Window {
   #[deprecated(note = "The core window should not depends on shell window event.")]
   #[inline]
   /// processes native events from this native window
   pub fn processes_native_event(&self, event: WindowEvent) {
+    let _span = tracing::info_span!("event_dispatch", kind = "native").entered();
     let ratio = self.device_pixel_ratio() as f64;
     self
       .dispatcher
@@ -123,6 +157,7 @@ impl Window {
     &self, physical_key: PhysicalKey, key: VirtualKey, is_repeat: bool, location: KeyLocation,
     state: ElementState,
   ) {
+    let _span = tracing::info_span!("event_dispatch", kind = "keyboard").entered();
     self
       .dispatcher
       .borrow_mut()
@@ -130,6 +165,7 @@ impl Window {
   }
 
   pub fn processes_receive_chars(&self, chars: String) {
+    let _span = tracing::info_span!("event_dispatch", kind = "chars").entered();
     self
       .dispatcher
       .borrow_mut()
@@ -137,6 +173,7 @@ impl Window {
   }
 
   pub fn processes_ime_pre_edit(&self, ime: ImePreEdit) {
+    let _span = tracing::info_span!("event_dispatch", kind = "ime").entered();
     self
       .dispatcher
       .borrow_mut()
@@ -144,12 +181,66 @@ impl Window {
   }
 
   pub fn process_mouse_input(&self, device_id: DeviceId, state: ElementState, button: MouseButton) {
+    let _span = tracing::info_span!("event_dispatch", kind = "mouse").entered();
     self
       .dispatcher
       .borrow_mut()
       .dispatch_mouse_input(device_id, state, button);
   }
 
+  /// Move the cursor to `pos` as the user does, the position is in logical
+  /// pixels relative to the window.
+  ///
+  /// The `inject_*` methods dispatch the input the same way as the native
+  /// events of the platform, they're used to drive the window by the tests or
+  /// the tools. The widget events they generate are emitted by
+  /// [`Window::emit_events`].
+  pub fn inject_cursor_move(&self, pos: Point) {
+    let _span = tracing::info_span!("event_dispatch", kind = "inject").entered();
+    self.dispatcher.borrow_mut().cursor_move_to(pos);
+  }
+
+  /// Press or release the mouse `button` at the current cursor position.
+  pub fn inject_mouse_input(&self, state: ElementState, button: MouseButton) {
+    if state == ElementState::Pressed {
+      self.force_exit_pre_edit();
+    }
+    let device_id = unsafe { DeviceId::dummy() };
+    self.process_mouse_input(device_id, state, button);
+  }
+
+  /// Move the cursor to `pos`, then press and release the left mouse button.
+  pub fn inject_tap(&self, pos: Point) {
+    self.inject_cursor_move(pos);
+    for state in [ElementState::Pressed, ElementState::Released] {
+      self.inject_mouse_input(state, MouseButton::Left);
+    }
+  }
+
+  /// Press and release a key, a character key also types its character to the
+  /// focused widget.
+  pub fn inject_key_press(&self, physical_key: PhysicalKey, key: VirtualKey) {
+    let chars = match &key {
+      VirtualKey::Character(chars) => Some(chars.to_string()),
+      _ => None,
+    };
+    let location = KeyLocation::Standard;
+    self.processes_keyboard_event(
+      physical_key,
+      key.clone(),
+      false,
+      location,
+      ElementState::Pressed,
+    );
+    if let Some(chars) = chars {
+      self.processes_receive_chars(chars);
+    }
+    self.processes_keyboard_event(physical_key, key, false, location, ElementState::Released);
+  }
+
+  /// Type the `text` to the focused widget.
+  pub fn inject_text(&self, text: &str) { self.processes_receive_chars(text.to_string()); }
+
   /// Request switch the focus to next widget.
   pub fn request_next_focus(&self) {
     self
@@ -215,12 +306,33 @@ impl Window {
       .set(self.running_animates.get() - 1);
   }
 
+  /// Set the maximum frame rate of the window, `None` means no limit.
+  pub fn set_max_fps(&self, max_fps: Option<u32>) {
+    self
+      .frame_limiter
+      .borrow_mut()
+      .set_max_fps(max_fps);
+  }
+
+  /// Return the time when the window is allowed to draw the next frame, or
+  /// `None` if it can draw at `now`.
+  pub fn next_frame_time(&self, now: Instant) -> Option<Instant> {
+    self.frame_limiter.borrow().next_frame_time(now)
+  }
+
   /// Draw an image what current render tree represent.
+  ///
+  /// The frame is traced by a `frame` span, with the nested `layout`, `paint`
+  /// and `composite` spans for its phases.
   #[track_caller]
   pub fn draw_frame(&self) -> bool {
+    let _frame_span = tracing::info_span!("frame").entered();
     AppCtx::run_until_stalled();
     let mut ticker = self.frame_ticker.clone();
-    ticker.next(FrameMsg::NewFrame(Instant::now()));
+    let now = Instant::now();
+    ticker.next(FrameMsg::NewFrame(now));
+    self.update_safe_area();
+    self.update_keyboard_rect();
     self.run_frame_tasks();
 
     self.update_painter_viewport();
@@ -233,19 +345,25 @@ impl Window {
         Palette::of(BuildCtx::get()).surface()
       };
       self.shell_wnd.borrow_mut().begin_frame(surface);
+      self.drawing.set(true);
 
       ticker.next(FrameMsg::BeforeLayout(Instant::now()));
-      self.layout();
+      tracing::info_span!("layout").in_scope(|| self.layout());
 
-      self.tree().draw();
-      self.draw_delay_drop_widgets();
+      tracing::info_span!("paint").in_scope(|| {
+        self.tree().draw();
+        self.draw_delay_drop_widgets();
+      });
 
+      let _composite_span = tracing::info_span!("composite").entered();
       let mut shell = self.shell_wnd.borrow_mut();
       let inner_size = shell.inner_size();
       let mut painter = self.painter.borrow_mut();
       shell.draw_commands(Rect::from_size(inner_size), &painter.finish());
 
       shell.end_frame();
+      self.drawing.set(false);
+      self.frame_limiter.borrow_mut().frame_drawn(now);
     }
 
     AppCtx::end_frame();
@@ -293,6 +411,38 @@ impl Window {
     }
   }
 
+  /// Sync the safe area insets with the shell window, they may change with the
+  /// window size or the screen orientation.
+  fn update_safe_area(&self) {
+    let insets = SafeAreaInsets(self.shell_wnd.borrow().safe_area_insets());
+    if *self.safe_area.read() != insets {
+      *self.safe_area.write() = insets;
+    }
+  }
+
+  /// Sync the rect covered by the on-screen keyboard with the shell window.
+  fn update_keyboard_rect(&self) {
+    let rect = self.shell_wnd.borrow().keyboard_rect();
+    if *self.keyboard_rect.read() != rect {
+      *self.keyboard_rect.write() = rect;
+    }
+  }
+
+  /// Notify the window that its device pixel ratio has changed, such as it's
+  /// moved to a monitor with a different scale factor. The whole window will
+  /// be repainted, the backend caches its rasterizations with the scale, so
+  /// the glyphs and paths are re-rasterized at the new ratio, and the entries
+  /// cached at a ratio not less than the new one are reused.
+  pub fn on_scale_factor_changed(&self) {
+    let tree = self.tree();
+    tree
+      .dirty_marker()
+      .mark(tree.root(), DirtyPhase::Paint);
+  }
+
+  /// Return if the window needs to draw a new frame. The window only renders
+  /// on demand, a new frame is required only if the widget tree is dirty or an
+  /// animation is running, so an idle window produces no frames.
   pub fn need_draw(&self) -> bool { self.tree().is_dirty() || self.running_animates.get() > 0 }
 
   pub fn new(shell_wnd: Box<dyn ShellWindow>) -> Sc<Self> {
@@ -302,6 +452,8 @@ impl Window {
     let dispatcher = RefCell::new(Dispatcher::new(wnd_id));
     let size = shell_wnd.inner_size();
     let painter = Painter::new(Rect::from_size(size));
+    let safe_area = Stateful::new(SafeAreaInsets(shell_wnd.safe_area_insets()));
+    let keyboard_rect = Stateful::new(shell_wnd.keyboard_rect());
     let window = Self {
       tree: NonNull::new(Box::into_raw(tree)).unwrap(),
       dispatcher,
@@ -310,11 +462,16 @@ impl Window {
       delay_emitter: <_>::default(),
       frame_ticker: FrameTicker::default(),
       running_animates: <_>::default(),
+      frame_limiter: <_>::default(),
       frame_pool: <_>::default(),
       priority_task_queue: PriorityTaskQueue::default(),
       shell_wnd: RefCell::new(shell_wnd),
+      size_limit: <_>::default(),
+      safe_area,
+      keyboard_rect,
       delay_drop_widgets: <_>::default(),
       flags: Cell::new(WindowFlags::DEFAULT),
+      drawing: Cell::new(false),
       pre_edit: <_>::default(),
     };
 
@@ -439,6 +596,7 @@ impl Window {
               if Some(id) == self.focusing() {
                 self.focus_mgr.borrow_mut().blur_on_dispose();
               }
+              self.dispatcher.borrow().on_widget_disposed(id);
               let mut e = Event::Disposed(LifecycleEvent::new(id, self.tree));
               self.emit(id, &mut e);
             });
@@ -537,10 +695,12 @@ impl Window {
             .borrow_mut()
             .refresh_focus(self.tree());
         }
-        DelayEvent::PointerMove(id) => {
-          let event = PointerEvent::from_mouse(self.tree().root(), self);
+        DelayEvent::PointerMove(id, samples) => {
+          let event =
+            PointerEvent::from_mouse(self.tree().root(), self).with_coalesced(samples.clone());
           self.top_down_emit(&mut Event::PointerMoveCapture(event), id);
-          self.bottom_up_emit(&mut Event::PointerMove(PointerEvent::from_mouse(id, self)), None);
+          let event = PointerEvent::from_mouse(id, self).with_coalesced(samples);
+          self.bottom_up_emit(&mut Event::PointerMove(event), None);
         }
         DelayEvent::PointerUp(id) => {
           let event = PointerEvent::from_mouse(self.tree().root(), self);
@@ -548,7 +708,7 @@ impl Window {
           let event = PointerEvent::from_mouse(id, self);
           self.bottom_up_emit(&mut Event::PointerUp(event), None);
         }
-        DelayEvent::_PointerCancel(id) => {
+        DelayEvent::PointerCancel(id) => {
           let event = PointerEvent::from_mouse(self.tree().root(), self);
           self.top_down_emit(&mut Event::PointerCancelCapture(event), id);
           let event = PointerEvent::from_mouse(id, self);
@@ -578,8 +738,9 @@ impl Window {
           let mut e = Event::PointerDown(PointerEvent::from_mouse(wid, self));
           self.emit(wid, &mut e);
         }
-        DelayEvent::GrabPointerMove(wid) => {
-          let mut e = Event::PointerMove(PointerEvent::from_mouse(wid, self));
+        DelayEvent::GrabPointerMove(wid, samples) => {
+          let event = PointerEvent::from_mouse(wid, self).with_coalesced(samples);
+          let mut e = Event::PointerMove(event);
           self.emit(wid, &mut e);
         }
         DelayEvent::GrabPointerUp(wid) => {
@@ -674,6 +835,54 @@ impl Window {
 
   pub fn widget_pos(&self, id: WidgetId) -> Option<Point> { self.tree().store.layout_box_pos(id) }
 
+  /// Capture the last completed frame of the window as an image. If a frame is
+  /// being drawn, it waits for the frame to finish, so a half-drawn frame is
+  /// never captured.
+  pub fn capture(&self) -> CaptureFuture {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    let id = self.id();
+    let capture = move || {
+      let img = AppCtx::get_window(id).and_then(|wnd| wnd.shell_wnd.borrow_mut().capture());
+      let _ = tx.send(img);
+    };
+    if self.drawing.get() {
+      self.once_frame_finished(capture)
+    } else {
+      capture()
+    }
+
+    Box::pin(async move {
+      match rx.await {
+        Ok(Some(img)) => img.await,
+        _ => None,
+      }
+    })
+  }
+
+  /// Dump the widget tree of the window in text for debugging, every line is a
+  /// widget with its type, id, layout rect relative to its parent and the
+  /// clamp it was laid out with. For example:
+  ///
+  /// ```text
+  /// Root #2 (0, 0, 200x200) clamp: 0x0..200x200
+  /// └─MockBox #1 (0, 0, 100x100) clamp: 0x0..200x200
+  ///   └─MockMulti #3 (0, 0, 30x20) clamp: 0x0..100x100
+  ///     ├─MockBox #4 (0, 0, 10x10) clamp: 0x0..100x100
+  ///     └─MockBox #5 (10, 0, 20x20) clamp: 0x0..100x100
+  /// ```
+  pub fn dump_tree(&self) -> String {
+    let tree = self.tree();
+    tree.display_tree(tree.root())
+  }
+
+  /// Dump the widget tree of the window in JSON, the same information as
+  /// [`Window::dump_tree`].
+  #[cfg(feature = "debug")]
+  pub fn json_tree(&self) -> serde_json::Value {
+    let tree = self.tree();
+    tree.json_tree(tree.root())
+  }
+
   pub(crate) fn tree(&self) -> &WidgetTree {
     // Safety: Please refer to the comments in `WidgetTree::tree_mut` for more
     // information.
@@ -696,6 +905,38 @@ impl Window {
   /// Return the current focused widget id.
   pub fn focusing(&self) -> Option<WidgetId> { self.focus_mgr.borrow().focusing() }
 
+  /// Return the reason why the focusing widget gains the focus.
+  pub fn focus_reason(&self) -> FocusReason { self.focus_mgr.borrow().focus_reason() }
+
+  /// Capture the pointer to the widget `wid`, it receives all the pointer
+  /// events regardless of the pointer position until the pointer is released
+  /// or the widget is disposed.
+  ///
+  /// Return `false` if another widget has already captured the pointer.
+  pub fn capture_pointer(&self, wid: WidgetId) -> bool {
+    self.dispatcher.borrow().capture_pointer(wid)
+  }
+
+  /// Release the pointer captured by [`Window::capture_pointer`], the pointer
+  /// grabbed by a [`GrabPointer`] is not affected.
+  pub fn release_pointer(&self) { self.dispatcher.borrow().release_pointer(); }
+
+  /// Return the widget that captures the pointer.
+  pub fn pointer_capture(&self) -> Option<WidgetId> { self.dispatcher.borrow().pointer_capture() }
+
+  /// Join the widget `wid` to the gesture arena of the pressed pointer as a
+  /// drag recognizer. Once the pointer moves farther than [`DRAG_SLOP`], the
+  /// first joined recognizer wins, captures the pointer and cancels the tap.
+  ///
+  /// Return `false` if no pointer is pressed or the arena is already resolved.
+  pub fn join_drag_gesture(&self, wid: WidgetId) -> bool {
+    self.dispatcher.borrow_mut().arena.join_drag(wid)
+  }
+
+  /// Return the drag recognizer that wins the gesture arena of the pressed
+  /// pointer.
+  pub fn gesture_winner(&self) -> Option<WidgetId> { self.dispatcher.borrow().arena.winner() }
+
   /// The device pixel ratio of Window interface returns the ratio of the
   /// resolution in physical pixels to the logic pixels for the current display
   /// device.
@@ -711,6 +952,32 @@ impl Window {
     self
   }
 
+  /// Returns the level of the window, it decides whether the window is always
+  /// on top of or below the other windows.
+  pub fn window_level(&self) -> WindowLevel { self.shell_wnd.borrow().window_level() }
+
+  /// Modifies the level of the window. Setting the same level as the current
+  /// one does nothing.
+  pub fn set_window_level(&self, level: WindowLevel) -> &Self {
+    if self.window_level() != level {
+      self
+        .shell_wnd
+        .borrow_mut()
+        .set_window_level(level);
+    }
+    self
+  }
+
+  /// Returns if the window is always on top of the other windows.
+  pub fn is_always_on_top(&self) -> bool { self.window_level() == WindowLevel::AlwaysOnTop }
+
+  /// Keep the window always on top of the other windows or back to the
+  /// normal level.
+  pub fn set_always_on_top(&self, on_top: bool) -> &Self {
+    let level = if on_top { WindowLevel::AlwaysOnTop } else { WindowLevel::Normal };
+    self.set_window_level(level)
+  }
+
   /// Returns the cursor icon of the window.
   pub fn get_cursor(&self) -> CursorIcon { self.shell_wnd.borrow().cursor() }
 
@@ -745,12 +1012,118 @@ impl Window {
     self
   }
 
-  pub fn request_resize(&self, size: Size) { self.shell_wnd.borrow_mut().request_resize(size) }
+  /// Request to resize the inner size of the window, the size is clamped by
+  /// the minimum and maximum inner size of the window.
+  pub fn request_resize(&self, size: Size) {
+    let size = self.size_limit.get().clamp(size);
+    self.shell_wnd.borrow_mut().request_resize(size)
+  }
+
+  /// Sets the inner size of the window, see [`Window::request_resize`].
+  pub fn set_inner_size(&self, size: Size) -> &Self {
+    self.request_resize(size);
+    self
+  }
 
   pub fn size(&self) -> Size { self.shell_wnd.borrow().inner_size() }
 
-  pub fn set_min_size(&self, size: Size) -> &Self {
-    self.shell_wnd.borrow_mut().set_min_size(size);
+  /// The insets of the window that are obscured by the notches, the rounded
+  /// corners or the system bars. See [`SafeArea`] to keep the content out of
+  /// them.
+  pub fn safe_area_insets(&self) -> EdgeInsets { self.safe_area.read().0 }
+
+  pub(crate) fn safe_area_watcher(&self) -> Watcher<Reader<SafeAreaInsets>> {
+    self.safe_area.clone_watcher()
+  }
+
+  /// The rect of the window covered by the on-screen keyboard, `None` if the
+  /// keyboard is hidden. See [`KeyboardAvoider`] to keep the focused widget
+  /// above it.
+  pub fn keyboard_rect(&self) -> Option<Rect> { *self.keyboard_rect.read() }
+
+  /// The watcher of the rect covered by the on-screen keyboard, to follow the
+  /// keyboard showing and hiding.
+  pub fn keyboard_rect_watcher(&self) -> Watcher<Reader<Option<Rect>>> {
+    self.keyboard_rect.clone_watcher()
+  }
+
+  #[deprecated(note = "Use `set_min_inner_size` instead.")]
+  pub fn set_min_size(&self, size: Size) -> &Self { self.set_min_inner_size(size) }
+
+  /// Sets the minimum inner size of the window, the window is enlarged if it's
+  /// smaller than the size.
+  pub fn set_min_inner_size(&self, size: Size) -> &Self {
+    let mut limit = self.size_limit.get();
+    limit.min = size;
+    limit.max = limit.max.max(size);
+    self.set_size_limit(limit)
+  }
+
+  /// Sets the maximum inner size of the window, the window is shrunk if it's
+  /// larger than the size.
+  pub fn set_max_inner_size(&self, size: Size) -> &Self {
+    let mut limit = self.size_limit.get();
+    limit.max = size;
+    limit.min = limit.min.min(size);
+    self.set_size_limit(limit)
+  }
+
+  /// Moves the top-left corner of the window to the `pos` in the screen
+  /// coordinates, the custom title bar of a window without decorations can
+  /// use it to drag the window.
+  pub fn set_outer_position(&self, pos: Point) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_outer_position(pos);
+    self
+  }
+
+  pub fn is_minimized(&self) -> bool { self.shell_wnd.borrow().is_minimized() }
+
+  pub fn set_minimized(&self, minimized: bool) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_minimized(minimized);
+    self
+  }
+
+  pub fn is_maximized(&self) -> bool { self.shell_wnd.borrow().is_maximized() }
+
+  pub fn set_maximized(&self, maximized: bool) -> &Self {
+    self
+      .shell_wnd
+      .borrow_mut()
+      .set_maximized(maximized);
+    self
+  }
+
+  /// Move the window with the pressed primary button until it's released, a
+  /// custom title bar calls it in the pointer down event.
+  pub fn drag_window(&self) -> &Self {
+    self.shell_wnd.borrow_mut().drag_window();
+    self
+  }
+
+  /// Close the window. The window is removed after the current event is
+  /// processed, the application exits if it's the last window.
+  pub fn close(&self) {
+    let id = self.id();
+    let _ = AppCtx::spawn_local(async move { AppCtx::remove_wnd(id) });
+  }
+
+  fn set_size_limit(&self, limit: BoxClamp) -> &Self {
+    self.size_limit.set(limit);
+    {
+      let mut shell_wnd = self.shell_wnd.borrow_mut();
+      shell_wnd.set_min_size(limit.min);
+      shell_wnd.set_max_size(limit.max);
+    }
+    let size = self.size();
+    if limit.clamp(size) != size {
+      self.request_resize(size);
+    }
     self
   }
 
@@ -842,9 +1215,9 @@ pub(crate) enum DelayEvent {
     delta_y: f32,
   },
   PointerDown(WidgetId),
-  PointerMove(WidgetId),
+  PointerMove(WidgetId, Vec<PointerSample>),
   PointerUp(WidgetId),
-  _PointerCancel(WidgetId),
+  PointerCancel(WidgetId),
   PointerEnter {
     bottom: WidgetId,
     up: Option<WidgetId>,
@@ -859,7 +1232,7 @@ pub(crate) enum DelayEvent {
     pre_edit: ImePreEdit,
   },
   GrabPointerDown(WidgetId),
-  GrabPointerMove(WidgetId),
+  GrabPointerMove(WidgetId, Vec<PointerSample>),
   GrabPointerUp(WidgetId),
   BubbleCustomEvent {
     from: WidgetId,
@@ -899,6 +1272,42 @@ mod tests {
     wnd.assert_root_size(new_size);
   }
 
+  #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+  #[test]
+  fn render_on_demand() {
+    reset_test_env!();
+
+    let size = Stateful::new(Size::new(10., 10.));
+    let c_size = size.clone_watcher();
+    let mut wnd = TestWindow::new(fn_widget! { @MockBox { size: pipe!(*$c_size) } });
+    wnd.draw_frame();
+    assert!(wnd.take_last_frame().is_some());
+
+    // No state changes, no new frames.
+    for _ in 0..3 {
+      wnd.draw_frame();
+      assert!(!wnd.need_draw());
+      assert!(wnd.take_last_frame().is_none());
+    }
+
+    // A state write schedules exactly one frame.
+    *size.write() = Size::new(20., 20.);
+    wnd.draw_frame();
+    assert!(wnd.take_last_frame().is_some());
+    wnd.draw_frame();
+    assert!(wnd.take_last_frame().is_none());
+
+    // A running animation keeps producing frames until it finishes.
+    wnd.inc_running_animate();
+    for _ in 0..2 {
+      wnd.draw_frame();
+      assert!(wnd.take_last_frame().is_some());
+    }
+    wnd.dec_running_animate();
+    wnd.draw_frame();
+    assert!(wnd.take_last_frame().is_none());
+  }
+
   #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
   #[test]
   fn fire_tasks_before_new_window() {
@@ -918,4 +1327,117 @@ mod tests {
 
     wnd.draw_frame();
   }
+
+  #[test]
+  fn clamp_inner_size() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new_with_size(
+      fn_widget! { @MockBox { size: Size::zero() } },
+      Size::new(200., 200.),
+    );
+    wnd.set_min_inner_size(Size::new(100., 100.));
+    wnd.set_inner_size(Size::new(50., 150.));
+    assert_eq!(wnd.size(), Size::new(100., 150.));
+
+    wnd.set_max_inner_size(Size::new(300., 300.));
+    wnd.set_inner_size(Size::new(500., 500.));
+    assert_eq!(wnd.size(), Size::new(300., 300.));
+
+    // The current size is clamped when the limit changes.
+    wnd.set_max_inner_size(Size::new(120., 120.));
+    assert_eq!(wnd.size(), Size::new(120., 120.));
+  }
+
+  #[test]
+  fn toggle_always_on_top() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { @MockBox { size: Size::zero() } });
+    assert!(!wnd.is_always_on_top());
+
+    wnd.set_always_on_top(true);
+    wnd.set_always_on_top(true);
+    assert!(wnd.is_always_on_top());
+    wnd.set_always_on_top(false);
+    wnd.set_always_on_top(false);
+    wnd.set_window_level(WindowLevel::AlwaysOnBottom);
+    assert_eq!(wnd.window_level(), WindowLevel::AlwaysOnBottom);
+
+    let shell_wnd = wnd.shell_wnd().borrow();
+    let shell_wnd = shell_wnd
+      .as_any()
+      .downcast_ref::<TestShellWindow>()
+      .unwrap();
+    assert_eq!(shell_wnd.level_changes, [
+      WindowLevel::AlwaysOnTop,
+      WindowLevel::Normal,
+      WindowLevel::AlwaysOnBottom
+    ]);
+  }
+
+  /// The name of an entered span and its parent.
+  type EnteredSpan = (&'static str, Option<&'static str>);
+
+  /// Records the spans in the order they are entered, with their parent.
+  #[derive(Default)]
+  struct SpanRecorder {
+    names: std::sync::Mutex<Vec<&'static str>>,
+    stack: std::sync::Mutex<Vec<&'static str>>,
+    entered: std::sync::Arc<std::sync::Mutex<Vec<EnteredSpan>>>,
+  }
+
+  impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool { true }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+      let mut names = self.names.lock().unwrap();
+      names.push(span.metadata().name());
+      tracing::span::Id::from_u64(names.len() as u64)
+    }
+
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, span: &tracing::span::Id) {
+      let name = self.names.lock().unwrap()[span.into_u64() as usize - 1];
+      let mut stack = self.stack.lock().unwrap();
+      let parent = stack.last().copied();
+      self.entered.lock().unwrap().push((name, parent));
+      stack.push(name);
+    }
+
+    fn exit(&self, _: &tracing::span::Id) { self.stack.lock().unwrap().pop(); }
+  }
+
+  #[test]
+  fn frame_phase_spans() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! { MockBox { size: INFINITY_SIZE } },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    let recorder = SpanRecorder::default();
+    let entered = recorder.entered.clone();
+    tracing::subscriber::with_default(recorder, || {
+      wnd.mouse_press().mouse_release();
+      wnd.request_resize(Size::new(200., 200.));
+      wnd.draw_frame();
+    });
+
+    assert_eq!(&*entered.lock().unwrap(), &[
+      ("event_dispatch", None),
+      ("event_dispatch", None),
+      ("frame", None),
+      ("layout", Some("frame")),
+      ("paint", Some("frame")),
+      ("composite", Some("frame")),
+    ]);
+  }
 }
@@ -31,6 +31,14 @@ pub trait WrapRender {
 
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> { host.get_transform() }
 
+  fn baseline(&self, host: &dyn Render) -> Option<f32> { host.baseline() }
+
+  fn semantics(&self, host: &dyn Render) -> Option<Semantics> { host.semantics() }
+
+  fn debug_properties(&self, host: &dyn Render, props: &mut Vec<String>) {
+    host.debug_properties(props)
+  }
+
   fn combine_child(
     this: impl StateWriter<Value = Self>, mut child: Widget, dirty: DirtyPhase,
   ) -> Widget
@@ -106,6 +114,18 @@ impl Render for RenderPair {
   fn dirty_phase(&self) -> DirtyPhase { self.host.dirty_phase() }
 
   fn get_transform(&self) -> Option<Transform> { self.wrapper.get_transform(self.host.as_render()) }
+
+  fn semantics(&self) -> Option<Semantics> { self.wrapper.semantics(self.host.as_render()) }
+
+  fn baseline(&self) -> Option<f32> { self.wrapper.baseline(self.host.as_render()) }
+
+  fn type_name(&self) -> &'static str { self.host.type_name() }
+
+  fn debug_properties(&self, props: &mut Vec<String>) {
+    self
+      .wrapper
+      .debug_properties(self.host.as_render(), props)
+  }
 }
 
 impl<R> WrapRender for R
@@ -130,8 +150,20 @@ where
   fn get_transform(&self, host: &dyn Render) -> Option<Transform> {
     self.read().get_transform(host)
   }
+
+  fn baseline(&self, host: &dyn Render) -> Option<f32> { self.read().baseline(host) }
+
+  fn semantics(&self, host: &dyn Render) -> Option<Semantics> { self.read().semantics(host) }
+
+  fn debug_properties(&self, host: &dyn Render, props: &mut Vec<String>) {
+    self.read().debug_properties(host, props)
+  }
 }
 
+/// Implement `ComposeChild` for a `WrapRender` widget, `$dirty` is the phase
+/// marked on the host when the wrapper's state is modified. A wrapper that
+/// only affects the painting, like `Opacity` or `Background`, should use
+/// `DirtyPhase::Paint` to avoid an unnecessary relayout.
 #[macro_export]
 macro_rules! impl_compose_child_for_wrap_render {
   ($name:ty, $dirty:expr) => {
@@ -17,9 +17,14 @@ use ribir_painter::{PixelImage, image::ColorFormat};
 /// - the `{function  name}` is the function you pass to the macro.
 /// - the `{fmt}` is the file format the backend wants to check.
 ///
-/// You can run the test with `RIBIR_IMG_TEST=overwrite` to overwrite the image
-/// file, for example ```
-/// RIBIR_IMG_TEST=overwrite cargo test -- smoke
+/// You can run the test with `RIBIR_IMG_TEST=update` to rewrite the image
+/// files that are missing or mismatched, or `RIBIR_IMG_TEST=overwrite` to
+/// rewrite all of them, for example
+/// ```text
+/// RIBIR_IMG_TEST=update cargo test -- smoke
+/// ```
+/// Both modes are refused when the `CI` environment variable is set, so a
+/// mismatched image never passes in CI. See [`SnapshotMode`].
 #[cfg(not(target_arch = "wasm32"))]
 #[macro_export]
 macro_rules! painter_backend_eq_image_test {
@@ -52,17 +57,70 @@ macro_rules! test_case_name {
   }};
 }
 
+/// How the image test treats the reference image, decided by the
+/// `RIBIR_IMG_TEST` environment variable.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+  /// Compare with the reference image, and fail if they are different.
+  Compare,
+  /// `RIBIR_IMG_TEST=update`, rewrite the reference image if it's missing or
+  /// mismatched instead of failing.
+  Update,
+  /// `RIBIR_IMG_TEST=overwrite`, always rewrite the reference image.
+  Overwrite,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SnapshotMode {
+  /// Read the mode from the environment variables.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a rewrite mode is requested while the `CI` environment variable
+  /// is set.
+  #[track_caller]
+  pub fn from_env() -> Self {
+    let img_test = std::env::var("RIBIR_IMG_TEST").ok();
+    let ci = std::env::var("CI").ok();
+    Self::from_vars(img_test.as_deref(), ci.as_deref())
+  }
+
+  #[track_caller]
+  fn from_vars(img_test: Option<&str>, ci: Option<&str>) -> Self {
+    let mode = match img_test {
+      Some("update") => SnapshotMode::Update,
+      Some("overwrite") => SnapshotMode::Overwrite,
+      _ => SnapshotMode::Compare,
+    };
+    let in_ci = ci.is_some_and(|v| !v.is_empty() && v != "0" && v != "false");
+    assert!(
+      mode == SnapshotMode::Compare || !in_ci,
+      "`RIBIR_IMG_TEST={}` rewrites the reference images, it's not allowed in CI.",
+      img_test.unwrap_or_default()
+    );
+    mode
+  }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub struct ImageTest<'a> {
   test_img: PixelImage,
   ref_path: &'a std::path::Path,
   comparison: f64,
+  perceptual: bool,
 }
 
+/// The standard deviation of the Gaussian blur applied before a perceptual
+/// comparison, it's large enough to hide the anti-aliasing differences of an
+/// edge but keeps the shapes.
+#[cfg(not(target_arch = "wasm32"))]
+const PERCEPTUAL_BLUR_SIGMA: f32 = 1.;
+
 #[cfg(not(target_arch = "wasm32"))]
 impl<'a> ImageTest<'a> {
   pub fn new(test_img: PixelImage, ref_path: &'a std::path::Path) -> Self {
-    Self { test_img, ref_path, comparison: 0.000025 }
+    Self { test_img, ref_path, comparison: 0.000025, perceptual: false }
   }
 
   pub fn with_comparison(mut self, comparison: f64) -> Self {
@@ -70,70 +128,178 @@ impl<'a> ImageTest<'a> {
     self
   }
 
+  /// Compare the images perceptually, the test passes if their structural
+  /// dissimilarity (DSSIM) after a slight blur is less than `threshold`.
+  ///
+  /// It's robust to the anti-aliasing differences across GPUs, but the
+  /// images with different shapes still fail.
+  pub fn with_perceptual_comparison(mut self, threshold: f64) -> Self {
+    self.comparison = threshold;
+    self.perceptual = true;
+    self
+  }
+
   #[track_caller]
-  pub fn test(self) {
-    let Self { test_img, ref_path, comparison } = self;
+  pub fn test(self) { self.test_with_mode(SnapshotMode::from_env()) }
+
+  #[track_caller]
+  pub fn test_with_mode(self, mode: SnapshotMode) {
+    let Self { test_img, ref_path, comparison, perceptual } = self;
 
     use std::fs::File;
 
-    let overwrite = std::ffi::OsStr::new("overwrite");
     let dir = ref_path.parent().unwrap();
     let stem = ref_path.file_stem().unwrap().to_str().unwrap();
-    if std::env::var_os("RIBIR_IMG_TEST").is_some_and(|var| var == overwrite) {
+    let diff_path = dir.join(format!("{stem}_diff.png"));
+    let actual_path = dir.join(format!("{stem}_actual.png"));
+    let pixel_diff_path = dir.join(format!("{stem}_pixel_diff.png"));
+    let side_by_side_path = dir.join(format!("{stem}_side_by_side.png"));
+    let write_ref = |test_img: &PixelImage| {
       std::fs::create_dir_all(dir).unwrap();
       let mut file = File::create(ref_path).unwrap();
       test_img.write_as_png(&mut file).unwrap();
-    } else {
-      let mut f = File::open(ref_path).unwrap();
-      let mut bytes = Vec::new();
-      std::io::Read::read_to_end(&mut f, &mut bytes).unwrap();
-      let ref_img = PixelImage::from_png(&bytes);
-
-      assert_eq!(test_img.pixel_bytes().len(), ref_img.pixel_bytes().len());
-      assert_eq!(test_img.color_format(), ColorFormat::Rgba8);
-      assert_eq!(ref_img.color_format(), ColorFormat::Rgba8);
-
-      let mut dssim = dssim_core::Dssim::new();
-      dssim.set_save_ssim_maps(1);
-      let test_data = unsafe {
-        let ptr = test_img.pixel_bytes().as_ptr() as *const _;
-        std::slice::from_raw_parts(ptr, test_img.pixel_bytes().len() / 4)
-      };
-      let d_test = dssim
-        .create_image_rgba(test_data, test_img.width() as usize, test_img.height() as usize)
-        .unwrap();
-      let ref_dat = unsafe {
-        let ptr = ref_img.pixel_bytes().as_ptr() as *const _;
-        std::slice::from_raw_parts(ptr, ref_img.pixel_bytes().len() / 4)
-      };
-      let d_ref = dssim
-        .create_image_rgba(ref_dat, ref_img.width() as usize, ref_img.height() as usize)
+      for path in [&actual_path, &diff_path, &pixel_diff_path, &side_by_side_path] {
+        let _ = std::fs::remove_file(path);
+      }
+    };
+
+    if mode == SnapshotMode::Overwrite || (mode == SnapshotMode::Update && !ref_path.exists()) {
+      write_ref(&test_img);
+      return;
+    }
+
+    let mut f = File::open(ref_path).unwrap();
+    let mut bytes = Vec::new();
+    std::io::Read::read_to_end(&mut f, &mut bytes).unwrap();
+    let ref_img = PixelImage::from_png(&bytes);
+
+    let same_size = test_img.width() == ref_img.width() && test_img.height() == ref_img.height();
+    if mode == SnapshotMode::Update && !same_size {
+      write_ref(&test_img);
+      return;
+    }
+    if !same_size {
+      test_img
+        .write_as_png(&mut File::create(&actual_path).unwrap())
         .unwrap();
+      panic!(
+        "Image test failed. The actual image is {}x{}, but the expected image is {}x{}, no \
+         difference image is generated. The actual image has been saved next to the expected \
+         image.
+      Expected image location: {ref_path:?}
+      Actual image location: {actual_path:?}",
+        test_img.width(),
+        test_img.height(),
+        ref_img.width(),
+        ref_img.height()
+      );
+    }
+    assert_eq!(test_img.color_format(), ColorFormat::Rgba8);
+    assert_eq!(ref_img.color_format(), ColorFormat::Rgba8);
 
-      let (v, mut diffs) = dssim.compare(&d_ref, d_test);
-      let dssim: f64 = v.into();
+    let (dssim, diff_map) = if perceptual {
+      Self::dssim(&Self::blur(&test_img), &Self::blur(&ref_img))
+    } else {
+      Self::dssim(&test_img, &ref_img)
+    };
 
-      let diff_path = dir.join(format!("{stem}_diff.png"));
-      let actual_path = dir.join(format!("{stem}_actual.png"));
-      if dssim > f64::EPSILON {
-        // write the actual image to the same folder
-        test_img
-          .write_as_png(&mut File::create(&actual_path).unwrap())
-          .unwrap();
+    if mode == SnapshotMode::Update && dssim >= comparison {
+      write_ref(&test_img);
+      return;
+    }
 
-        // write the diff image to the same folder
-        Self::write_ssim_maps(diffs.pop().unwrap(), &diff_path);
-      }
+    if dssim > f64::EPSILON {
+      // write the actual image to the same folder
+      test_img
+        .write_as_png(&mut File::create(&actual_path).unwrap())
+        .unwrap();
 
-      assert!(
-        dssim < comparison,
-        "Image test failed. Expected Diff({dssim}) to be less than {comparison}. The actual image \
-         and difference image have been saved next to the expected image.
+      // write the diff images to the same folder
+      Self::write_ssim_maps(diff_map, &diff_path);
+      let pixel_diff = Self::pixel_diff(&test_img, &ref_img);
+      pixel_diff
+        .write_as_png(&mut File::create(&pixel_diff_path).unwrap())
+        .unwrap();
+      Self::side_by_side(&[&ref_img, &test_img, &pixel_diff])
+        .write_as_png(&mut File::create(&side_by_side_path).unwrap())
+        .unwrap();
+    }
+
+    assert!(
+      dssim < comparison,
+      "Image test failed. Expected Diff({dssim}) to be less than {comparison}. The actual image \
+       and difference image have been saved next to the expected image.
       Expected image location: {ref_path:?}
       Actual image location: {actual_path:?}
-      Difference file location: {diff_path:?}"
-      );
+      Difference file location: {diff_path:?}
+      Pixel difference file location: {pixel_diff_path:?}
+      Side by side file location: {side_by_side_path:?}
+      Run with `RIBIR_IMG_TEST=update` to accept the actual image."
+    );
+  }
+
+  fn blur(img: &PixelImage) -> PixelImage {
+    let (width, height) = (img.width(), img.height());
+    let buffer = image::RgbaImage::from_raw(width, height, img.pixel_bytes().to_vec()).unwrap();
+    let data = image::imageops::blur(&buffer, PERCEPTUAL_BLUR_SIGMA).into_raw();
+    PixelImage::new(data.into(), width, height, ColorFormat::Rgba8)
+  }
+
+  /// Return the structural dissimilarity of the two images and its map.
+  fn dssim(test_img: &PixelImage, ref_img: &PixelImage) -> (f64, dssim_core::SsimMap) {
+    let mut dssim = dssim_core::Dssim::new();
+    dssim.set_save_ssim_maps(1);
+    let test_data = unsafe {
+      let ptr = test_img.pixel_bytes().as_ptr() as *const _;
+      std::slice::from_raw_parts(ptr, test_img.pixel_bytes().len() / 4)
+    };
+    let d_test = dssim
+      .create_image_rgba(test_data, test_img.width() as usize, test_img.height() as usize)
+      .unwrap();
+    let ref_dat = unsafe {
+      let ptr = ref_img.pixel_bytes().as_ptr() as *const _;
+      std::slice::from_raw_parts(ptr, ref_img.pixel_bytes().len() / 4)
+    };
+    let d_ref = dssim
+      .create_image_rgba(ref_dat, ref_img.width() as usize, ref_img.height() as usize)
+      .unwrap();
+
+    let (v, mut diffs) = dssim.compare(&d_ref, d_test);
+    (v.into(), diffs.pop().unwrap())
+  }
+
+  /// Highlight the changed pixels in red over a faded gray reference image.
+  fn pixel_diff(test_img: &PixelImage, ref_img: &PixelImage) -> PixelImage {
+    let data = test_img
+      .pixel_bytes()
+      .chunks_exact(4)
+      .zip(ref_img.pixel_bytes().chunks_exact(4))
+      .flat_map(|(test, reference)| {
+        if test == reference {
+          let [r, g, b, a] = [reference[0], reference[1], reference[2], reference[3]];
+          let gray = (r as u32 * 3 + g as u32 * 6 + b as u32) / 10 * a as u32 / 255;
+          let faded = 255 - (255 - gray as u8) / 4;
+          [faded, faded, faded, 255]
+        } else {
+          [255, 0, 0, 255]
+        }
+      })
+      .collect();
+    PixelImage::new(data, test_img.width(), test_img.height(), ColorFormat::Rgba8)
+  }
+
+  /// Place the images of the same height from left to right.
+  fn side_by_side(images: &[&PixelImage]) -> PixelImage {
+    let height = images[0].height();
+    let width = images.iter().map(|img| img.width()).sum();
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for row in 0..height as usize {
+      for img in images {
+        let stride = img.width() as usize * 4;
+        data.extend_from_slice(&img.pixel_bytes()[row * stride..(row + 1) * stride]);
+      }
     }
+    PixelImage::new(data.into(), width, height, ColorFormat::Rgba8)
   }
 
   #[track_caller]
@@ -224,3 +390,163 @@ pub fn wgpu_render_commands(
     draw_img(&mut backend)
   }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+  use std::path::PathBuf;
+
+  use super::*;
+
+  fn solid(color: [u8; 4]) -> PixelImage {
+    let data = color.repeat(16 * 16);
+    PixelImage::new(data.into(), 16, 16, ColorFormat::Rgba8)
+  }
+
+  fn ref_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ribir_img_test_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("ref.png");
+    solid([255, 0, 0, 255])
+      .write_as_png(&mut std::fs::File::create(&path).unwrap())
+      .unwrap();
+    path
+  }
+
+  fn read_png(path: &std::path::Path) -> PixelImage {
+    PixelImage::from_png(&std::fs::read(path).unwrap())
+  }
+
+  #[test]
+  fn update_rewrites_mismatched_reference() {
+    let path = ref_file("update");
+    ImageTest::new(solid([0, 0, 255, 255]), &path).test_with_mode(SnapshotMode::Update);
+
+    assert_eq!(read_png(&path).pixel_bytes(), solid([0, 0, 255, 255]).pixel_bytes());
+    assert!(!path.with_file_name("ref_actual.png").exists());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn compare_fails_on_mismatch() {
+    let path = ref_file("compare");
+    let res = std::panic::catch_unwind(|| {
+      ImageTest::new(solid([0, 0, 255, 255]), &path).test_with_mode(SnapshotMode::Compare);
+    });
+
+    assert!(res.is_err());
+    assert_eq!(read_png(&path).pixel_bytes(), solid([255, 0, 0, 255]).pixel_bytes());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  /// A disc of radius 10 whose edge coverage is mapped by `ramp`, different
+  /// ramps simulate the anti-aliasing of different GPUs.
+  fn disc(ramp: impl Fn(f32) -> f32) -> PixelImage {
+    let data = (0..32 * 32)
+      .flat_map(|i| {
+        let (x, y) = ((i % 32) as f32 + 0.5, (i / 32) as f32 + 0.5);
+        let dist = ((x - 16.).powi(2) + (y - 16.).powi(2)).sqrt();
+        let v = ramp((dist - 10.).clamp(-0.5, 0.5) + 0.5) * 255.;
+        [v as u8, v as u8, v as u8, 255]
+      })
+      .collect::<Vec<_>>();
+    PixelImage::new(data.into(), 32, 32, ColorFormat::Rgba8)
+  }
+
+  fn disc_ref(name: &str) -> PathBuf {
+    let path = ref_file(name);
+    disc(|c| c)
+      .write_as_png(&mut std::fs::File::create(&path).unwrap())
+      .unwrap();
+    path
+  }
+
+  #[test]
+  fn perceptual_tolerates_anti_aliasing() {
+    let path = disc_ref("perceptual_aa");
+    let smooth = || disc(|c| c * c * (3. - 2. * c));
+
+    let plain = std::panic::catch_unwind(|| {
+      ImageTest::new(smooth(), &path)
+        .with_comparison(0.0001)
+        .test_with_mode(SnapshotMode::Compare);
+    });
+    assert!(plain.is_err());
+    ImageTest::new(smooth(), &path)
+      .with_perceptual_comparison(0.0001)
+      .test_with_mode(SnapshotMode::Compare);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn perceptual_fails_on_different_shape() {
+    let path = disc_ref("perceptual_shape");
+    let data = (0..32 * 32)
+      .flat_map(|i| {
+        let (x, y) = (i % 32, i / 32);
+        let v = if (7..25).contains(&x) && (7..25).contains(&y) { 0 } else { 255 };
+        [v, v, v, 255]
+      })
+      .collect::<Vec<_>>();
+    let square = PixelImage::new(data.into(), 32, 32, ColorFormat::Rgba8);
+
+    let res = std::panic::catch_unwind(|| {
+      ImageTest::new(square, &path)
+        .with_perceptual_comparison(0.0001)
+        .test_with_mode(SnapshotMode::Compare);
+    });
+    assert!(res.is_err());
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn failure_writes_diff_images() {
+    let path = ref_file("diff_output");
+    let mut data = solid([255, 0, 0, 255]).pixel_bytes().to_vec();
+    data[..4].copy_from_slice(&[0, 0, 255, 255]);
+    let test_img = PixelImage::new(data.into(), 16, 16, ColorFormat::Rgba8);
+    let res = std::panic::catch_unwind(|| {
+      ImageTest::new(test_img, &path).test_with_mode(SnapshotMode::Compare);
+    });
+
+    assert!(res.is_err());
+    let pixel_diff = read_png(&path.with_file_name("ref_pixel_diff.png"));
+    assert_eq!(&pixel_diff.pixel_bytes()[..4], &[255, 0, 0, 255]);
+    assert_ne!(&pixel_diff.pixel_bytes()[4..8], &[255, 0, 0, 255]);
+    let side_by_side = read_png(&path.with_file_name("ref_side_by_side.png"));
+    assert_eq!((side_by_side.width(), side_by_side.height()), (48, 16));
+    assert!(path.with_file_name("ref_diff.png").exists());
+    assert!(path.with_file_name("ref_actual.png").exists());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn size_mismatch_message() {
+    let path = ref_file("size_mismatch");
+    let test_img = PixelImage::new(vec![0; 8 * 8 * 4].into(), 8, 8, ColorFormat::Rgba8);
+    let res = std::panic::catch_unwind(|| {
+      ImageTest::new(test_img, &path).test_with_mode(SnapshotMode::Compare);
+    });
+
+    let err = res.unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("The actual image is 8x8, but the expected image is 16x16"));
+    assert!(!path.with_file_name("ref_diff.png").exists());
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+  }
+
+  #[test]
+  fn rewrite_modes_refused_in_ci() {
+    assert_eq!(SnapshotMode::from_vars(Some("update"), None), SnapshotMode::Update);
+    assert_eq!(SnapshotMode::from_vars(Some("update"), Some("false")), SnapshotMode::Update);
+    assert_eq!(SnapshotMode::from_vars(None, Some("true")), SnapshotMode::Compare);
+    assert!(
+      std::panic::catch_unwind(|| SnapshotMode::from_vars(Some("update"), Some("true"))).is_err()
+    );
+    assert!(
+      std::panic::catch_unwind(|| SnapshotMode::from_vars(Some("overwrite"), Some("1"))).is_err()
+    );
+  }
+}
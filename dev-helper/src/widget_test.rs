@@ -26,6 +26,7 @@ macro_rules! widget_layout_test {
       #[test]
       fn [<$name _layout>]() {
         let _scope = unsafe { AppCtx::new_lock_scope() };
+        AppCtx::use_test_fonts();
         $widget_tester.layout_check(&[$($case),+]);
       }
     }
@@ -42,9 +43,10 @@ macro_rules! widget_layout_test {
 /// The image file is stored in the `test_cases` folder at the workspace's root,
 /// relative to the test source path.
 ///
-/// To run the test and overwrite the image file, you can use
-/// `RIBIR_IMG_TEST=overwrite`. For instance: ```
-/// RIBIR_IMG_TEST=overwrite cargo test --smoke
+/// To run the test and rewrite the mismatched image files, you can use
+/// `RIBIR_IMG_TEST=update`, or `RIBIR_IMG_TEST=overwrite` to rewrite all of
+/// them. For instance: ```
+/// RIBIR_IMG_TEST=update cargo test --smoke
 /// ```
 #[cfg(not(target_arch = "wasm32"))]
 #[macro_export]
@@ -54,6 +56,7 @@ macro_rules! widget_image_tests {
       #[test]
       fn [<$name _with_default_by_wgpu>]() {
         let _scope = unsafe { AppCtx::new_lock_scope() };
+        AppCtx::use_test_fonts();
         svg::named_svgs::reset();
         unsafe { AppCtx::set_app_theme(ribir_slim::purple()) };
 
@@ -64,6 +67,7 @@ macro_rules! widget_image_tests {
       #[test]
       fn [<$name _with_material_by_wgpu>]() {
         let _scope = unsafe { AppCtx::new_lock_scope() };
+        AppCtx::use_test_fonts();
         svg::named_svgs::reset();
         unsafe { AppCtx::set_app_theme(ribir_material::purple::light()) };
 
@@ -87,7 +91,11 @@ macro_rules! assert_widget_eq_image {
 
     let mut img_test = $crate::ImageTest::new(img, &img_path);
     if let Some(c) = $widget_tester.comparison {
-      img_test = img_test.with_comparison(c);
+      img_test = if $widget_tester.perceptual {
+        img_test.with_perceptual_comparison(c)
+      } else {
+        img_test.with_comparison(c)
+      };
     }
     img_test.test();
   };
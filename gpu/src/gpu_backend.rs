@@ -1,21 +1,23 @@
-use std::error::Error;
+use std::{error::Error, future::Future, pin::Pin};
 
 use guillotiere::euclid::Vector2D;
 use ribir_geom::{
-  DeviceRect, DeviceSize, Point, Transform, rect_corners, transform_to_device_rect,
+  DevicePoint, DeviceRect, DeviceSize, Point, Transform, rect_corners, transform_to_device_rect,
 };
 use ribir_painter::{
-  Color, CommandBrush, PaintCommand, PaintPath, PaintPathAction, PainterBackend, PaintingStyle,
-  PathCommand, PixelImage, Vertex, VertexBuffers, image::ColorFormat,
+  BlendMode, Brush, Color, CommandBrush, LineCap, PaintCommand, PaintPath, PaintPathAction,
+  PainterBackend, PaintingStyle, PathCommand, PixelImage, StrokeOptions, Svg, SvgFit, Vertex,
+  VertexBuffers, color::PathGradient, image::ColorFormat,
 };
 
 use crate::{
   ColorAttr, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
-  LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex,
-  RadialGradientPrimitive,
+  LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, PathGradientAttr,
+  PathGradientPrimitive, RadialGradientPrimIndex, RadialGradientPrimitive,
 };
 
 mod atlas;
+pub use atlas::AtlasStats;
 
 mod textures_mgr;
 use textures_mgr::*;
@@ -32,6 +34,9 @@ pub struct GPUBackend<Impl: GPUBackendImpl> {
   linear_gradient_prims: Vec<LinearGradientPrimitive>,
   linear_gradient_stops: Vec<GradientStopPrimitive>,
   linear_gradient_vertices_buffer: VertexBuffers<LinearGradientPrimIndex>,
+  path_gradient_prims: Vec<PathGradientPrimitive>,
+  path_gradient_stops: Vec<GradientStopPrimitive>,
+  path_gradient_vertices_buffer: VertexBuffers<PathGradientAttr>,
   current_phase: CurrentPhase,
   tex_ids_map: TextureIdxMap,
   viewport: DeviceRect,
@@ -39,8 +44,12 @@ pub struct GPUBackend<Impl: GPUBackendImpl> {
   clip_layer_stack: Vec<ClipLayer>,
   skip_clip_cnt: usize,
   surface_color: Option<Color>,
+  in_frame: bool,
+  purge_at_frame_end: bool,
 }
 
+type ImageFuture = Pin<Box<dyn Future<Output = Result<PixelImage, Box<dyn Error>>>>>;
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum CurrentPhase {
   None,
@@ -48,6 +57,7 @@ enum CurrentPhase {
   Img,
   RadialGradient,
   LinearGradient,
+  PathGradient,
 }
 
 struct ClipLayer {
@@ -91,6 +101,7 @@ where
   type Texture = Impl::Texture;
 
   fn begin_frame(&mut self, surface: Color) {
+    self.in_frame = true;
     self.surface_color = Some(surface);
     self.gpu_impl.begin_frame();
   }
@@ -115,6 +126,10 @@ where
   fn end_frame(&mut self) {
     self.mask_layers.clear();
     self.tex_mgr.end_frame();
+    self.in_frame = false;
+    if self.purge_at_frame_end {
+      self.purge_unused();
+    }
     self.gpu_impl.end_frame();
   }
 }
@@ -140,13 +155,68 @@ where
       linear_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
       linear_gradient_stops: vec![],
       linear_gradient_prims: vec![],
+      path_gradient_vertices_buffer: VertexBuffers::with_capacity(256, 512),
+      path_gradient_stops: vec![],
+      path_gradient_prims: vec![],
       img_prims: vec![],
       current_phase: CurrentPhase::None,
       viewport: DeviceRect::zero(),
       surface_color: Some(Color::WHITE),
+      in_frame: false,
+      purge_at_frame_end: false,
+    }
+  }
+
+  /// The statistics of all the texture atlases the backend holds.
+  pub fn atlas_stats(&self) -> AtlasStats { self.tex_mgr.atlas_stats() }
+
+  /// Release the texture memory not used by the cached resources. If it's
+  /// called within a frame, the purge is delayed to the frame end, so the
+  /// textures referenced by the current frame are never dropped.
+  pub fn purge_unused(&mut self) {
+    if self.in_frame {
+      self.purge_at_frame_end = true;
+    } else {
+      self.purge_at_frame_end = false;
+      self.tex_mgr.purge_unused(&mut self.gpu_impl);
     }
   }
 
+  /// Rasterize the `svg` to an image of `size` in an offscreen texture, the
+  /// SVG is scaled by the `fit` mode and its inherited colors use the `brush`.
+  ///
+  /// It draws a whole frame, so it should not be called within a frame. As
+  /// same as `Texture::copy_as_image`, the returned future resolves after the
+  /// GPU finishes the work.
+  pub fn rasterize_svg(
+    &mut self, svg: &Svg, size: DeviceSize, fit: SvgFit, brush: &Brush,
+  ) -> ImageFuture {
+    assert!(!self.in_frame, "Can't rasterize an SVG within a frame.");
+    let commands = svg.fit_commands(size.to_f32().cast_unit(), fit, brush);
+    self.draw_offscreen(size, &commands, &Transform::identity(), Color::TRANSPARENT)
+  }
+
+  /// Draw the `commands` to an image of `size` in an offscreen texture, the
+  /// image is cleared with the `surface` color first.
+  ///
+  /// It draws a whole frame, so it should not be called within a frame. The
+  /// returned future resolves after the GPU finishes the work.
+  pub fn draw_offscreen(
+    &mut self, size: DeviceSize, commands: &[PaintCommand], global_matrix: &Transform,
+    surface: Color,
+  ) -> ImageFuture {
+    assert!(!self.in_frame, "Can't draw offscreen within a frame.");
+    let rect = DeviceRect::from_size(size);
+    let mut texture = self
+      .gpu_impl
+      .new_texture(size, ColorFormat::Rgba8);
+    self.begin_frame(surface);
+    self.draw_commands(rect, commands, global_matrix, &mut texture);
+    let img = texture.copy_as_image(&rect, &mut self.gpu_impl);
+    self.end_frame();
+    Box::pin(img)
+  }
+
   #[inline]
   pub fn get_impl(&self) -> &Impl { &self.gpu_impl }
 
@@ -179,6 +249,16 @@ where
           return;
         };
 
+        if let PaintPathAction::Paint {
+          brush: CommandBrush::PathGradient(_),
+          painting_style: PaintingStyle::Fill,
+        } = action
+        {
+          // The path gradient only works with stroking, the painter never fills a path
+          // with it.
+          return;
+        }
+
         if !self.can_batch_path_command(cmd) {
           self.new_draw_phase(output);
         }
@@ -196,7 +276,7 @@ where
           PaintPathAction::Clip => self
             .clip_layer_stack
             .push(ClipLayer { viewport, mask_head }),
-          PaintPathAction::Paint { brush, .. } => match brush {
+          PaintPathAction::Paint { brush, painting_style } => match brush {
             CommandBrush::Color(color) => {
               let color = color.into_components();
               let color_attr = ColorAttr { color, mask_head };
@@ -254,6 +334,10 @@ where
               add_rect_vertices(rect, output_tex_size, LinearGradientPrimIndex(prim_idx), buffer);
               self.current_phase = CurrentPhase::LinearGradient;
             }
+            CommandBrush::PathGradient(gradient) => {
+              let PaintingStyle::Stroke(options) = painting_style else { unreachable!() };
+              self.draw_path_gradient(path, options, gradient, &matrix, mask_head, output_tex_size);
+            }
           },
         }
       }
@@ -264,7 +348,7 @@ where
           self.clip_layer_stack.pop();
         }
       }
-      PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
+      PaintCommand::Bundle { transform, opacity, bounds, blend_mode, cmds } => {
         let matrix = transform.then(global_matrix);
         let scale = self.tex_mgr.cache_scale(&bounds.size, &matrix);
         let cache_size = bounds.size * scale;
@@ -275,7 +359,7 @@ where
           cmds.clone().into_any(),
           scale,
           &mut self.gpu_impl,
-          |slice, tex, _| {
+          |slice, tex, gpu| {
             // SAFETY: We already hold a mut reference to the texture in the texture
             // manager, so we cant use `self` here, but this texture should always exist
             // within the frame, and no modifications will be made to the slice
@@ -285,15 +369,18 @@ where
             // Initiate a new drawing phase to ensure a clean state for rendering in a new
             // texture.
             this.new_draw_phase(output);
+            // The slice may be reused from a released allocation, clear the stale
+            // content before drawing.
+            tex.clear_areas(&[*slice], gpu);
 
             // store the viewport
             let viewport = self.viewport;
             // Overwrite the viewport to the slice bounds.
             self
               .clip_layer_stack
-              .push(ClipLayer { viewport, mask_head: -1 });
+              .push(ClipLayer { viewport: *slice, mask_head: -1 });
 
-            let matrix = Transform::translation(bounds.origin.x, bounds.origin.y)
+            let matrix = Transform::translation(-bounds.origin.x, -bounds.origin.y)
               .then_scale(scale, scale)
               .then_translate(slice.origin.to_f32().cast_unit().to_vector());
             this.draw_commands(*slice, cmds, &matrix, tex);
@@ -319,14 +406,42 @@ where
           // scale to the cache size.
           .then_scale(cache_scale, cache_scale);
 
-        if !self.can_batch_img_path() {
+        if *blend_mode == BlendMode::Normal {
+          if !self.can_batch_img_path() {
+            self.new_draw_phase(output);
+          }
+          let mask_head = self.current_clip_mask_index();
+          self.draw_img_slice(slice, &view_to_slice, mask_head, *opacity, output_tex_size, points);
+        } else {
+          let device_rect = transform_to_device_rect(bounds, &matrix);
+          let Some(rect) = self.viewport().intersection(&device_rect) else {
+            return;
+          };
+          // Flush the painted content, then copy it out as the backdrop to blend with.
           self.new_draw_phase(output);
+          let gpu = &mut self.gpu_impl;
+          let mut backdrop = gpu.new_texture(rect.size, ColorFormat::Rgba8);
+          gpu.copy_texture_from_texture(&mut backdrop, DevicePoint::zero(), output, &rect);
+
+          let (x, y) = (rect.origin.x as f32, rect.origin.y as f32);
+          let backdrop_to_slice = Transform::translation(x, y).then(&view_to_slice);
+          let blended =
+            self
+              .tex_mgr
+              .store_blend_layer(&slice, &backdrop_to_slice, &backdrop, *blend_mode, gpu);
+
+          let points = rect_corners(&rect.to_f32().cast_unit());
+          let view_to_blended = Transform::translation(-x, -y);
+          let mask_head = self.current_clip_mask_index();
+          self.draw_img_slice(
+            blended,
+            &view_to_blended,
+            mask_head,
+            *opacity,
+            output_tex_size,
+            points,
+          );
         }
-        let mask_head = self
-          .clip_layer_stack
-          .last()
-          .map_or(-1, |l| l.mask_head);
-        self.draw_img_slice(slice, &view_to_slice, mask_head, *opacity, output_tex_size, points);
       }
     }
   }
@@ -405,6 +520,13 @@ where
       .indices
       .clear();
     self.linear_gradient_stops.clear();
+    self.path_gradient_prims.clear();
+    self.path_gradient_stops.clear();
+    self
+      .path_gradient_vertices_buffer
+      .vertices
+      .clear();
+    self.path_gradient_vertices_buffer.indices.clear();
   }
 
   fn draw_img_slice(
@@ -428,6 +550,59 @@ where
     self.current_phase = CurrentPhase::Img;
   }
 
+  /// Draw the stroke of the path with the triangles of a slightly wider stroke,
+  /// every vertex carries its rate of the path length, so the fragment shader
+  /// can pick the gradient color by the interpolated rate. The mask layer of
+  /// the stroke clips the triangles to the exact coverage.
+  fn draw_path_gradient(
+    &mut self, path: &PaintPath, options: &StrokeOptions, gradient: &PathGradient,
+    matrix: &Transform, mask_head: i32, output_tex_size: DeviceSize,
+  ) {
+    let scale = matrix
+      .m11
+      .hypot(matrix.m12)
+      .min(matrix.m21.hypot(matrix.m22));
+    if scale <= 0. {
+      return;
+    }
+    let mut options = options.clone();
+    // Widen the stroke one pixel for each side to cover the anti-aliasing edge
+    // of the mask, and the butt caps are extended by the square caps.
+    options.width += 2. / scale;
+    if options.line_cap == LineCap::Butt {
+      options.line_cap = LineCap::Square;
+    }
+
+    let prim_idx = self.path_gradient_prims.len() as u32;
+    let buffer = &mut self.path_gradient_vertices_buffer;
+    let vertex_start = buffer.vertices.len();
+    path.stroke_tessellate_with_distance(TOLERANCE / scale, options, buffer, |pos, dist| {
+      let pos = matrix.transform_point(pos);
+      Vertex::new(vertices_coord(pos, output_tex_size), PathGradientAttr { rate: dist, prim_idx })
+    });
+    let vertices = &mut buffer.vertices[vertex_start..];
+    let length = vertices
+      .iter()
+      .fold(0., |len: f32, v| len.max(v.attr.rate));
+    if length > 0. {
+      vertices
+        .iter_mut()
+        .for_each(|v| v.attr.rate /= length);
+    }
+
+    let stop = (self.path_gradient_stops.len() << 16 | gradient.stops.len()) as u32;
+    let mask_head_and_spread = mask_head << 16 | gradient.spread_method as i32;
+    self
+      .path_gradient_prims
+      .push(PathGradientPrimitive { stop, mask_head_and_spread, _dummy: [0; 2] });
+    let stops = gradient
+      .stops
+      .iter()
+      .map(GradientStopPrimitive::new);
+    self.path_gradient_stops.extend(stops);
+    self.current_phase = CurrentPhase::PathGradient;
+  }
+
   fn can_batch_path_command(&self, cmd: &PathCommand) -> bool {
     let limits = self.gpu_impl.limits();
     let tex_used = self.tex_ids_map.len();
@@ -452,6 +627,11 @@ where
           && self.linear_gradient_prims.len() < limits.max_linear_gradient_primitives
           && self.linear_gradient_stops.len() < limits.max_gradient_stop_primitives
       }
+      (CurrentPhase::PathGradient, CommandBrush::PathGradient(_)) => {
+        tex_used < limits.max_tex_load
+          && self.path_gradient_prims.len() < limits.max_path_gradient_primitives
+          && self.path_gradient_stops.len() < limits.max_gradient_stop_primitives
+      }
       _ => false,
     }
   }
@@ -558,6 +738,18 @@ where
         let rg = 0..self.linear_gradient_vertices_buffer.indices.len() as u32;
         gpu_impl.draw_linear_gradient_triangles(output, rg, color.take())
       }
+      CurrentPhase::PathGradient
+        if !self
+          .path_gradient_vertices_buffer
+          .indices
+          .is_empty() =>
+      {
+        gpu_impl.load_path_gradient_primitives(&self.path_gradient_prims);
+        gpu_impl.load_path_gradient_stops(&self.path_gradient_stops);
+        gpu_impl.load_path_gradient_vertices(&self.path_gradient_vertices_buffer);
+        let rg = 0..self.path_gradient_vertices_buffer.indices.len() as u32;
+        gpu_impl.draw_path_gradient_triangles(output, rg, color.take())
+      }
       _ => {}
     }
   }
@@ -611,7 +803,7 @@ mod tests {
   use ribir_algo::Resource;
   use ribir_dev_helper::*;
   use ribir_geom::*;
-  use ribir_painter::{Brush, Painter, Path, Svg};
+  use ribir_painter::{BlendMode, Brush, Painter, Path, Svg};
 
   use super::*;
 
@@ -813,4 +1005,302 @@ mod tests {
     painter
   }
   painter_backend_eq_image_test!(draw_bundle_svg, comparison = 0.001);
+
+  fn draw_text_on_arc() -> Painter {
+    use std::cell::RefCell;
+
+    use ribir_algo::Sc;
+    use ribir_painter::{
+      Em, FontFace, FontFamily, TextAlign, TextOverflow, TextStyle, TypographyStore,
+      font_db::{FontDB, GlyphBaseline},
+      typography::PlaceLineDirection,
+    };
+
+    let font_db = Sc::new(RefCell::new(FontDB::default()));
+    let font_path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    font_db.borrow_mut().load_font_file(font_path).unwrap();
+    let style = TextStyle {
+      font_size: 24.,
+      font_face: FontFace {
+        families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+        ..<_>::default()
+      },
+      letter_space: 2.,
+      line_height: 24.,
+      overflow: TextOverflow::Overflow,
+      first_line_indent: Em(0.),
+      hanging_indent: Em(0.),
+    };
+    let mut store = TypographyStore::new(font_db.clone());
+    let glyphs = store.typography(
+      "Hello, Ribir!".into(),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+
+    let mut builder = Path::builder();
+    builder.begin_path(Point::new(28., 128.));
+    builder.arc_to(Point::new(128., 128.), 100., Angle::pi() * -1., Angle::zero());
+    builder.end_path(false);
+    let arc = builder.build();
+
+    let mut painter = painter(Size::new(256., 160.));
+    painter
+      .set_stroke_brush(Color::GREEN)
+      .set_line_width(1.)
+      .stroke_path(arc.clone().into())
+      .set_fill_brush(Color::RED)
+      .draw_glyphs_on_path(&glyphs, &arc, &font_db.borrow());
+    painter
+  }
+  painter_backend_eq_image_test!(draw_text_on_arc, comparison = 0.002);
+
+  fn stroke_path_gradient() -> Painter {
+    use ribir_painter::{GradientStop, SpreadMethod, color::PathGradient};
+
+    let mut builder = Path::builder();
+    builder.begin_path(Point::new(28., 128.));
+    builder.arc_to(Point::new(128., 128.), 100., Angle::pi() * -1., Angle::zero());
+    builder.end_path(false);
+    let gradient = PathGradient {
+      stops: vec![GradientStop::new(Color::RED, 0.), GradientStop::new(Color::BLUE, 1.)],
+      spread_method: SpreadMethod::Pad,
+    };
+
+    let mut painter = painter(Size::new(256., 160.));
+    painter
+      .set_stroke_brush(Brush::PathGradient(gradient))
+      .set_line_width(16.)
+      .stroke_path(builder.build().into());
+    painter
+  }
+  painter_backend_eq_image_test!(stroke_path_gradient, comparison = 0.002);
+
+  fn stroke_scaled_circle_path_gradient() -> Painter {
+    use ribir_painter::{GradientStop, SpreadMethod, color::PathGradient};
+
+    let gradient = PathGradient {
+      stops: vec![
+        GradientStop::new(Color::RED, 0.),
+        GradientStop::new(Color::GREEN, 0.5),
+        GradientStop::new(Color::BLUE, 0.8),
+      ],
+      spread_method: SpreadMethod::Repeat,
+    };
+
+    let mut painter = painter(Size::new(200., 200.));
+    painter
+      .scale(4., 4.)
+      .set_stroke_brush(Brush::PathGradient(gradient))
+      .set_line_width(4.)
+      .circle(Point::new(25., 25.), 20.)
+      .stroke();
+    painter
+  }
+  painter_backend_eq_image_test!(stroke_scaled_circle_path_gradient, comparison = 0.002);
+
+  #[test]
+  fn path_gradient_along_arc() {
+    let mut painter = stroke_path_gradient();
+    let viewport = painter.viewport().to_i32().cast_unit();
+    let img = wgpu_render_commands(&painter.finish(), viewport, Color::TRANSPARENT);
+    let pixel = |x: usize, y: usize| {
+      let idx = (y * img.width() as usize + x) * 4;
+      img.pixel_bytes()[idx..idx + 4].to_vec()
+    };
+
+    // The arc starts red and ends blue.
+    let start = pixel(28, 124);
+    let end = pixel(228, 124);
+    assert!(start[0] > 200 && start[2] < 50, "{start:?}");
+    assert!(end[2] > 200 && end[0] < 50, "{end:?}");
+    // The top of the arc is in the middle of the gradient.
+    let top = pixel(128, 28);
+    assert!(top[0] > 80 && top[2] > 80, "{top:?}");
+  }
+
+  #[test]
+  fn group_alpha_not_overlap() {
+    let mut painter = painter(Size::new(64., 32.));
+    painter
+      .translate(8., 8.)
+      .apply_group_alpha(0.5)
+      .set_fill_brush(Color::RED)
+      .rect(&rect(0., 0., 32., 16.))
+      .fill()
+      .rect(&rect(16., 0., 32., 16.))
+      .fill();
+
+    let viewport = DeviceRect::from_size(DeviceSize::new(64, 32));
+    let img = wgpu_render_commands(&painter.finish(), viewport, Color::TRANSPARENT);
+    let pixel = |x: usize, y: usize| {
+      let idx = (y * img.width() as usize + x) * 4;
+      img.pixel_bytes()[idx..idx + 4].to_vec()
+    };
+    let alpha_eq = |a: Vec<u8>, b: Vec<u8>| a[3].abs_diff(b[3]) <= 4;
+    // The overlapped area is composited once, so it's almost the same as the
+    // others rather than a darker seam.
+    assert!(alpha_eq(pixel(16, 16), pixel(32, 16)));
+    assert!(alpha_eq(pixel(48, 16), pixel(32, 16)));
+    assert!(pixel(32, 16)[3] < 160);
+  }
+
+  #[test]
+  fn blend_multiply() {
+    let backdrop = Color::from_rgb(200, 100, 50);
+    let src = Color::from_rgb(128, 255, 64);
+    let mut painter = painter(Size::new(64., 64.));
+    painter
+      .set_fill_brush(backdrop)
+      .rect(&rect(0., 0., 64., 64.))
+      .fill()
+      .save()
+      .set_blend_mode(BlendMode::Multiply)
+      .set_fill_brush(src)
+      .rect(&rect(16., 16., 32., 32.))
+      .fill()
+      .restore();
+
+    let viewport = DeviceRect::from_size(DeviceSize::new(64, 64));
+    let img = wgpu_render_commands(&painter.finish(), viewport, Color::TRANSPARENT);
+    let pixel = |x: usize, y: usize| {
+      let idx = (y * img.width() as usize + x) * 4;
+      img.pixel_bytes()[idx..idx + 4].to_vec()
+    };
+
+    let color_eq = |a: &[u8], b: &[u32]| {
+      a.iter()
+        .zip(b)
+        .all(|(a, b)| (*a as u32).abs_diff(*b) <= 4)
+    };
+    let bg = pixel(8, 8);
+    assert!(color_eq(&bg, &[200, 100, 50]), "{bg:?}");
+
+    let expected = [
+      backdrop.red as u32 * src.red as u32 / 255,
+      backdrop.green as u32 * src.green as u32 / 255,
+      backdrop.blue as u32 * src.blue as u32 / 255,
+    ];
+    let blended = pixel(32, 32);
+    assert!(color_eq(&blended, &expected), "{blended:?} != {expected:?}");
+  }
+
+  #[test]
+  fn blend_multiply_semi_transparent() {
+    let backdrop = Color::from_rgb(200, 100, 50);
+    let src = Color::from_rgb(128, 255, 64).with_alpha(0.5);
+    let mut painter = painter(Size::new(64., 64.));
+    painter
+      .set_fill_brush(backdrop)
+      .rect(&rect(0., 0., 64., 64.))
+      .fill()
+      .save()
+      .set_blend_mode(BlendMode::Multiply)
+      .set_fill_brush(src)
+      .rect(&rect(16., 16., 32., 32.))
+      .fill()
+      .restore();
+
+    let viewport = DeviceRect::from_size(DeviceSize::new(64, 64));
+    let img = wgpu_render_commands(&painter.finish(), viewport, Color::TRANSPARENT);
+    let idx = (32 * img.width() as usize + 32) * 4;
+    let blended = &img.pixel_bytes()[idx..idx + 4];
+
+    // The multiplied color covers the backdrop by the alpha of the source.
+    let alpha = src.alpha as f32 / 255.;
+    let [r, g, b] = [
+      (backdrop.red, src.red),
+      (backdrop.green, src.green),
+      (backdrop.blue, src.blue),
+    ]
+    .map(|(b, s)| {
+      let b = b as f32;
+      b * s as f32 / 255. * alpha + b * (1. - alpha)
+    });
+    let expected = [r, g, b, 255.];
+    let color_eq = blended
+      .iter()
+      .zip(expected)
+      .all(|(a, b)| (*a as f32 - b).abs() <= 4.);
+    assert!(color_eq, "{blended:?} != {expected:?}");
+  }
+
+  #[test]
+  fn rasterize_svg() {
+    use futures::executor::block_on;
+
+    use crate::WgpuImpl;
+
+    let svg = Svg::parse_from_bytes(
+      br#"<svg width="10" height="10" xmlns="http://www.w3.org/2000/svg">
+        <circle cx="5" cy="5" r="5" fill="red"/>
+      </svg>"#,
+      false,
+      false,
+    )
+    .unwrap();
+
+    let mut backend = GPUBackend::new(block_on(WgpuImpl::headless()));
+    let pixel = |img: &PixelImage, x: usize, y: usize| {
+      let idx = (y * img.width() as usize + x) * 4;
+      img.pixel_bytes()[idx..idx + 4].to_vec()
+    };
+    let is_red = |c: Vec<u8>| c[0] > 250 && c[1] == 0 && c[2] == 0 && c[3] > 250;
+
+    let size = DeviceSize::new(32, 32);
+    let img = backend.rasterize_svg(&svg, size, SvgFit::Contain, &Color::BLACK.into());
+    let img = block_on(img).unwrap();
+    assert_eq!((img.width(), img.height()), (32, 32));
+    assert!(is_red(pixel(&img, 16, 16)));
+    assert_eq!(pixel(&img, 0, 0), [0, 0, 0, 0]);
+
+    // The circle keeps its aspect ratio and is centered in a wide target.
+    let size = DeviceSize::new(64, 32);
+    let img = backend.rasterize_svg(&svg, size, SvgFit::Contain, &Color::BLACK.into());
+    let img = block_on(img).unwrap();
+    assert!(is_red(pixel(&img, 32, 16)));
+    assert_eq!(pixel(&img, 10, 16), [0, 0, 0, 0]);
+    assert_eq!(pixel(&img, 54, 16), [0, 0, 0, 0]);
+  }
+
+  #[test]
+  fn anti_aliasing_none() {
+    use futures::executor::block_on;
+
+    use crate::{AntiAliasing, WgpuImpl};
+
+    let mut wgpu = block_on(WgpuImpl::headless());
+    wgpu.set_anti_aliasing(AntiAliasing::from_sample_count(1));
+    assert_eq!(wgpu.anti_aliasing().sample_count(), 1);
+
+    let mut painter = painter(Size::new(64., 64.));
+    painter
+      .set_fill_brush(Color::RED)
+      .circle(Point::new(32., 32.), 20.3)
+      .fill();
+    let commands = painter.finish();
+
+    let mut backend = GPUBackend::new(wgpu);
+    let rect = DeviceRect::from_size(DeviceSize::new(64, 64));
+    let mut texture = backend
+      .get_impl_mut()
+      .new_texture(rect.size, ColorFormat::Rgba8);
+    backend.begin_frame(Color::TRANSPARENT);
+    backend.draw_commands(rect, &commands, &Transform::identity(), &mut texture);
+    let img = texture.copy_as_image(&rect, backend.get_impl_mut());
+    backend.end_frame();
+    let img = block_on(img).unwrap();
+
+    // Without anti-aliasing, every pixel is either fully covered or not.
+    let alphas: Vec<u8> = img
+      .pixel_bytes()
+      .chunks(4)
+      .map(|c| c[3])
+      .collect();
+    assert!(alphas.contains(&255));
+    assert!(alphas.iter().all(|a| *a == 0 || *a == 255));
+  }
 }
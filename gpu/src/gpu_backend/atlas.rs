@@ -21,6 +21,17 @@ pub struct AtlasHandle {
   pub dist: AtlasDist,
 }
 
+/// The statistics of an atlas, help to observe the memory pressure of it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AtlasStats {
+  /// The pixels count of all the alive allocations.
+  pub used: usize,
+  /// The pixels count of all the textures the atlas holds.
+  pub capacity: usize,
+  /// The count of the cached entries evicted since the atlas created.
+  pub evictions: usize,
+}
+
 pub(crate) struct AtlasConfig {
   label: &'static str,
   min_size: DeviceSize,
@@ -36,6 +47,7 @@ pub(crate) struct Atlas<K, T: Texture> {
   extras: Slab<T>,
   /// All allocations in the current frame and not cached.
   islands: ahash::HashSet<AtlasDist>,
+  evictions: usize,
 }
 
 impl<K, T: Texture> Atlas<K, T>
@@ -53,6 +65,7 @@ where
       cache: FrameCache::new(),
       extras: Slab::default(),
       islands: <_>::default(),
+      evictions: 0,
     }
   }
 
@@ -165,13 +178,79 @@ where
       && size.area() <= self.config.max_size.area() / 4
   }
 
+  pub fn stats(&self) -> AtlasStats {
+    let extras = self
+      .extras
+      .iter()
+      .map(|(_, t)| t.size().area() as usize)
+      .sum::<usize>();
+    let mut used = extras;
+    self
+      .atlas_allocator
+      .for_each_allocated_rectangle(|_, rect| used += rect.area() as usize);
+
+    AtlasStats { used, capacity: self.size().area() as usize + extras, evictions: self.evictions }
+  }
+
+  /// Shrink the atlas texture to the smallest size that can hold all the
+  /// cached entries, release the memory not used by them. The entries not hit
+  /// in a frame are already evicted when the frame ends.
+  ///
+  /// The cached entries may be relocated, so the caller must guarantee that
+  /// no slice of the atlas is referenced by the current frame. Return if the
+  /// texture is replaced.
+  pub fn purge_unused(&mut self, gpu_impl: &mut T::Host) -> bool {
+    if !self.islands.is_empty() {
+      return false;
+    }
+
+    let current_size = self.size();
+    let mut size = self.config.min_size;
+    let (allocator, changes) = loop {
+      if size.greater_than(current_size).any() || size == current_size {
+        return false;
+      }
+      let mut allocator = self.atlas_allocator.clone();
+      let list = allocator.resize_and_rearrange(size.cast_unit());
+      if list.failures.is_empty() {
+        break (allocator, list.changes);
+      }
+      size = (size * 2).min(self.config.max_size);
+    };
+
+    let mut new_tex = gpu_impl.new_texture(size, self.texture.color_format());
+    let mut moved = ahash::HashMap::default();
+    for c in changes {
+      gpu_impl.copy_texture_from_texture(
+        &mut new_tex,
+        c.new.rectangle.min.cast_unit(),
+        &self.texture,
+        &c.old.rectangle.to_rect().cast_unit(),
+      );
+      moved.insert(c.old.id, c.new);
+    }
+    for (_, h) in self.cache.iter_mut() {
+      if let AtlasDist::Atlas(alloc) = &mut h.dist {
+        *alloc = moved[&alloc.id];
+      }
+    }
+
+    self.atlas_allocator = allocator;
+    self.texture = new_tex;
+    true
+  }
+
   pub(crate) fn end_frame(&mut self) { self.end_frame_with(|_| {}) }
 
   pub(crate) fn end_frame_with(&mut self, mut on_deallocate: impl FnMut(DeviceRect)) {
+    let evictions = &mut self.evictions;
     self
       .cache
       .end_frame(self.config.label)
-      .map(|h| h.dist)
+      .map(|h| {
+        *evictions += 1;
+        h.dist
+      })
       .chain(self.islands.drain())
       .for_each(|dist| match dist {
         AtlasDist::Atlas(alloc) => {
@@ -380,4 +459,53 @@ mod tests {
       icon.area() as usize + second_area * 2
     )
   }
+
+  #[test]
+  fn purge_unused() {
+    let mut wgpu = block_on(WgpuImpl::headless());
+    let mut atlas = Atlas::<Resource<dyn Any>, WgpuTexture>::new(
+      AtlasConfig::new("", DeviceSize::new(4096, 4096)),
+      ColorFormat::Alpha8,
+      &mut wgpu,
+    );
+    let min_size = atlas.config.min_size;
+    let icon = DeviceSize::new(32, 32);
+    let in_use = Resource::new(0).into_any();
+    let init = |rect: &DeviceRect, tex: &mut WgpuTexture, gpu: &mut WgpuImpl| {
+      tex.write_data(rect, &[1; 32 * 32], gpu)
+    };
+    // Fill the atlas to force it to grow.
+    let keys: Vec<_> = (1..6)
+      .map(|i| Resource::new(i).into_any())
+      .collect();
+    for key in keys.iter() {
+      atlas.get_or_cache(key.clone(), 1., min_size / 2, &mut wgpu, |_, _, _| {});
+    }
+    atlas.get_or_cache(in_use.clone(), 1., icon, &mut wgpu, init);
+    atlas.end_frame();
+    assert!(atlas.size().greater_than(min_size).any());
+
+    // Only the `in_use` entry is referenced by the next frame.
+    atlas.get_or_cache(in_use.clone(), 1., icon, &mut wgpu, init);
+    atlas.end_frame();
+    let stats = atlas.stats();
+    assert_eq!(stats.evictions, keys.len());
+    assert_eq!(stats.used, icon.area() as usize);
+
+    assert!(atlas.purge_unused(&mut wgpu));
+    assert_eq!(atlas.size(), min_size);
+    assert_eq!(atlas.stats().capacity, min_size.area() as usize);
+    for key in keys.iter() {
+      assert!(atlas.get(key, 1.).is_none());
+    }
+
+    // The in-use entry is relocated to the new texture with its content.
+    let h = *atlas.get(&in_use, 1.).unwrap();
+    let img = atlas
+      .texture
+      .copy_as_image(&h.tex_rect(&atlas), &mut wgpu);
+    wgpu.end_frame();
+    let img = block_on(img).unwrap();
+    assert!(img.pixel_bytes().iter().all(|v| *v == 1));
+  }
 }
@@ -5,16 +5,16 @@ use rayon::{prelude::ParallelIterator, slice::ParallelSlice};
 use ribir_algo::Resource;
 use ribir_geom::{DeviceRect, DeviceSize, Size, Transform, transform_to_device_rect};
 use ribir_painter::{
-  PaintPath, PaintingStyle, Path, PixelImage, StrokeOptions, Vertex, VertexBuffers,
+  BlendMode, PaintPath, PaintingStyle, Path, PixelImage, StrokeOptions, Vertex, VertexBuffers,
   image::ColorFormat,
 };
 
 use super::{
   Texture,
-  atlas::{Atlas, AtlasConfig, AtlasDist},
+  atlas::{Atlas, AtlasConfig, AtlasDist, AtlasStats},
 };
-use crate::GPUBackendImpl;
-const TOLERANCE: f32 = 0.1_f32;
+use crate::{BlendPrimitive, GPUBackendImpl};
+pub(super) const TOLERANCE: f32 = 0.1_f32;
 const PAR_CHUNKS_SIZE: usize = 64;
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Copy)]
@@ -223,6 +223,41 @@ where
     })
   }
 
+  /// Blend the bundle slice `src` with the `backdrop` texture, and store the
+  /// result in a slice that only lives in the current frame. The
+  /// `backdrop_to_src` transforms a position of the backdrop to the position
+  /// relative to the `src` slice.
+  pub(super) fn store_blend_layer(
+    &mut self, src: &TextureSlice, backdrop_to_src: &Transform, backdrop: &T, mode: BlendMode,
+    gpu: &mut T::Host,
+  ) -> TextureSlice {
+    let TextureID::Bundle(src_id) = src.tex_id else {
+      unreachable!("Only the bundle slice can be blended.");
+    };
+
+    let size = backdrop.size();
+    let dist = self.rgba_atlas.allocate(size, gpu);
+    let rect = DeviceRect::new(dist.tex_rect(&self.rgba_atlas).origin, size);
+    let transform = Transform::translation(-rect.origin.x as f32, -rect.origin.y as f32)
+      .then(backdrop_to_src)
+      .then_translate(src.rect.origin.to_f32().cast_unit().to_vector());
+    let src_box = src.rect.to_box2d().to_f32();
+    let prim = BlendPrimitive {
+      transform: transform.to_array(),
+      src_min: src_box.min.to_array(),
+      src_max: src_box.max.to_array(),
+      backdrop_offset: (-rect.origin.to_f32()).to_array(),
+      mode: mode as u32,
+      _dummy: [0; 3],
+    };
+
+    let src_tex = self.target_atlas.get_texture(src_id);
+    let dist_tex = self.rgba_atlas.get_texture_mut(dist.tex_id());
+    gpu.draw_blend_texture(dist_tex, &rect, src_tex, backdrop, &prim);
+
+    TextureSlice { tex_id: TextureID::Rgba(dist.tex_id()), rect }
+  }
+
   pub(super) fn texture(&self, tex_id: TextureID) -> &T { id_to_texture!(self, tex_id) }
 
   fn alpha_allocate(
@@ -394,6 +429,26 @@ where
     self.tess_task_buffer.indices.clear();
   }
 
+  pub(super) fn atlas_stats(&self) -> AtlasStats {
+    [self.alpha_atlas.stats(), self.rgba_atlas.stats(), self.target_atlas.stats()]
+      .into_iter()
+      .fold(AtlasStats::default(), |sum, s| AtlasStats {
+        used: sum.used + s.used,
+        capacity: sum.capacity + s.capacity,
+        evictions: sum.evictions + s.evictions,
+      })
+  }
+
+  pub(super) fn purge_unused(&mut self, gpu: &mut T::Host) {
+    if self.alpha_atlas.purge_unused(gpu) {
+      // The new texture only contains the relocated entries, the released areas
+      // not need to clear anymore.
+      self.need_clear_areas.clear();
+    }
+    self.rgba_atlas.purge_unused(gpu);
+    self.target_atlas.purge_unused(gpu);
+  }
+
   pub(crate) fn end_frame(&mut self) {
     self.alpha_atlas.end_frame_with(|rect| {
       self.need_clear_areas.push(rect);
@@ -573,4 +628,37 @@ pub mod tests {
       assert!(mgr.rgba_atlas.get(&red_img, 1.).is_none());
     }
   }
+
+  #[test]
+  fn rerasterize_on_scale_factor_change() {
+    let mut wgpu = block_on(WgpuImpl::headless());
+    let mut mgr = TexturesMgr::<WgpuTexture>::new(&mut wgpu);
+
+    // A glyph outline is a shared path.
+    let glyph = PaintPath::Share(Resource::new(Path::circle(Point::new(50., 50.), 50.)));
+    let viewport = rect(0, 0, 1024, 1024);
+    let mut draw_frame = |mgr: &mut TexturesMgr<WgpuTexture>, dpr: f32| {
+      let ts = Transform::scale(dpr, dpr);
+      let (slice, _) =
+        mgr.store_alpha_path(&glyph, &PaintingStyle::Fill, &ts, &viewport, &mut wgpu);
+      let rasterized = mgr.tess_task.len();
+      mgr.draw_alpha_textures(&mut wgpu);
+      mgr.end_frame();
+      (slice, rasterized)
+    };
+
+    let (slice_1x, rasterized) = draw_frame(&mut mgr, 1.);
+    assert_eq!(rasterized, 1);
+
+    // Moved to a monitor of the double scale factor, the glyph is rasterized
+    // again at the new scale.
+    let (slice_2x, rasterized) = draw_frame(&mut mgr, 2.);
+    assert_eq!(rasterized, 1);
+    assert!(slice_2x.rect.area() > slice_1x.rect.area());
+
+    // Moved back, the cached entry is reused without rasterizing.
+    let (slice, rasterized) = draw_frame(&mut mgr, 1.);
+    assert_eq!(rasterized, 0);
+    assert_eq!(slice, slice_2x);
+  }
 }
@@ -59,8 +59,15 @@ pub use wgpu_impl::*;
 ///   |     |  +------------------------------------+    |
 ///   |     |  | load_linear_gradient_primitives()  |    |
 ///   |     +->| load_linear_gradient_stops()       |    |
-///   |        | load_linear_gradient_vertices()    |    |
-///   |        | draw_linear_gradient_triangles()   |    |
+///   |     |  | load_linear_gradient_vertices()    |    |
+///   |     |  | draw_linear_gradient_triangles()   |    |
+///   |     |  +------------------------------------+    |
+///   |     |                                            |
+///   |     |  +------------------------------------+    |
+///   |     |  | load_path_gradient_primitives()    |    |
+///   |     +->| load_path_gradient_stops()         |    |
+///   |        | load_path_gradient_vertices()      |    |
+///   |        | draw_path_gradient_triangles()     |    |
 ///   |        +------------------------------------+    |
 ///   +---<----------------------------------------------+
 ///
@@ -131,6 +138,15 @@ pub trait GPUBackendImpl {
   /// Load the vertices and indices buffer that `draw_linear_gradient_triangles`
   /// will use.
   fn load_linear_gradient_vertices(&mut self, buffers: &VertexBuffers<LinearGradientPrimIndex>);
+
+  /// Load the primitives that `draw_path_gradient_triangles` will use.
+  fn load_path_gradient_primitives(&mut self, primitives: &[PathGradientPrimitive]);
+  /// Load the gradient color stops that `draw_path_gradient_triangles` will
+  /// use.
+  fn load_path_gradient_stops(&mut self, stops: &[GradientStopPrimitive]);
+  /// Load the vertices and indices buffer that `draw_path_gradient_triangles`
+  /// will use.
+  fn load_path_gradient_vertices(&mut self, buffers: &VertexBuffers<PathGradientAttr>);
   /// Draw pure color triangles in the texture. And use the clear color clear
   /// the texture first if it's a Some-Value
   fn draw_color_triangles(
@@ -153,14 +169,91 @@ pub trait GPUBackendImpl {
     &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
   );
 
+  /// Draw triangles fill with the gradient along the path length, the color
+  /// of a fragment is picked by its interpolated rate of the path length. And
+  /// use the clear color clear the texture first if it's a Some-Value
+  fn draw_path_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  );
+
   fn copy_texture_from_texture(
     &mut self, dist_tex: &mut Self::Texture, copy_to: DevicePoint, from_tex: &Self::Texture,
     from_rect: &DeviceRect,
   );
+
+  /// Draw the `src_tex` blended with the `backdrop` texture into the
+  /// `dist_rect` of the `dist_tex`, replace the original content. See
+  /// [`BlendPrimitive`] for how the pixels of the textures are located.
+  fn draw_blend_texture(
+    &mut self, dist_tex: &mut Self::Texture, dist_rect: &DeviceRect, src_tex: &Self::Texture,
+    backdrop: &Self::Texture, prim: &BlendPrimitive,
+  );
   /// A frame end, call once per frame
   fn end_frame(&mut self);
 }
 
+/// The anti-aliasing level used to rasterize the alpha mask of paths. The
+/// value is the number of samples taken for every pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u32)]
+pub enum AntiAliasing {
+  /// Only sample the center of the pixel, the edges are aliased.
+  None = 1,
+  Msaa2X = 2,
+  Msaa4X = 4,
+  /// A 3x2 sample pattern, it has a better horizontal resolution than the 4x
+  /// sample pattern, which is good for small text.
+  #[default]
+  Msaa6X = 6,
+  Msaa8X = 8,
+}
+
+impl AntiAliasing {
+  const LEVELS: [AntiAliasing; 5] = [
+    AntiAliasing::None,
+    AntiAliasing::Msaa2X,
+    AntiAliasing::Msaa4X,
+    AntiAliasing::Msaa6X,
+    AntiAliasing::Msaa8X,
+  ];
+
+  /// Return the anti-aliasing level of the sample count. If the count is not
+  /// supported, downgrade to the nearest lower level, a count less than 1 is
+  /// treated as 1.
+  pub fn from_sample_count(count: u32) -> Self {
+    let aa = Self::LEVELS
+      .into_iter()
+      .rev()
+      .find(|aa| aa.sample_count() <= count)
+      .unwrap_or(AntiAliasing::None);
+    if aa.sample_count() != count {
+      log::warn!("Sample count {count} is not supported, downgrade to {aa:?}.");
+    }
+    aa
+  }
+
+  /// The number of samples taken for every pixel.
+  pub fn sample_count(self) -> u32 { self as u32 }
+}
+
+/// The presentation mode of a window surface, it controls how the rendered
+/// frames sync with the display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PresentMode {
+  /// Wait for the vertical blanking period, the frame rate is capped to the
+  /// refresh rate of the display and there is no tearing. It's supported on
+  /// all platforms.
+  #[default]
+  AutoVsync,
+  /// Present the frame immediately, it has the lowest latency but may tear.
+  /// Falls back to `Mailbox` and then `AutoVsync` if not supported.
+  Immediate,
+  /// Replace the waiting frame with the new one, it has low latency and no
+  /// tearing, but keeps rendering frames that may never be shown. Falls back
+  /// to `AutoVsync` if not supported.
+  Mailbox,
+}
+
 /// Represents the sets of limits an GPU backend can provide in a single draw
 pub struct DrawPhaseLimits {
   /// The maximum size of the texture that the backend can create.
@@ -176,6 +269,9 @@ pub struct DrawPhaseLimits {
   /// The maximum number of linear gradient primitives that the backend can load
   /// in a single draw
   pub max_linear_gradient_primitives: usize,
+  /// The maximum number of path gradient primitives that the backend can load
+  /// in a single draw
+  pub max_path_gradient_primitives: usize,
   /// The maximum number of gradient stops that the backend can load in a single
   /// draw phase
   pub max_gradient_stop_primitives: usize,
@@ -204,6 +300,15 @@ pub struct RadialGradientPrimIndex(u32);
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct LinearGradientPrimIndex(u32);
 
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct PathGradientAttr {
+  /// The rate of the vertex along the path length.
+  pub rate: f32,
+  /// The index of the path gradient primitive.
+  pub prim_idx: u32,
+}
+
 #[repr(packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
 pub struct GradientStopPrimitive {
@@ -263,6 +368,21 @@ pub struct LinearGradientPrimitive {
   pub mask_head_and_spread: i32,
 }
 
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct PathGradientPrimitive {
+  /// The color stop information, there are two parts:
+  /// - The high 16-bit index represents the start index of the color stop.
+  /// - The low 16-bit index represents the size of the color stop.
+  pub stop: u32,
+  /// A mix of two 16-bit values:
+  /// - The high 16-bit index represents the head mask layer.
+  /// - The low 16-bit represents the spread method of the gradient. 0 for pad,
+  ///   1 for reflect and 2 for repeat
+  pub mask_head_and_spread: i32,
+  pub _dummy: [u32; 2],
+}
+
 #[repr(packed)]
 #[derive(AsBytes, PartialEq, Clone, Copy)]
 pub struct ImgPrimitive {
@@ -281,6 +401,25 @@ pub struct ImgPrimitive {
   pub opacity: f32,
 }
 
+#[repr(packed)]
+#[derive(AsBytes, PartialEq, Clone, Copy, Debug)]
+pub struct BlendPrimitive {
+  /// A 2x3 column-major matrix, transform a position of the dist texture to
+  /// the source texture position.
+  pub transform: [f32; 6],
+  /// The min position of the source slice in the source texture, the content
+  /// out of the slice is transparent.
+  pub src_min: [f32; 2],
+  /// The max position of the source slice in the source texture.
+  pub src_max: [f32; 2],
+  /// The offset to add to a position of the dist texture to get the backdrop
+  /// texture position.
+  pub backdrop_offset: [f32; 2],
+  /// The blend mode, the value of `BlendMode` as u32.
+  pub mode: u32,
+  pub _dummy: [u32; 3],
+}
+
 /// The mask layer describes an alpha channel layer that is used in the fragment
 /// shader to sample the alpha channel and apply it to the color.
 #[derive(AsBytes, Clone)]
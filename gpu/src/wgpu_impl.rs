@@ -13,13 +13,15 @@ use self::{
   draw_color_triangles_pass::DrawColorTrianglesPass,
   draw_img_triangles_pass::DrawImgTrianglesPass,
   draw_linear_gradient_pass::DrawLinearGradientTrianglesPass,
+  draw_path_gradient_pass::DrawPathGradientTrianglesPass,
   draw_radial_gradient_pass::DrawRadialGradientTrianglesPass,
-  texture_pass::{ClearTexturePass, CopyTexturePass},
+  texture_pass::{BlendTexturePass, ClearTexturePass, CopyTexturePass},
   uniform::Uniform,
 };
 use crate::{
-  ColorAttr, DrawPhaseLimits, GPUBackendImpl, GradientStopPrimitive, ImagePrimIndex, ImgPrimitive,
-  LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer, RadialGradientPrimIndex,
+  AntiAliasing, BlendPrimitive, ColorAttr, DrawPhaseLimits, GPUBackendImpl, GradientStopPrimitive,
+  ImagePrimIndex, ImgPrimitive, LinearGradientPrimIndex, LinearGradientPrimitive, MaskLayer,
+  PathGradientAttr, PathGradientPrimitive, PresentMode, RadialGradientPrimIndex,
   RadialGradientPrimitive, gpu_backend::Texture,
 };
 mod shaders;
@@ -30,6 +32,7 @@ mod draw_alpha_triangles_pass;
 mod draw_color_triangles_pass;
 mod draw_img_triangles_pass;
 mod draw_linear_gradient_pass;
+mod draw_path_gradient_pass;
 mod draw_radial_gradient_pass;
 mod texture_pass;
 
@@ -46,10 +49,12 @@ pub struct WgpuImpl {
   clear_tex_pass: ClearTexturePass,
   alpha_triangles_pass: DrawAlphaTrianglesPass,
   copy_tex_pass: Option<CopyTexturePass>,
+  blend_tex_pass: Option<BlendTexturePass>,
   color_triangles_pass: Option<DrawColorTrianglesPass>,
   img_triangles_pass: Option<DrawImgTrianglesPass>,
   radial_gradient_pass: Option<DrawRadialGradientTrianglesPass>,
   linear_gradient_pass: Option<DrawLinearGradientTrianglesPass>,
+  path_gradient_pass: Option<DrawPathGradientTrianglesPass>,
   texs_layout: wgpu::BindGroupLayout,
   textures_bind: Option<wgpu::BindGroup>,
   mask_layers_uniform: Uniform<MaskLayer>,
@@ -125,11 +130,28 @@ macro_rules! linear_gradient_pass {
   };
 }
 
+macro_rules! path_gradient_pass {
+  ($backend:ident) => {
+    $backend
+      .path_gradient_pass
+      .get_or_insert_with(|| {
+        DrawPathGradientTrianglesPass::new(
+          &$backend.device,
+          $backend.mask_layers_uniform.layout(),
+          &$backend.texs_layout,
+          &$backend.limits,
+        )
+      })
+  };
+}
+
 pub(crate) use command_encoder;
 
 pub struct Surface<'a> {
   surface: wgpu::Surface<'a>,
   config: wgpu::SurfaceConfiguration,
+  present_modes: Vec<wgpu::PresentMode>,
+  alpha_modes: Vec<wgpu::CompositeAlphaMode>,
   current_texture: Option<WgpuTexture>,
 }
 
@@ -216,6 +238,18 @@ impl GPUBackendImpl for WgpuImpl {
     linear_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
   }
 
+  fn load_path_gradient_primitives(&mut self, primitives: &[PathGradientPrimitive]) {
+    path_gradient_pass!(self).load_path_gradient_primitives(&self.queue, primitives);
+  }
+
+  fn load_path_gradient_stops(&mut self, stops: &[GradientStopPrimitive]) {
+    path_gradient_pass!(self).load_gradient_stops(&self.queue, stops);
+  }
+
+  fn load_path_gradient_vertices(&mut self, buffers: &VertexBuffers<PathGradientAttr>) {
+    path_gradient_pass!(self).load_triangles_vertices(buffers, &self.device, &self.queue);
+  }
+
   fn load_mask_layers(&mut self, layers: &[crate::MaskLayer]) {
     self
       .mask_layers_uniform
@@ -265,6 +299,24 @@ impl GPUBackendImpl for WgpuImpl {
     self.submit()
   }
 
+  fn draw_path_gradient_triangles(
+    &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
+  ) {
+    let encoder = command_encoder!(self);
+
+    path_gradient_pass!(self).draw_triangles(
+      texture,
+      indices,
+      clear,
+      &self.device,
+      encoder,
+      self.textures_bind.as_ref().unwrap(),
+      &self.mask_layers_uniform,
+    );
+
+    self.submit()
+  }
+
   fn draw_alpha_triangles_with_scissor(
     &mut self, indices: &Range<u32>, texture: &mut Self::Texture, scissor: DeviceRect,
   ) {
@@ -326,6 +378,13 @@ impl GPUBackendImpl for WgpuImpl {
     }
   }
 
+  fn draw_blend_texture(
+    &mut self, dist_tex: &mut Self::Texture, dist_rect: &DeviceRect, src_tex: &Self::Texture,
+    backdrop: &Self::Texture, prim: &BlendPrimitive,
+  ) {
+    self.draw_blend_texture_to_texture(dist_tex, dist_rect, src_tex, backdrop, prim);
+  }
+
   fn end_frame(&mut self) {
     self.submit();
     self.device.poll(wgpu::Maintain::Wait);
@@ -346,6 +405,49 @@ impl<'a> Surface<'a> {
     }
   }
 
+  /// Set the presentation mode of the surface, if the mode is not supported by
+  /// the surface, a fallback mode is chosen.
+  pub fn set_present_mode(&mut self, mode: PresentMode, backend: &WgpuImpl) {
+    let present_mode = select_present_mode(mode, &self.present_modes);
+    if present_mode != self.config.present_mode {
+      self.config.present_mode = present_mode;
+      if !self.size().is_empty() {
+        self
+          .surface
+          .configure(backend.device(), &self.config);
+      }
+    }
+  }
+
+  /// The wgpu presentation mode the surface actually uses.
+  pub fn present_mode(&self) -> wgpu::PresentMode { self.config.present_mode }
+
+  /// Set whether the surface is composited with the content behind the window
+  /// by its alpha. If the platform doesn't support a transparent surface, it
+  /// keeps opaque.
+  pub fn set_transparent(&mut self, transparent: bool, backend: &WgpuImpl) {
+    let alpha_mode = select_alpha_mode(transparent, &self.alpha_modes);
+    if alpha_mode != self.config.alpha_mode {
+      self.config.alpha_mode = alpha_mode;
+      if !self.size().is_empty() {
+        self
+          .surface
+          .configure(backend.device(), &self.config);
+      }
+    }
+  }
+
+  /// Return if the surface is composited by its alpha.
+  pub fn is_transparent(&self) -> bool {
+    matches!(
+      self.config.alpha_mode,
+      wgpu::CompositeAlphaMode::PreMultiplied | wgpu::CompositeAlphaMode::PostMultiplied
+    )
+  }
+
+  /// The wgpu alpha composite mode the surface actually uses.
+  pub fn alpha_mode(&self) -> wgpu::CompositeAlphaMode { self.config.alpha_mode }
+
   /// Get the size of the surface.
   pub fn size(&self) -> DeviceSize {
     DeviceSize::new(self.config.width as i32, self.config.height as i32)
@@ -366,6 +468,43 @@ impl<'a> Surface<'a> {
     }
   }
 }
+fn select_present_mode(mode: PresentMode, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+  use wgpu::PresentMode::*;
+  let candidates: &[wgpu::PresentMode] = match mode {
+    PresentMode::AutoVsync => &[Fifo],
+    PresentMode::Immediate => &[Immediate, Mailbox, Fifo],
+    PresentMode::Mailbox => &[Mailbox, Fifo],
+  };
+  // `Fifo` is guaranteed to be supported.
+  let present_mode = candidates
+    .iter()
+    .find(|m| supported.contains(m))
+    .copied()
+    .unwrap_or(Fifo);
+  if mode != PresentMode::AutoVsync && present_mode != candidates[0] {
+    log::warn!("Present mode {mode:?} is not supported, fallback to {present_mode:?}.");
+  }
+  present_mode
+}
+
+fn select_alpha_mode(
+  transparent: bool, supported: &[wgpu::CompositeAlphaMode],
+) -> wgpu::CompositeAlphaMode {
+  use wgpu::CompositeAlphaMode::*;
+  if !transparent {
+    return Auto;
+  }
+  // The content is blended on a transparent surface, so its color is already
+  // premultiplied by alpha.
+  let alpha_mode = [PreMultiplied, PostMultiplied]
+    .into_iter()
+    .find(|m| supported.contains(m));
+  alpha_mode.unwrap_or_else(|| {
+    log::warn!("Transparent window is not supported by the platform, fallback to opaque.");
+    Auto
+  })
+}
+
 pub struct WgpuTexture {
   inner_tex: InnerTexture,
   view: wgpu::TextureView,
@@ -543,6 +682,17 @@ impl WgpuImpl {
     (gpu_impl, surface.unwrap())
   }
 
+  /// The anti-aliasing level used to rasterize the paths.
+  pub fn anti_aliasing(&self) -> AntiAliasing { self.alpha_triangles_pass.anti_aliasing() }
+
+  /// Set the anti-aliasing level used to rasterize the paths, it takes effect
+  /// from the next draw.
+  pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) {
+    self
+      .alpha_triangles_pass
+      .set_anti_aliasing(anti_aliasing);
+  }
+
   #[allow(clippy::needless_lifetimes)]
   async fn create<'a>(target: Option<wgpu::SurfaceTarget<'a>>) -> (WgpuImpl, Option<Surface<'a>>) {
     let mut instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
@@ -600,7 +750,7 @@ impl WgpuImpl {
       ..Default::default()
     });
 
-    let alpha_triangles_pass = DrawAlphaTrianglesPass::new(&device);
+    let alpha_triangles_pass = DrawAlphaTrianglesPass::new(&device, AntiAliasing::default());
 
     let limits = device.limits();
     let uniform_bytes = limits
@@ -617,6 +767,7 @@ impl WgpuImpl {
       max_image_primitives: uniform_bytes / size_of::<ImgPrimitive>(),
       max_radial_gradient_primitives: uniform_bytes / size_of::<RadialGradientPrimitive>(),
       max_linear_gradient_primitives: uniform_bytes / size_of::<LinearGradientPrimitive>(),
+      max_path_gradient_primitives: uniform_bytes / size_of::<PathGradientPrimitive>(),
       max_gradient_stop_primitives: uniform_bytes / size_of::<GradientStopPrimitive>(),
       max_mask_layers: uniform_bytes / size_of::<MaskLayer>(),
     };
@@ -634,10 +785,12 @@ impl WgpuImpl {
       alpha_triangles_pass,
       clear_tex_pass,
       copy_tex_pass: None,
+      blend_tex_pass: None,
       color_triangles_pass: None,
       img_triangles_pass: None,
       radial_gradient_pass: None,
       linear_gradient_pass: None,
+      path_gradient_pass: None,
       texs_layout,
       textures_bind: None,
       mask_layers_uniform,
@@ -646,25 +799,29 @@ impl WgpuImpl {
 
     let surface = surface.map(|surface| {
       use wgpu::TextureFormat::*;
-      let format = surface
-        .get_capabilities(&adapter)
+      let capabilities = surface.get_capabilities(&adapter);
+      let format = capabilities
         .formats
         .into_iter()
         .find(|&f| f == Rgba8Unorm || f == Bgra8Unorm)
         .expect("No suitable format found for the surface!");
+      // Read back the surface content as the backdrop of the blend modes.
+      let read_back = wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::TEXTURE_BINDING;
 
       let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | (capabilities.usages & read_back),
         format,
         width: 0,
         height: 0,
-        present_mode: wgpu::PresentMode::Fifo,
+        present_mode: select_present_mode(PresentMode::default(), &capabilities.present_modes),
         alpha_mode: wgpu::CompositeAlphaMode::Auto,
         view_formats: vec![format],
         desired_maximum_frame_latency: 2,
       };
 
-      Surface { surface, config, current_texture: None }
+      let present_modes = capabilities.present_modes;
+      let alpha_modes = capabilities.alpha_modes;
+      Surface { surface, config, present_modes, alpha_modes, current_texture: None }
     });
 
     (gpu_impl, surface)
@@ -794,3 +951,39 @@ fn textures_bind(
     label: Some("textures bind group"),
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use wgpu::PresentMode::*;
+
+  use super::*;
+
+  #[test]
+  fn present_mode_fallback() {
+    let all = [Fifo, FifoRelaxed, Immediate, Mailbox];
+    assert_eq!(select_present_mode(PresentMode::AutoVsync, &all), Fifo);
+    assert_eq!(select_present_mode(PresentMode::Immediate, &all), Immediate);
+    assert_eq!(select_present_mode(PresentMode::Mailbox, &all), Mailbox);
+
+    let vsync_only = [Fifo];
+    assert_eq!(select_present_mode(PresentMode::AutoVsync, &vsync_only), Fifo);
+    assert_eq!(select_present_mode(PresentMode::Immediate, &vsync_only), Fifo);
+    assert_eq!(select_present_mode(PresentMode::Mailbox, &vsync_only), Fifo);
+
+    let no_immediate = [Fifo, Mailbox];
+    assert_eq!(select_present_mode(PresentMode::Immediate, &no_immediate), Mailbox);
+  }
+
+  #[test]
+  fn transparent_alpha_mode() {
+    use wgpu::CompositeAlphaMode::*;
+
+    let all = [Opaque, PreMultiplied, PostMultiplied, Inherit];
+    assert_eq!(select_alpha_mode(true, &all), PreMultiplied);
+    assert_eq!(select_alpha_mode(false, &all), Auto);
+    assert_eq!(select_alpha_mode(true, &[Opaque, PostMultiplied]), PostMultiplied);
+
+    // No compositing, fallback to opaque.
+    assert_eq!(select_alpha_mode(true, &[Opaque]), Auto);
+  }
+}
@@ -5,24 +5,23 @@ use ribir_painter::{Vertex, VertexBuffers};
 use wgpu::include_wgsl;
 
 use super::{uniform::Uniform, vertex_buffer::VerticesBuffer};
-use crate::WgpuTexture;
-
-pub const SAMPLE_COUNT: u32 = 6;
+use crate::{AntiAliasing, WgpuTexture};
 
 pub struct DrawAlphaTrianglesPass {
   vertices_buffer: VerticesBuffer<()>,
   pipeline: wgpu::RenderPipeline,
   size_uniform: Uniform<u32>,
+  anti_aliasing: AntiAliasing,
 }
 
 impl DrawAlphaTrianglesPass {
-  pub fn new(device: &wgpu::Device) -> Self {
+  pub fn new(device: &wgpu::Device, anti_aliasing: AntiAliasing) -> Self {
     let vertices_buffer = VerticesBuffer::new(2048, 4096, device);
     let shader = device.create_shader_module(include_wgsl!("./shaders/alpha_triangles.wgsl"));
-    // Although we only need 2 x u32, we use 4 x f32 to align with the 16-byte
-    // uniform buffer. This is because WebGL requires the buffer to be 16-byte
-    // aligned.
-    let size_uniform = Uniform::new(device, wgpu::ShaderStages::VERTEX, 4);
+    // We only need 3 x u32, the view size and the sample count, but we use 4 x
+    // u32 to align with the 16-byte uniform buffer. This is because WebGL
+    // requires the buffer to be 16-byte aligned.
+    let size_uniform = Uniform::new(device, wgpu::ShaderStages::VERTEX_FRAGMENT, 4);
     let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
       label: Some("Alpha triangles pipeline layout"),
       bind_group_layouts: &[size_uniform.layout()],
@@ -77,7 +76,13 @@ impl DrawAlphaTrianglesPass {
       multiview: None,
     });
 
-    Self { vertices_buffer, pipeline, size_uniform }
+    Self { vertices_buffer, pipeline, size_uniform, anti_aliasing }
+  }
+
+  pub fn anti_aliasing(&self) -> AntiAliasing { self.anti_aliasing }
+
+  pub fn set_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) {
+    self.anti_aliasing = anti_aliasing;
   }
 
   pub fn load_alpha_vertices(
@@ -93,9 +98,11 @@ impl DrawAlphaTrianglesPass {
     queue: &wgpu::Queue, encoder: &mut wgpu::CommandEncoder,
   ) {
     let color_attachments = texture.color_attachments(None);
+    let size = texture.size().to_u32();
+    let sample_count = self.anti_aliasing.sample_count();
     self
       .size_uniform
-      .write_buffer(queue, &texture.size().to_u32().to_array());
+      .write_buffer(queue, &[size.width, size.height, sample_count, 0]);
 
     let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
       label: Some("Alpha triangles render pass"),
@@ -117,6 +124,6 @@ impl DrawAlphaTrianglesPass {
       );
     }
     rpass.set_pipeline(&self.pipeline);
-    rpass.draw_indexed(indices.clone(), 0, 0..SAMPLE_COUNT)
+    rpass.draw_indexed(indices.clone(), 0, 0..sample_count)
   }
 }
@@ -325,6 +325,143 @@ fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
 "#
 }
 
+pub fn path_gradient_shader(limits: &DrawPhaseLimits) -> String {
+  basic_template(limits.max_mask_layers)
+    + &format!(
+      r#"
+@group(2) @binding(0)
+var<uniform> prims: array<Primitive, {}>;
+
+@group(3) @binding(0)
+var<uniform> stops: array<StopPair, {}>;"#,
+      limits.max_path_gradient_primitives,
+      limits.max_gradient_stop_primitives / 2,
+    )
+    + r#"
+struct Vertex {
+  @location(0) pos: vec2<f32>,
+  @location(1) rate: f32,
+  @location(2) prim_idx: u32,
+};
+
+struct FragInput {
+  @builtin(position) pos: vec4<f32>,
+  @location(0) rate: f32,
+  @location(1) @interpolate(flat) prim_idx: u32,
+}
+
+@vertex
+fn vs_main(v: Vertex) -> FragInput {
+    var input: FragInput;
+    // convert from gpu-backend coords(0..1) to wgpu corrds(-1..1)
+    let pos = v.pos * vec2(2., -2.) + vec2(-1., 1.);
+    input.pos = vec4<f32>(pos, 0.0, 1.0);
+    input.rate = v.rate;
+    input.prim_idx = v.prim_idx;
+    return input;
+}
+
+// A pair of stops. This arrangement aligns the stops with 16 bytes, minimizing excessive padding.
+struct StopPair {
+    color1: u32,
+    offset1: f32,
+    color2: u32,
+    offset2: f32,
+}
+
+struct Stop {
+    color: vec4<f32>,
+    offset: f32,
+}
+
+struct Primitive {
+  // A value mixed stop_start(u16) and stop_cnt(u16)
+  stop: u32,
+  // A value mixed mask_head(i16) and spread(u16)
+  mask_head_and_spread: i32,
+  dummy1: u32,
+  dummy2: u32,
+}
+
+fn unpackUnorm4x8(color: u32) -> vec4<f32> {
+    return vec4<f32>(
+        f32((color & 0xff000000) >> 24) / 255.0,
+        f32((color & 0x00ff0000) >> 16) / 255.0,
+        f32((color & 0x0000ff00) >> 8) / 255.0,
+        f32((color & 0x000000ff) >> 0) / 255.0
+    );
+}
+
+fn get_stop(idx: u32) -> Stop {
+    let pair = stops[idx / 2];
+    if idx % 2 == 0 {
+        return Stop(unpackUnorm4x8(pair.color1), pair.offset1);
+    } else {
+        return Stop(unpackUnorm4x8(pair.color2), pair.offset2);
+    }
+}
+
+fn lerp_stop(prev: Stop, next: Stop, offset: f32) -> vec4<f32> {
+    let dist = next.offset - prev.offset;
+    var weight = 1.;
+    if dist > 0. {
+        weight = clamp((offset - prev.offset) / dist, 0., 1.);
+    }
+    return mix(prev.color, next.color, weight);
+}
+
+@fragment
+fn fs_main(input: FragInput) -> @location(0) vec4<f32> {
+    let prim = prims[input.prim_idx];
+
+    var alpha = 1.;
+    var mask_idx = prim.mask_head_and_spread >> 16;
+    loop {
+        if mask_idx < 0 { break; }
+
+        let mask = mask_layers[u32(mask_idx)];
+        alpha *= mask_sample(mask, input.pos.xy);
+        mask_idx = mask.prev_mask_idx;
+    }
+
+    // The rate is always in the path, pad and reflect are the same.
+    let offset = clamp(input.rate, 0., 1.);
+    let wrap = abs(prim.mask_head_and_spread & 0x0000ffff) == 2;
+    let stop_start = prim.stop >> 16;
+    let stop_cnt = prim.stop & 0x0000ffff;
+    let first = get_stop(stop_start);
+    let last = get_stop(stop_start + stop_cnt - 1);
+
+    var color: vec4<f32>;
+    if offset <= first.offset {
+        if wrap {
+            // Repeat interpolates from the last stop to the first one, so the
+            // gradient wraps seamlessly on a closed path.
+            color = lerp_stop(Stop(last.color, last.offset - 1.), first, offset);
+        } else {
+            color = first.color;
+        }
+    } else if last.offset < offset {
+        if wrap {
+            color = lerp_stop(last, Stop(first.color, first.offset + 1.), offset);
+        } else {
+            color = last.color;
+        }
+    } else {
+        var prev = first;
+        var next = get_stop(stop_start + 1);
+        for (var i = 2u; i < stop_cnt && next.offset < offset; i++) {
+            prev = next;
+            next = get_stop(stop_start + i);
+        }
+        color = lerp_stop(prev, next, offset);
+    }
+
+    return color * vec4<f32>(1., 1., 1., alpha);
+}
+"#
+}
+
 pub fn color_triangles_shader(max_mask_layers: usize) -> String {
   basic_template(max_mask_layers)
     + r#"
@@ -22,22 +22,23 @@ fn vs_main(@location(0) pos: vec2<f32>, @builtin(instance_index) instance: u32)
   // This is an experiment and hasn't been extensively tested. 
   // If we encounter problems or if it performs worse than the 4x sample pattern,
   //  we can easily revert to the 4x sample pattern.
+
+  // The `view_size.z` is the sample count, every instance draws one sample. All
+  // the sample patterns are stored in one array, a pattern starts at the sum of
+  // the smaller sample counts.
   var sample_pattern = array(
-    // 4x sample pattern
-    // vec2(-6.0, 2.0) / 16.0,
-    // vec2(-2.0, -6.0) / 16.0,
-    // vec2(2.0, 6.0) / 16.0
-    // vec2(6.0, -2.0) / 16.0,
+    // 1x sample pattern
+    vec2(0., 0.),
 
-    // 8x sample pattern
-    // vec2( -7.,  -1.) / 16.,
-    // vec2( -5.,  5.) / 16.,
-    // vec2( -3.,  -5.) / 16.,
-    // vec2( -1.,  3.) / 16.,
-    // vec2(1., -3.) / 16.,
-    // vec2( 3.,  7.) / 16.,
-    // vec2( 5., 1.) / 16.,
-    // vec2( 7.,  -7.) / 16.
+    // 2x sample pattern
+    vec2(4., 4.) / 16.,
+    vec2(-4., -4.) / 16.,
+
+    // 4x sample pattern
+    vec2(-6.0, 2.0) / 16.0,
+    vec2(-2.0, -6.0) / 16.0,
+    vec2(2.0, 6.0) / 16.0,
+    vec2(6.0, -2.0) / 16.0,
 
     // 3x2 sample pattern
     vec2(-8., -1.) / vec2(18., 12.),
@@ -45,17 +46,37 @@ fn vs_main(@location(0) pos: vec2<f32>, @builtin(instance_index) instance: u32)
     vec2(-2., -3.) / vec2(18., 12.),
     vec2(2., 3.) / vec2(18., 12.),
     vec2(5., 1.) / vec2(18., 12.),
-    vec2(8., -5) / vec2(18., 12.)
+    vec2(8., -5) / vec2(18., 12.),
+
+    // 8x sample pattern
+    vec2( -7.,  -1.) / 16.,
+    vec2( -5.,  5.) / 16.,
+    vec2( -3.,  -5.) / 16.,
+    vec2( -1.,  3.) / 16.,
+    vec2(1., -3.) / 16.,
+    vec2( 3.,  7.) / 16.,
+    vec2( 5., 1.) / 16.,
+    vec2( 7.,  -7.) / 16.
   );
 
-    let size = vec2(f32(view_size.x), f32(view_size.y));
-    var sample_pos = pos + sample_pattern[instance % 6];
-    sample_pos = sample_pos * vec2(2., -2.)  / size + vec2(-1., 1.);
-    return vec4<f32>(sample_pos, 0.0, 1.0);
+  let count = view_size.z;
+  var start = 0u;
+  switch count {
+    case 2u: { start = 1u; }
+    case 4u: { start = 3u; }
+    case 6u: { start = 7u; }
+    case 8u: { start = 13u; }
+    default: { start = 0u; }
+  }
+
+  let size = vec2(f32(view_size.x), f32(view_size.y));
+  var sample_pos = pos + sample_pattern[start + instance % count];
+  sample_pos = sample_pos * vec2(2., -2.)  / size + vec2(-1., 1.);
+  return vec4<f32>(sample_pos, 0.0, 1.0);
 }
 
 @fragment
 fn fs_main() -> @location(0) vec4<f32> {
-  let value: f32 = 1.0 / 6.0;
+  let value: f32 = 1.0 / f32(view_size.z);
   return vec4(value, value, value, value);
 }
@@ -0,0 +1,70 @@
+struct BlendPrimitive {
+  t0: vec2<f32>,
+  t1: vec2<f32>,
+  t2: vec2<f32>,
+  src_min: vec2<f32>,
+  src_max: vec2<f32>,
+  backdrop_offset: vec2<f32>,
+  mode: u32,
+  _dummy0: u32,
+  _dummy1: u32,
+  _dummy2: u32,
+}
+
+@group(0) @binding(0)
+var src_tex: texture_2d<f32>;
+@group(0) @binding(1)
+var backdrop_tex: texture_2d<f32>;
+@group(0) @binding(2)
+var s_sampler: sampler;
+@group(1) @binding(0)
+var<uniform> prim: BlendPrimitive;
+
+@vertex
+fn vs_main(@location(0) pos: vec2<f32>) -> @builtin(position) vec4<f32> {
+    return vec4(pos * vec2(2., -2.) + vec2(-1., 1.), 0., 1.);
+}
+
+@fragment
+fn fs_main(@builtin(position) pos: vec4<f32>) -> @location(0) vec4<f32> {
+    let src_pos = mat3x2(prim.t0, prim.t1, prim.t2) * vec3(pos.xy, 1.);
+    var src = vec4(0.);
+    if all(prim.src_min <= src_pos) && all(src_pos <= prim.src_max) {
+        let size = textureDimensions(src_tex);
+        let tex_size = vec2(f32(size.x), f32(size.y));
+        src = textureSampleLevel(src_tex, s_sampler, src_pos / tex_size, 0.);
+    }
+    let backdrop = textureLoad(backdrop_tex, vec2<i32>(pos.xy + prim.backdrop_offset), 0);
+
+    // The source and the backdrop are painted on transparent textures, their
+    // colors are premultiplied.
+    let cs = unpremultiply(src);
+    let cb = unpremultiply(backdrop);
+    let color = (1. - backdrop.a) * cs + backdrop.a * blend(cb, cs);
+    // The result is drawn by the image pass that blends with the source alpha,
+    // so return the straight color.
+    return vec4(color, src.a);
+}
+
+fn unpremultiply(c: vec4<f32>) -> vec3<f32> {
+    if c.a > 0. {
+        return c.rgb / c.a;
+    }
+    return vec3(0.);
+}
+
+fn blend(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    switch prim.mode {
+        // multiply
+        case 1u: { return cb * cs; }
+        // screen
+        case 2u: { return cb + cs - cb * cs; }
+        // overlay
+        case 3u: {
+            let multiply = 2. * cb * cs;
+            let screen = 1. - 2. * (1. - cb) * (1. - cs);
+            return select(screen, multiply, cb <= vec3(0.5));
+        }
+        default: { return cs; }
+    }
+}
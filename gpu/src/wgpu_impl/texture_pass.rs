@@ -2,11 +2,13 @@ use std::mem::size_of;
 
 use ribir_geom::{DevicePoint, DeviceRect, DeviceSize, rect_corners};
 use ribir_painter::Vertex;
-use wgpu::{StoreOp, include_wgsl};
+use wgpu::{StoreOp, include_wgsl, util::DeviceExt};
 use zerocopy::AsBytes;
 
-use super::vertex_buffer::new_vertices;
-use crate::{WgpuImpl, WgpuTexture, command_encoder, gpu_backend::Texture, vertices_coord};
+use super::{uniform::Uniform, vertex_buffer::new_vertices};
+use crate::{
+  BlendPrimitive, WgpuImpl, WgpuTexture, command_encoder, gpu_backend::Texture, vertices_coord,
+};
 
 pub struct CopyTexturePass {
   pipeline: Option<wgpu::RenderPipeline>,
@@ -89,7 +91,6 @@ pub struct ClearTexturePass {
   shader: wgpu::ShaderModule,
   layout: wgpu::PipelineLayout,
   format: Option<wgpu::TextureFormat>,
-  vertices_buffer: wgpu::Buffer,
 }
 
 impl ClearTexturePass {
@@ -102,8 +103,7 @@ impl ClearTexturePass {
       push_constant_ranges: &[],
     });
 
-    let vertices_buffer = new_vertices::<()>(device, 256);
-    Self { pipeline: None, shader, format: None, layout, vertices_buffer }
+    Self { pipeline: None, shader, format: None, layout }
   }
 
   pub fn update(&mut self, format: wgpu::TextureFormat, device: &wgpu::Device) {
@@ -132,7 +132,144 @@ impl ClearTexturePass {
   }
 }
 
+pub struct BlendTexturePass {
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  layout: wgpu::PipelineLayout,
+  bind_layout: wgpu::BindGroupLayout,
+  prim_uniform: Uniform<BlendPrimitive>,
+  format: Option<wgpu::TextureFormat>,
+  vertices_buffer: wgpu::Buffer,
+}
+
+impl BlendTexturePass {
+  pub fn new(device: &wgpu::Device) -> Self {
+    let shader = device.create_shader_module(include_wgsl!("./shaders/blend_texture.wgsl"));
+
+    let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+      binding,
+      visibility: wgpu::ShaderStages::FRAGMENT,
+      ty: wgpu::BindingType::Texture {
+        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+        view_dimension: wgpu::TextureViewDimension::D2,
+        multisampled: false,
+      },
+      count: None,
+    };
+    let bind_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      entries: &[texture_entry(0), texture_entry(1), wgpu::BindGroupLayoutEntry {
+        binding: 2,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+        count: None,
+      }],
+      label: Some("Blend texture"),
+    });
+    let prim_uniform = Uniform::new(device, wgpu::ShaderStages::FRAGMENT, 1);
+
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Blend texture"),
+      bind_group_layouts: &[&bind_layout, prim_uniform.layout()],
+      push_constant_ranges: &[],
+    });
+    let vertices_buffer = new_vertices::<()>(device, 4);
+    Self {
+      pipeline: None,
+      shader,
+      layout,
+      bind_layout,
+      prim_uniform,
+      format: None,
+      vertices_buffer,
+    }
+  }
+
+  pub fn update(&mut self, format: wgpu::TextureFormat, device: &wgpu::Device) {
+    if Some(format) != self.format {
+      self.format = Some(format);
+      self.pipeline.take();
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = tex_render_pipeline::<()>(
+        "Blend texture",
+        device,
+        &self.layout,
+        &self.shader,
+        &[wgpu::VertexAttribute {
+          offset: 0,
+          shader_location: 0,
+          format: wgpu::VertexFormat::Float32x2,
+        }],
+        format,
+        wgpu::PrimitiveTopology::TriangleStrip,
+      );
+      self.pipeline = Some(pipeline);
+    }
+  }
+}
+
 impl WgpuImpl {
+  pub(crate) fn draw_blend_texture_to_texture(
+    &mut self, dist_tex: &WgpuTexture, dist_rect: &DeviceRect, src_tex: &WgpuTexture,
+    backdrop: &WgpuTexture, prim: &BlendPrimitive,
+  ) {
+    self.finish_command();
+
+    let Self { blend_tex_pass, device, queue, sampler, .. } = self;
+    let pass = blend_tex_pass.get_or_insert_with(|| BlendTexturePass::new(device));
+    pass.update(dist_tex.format(), device);
+
+    let [lt, rt, rb, lb] = vertices_corners(dist_rect, Texture::size(dist_tex));
+    queue.write_buffer(&pass.vertices_buffer, 0, [lt, lb, rt, rb].as_bytes());
+    pass
+      .prim_uniform
+      .write_buffer(queue, std::slice::from_ref(prim));
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+      layout: &pass.bind_layout,
+      entries: &[
+        wgpu::BindGroupEntry {
+          binding: 0,
+          resource: wgpu::BindingResource::TextureView(src_tex.view()),
+        },
+        wgpu::BindGroupEntry {
+          binding: 1,
+          resource: wgpu::BindingResource::TextureView(backdrop.view()),
+        },
+        wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(sampler) },
+      ],
+      label: Some("Blend texture bind group"),
+    });
+
+    let color_attachments = wgpu::RenderPassColorAttachment {
+      view: dist_tex.view(),
+      resolve_target: None,
+      ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: StoreOp::Store },
+    };
+
+    let encoder = command_encoder!(self);
+    let pass = self.blend_tex_pass.as_ref().unwrap();
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("Blend texture"),
+      color_attachments: &[Some(color_attachments)],
+      depth_stencil_attachment: None,
+      timestamp_writes: None,
+      occlusion_query_set: None,
+    });
+
+    rpass.set_vertex_buffer(0, pass.vertices_buffer.slice(..));
+    rpass.set_bind_group(0, &bind_group, &[]);
+    rpass.set_bind_group(1, pass.prim_uniform.bind_group(), &[]);
+    rpass.set_pipeline(pass.pipeline.as_ref().unwrap());
+    rpass.draw(0..4, 0..1);
+    drop(rpass);
+
+    // The vertices and the uniform buffers are shared by every blend, submit now
+    // to avoid the next blend overwriting them before this one is executed.
+    self.submit();
+  }
+
   pub(crate) fn draw_texture_to_texture(
     &mut self, dist_tex: &WgpuTexture, dist_at: DevicePoint, from_tex: &WgpuTexture,
     src_rect: &DeviceRect,
@@ -207,9 +344,7 @@ impl WgpuImpl {
   }
 
   pub(crate) fn clear_tex_areas(&mut self, clear_areas: &[DeviceRect], tex: &WgpuTexture) {
-    self.finish_command();
-
-    let Self { clear_tex_pass: pass, device, queue, .. } = self;
+    let Self { clear_tex_pass: pass, device, .. } = self;
     pass.update(tex.format(), device);
 
     let tex_size = tex.size();
@@ -224,12 +359,13 @@ impl WgpuImpl {
       vertices.push(d_lt);
     }
 
-    let vertices_data = vertices.as_bytes();
-    if pass.vertices_buffer.size() < vertices_data.len() as wgpu::BufferAddress {
-      pass.vertices_buffer = new_vertices::<()>(device, vertices.len());
-    }
-
-    queue.write_buffer(&pass.vertices_buffer, 0, vertices_data);
+    // Every clear has its own vertices buffer, so the clears are batched into
+    // the frame encoder without overwriting the vertices of each other.
+    let vertices_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+      label: Some("Clear texture areas vertices buffer"),
+      contents: vertices.as_bytes(),
+      usage: wgpu::BufferUsages::VERTEX,
+    });
 
     let color_attachments = wgpu::RenderPassColorAttachment {
       view: tex.view(),
@@ -246,7 +382,7 @@ impl WgpuImpl {
       occlusion_query_set: None,
     });
 
-    rpass.set_vertex_buffer(0, pass.vertices_buffer.slice(..));
+    rpass.set_vertex_buffer(0, vertices_buffer.slice(..));
     rpass.set_pipeline(pass.pipeline.as_ref().unwrap());
     rpass.draw(0..vertices.len() as u32, 0..1);
   }
@@ -1,13 +1,16 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
-  AngleBracketedGenericArguments, DataEnum, Field, Fields, FieldsNamed, FieldsUnnamed,
-  GenericArgument, Index, PathArguments, PathSegment, Type, parse_quote, punctuated::Pair,
-  spanned::Spanned, token::Comma,
+  AngleBracketedGenericArguments, DataEnum, Field, Fields, FieldsUnnamed, GenericArgument,
+  GenericParam, Index, PathArguments, PathSegment, Type, parse_quote, spanned::Spanned,
 };
 
+use crate::simple_declare_attr::DefaultMeta;
+
 const BUILDER: &str = "Builder";
 const TEMPLATE: &str = "Template";
+const TEMPLATE_ATTR: &str = "template";
+
 fn with_child_generics(generics: &syn::Generics, child_ty: &Type) -> syn::Generics {
   let mut gen = generics.clone();
   gen.params.push(parse_quote!('_c));
@@ -27,9 +30,40 @@ pub(crate) fn derive_child_template(input: &mut syn::DeriveInput) -> syn::Result
   let syn::DeriveInput { vis, ident: name, generics, data, .. } = input;
   let (g_impl, g_ty, g_where) = generics.split_for_impl();
   let builder = Ident::new(&format!("{name}{BUILDER}"), name.span());
+
+  let fields = match data {
+    syn::Data::Struct(stt) => {
+      if matches!(stt.fields, Fields::Unit) {
+        let err_str = format!("Can't derive `{TEMPLATE}` for a empty template.",);
+        return Err(syn::Error::new(Span::call_site(), err_str));
+      }
+      stt
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| TmlField::new(idx, f))
+        .collect::<syn::Result<Vec<_>>>()?
+    }
+    syn::Data::Enum(_) => vec![],
+    syn::Data::Union(u) => {
+      let err_str = format!("`{TEMPLATE}` not support for Union");
+      return Err(syn::Error::new(u.union_token.span(), err_str));
+    }
+  };
+
+  // Every required field has a const flag in the builder to track if it's
+  // filled, so the template can only be built after all the required fields are
+  // filled.
+  let flags: Vec<_> = (0..fields.iter().filter(|f| f.is_required()).count())
+    .map(|i| Ident::new(&format!("_R{i}"), Span::call_site()))
+    .collect();
+  let builder_ty = BuilderType::new(&builder, generics);
+  let unfilled_ty = builder_ty.with_flags(flags.iter().map(|_| quote!(false)));
+  let filled_ty = builder_ty.with_flags(flags.iter().map(|_| quote!(true)));
+
   let mut tokens = quote! {
     impl #g_impl Template for #name #g_ty #g_where {
-      type Builder = #builder #g_ty;
+      type Builder = #unfilled_ty;
 
       #[inline]
       fn builder() -> Self::Builder {  <_>::default() }
@@ -37,118 +71,101 @@ pub(crate) fn derive_child_template(input: &mut syn::DeriveInput) -> syn::Result
 
     impl #g_impl ChildOfCompose for #name #g_ty {}
 
-    impl #g_impl ComposeChildFrom<#builder #g_ty, 1> for #name #g_ty {
+    impl #g_impl ComposeChildFrom<#filled_ty, 1> for #name #g_ty #g_where {
       #[inline]
-      fn compose_child_from(from: #builder #g_ty) -> Self { from.build_tml() }
+      fn compose_child_from(from: #filled_ty) -> Self { from.build_tml() }
     }
   };
   match data {
     syn::Data::Struct(stt) => {
+      let any_ty = builder_ty.with_flags(flags.iter().map(|f| quote!(#f)));
+      let flag_generics = with_flag_generics(generics, &flags, false);
+      let (flag_g_impl, _, _) = flag_generics.split_for_impl();
       tokens.extend(quote! {
         impl #g_impl Declare for #name #g_ty #g_where {
-          type Builder = #builder #g_ty;
+          type Builder = #unfilled_ty;
           #[inline]
           fn declarer() -> Self::Builder { #name::builder() }
         }
 
-        impl #g_impl ObjDeclarer for #builder #g_ty {
+        impl #flag_g_impl ObjDeclarer for #any_ty #g_where {
           type Target = Self;
           #[inline]
           fn finish(self) -> Self { self }
         }
       });
 
-      let with_child_impl = |f_idx: usize, f: &mut Field, tokens: &mut TokenStream| {
-        let field_name = if let Some(name) = f.ident.as_ref() {
-          quote! {#name}
-        } else {
-          let f_idx = Index::from(f_idx);
-          quote!(#f_idx)
-        };
+      let members: Vec<_> = fields.iter().map(|f| &f.member).collect();
+      let rebuild = match &stt.fields {
+        Fields::Named(_) => quote! { #builder { #(#members: self.#members),* } },
+        _ => quote! { #builder(#(self.#members),*) },
+      };
+
+      let mut flag_idx = 0;
+      for (f_idx, f) in fields.iter().enumerate() {
+        let field_name = &f.member;
         let ty = option_type_extract(&f.ty).unwrap_or(&f.ty);
 
-        let gen = with_child_generics(generics, ty);
+        let gen = with_child_generics(&flag_generics, ty);
         let (g_impl, _, g_where) = gen.split_for_impl();
+        let fill = quote! {
+          assert!(self.#field_name.is_none(), "Try to fill same type twice.");
+          self.#field_name = Some(c.into_child_compose());
+        };
+        let (target, body) = if f.is_required() {
+          let target = builder_ty.with_flags(
+            flags.iter().enumerate().map(
+              |(i, flag)| {
+                if i == flag_idx { quote!(true) } else { quote!(#flag) }
+              },
+            ),
+          );
+          flag_idx += 1;
+          (target, quote! { #fill #rebuild })
+        } else {
+          (quote!(Self), quote! { #fill self })
+        };
         tokens.extend(quote! {
           impl #g_impl ComposeWithChild<'_c, _C, false, true, {#f_idx + 1}, _M>
-            for #builder #g_ty #g_where
+            for #any_ty #g_where
           {
-            type Target = Self;
+            type Target = #target;
             #[track_caller]
             fn with_child(mut self, c: _C) -> Self::Target {
-              assert!(self.#field_name.is_none(), "Try to fill same type twice.");
-              self.#field_name = Some(c.into_child_compose());
-              self
+              #body
             }
           }
         });
-      };
+      }
 
-      match &mut stt.fields {
-        Fields::Named(FieldsNamed { named: fields, .. }) => {
-          fields
-            .iter_mut()
-            .enumerate()
-            .for_each(|(f_idx, f)| with_child_impl(f_idx, f, &mut tokens));
-          let builder_fields = fields
-            .clone()
-            .into_pairs()
-            .map(convert_to_builder_pair);
-          tokens.extend(quote! {
-            #[derive(Default)]
-            #vis struct #builder #g_impl #g_where {
-              #(#builder_fields)*
-            }
-          });
-
-          let init_values = fields.iter().map(|field| {
-            let field_name = field.ident.as_ref().unwrap();
-            let ty = &field.ty;
-            let value = gen_init_value_tokens(quote!(#field_name), ty);
-            quote! {#field_name: #value}
-          });
-
-          tokens.extend(quote! {
-            impl #g_impl TemplateBuilder for #builder #g_ty #g_where {
-              type Target = #name #g_ty;
-              #[inline]
-              #[track_caller]
-              fn build_tml(self) -> Self::Target {#name { #(#init_values),* }}
-            }
-          });
-        }
-        Fields::Unnamed(FieldsUnnamed { unnamed: fields, .. }) => {
-          fields
-            .iter_mut()
-            .enumerate()
-            .for_each(|(f_idx, f)| with_child_impl(f_idx, f, &mut tokens));
-          let builder_fields = fields
-            .clone()
-            .into_pairs()
-            .map(convert_to_builder_pair);
-          tokens.extend(quote! {
-            #[derive(Default)]
-            #vis struct #builder #g_impl #g_where(#(#builder_fields)*);
-          });
-
-          let init_values = fields.iter().enumerate().map(|(idx, field)| {
-            let idx = Index::from(idx);
-            gen_init_value_tokens(quote!(#idx), &field.ty)
-          });
-
-          tokens.extend(quote! {
-            impl #g_impl TemplateBuilder for #builder #g_ty #g_where {
-              type Target = #name #g_ty;
-              #[track_caller]
-              fn build_tml(self) -> Self::Target {#name(#(#init_values),* ) }
-            }
-          });
-        }
-        Fields::Unit => {
-          let err_str = format!("Can't derive `{TEMPLATE}` for a empty template.",);
-          return Err(syn::Error::new(Span::call_site(), err_str));
-        }
-      };
+      let struct_generics = with_flag_generics(generics, &flags, true);
+      let builder_fields = fields.iter().map(|f| &f.builder_field);
+      let init_values = fields.iter().map(TmlField::init_value);
+      match &stt.fields {
+        Fields::Named(_) => tokens.extend(quote! {
+          #[derive(Default)]
+          #vis struct #builder #struct_generics #g_where {
+            #(#builder_fields),*
+          }
+
+          impl #g_impl TemplateBuilder for #filled_ty #g_where {
+            type Target = #name #g_ty;
+            #[inline]
+            #[track_caller]
+            fn build_tml(self) -> Self::Target {#name { #(#members: #init_values),* }}
+          }
+        }),
+        _ => tokens.extend(quote! {
+          #[derive(Default)]
+          #vis struct #builder #struct_generics(#(#builder_fields),*) #g_where;
+
+          impl #g_impl TemplateBuilder for #filled_ty #g_where {
+            type Target = #name #g_ty;
+            #[track_caller]
+            fn build_tml(self) -> Self::Target {#name(#(#init_values),* ) }
+          }
+        }),
+      }
 
       Ok(tokens)
     }
@@ -194,11 +211,127 @@ pub(crate) fn derive_child_template(input: &mut syn::DeriveInput) -> syn::Result
       });
       Ok(tokens)
     }
-    syn::Data::Union(u) => {
-      let err_str = format!("`{TEMPLATE}` not support for Union");
-      Err(syn::Error::new(u.union_token.span(), err_str))
+    syn::Data::Union(_) => unreachable!(),
+  }
+}
+
+/// A field of the template struct.
+struct TmlField {
+  member: TokenStream,
+  ty: Type,
+  default: Option<TokenStream>,
+  /// The field in the builder, it's always an `Option` and without the
+  /// `template` attribute.
+  builder_field: Field,
+}
+
+impl TmlField {
+  fn new(idx: usize, field: &Field) -> syn::Result<Self> {
+    let member = if let Some(name) = field.ident.as_ref() {
+      quote! {#name}
+    } else {
+      let idx = Index::from(idx);
+      quote!(#idx)
+    };
+
+    let mut default = None;
+    for attr in field
+      .attrs
+      .iter()
+      .filter(|attr| attr.path().is_ident(TEMPLATE_ATTR))
+    {
+      let meta: DefaultMeta = attr.parse_args()?;
+      if option_type_extract(&field.ty).is_some() {
+        let err = "An optional child is `None` by default, it can't have a default value.";
+        return Err(syn::Error::new(attr.span(), err));
+      }
+      default = Some(if let Some(value) = meta.value {
+        quote! { From::from(#value) }
+      } else {
+        quote! { <_>::default() }
+      });
+    }
+
+    let mut builder_field = field.clone();
+    builder_field
+      .attrs
+      .retain(|attr| !attr.path().is_ident(TEMPLATE_ATTR));
+    let ty = &field.ty;
+    if option_type_extract(ty).is_none() {
+      builder_field.ty = parse_quote!(Option<#ty>);
+    }
+
+    Ok(Self { member, ty: field.ty.clone(), default, builder_field })
+  }
+
+  /// A required field must be filled before building the template, it's not an
+  /// `Option` and has no default value.
+  fn is_required(&self) -> bool {
+    self.default.is_none() && option_type_extract(&self.ty).is_none()
+  }
+
+  fn init_value(&self) -> TokenStream {
+    let Self { member, ty, default, .. } = self;
+    if let Some(default) = default {
+      quote! { self.#member.unwrap_or_else(|| #default) }
+    } else if option_type_extract(ty).is_none() {
+      let err = format!("Required child `{}` not specify", quote! { #ty });
+      quote! { self.#member.expect(#err) }
+    } else {
+      quote! { self.#member }
+    }
+  }
+}
+
+/// Helper to generate the builder type with the generic arguments of the
+/// template and the fill flags of the required fields.
+struct BuilderType<'a> {
+  name: &'a Ident,
+  args: Vec<TokenStream>,
+}
+
+impl<'a> BuilderType<'a> {
+  fn new(name: &'a Ident, generics: &syn::Generics) -> Self {
+    let args = generics
+      .params
+      .iter()
+      .map(|p| match p {
+        GenericParam::Lifetime(l) => {
+          let l = &l.lifetime;
+          quote!(#l)
+        }
+        GenericParam::Type(t) => {
+          let t = &t.ident;
+          quote!(#t)
+        }
+        GenericParam::Const(c) => {
+          let c = &c.ident;
+          quote!(#c)
+        }
+      })
+      .collect();
+    Self { name, args }
+  }
+
+  fn with_flags(&self, flags: impl Iterator<Item = TokenStream>) -> TokenStream {
+    let name = self.name;
+    let args: Vec<_> = self.args.iter().cloned().chain(flags).collect();
+    if args.is_empty() { quote!(#name) } else { quote!(#name<#(#args),*>) }
+  }
+}
+
+fn with_flag_generics(generics: &syn::Generics, flags: &[Ident], default: bool) -> syn::Generics {
+  let mut gen = generics.clone();
+  for f in flags {
+    if default {
+      gen
+        .params
+        .push(parse_quote!(const #f: bool = false));
+    } else {
+      gen.params.push(parse_quote!(const #f: bool));
     }
   }
+  gen
 }
 
 fn option_type_extract(ty: &syn::Type) -> Option<&syn::Type> {
@@ -233,20 +366,3 @@ fn option_type_extract(ty: &syn::Type) -> Option<&syn::Type> {
       _ => None,
     })
 }
-
-fn convert_to_builder_pair(mut p: Pair<Field, Comma>) -> Pair<Field, Comma> {
-  let ty = &p.value().ty;
-  if option_type_extract(ty).is_none() {
-    p.value_mut().ty = parse_quote!(Option<#ty>);
-  };
-  p
-}
-
-fn gen_init_value_tokens(field_name: TokenStream, ty: &syn::Type) -> TokenStream {
-  let mut value = quote! { self.#field_name };
-  if option_type_extract(ty).is_none() {
-    let err = format!("Required child `{}` not specify", quote! { #ty });
-    value.extend(quote! { .expect(#err)});
-  };
-  value
-}
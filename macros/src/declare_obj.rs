@@ -106,18 +106,15 @@ impl ToTokens for DeclareObj {
 impl DeclareObj {
   pub fn error_check(&self) -> Result<(), Error> {
     if matches!(self.this.node_type, ObjType::Var { .. }) {
-      let invalid_fields = self
+      let invalid_fields: Box<[_]> = self
         .this
         .fields
         .iter()
         .filter(|f| !BUILTIN_INFOS.contains_key(&f.member.to_string()))
-        .collect::<Vec<_>>();
+        .map(|f| f.member.clone())
+        .collect();
       if !invalid_fields.is_empty() {
-        let spans = invalid_fields
-          .iter()
-          .map(|f| f.member.span())
-          .collect();
-        return Err(Error::InvalidFieldInVar(spans));
+        return Err(Error::InvalidFieldInVar(invalid_fields));
       }
     }
 
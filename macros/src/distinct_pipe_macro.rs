@@ -11,11 +11,13 @@ pub fn gen_code(input: TokenStream, refs_ctx: Option<&mut DollarRefsCtx>) -> Tok
   let res = process_watch_body(input, refs_ctx).map(|(upstream, map_handler)| {
     quote_spanned! {span =>
       MapPipe::new(
-        ModifiesPipe::new(#upstream.box_it()),
+        // Since the pipe has an initial value, we skip the initial notification.
+        // Skip it before the scope filter of the pipe, or a pipe that doesn't
+        // accept the initial notification will skip its first modification.
+        ModifiesPipe::new(#upstream.skip(1).box_it()),
         #map_handler
       )
-      // Since the pipe has an initial value, we skip the initial notification.
-      .value_chain(|s| s.skip(1).distinct_until_key_changed(|v: &(_, _)| v.1).box_it())
+      .value_chain(|s| s.distinct_until_key_changed(|v: &(_, _)| v.1).box_it())
     }
   });
   result_to_token_stream(res)
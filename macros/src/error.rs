@@ -1,11 +1,17 @@
 use proc_macro2::{Span, TokenStream};
-use quote::{ToTokens, quote_spanned};
+use quote::{ToTokens, quote, quote_spanned};
+use syn::Ident;
+
+use crate::variable_names::similar_builtin;
 
 pub enum Error {
-  InvalidFieldInVar(Box<[Span]>),
+  InvalidFieldInVar(Box<[Ident]>),
   WatchNothing(Span),
   RdlAtSyntax { at: Span, follow: Option<Span> },
   IdentNotFollowDollar(Span),
+  RdlIfSyntax(Span),
+  RdlElseWithoutIf(Span),
+  RdlForSyntax(Span),
 
   Syn(syn::Error),
 }
@@ -14,14 +20,19 @@ impl Error {
   pub fn to_compile_error(&self) -> TokenStream {
     match self {
       Error::InvalidFieldInVar(fields) => {
-        let mut tokens = TokenStream::new();
-        for span in fields.iter() {
-          quote_spanned! { *span =>
-            compile_error!("Only allow to declare builtin fields in a variable parent.");
+        let errors = fields.iter().map(|field| {
+          let mut msg = format!(
+            "`{field}` is not a builtin field, only allow to declare builtin fields in a variable \
+             parent."
+          );
+          if let Some(similar) = similar_builtin(&field.to_string()) {
+            msg.push_str(&format!(" Did you mean `{similar}`?"));
           }
-          .to_tokens(&mut tokens);
-        }
-        tokens
+          quote_spanned! { field.span() => compile_error!(#msg) }
+        });
+        // The last error is the value of the block, so the object has no type
+        // to cause other errors.
+        quote! {{ #(#errors);* }}
       }
       Error::WatchNothing(span) => quote_spanned! { *span =>
         compile_error!("expression not subscribe anything, it must contain at least one $")
@@ -35,6 +46,16 @@ impl Error {
       Error::IdentNotFollowDollar(span) => {
         quote_spanned! { *span => compile_error!("Syntax error: expected an identifier after `$`"); }
       }
+      Error::RdlIfSyntax(span) => quote_spanned! { *span =>
+        compile_error!("Syntax Error: the conditional objects must be: \
+        `@if cond { ... } @else if cond { ... } @else { ... }`, the `@else` branches are optional.")
+      },
+      Error::RdlElseWithoutIf(span) => quote_spanned! { *span =>
+        compile_error!("Syntax Error: `@else` must follow an `@if` branch.")
+      },
+      Error::RdlForSyntax(span) => quote_spanned! { *span =>
+        compile_error!("Syntax Error: the list objects must be: `@for pat in iter { ... }`.")
+      },
 
       Error::Syn(err) => err.to_compile_error(),
     }
@@ -21,7 +21,9 @@ mod watch_macro;
 pub(crate) use rdl_macro::*;
 pub(crate) mod declare_obj;
 pub(crate) mod distinct_pipe_macro;
+pub(crate) mod effect_macro;
 pub(crate) mod error;
+pub(crate) mod sprite_atlas;
 pub(crate) mod symbol_process;
 
 #[proc_macro_derive(SingleChild)]
@@ -160,6 +162,31 @@ pub fn rdl(input: TokenStream) -> TokenStream { RdlMacro::gen_code(input.into(),
 /// Its syntax extends the Rust syntax, allowing the use of `@` and `$` within
 /// the expression. The `@` serves as a shorthand for the `rdl` macro, while
 /// `$name` is used to express a state reference to `name`.
+///
+/// The children can be declared by conditions with `@if` and `@else`, every
+/// branch is declared like the body of `rdl!`. If the conditions use any `$`
+/// state, the chosen branch is rebuilt when the state changes.
+///
+/// ```ignore
+///   @Row {
+///     @if *$wide { SizedBox { size: Size::new(100., 50.) } }
+///     @else { Text { text: "narrow" } }
+///   }
+/// ```
+///
+/// The children can also be declared by a list with `@for`, the body is
+/// declared for every item. If the iterator uses any `$` state, the list is
+/// regenerated from a clone of it when the state changes, and every child is
+/// keyed by the hash of its item, so the children of the same item are
+/// matched. Declare the body as a `KeyWidget` to key the children yourself.
+///
+/// ```ignore
+///   @Row {
+///     @for name in $names {
+///       @Text { text: name }
+///     }
+///   }
+/// ```
 #[proc_macro]
 pub fn fn_widget(input: TokenStream) -> TokenStream {
   fn_widget_macro::gen_code(input.into(), None).into()
@@ -229,9 +256,50 @@ pub fn distinct_pipe(input: TokenStream) -> TokenStream {
 /// // Call unsubscribe at the appropriate time to ensure the state can be dropped.
 /// u.unsubscribe();
 /// ```
+///
+/// If the subscription belongs to a widget, use `while_mounted` to unsubscribe
+/// it automatically when the widget is disposed.
+///
+/// ```rust ignore
+/// use ribir::prelude::*;
+///
+/// fn_widget! {
+///   let even = Stateful::new(1);
+///   let mut w = @Text { text: "even" };
+///   watch!(*$even)
+///     .while_mounted(&mut w)
+///     .subscribe(move |v| {
+///       if v % 2 == 1 {
+///         *$even.write() = v + 1;
+///       }
+///     });
+///   w
+/// };
+/// ```
 #[proc_macro]
 pub fn watch(input: TokenStream) -> TokenStream { watch_macro::gen_code(input.into(), None).into() }
 
+/// The `effect` macro runs a side effect when the `$` states in it are
+/// modified, and cleans up the previous run before running it again.
+///
+/// The body is run immediately and returns a closure to clean up what it does.
+/// The cleanup is called before the body runs again, and when the returned
+/// `Effect` is unsubscribed.
+///
+/// ```ignore
+///   let visible = Stateful::new(true);
+///   let effect = effect! {
+///     let id = register(*$visible);
+///     move || unregister(id)
+///   };
+///   // Stop the effect and clean up its last run.
+///   effect.unsubscribe();
+/// ```
+#[proc_macro]
+pub fn effect(input: TokenStream) -> TokenStream {
+  effect_macro::gen_code(input.into(), None).into()
+}
+
 /// The `part_writer` macro creates a partial writer from a mutable reference of
 /// a writer.
 ///
@@ -240,6 +308,9 @@ pub fn watch(input: TokenStream) -> TokenStream { watch_macro::gen_code(input.in
 ///
 /// - For a field: `part_writer!(&mut writer.xxx)`
 /// - For a method returning a mutable reference: `part_writer!(writer.xxx())`.
+/// - For an element of a collection: `part_writer!(&mut writer.items[3])` or
+///   `part_writer!(&mut writer.map["key"])`. The index is evaluated every time
+///   the part is accessed, and it panics if the element doesn't exist.
 ///
 /// Since it operates on a writer and not a state reference of the writer, the
 /// use of `$` is unnecessary.
@@ -288,6 +359,8 @@ pub fn map_watcher(input: TokenStream) -> TokenStream {
 ///
 /// - For a field: `part_reader!(&reader.xxx)`
 /// - For a method returning a reference: `part_reader!(reader.xxx())`.
+/// - For an element of a collection: `part_reader!(&reader.items[3])`, the
+///   index is evaluated every time the part is accessed.
 ///
 /// Since it operates on a reader and not a state reference of the reader, the
 /// use of `$` is unnecessary.
@@ -304,13 +377,120 @@ pub fn part_reader(input: TokenStream) -> TokenStream {
 /// backslashes \ would not compile correctly on Unix.
 ///
 /// This macro returns an expression of type `Svg`.
+///
+/// The SVG is serialized at compile time, pass `compress = "gzip"` or
+/// `compress = "zstd"` to embed the compressed data, it requires the feature of
+/// the same name of Ribir, which is off by default. The `level` sets the
+/// compression level, it's from 0 to 9 and defaults to 6 for gzip, and from 1
+/// to 22 and defaults to 3 for zstd. Set the `RIBIR_ASSET_REPORT` environment
+/// variable to print the size of the embedded data in the build output.
+///
+/// ```ignore
+///   let svg: Svg = include_crate_svg!("./logo.svg", true, false, compress = "zstd", level = 19);
+/// ```
 #[proc_macro]
 pub fn include_crate_svg(input: TokenStream) -> TokenStream {
-  let IncludeSvgArgs { path, inherit_fill, inherit_stroke } =
+  let IncludeSvgArgs { path, inherit_fill, inherit_stroke, compress } =
     parse_macro_input! { input as IncludeSvgArgs };
   let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
   let path = std::path::Path::new(&dir).join(path);
-  include_svg_from_path(path, inherit_fill, inherit_stroke)
+  include_svg_from_path(path, inherit_fill, inherit_stroke, compress)
+}
+
+/// Includes a PNG file as a `&'static LazyImage`.
+///
+/// The file is located relative to the current crate (similar to the location
+/// of your `cargo.toml`). The bytes of the file are embedded in the binary,
+/// but the image is decoded at its first use rather than at load, and the
+/// decoded image is cached for the later uses.
+///
+/// ```ignore
+///   let img: &'static LazyImage = include_crate_png!("./assets/logo.png");
+///   // The image is decoded here.
+///   let size = img.size();
+/// ```
+#[proc_macro]
+pub fn include_crate_png(input: TokenStream) -> TokenStream {
+  let path = parse_macro_input! { input as syn::LitStr }.value();
+  let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+  let path = std::path::Path::new(&dir).join(path);
+  if !path.is_file() {
+    let err = format!("No such file: {:?}", &path);
+    return quote! { compile_error!(#err) }.into();
+  }
+  let path = path.to_string_lossy();
+  quote! {{
+    static IMAGE: LazyImage = LazyImage::new(include_bytes!(#path), PixelImage::from_png);
+    &IMAGE
+  }}
+  .into()
+}
+
+/// Packs the PNG images in a directory into one atlas image at compile time,
+/// and returns a struct that has a `Sprite` field for every image.
+///
+/// The directory is located relative to the current crate (similar to the
+/// location of your `cargo.toml`). The field of a sprite is named by the snake
+/// case of its file name, and all the sprites share the same atlas image, so
+/// drawing them needs only one texture. The atlas is decoded at its first use.
+///
+/// It's a compile error if an image is larger than 4096x4096, or the images
+/// can't be packed in one atlas of that size.
+///
+/// ```ignore
+///   // The directory has `home.png` and `settings.png`.
+///   let icons = include_crate_sprites!("./assets/icons");
+///   let home: Sprite = icons.home;
+///   let atlas: &'static LazyImage = home.atlas();
+///   let region: DeviceRect = home.rect();
+/// ```
+#[proc_macro]
+pub fn include_crate_sprites(input: TokenStream) -> TokenStream {
+  let dir = parse_macro_input! { input as syn::LitStr };
+  sprite_atlas::gen_code(dir).into()
+}
+
+/// Includes a JSON file of translations as a `Translations`.
+///
+/// The file is located relative to the current crate (similar to the location
+/// of your `cargo.toml`). It's an object that maps the keys to the localized
+/// strings, and its file name is the language of the translations. The file is
+/// validated at compile time.
+///
+/// ```ignore
+///   // The `zh.json` is `{ "greet": "你好，{name}！" }`.
+///   let zh: Translations = include_crate_translations!("./i18n/zh.json");
+///   assert_eq!(zh.lang(), "zh");
+/// ```
+#[proc_macro]
+pub fn include_crate_translations(input: TokenStream) -> TokenStream {
+  let lit = parse_macro_input! { input as syn::LitStr };
+  let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+  let path = std::path::Path::new(&dir).join(lit.value());
+  let lang = path
+    .file_stem()
+    .map(|s| s.to_string_lossy().into_owned())
+    .unwrap_or_default();
+
+  let entries = std::fs::read_to_string(&path)
+    .map_err(|err| format!("{err}({path:?})"))
+    .and_then(|content| {
+      let table: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&content).map_err(|err| format!("{err}({path:?})"))?;
+      table
+        .into_iter()
+        .map(|(key, value)| match value {
+          serde_json::Value::String(value) => Ok(quote! { (#key, #value) }),
+          _ => Err(format!("The translation of `{key}` is not a string({path:?})")),
+        })
+        .collect::<Result<Vec<_>, _>>()
+    });
+  match entries {
+    Ok(entries) => quote! { Translations::from_entries(#lang, &[#(#entries),*]) }.into(),
+    Err(err) => syn::Error::new(lit.span(), err)
+      .to_compile_error()
+      .into(),
+  }
 }
 
 /// Includes an SVG file as an `Svg`.
@@ -324,7 +504,7 @@ pub fn include_crate_svg(input: TokenStream) -> TokenStream {
 #[cfg(feature = "nightly")]
 #[proc_macro]
 pub fn include_svg(input: TokenStream) -> TokenStream {
-  let IncludeSvgArgs { path, inherit_fill, inherit_stroke } =
+  let IncludeSvgArgs { path, inherit_fill, inherit_stroke, compress } =
     parse_macro_input! { input as IncludeSvgArgs };
 
   let mut span = proc_macro::Span::call_site();
@@ -335,19 +515,44 @@ pub fn include_svg(input: TokenStream) -> TokenStream {
   file.pop();
   file.push(path);
 
-  include_svg_from_path(file, inherit_fill, inherit_stroke)
+  include_svg_from_path(file, inherit_fill, inherit_stroke, compress)
 }
 
 fn include_svg_from_path(
-  path: std::path::PathBuf, inherit_fill: bool, inherit_stroke: bool,
+  path: std::path::PathBuf, inherit_fill: bool, inherit_stroke: bool, compress: SvgCompress,
 ) -> TokenStream {
-  let encoded_bytes = ribir_painter::Svg::open(path.as_path(), inherit_fill, inherit_stroke)
-    .and_then(|reader| reader.serialize());
-  match encoded_bytes {
-    Ok(data) => quote! {
-      Svg::deserialize(#data).unwrap()
+  let svg = match ribir_painter::Svg::open(path.as_path(), inherit_fill, inherit_stroke) {
+    Ok(svg) => svg,
+    Err(err) => {
+      let err = format!("{err}({:?})", &path);
+      return quote! { compile_error!(#err) }.into();
+    }
+  };
+  let res = match compress {
+    SvgCompress::None => svg.serialize().map(|data| {
+      let size = data.len();
+      (quote! { Svg::deserialize(#data).unwrap() }, size)
+    }),
+    #[cfg(feature = "gzip")]
+    SvgCompress::Gzip { level } => svg.serialize_gzip(level).map(|data| {
+      let size = data.len();
+      let data = syn::LitByteStr::new(&data, proc_macro2::Span::call_site());
+      (quote! { Svg::deserialize_gzip(#data).unwrap() }, size)
+    }),
+    #[cfg(feature = "zstd")]
+    SvgCompress::Zstd { level } => svg.serialize_zstd(level).map(|data| {
+      let size = data.len();
+      let data = syn::LitByteStr::new(&data, proc_macro2::Span::call_site());
+      (quote! { Svg::deserialize_zstd(#data).unwrap() }, size)
+    }),
+  };
+  match res {
+    Ok((tokens, size)) => {
+      if std::env::var_os("RIBIR_ASSET_REPORT").is_some() {
+        eprintln!("ribir asset: {path:?} embeds {size} bytes ({compress:?})");
+      }
+      tokens.into()
     }
-    .into(),
     Err(err) => {
       let err = format!("{err}({:?})", &path);
       quote! { compile_error!(#err) }.into()
@@ -359,6 +564,21 @@ struct IncludeSvgArgs {
   path: String,
   inherit_fill: bool,
   inherit_stroke: bool,
+  compress: SvgCompress,
+}
+
+/// The compression of the SVG data embedded by the `include_svg!` macros.
+#[derive(Debug, Clone, Copy)]
+enum SvgCompress {
+  None,
+  #[cfg(feature = "gzip")]
+  Gzip {
+    level: u32,
+  },
+  #[cfg(feature = "zstd")]
+  Zstd {
+    level: i32,
+  },
 }
 
 impl syn::parse::Parse for IncludeSvgArgs {
@@ -369,6 +589,69 @@ impl syn::parse::Parse for IncludeSvgArgs {
     input.parse::<syn::Token![,]>()?;
     let inherit_stroke = input.parse::<syn::LitBool>()?.value;
 
-    Ok(IncludeSvgArgs { path, inherit_fill, inherit_stroke })
+    let mut algorithm = None;
+    let mut level = None;
+    while input.parse::<Option<syn::Token![,]>>()?.is_some() && !input.is_empty() {
+      let name = input.parse::<syn::Ident>()?;
+      input.parse::<syn::Token![=]>()?;
+      if name == "compress" {
+        algorithm = Some(input.parse::<syn::LitStr>()?);
+      } else if name == "level" {
+        let lit = input.parse::<syn::LitInt>()?;
+        let value = lit.base10_parse::<u32>()?;
+        level = Some((lit, value));
+      } else {
+        let msg = format!("Unknown option `{name}`, the options are `compress` and `level`.");
+        return Err(syn::Error::new(name.span(), msg));
+      }
+    }
+
+    let compress = match algorithm.as_ref().map(|a| a.value()).as_deref() {
+      None | Some("none") => {
+        if let Some((lit, _)) = level {
+          let msg = "The compression level requires a `compress` algorithm.";
+          return Err(syn::Error::new(lit.span(), msg));
+        }
+        SvgCompress::None
+      }
+      #[cfg(feature = "gzip")]
+      Some("gzip") => SvgCompress::Gzip { level: compress_level(level, 0..=9, 6)? },
+      #[cfg(feature = "zstd")]
+      Some("zstd") => SvgCompress::Zstd { level: compress_level(level, 1..=22, 3)? as i32 },
+      #[cfg(not(feature = "gzip"))]
+      Some("gzip") => {
+        let msg = "The `gzip` compression requires the `gzip` feature of Ribir.";
+        return Err(syn::Error::new(algorithm.unwrap().span(), msg));
+      }
+      #[cfg(not(feature = "zstd"))]
+      Some("zstd") => {
+        let msg = "The `zstd` compression requires the `zstd` feature of Ribir.";
+        return Err(syn::Error::new(algorithm.unwrap().span(), msg));
+      }
+      Some(other) => {
+        let msg = format!(
+          "Unknown compression algorithm `{other}`, the supported are `none`, `gzip` and `zstd`."
+        );
+        return Err(syn::Error::new(algorithm.unwrap().span(), msg));
+      }
+    };
+
+    Ok(IncludeSvgArgs { path, inherit_fill, inherit_stroke, compress })
+  }
+}
+
+/// Check the compression `level` is in the `range` of the algorithm, or use
+/// the `default` level.
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn compress_level(
+  level: Option<(syn::LitInt, u32)>, range: std::ops::RangeInclusive<u32>, default: u32,
+) -> syn::Result<u32> {
+  match level {
+    None => Ok(default),
+    Some((_, value)) if range.contains(&value) => Ok(value),
+    Some((lit, _)) => {
+      let msg = format!("The compression level must be from {} to {}.", range.start(), range.end());
+      Err(syn::Error::new(lit.span(), msg))
+    }
   }
 }
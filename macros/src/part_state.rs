@@ -1,10 +1,12 @@
-use proc_macro2::{Ident, TokenStream};
-use quote::{ToTokens, quote_spanned};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{ToTokens, quote, quote_spanned};
 use syn::{
-  AngleBracketedGenericArguments, Expr, Member, Result, Token, parenthesized,
+  AngleBracketedGenericArguments, Expr, ExprIndex, Member, Result, Token, parenthesized,
   parse::{Parse, ParseStream},
+  parse_quote_spanned,
   punctuated::Punctuated,
-  token::Paren,
+  spanned::Spanned,
+  token::{Bracket, Paren},
 };
 
 use crate::{
@@ -17,11 +19,12 @@ pub fn gen_part_wrier(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> Token
     Ok(part) => {
       let info = part.state_info(refs_ctx);
       let host = part.host_tokens(&info, refs_ctx);
-      let PartState { and_token, mutability, state, dot, part_expr, tail_dot, tail_expr } = part;
+      let v = part.var();
+      let part_expr = part.part_expr(true);
+      let PartState { state, dot, .. } = part;
+      let dot = dot.unwrap_or_default();
       let tokens = quote_spanned! { state.span() =>
-        #host #dot map_writer(
-          |w| PartMut::new(#and_token #mutability w #dot #part_expr #tail_dot #tail_expr)
-        )
+        #host #dot map_writer(|#v| PartMut::new(#part_expr))
       };
       refs_ctx.add_dollar_ref(info);
       tokens
@@ -35,11 +38,12 @@ pub fn gen_split_wrier(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> Toke
     Ok(part) => {
       let info = part.state_info(refs_ctx);
       let host = part.host_tokens(&info, refs_ctx);
-      let PartState { and_token, mutability, state, dot, part_expr, tail_dot, tail_expr } = part;
+      let v = part.var();
+      let part_expr = part.part_expr(true);
+      let PartState { state, dot, .. } = part;
+      let dot = dot.unwrap_or_default();
       let tokens = quote_spanned! { state.span() =>
-        #host #dot split_writer(
-          |w| PartMut::new(#and_token #mutability w #dot #part_expr #tail_dot #tail_expr)
-        )
+        #host #dot split_writer(|#v| PartMut::new(#part_expr))
       };
       refs_ctx.add_dollar_ref(info);
       tokens
@@ -53,11 +57,12 @@ pub fn gen_part_reader(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> Toke
     Ok(part) => {
       let info = part.state_info(refs_ctx);
       let host = part.host_tokens(&info, refs_ctx);
-      let PartState { and_token, mutability, state, dot, part_expr, tail_dot, tail_expr } = part;
+      let v = part.var();
+      let part_expr = part.part_expr(false);
+      let PartState { state, dot, .. } = part;
+      let dot = dot.unwrap_or_default();
       let tokens = quote_spanned! { state.span() =>
-        #host #dot map_reader(
-          |r| PartRef::new(#and_token #mutability r #dot #part_expr #tail_dot #tail_expr)
-        )
+        #host #dot map_reader(|#v| PartRef::new(#part_expr))
       };
       refs_ctx.add_dollar_ref(info);
       tokens
@@ -71,11 +76,12 @@ pub fn gen_part_watcher(input: TokenStream, refs_ctx: &mut DollarRefsCtx) -> Tok
     Ok(part) => {
       let info = part.state_info(refs_ctx);
       let host = part.host_tokens(&info, refs_ctx);
-      let PartState { and_token, mutability, state, dot, part_expr, tail_dot, tail_expr } = part;
+      let v = part.var();
+      let part_expr = part.part_expr(false);
+      let PartState { state, dot, .. } = part;
+      let dot = dot.unwrap_or_default();
       let tokens = quote_spanned! { state.span() =>
-        #host #dot map_watcher(
-          |r| PartRef::new(#and_token #mutability r #dot #part_expr #tail_dot #tail_expr)
-        )
+        #host #dot map_watcher(|#v| PartRef::new(#part_expr))
       };
       refs_ctx.add_dollar_ref(info);
       tokens
@@ -88,10 +94,11 @@ struct PartState {
   and_token: Option<Token![&]>,
   mutability: Option<Token![mut]>,
   state: Ident,
-  dot: Token![.],
-  part_expr: PartExpr,
-  tail_dot: Option<Token![.]>,
-  tail_expr: Option<Expr>,
+  dot: Option<Token![.]>,
+  part_expr: Option<PartExpr>,
+  /// The whole part expression with the state replaced by the parameter of the
+  /// map closure.
+  expr: Expr,
 }
 
 enum PartExpr {
@@ -114,39 +121,62 @@ impl Parse for PartState {
     } else {
       input.parse()?
     };
-    let dot = input.parse()?;
 
-    let part_expr = if input.peek2(Token![::]) || input.peek2(Paren) {
-      let method = input.parse()?;
-      let turbofish = if input.peek(Token![::]) {
-        Some(AngleBracketedGenericArguments::parse_turbofish(input)?)
+    // The state can be indexed directly, like `writer[3]`.
+    let (dot, part_expr) = if input.peek(Bracket) {
+      (None, None)
+    } else {
+      let dot = input.parse()?;
+      let part_expr = if input.peek2(Token![::]) || input.peek2(Paren) {
+        let method = input.parse()?;
+        let turbofish = if input.peek(Token![::]) {
+          Some(AngleBracketedGenericArguments::parse_turbofish(input)?)
+        } else {
+          None
+        };
+        let content;
+        PartExpr::Method {
+          method,
+          turbofish,
+          paren_token: parenthesized!(content in input),
+          args: content.parse_terminated(Expr::parse, Token![,])?,
+        }
       } else {
-        None
+        PartExpr::Member(input.parse()?)
       };
-      let content;
-      PartExpr::Method {
-        method,
-        turbofish,
-        paren_token: parenthesized!(content in input),
-        args: content.parse_terminated(Expr::parse, Token![,])?,
-      }
-    } else {
-      PartExpr::Member(input.parse()?)
+      (Some(dot), Some(part_expr))
     };
 
-    let tail_dot = input.parse()?;
-    let tail_expr = if input.is_empty() { None } else { Some(input.parse()?) };
-    Ok(Self { and_token, mutability, state, dot, part_expr, tail_dot, tail_expr })
+    let tail: TokenStream = input.parse()?;
+    let var = part_var(state.span());
+    let expr = syn::parse2(quote! { #var #dot #part_expr #tail })?;
+    Ok(Self { and_token, mutability, state, dot, part_expr, expr })
   }
 }
 
+const PART_VAR: &str = "_ribir_part";
+
+/// The variable of the part closure, it's hygienic so it never captures the
+/// variables used by the part expression.
+fn part_var(span: Span) -> Ident { Ident::new(PART_VAR, Span::mixed_site().located_at(span)) }
+
 impl PartState {
+  fn var(&self) -> Ident { part_var(self.state.span()) }
+
+  /// The reference expression of the part, the indexes are converted to the
+  /// `PartIndex` methods, so it panics clearly if the element doesn't exist.
+  fn part_expr(&self, mutable: bool) -> TokenStream {
+    let Self { and_token, mutability, expr, .. } = self;
+    let expr = index_by_part(expr.clone(), mutable);
+    quote! { #and_token #mutability #expr }
+  }
+
   fn state_info(&self, refs_ctx: &DollarRefsCtx) -> DollarRef {
     let builtin_info = match &self.part_expr {
-      PartExpr::Member(Member::Named(member)) => BUILTIN_INFOS
+      Some(PartExpr::Member(Member::Named(member))) => BUILTIN_INFOS
         .get(&member.to_string())
         .filter(|info| info.mem_ty == BuiltinMemberType::Field),
-      PartExpr::Method { method, .. } => BUILTIN_INFOS
+      Some(PartExpr::Method { method, .. }) => BUILTIN_INFOS
         .get(&method.to_string())
         .filter(|info| info.mem_ty == BuiltinMemberType::Method),
       _ => None,
@@ -179,3 +209,24 @@ impl ToTokens for PartExpr {
     }
   }
 }
+
+/// Convert the indexes in the access chain of `expr` to the `PartIndex`
+/// methods.
+fn index_by_part(expr: Expr, mutable: bool) -> Expr {
+  match expr {
+    Expr::Index(ExprIndex { expr, index, .. }) => {
+      let base = index_by_part(*expr, mutable);
+      let method = if mutable { quote!(part_index_mut) } else { quote!(part_index) };
+      parse_quote_spanned! { index.span() => (*#base.#method(#index)) }
+    }
+    Expr::Field(mut field) => {
+      field.base = Box::new(index_by_part(*field.base, mutable));
+      Expr::Field(field)
+    }
+    Expr::MethodCall(mut call) => {
+      call.receiver = Box::new(index_by_part(*call.receiver, mutable));
+      Expr::MethodCall(call)
+    }
+    expr => expr,
+  }
+}
@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use heck::ToSnakeCase;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use ribir_painter::{PixelImage, image::ColorFormat};
+use syn::LitStr;
+
+/// The max width and height of the packed atlas, it's the texture size that
+/// most of the GPUs support.
+pub const MAX_ATLAS_SIZE: u32 = 4096;
+/// The gap between sprites to avoid sampling the pixels of the neighbors.
+const SPRITE_GAP: u32 = 1;
+
+struct SpriteImage {
+  name: String,
+  image: PixelImage,
+}
+
+/// The position of a sprite in the atlas.
+struct Placed {
+  x: u32,
+  y: u32,
+  width: u32,
+  height: u32,
+}
+
+pub fn gen_code(dir: LitStr) -> TokenStream {
+  let span = dir.span();
+  let root = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+  let path = Path::new(&root).join(dir.value());
+  match load_sprites(&path).and_then(|sprites| pack(sprites, &path)) {
+    Ok(tokens) => tokens,
+    Err(err) => syn::Error::new(span, err).to_compile_error(),
+  }
+}
+
+fn load_sprites(dir: &Path) -> Result<Vec<SpriteImage>, String> {
+  let entries = std::fs::read_dir(dir).map_err(|err| format!("{err}({dir:?})"))?;
+  let mut files = entries
+    .filter_map(|e| e.ok().map(|e| e.path()))
+    .filter(|p| {
+      p.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"))
+    })
+    .collect::<Vec<_>>();
+  files.sort();
+  if files.is_empty() {
+    return Err(format!("No PNG image in the directory {dir:?}"));
+  }
+
+  let mut sprites: Vec<SpriteImage> = vec![];
+  for file in files {
+    let stem = file.file_stem().unwrap().to_string_lossy();
+    let name = stem.to_snake_case();
+    if syn::parse_str::<syn::Ident>(&name).is_err() {
+      return Err(format!("`{stem}` can't be used as a sprite name, rename the file {file:?}"));
+    }
+    if let Some(same) = sprites.iter().find(|s| s.name == name) {
+      let name = &same.name;
+      return Err(format!("The sprite name `{name}` is used by more than one image in {dir:?}"));
+    }
+
+    let bytes = std::fs::read(&file).map_err(|err| format!("{err}({file:?})"))?;
+    let image = PixelImage::from_bytes(&bytes).map_err(|err| format!("{err}({file:?})"))?;
+    if image.width() > MAX_ATLAS_SIZE || image.height() > MAX_ATLAS_SIZE {
+      let (width, height) = (image.width(), image.height());
+      return Err(format!(
+        "The image {file:?} is {width}x{height}, larger than the max atlas size \
+         {MAX_ATLAS_SIZE}x{MAX_ATLAS_SIZE}"
+      ));
+    }
+    sprites.push(SpriteImage { name, image });
+  }
+  Ok(sprites)
+}
+
+/// Pack the sprites row by row, the taller sprites are placed first, and
+/// generate the atlas image and the region of every sprite.
+fn pack(sprites: Vec<SpriteImage>, dir: &Path) -> Result<TokenStream, String> {
+  let area: u32 = sprites
+    .iter()
+    .map(|s| (s.image.width() + SPRITE_GAP) * (s.image.height() + SPRITE_GAP))
+    .sum();
+  let widest = sprites
+    .iter()
+    .map(|s| s.image.width())
+    .max()
+    .unwrap();
+  let atlas_width = ((area as f32).sqrt().ceil() as u32)
+    .next_power_of_two()
+    .max(widest)
+    .min(MAX_ATLAS_SIZE);
+
+  let mut order = (0..sprites.len()).collect::<Vec<_>>();
+  order.sort_by_key(|i| std::cmp::Reverse(sprites[*i].image.height()));
+
+  let mut placed = Vec::with_capacity(sprites.len());
+  placed.resize_with(sprites.len(), || None);
+  let (mut x, mut y, mut row_height) = (0, 0, 0);
+  for i in order {
+    let (width, height) = (sprites[i].image.width(), sprites[i].image.height());
+    if x + width > atlas_width {
+      x = 0;
+      y += row_height + SPRITE_GAP;
+      row_height = 0;
+    }
+    placed[i] = Some(Placed { x, y, width, height });
+    x += width + SPRITE_GAP;
+    row_height = row_height.max(height);
+  }
+  let atlas_height = y + row_height;
+  if atlas_height > MAX_ATLAS_SIZE {
+    return Err(format!(
+      "The images in {dir:?} can't be packed in one atlas of the max size \
+       {MAX_ATLAS_SIZE}x{MAX_ATLAS_SIZE}"
+    ));
+  }
+
+  let stride = (atlas_width * 4) as usize;
+  let mut data = vec![0u8; stride * atlas_height as usize];
+  for (sprite, p) in sprites.iter().zip(placed.iter()) {
+    let p = p.as_ref().unwrap();
+    let row_len = (p.width * 4) as usize;
+    for (row, src) in sprite
+      .image
+      .pixel_bytes()
+      .chunks_exact(row_len)
+      .enumerate()
+    {
+      let start = (p.y as usize + row) * stride + (p.x * 4) as usize;
+      data[start..start + row_len].copy_from_slice(src);
+    }
+  }
+
+  let atlas = PixelImage::new(data.into(), atlas_width, atlas_height, ColorFormat::Rgba8);
+  let mut png = vec![];
+  atlas
+    .write_as_png(&mut png)
+    .map_err(|err| err.to_string())?;
+  let png = syn::LitByteStr::new(&png, Span::call_site());
+
+  let names = sprites
+    .iter()
+    .map(|s| format_ident!("{}", s.name))
+    .collect::<Vec<_>>();
+  let rects = placed.iter().map(|p| {
+    let Placed { x, y, width, height } = p.as_ref().unwrap();
+    let (x, y, width, height) = (*x as i32, *y as i32, *width as i32, *height as i32);
+    quote! {
+      Sprite::new(
+        &ATLAS, DeviceRect::new(DevicePoint::new(#x, #y), DeviceSize::new(#width, #height))
+      )
+    }
+  });
+
+  Ok(quote! {{
+    static ATLAS: LazyImage = LazyImage::new(#png, PixelImage::from_png);
+    struct Sprites { #(pub #names: Sprite,)* }
+    Sprites { #(#names: #rects,)* }
+  }})
+}
@@ -23,6 +23,7 @@ pub const KW_RDL: &str = "rdl";
 pub const KW_PIPE: &str = "pipe";
 pub const KW_DISTINCT_PIPE: &str = "distinct_pipe";
 pub const KW_WATCH: &str = "watch";
+pub const KW_EFFECT: &str = "effect";
 pub const KW_PART_WRITER: &str = "part_writer";
 pub const KW_PART_READER: &str = "part_reader";
 pub const KW_FN_WIDGET: &str = "fn_widget";
@@ -75,6 +76,7 @@ pub struct StackGuard<'a>(&'a mut DollarRefsCtx);
 
 mod tokens_pre_process {
   use proc_macro2::*;
+  use quote::{quote, quote_spanned};
 
   use super::KW_DOLLAR_STR;
   use crate::{error::*, symbol_process::KW_RDL};
@@ -91,6 +93,24 @@ mod tokens_pre_process {
           // maybe rust identify bind syntax, `identify @`
           if at.as_char() == '@' && !matches!(tokens.last(), Some(TokenTree::Ident(_))) =>
         {
+          match iter.clone().next() {
+            // declare the children by conditions, `@if cond { ... } @else { ... }`
+            Some(TokenTree::Ident(i)) if i == "if" => {
+              iter.next();
+              tokens.extend(if_children(at.span(), &mut iter)?);
+              continue;
+            }
+            // declare the children by a loop, `@for item in list { ... }`
+            Some(TokenTree::Ident(i)) if i == "for" => {
+              iter.next();
+              tokens.extend(for_children(at.span(), &mut iter)?);
+              continue;
+            }
+            Some(TokenTree::Ident(i)) if i == "else" => {
+              return Err(Error::RdlElseWithoutIf(at.span().join(i.span()).unwrap_or(i.span())));
+            }
+            _ => {}
+          }
           tokens.push(TokenTree::Ident(Ident::new(KW_RDL, at.span())));
           tokens.push(not_token(at.span()));
           let mut rdl_group = smallvec::SmallVec::<[TokenTree; 3]>::default();
@@ -163,6 +183,148 @@ mod tokens_pre_process {
     Ok(tokens.into_iter().collect())
   }
 
+  /// Convert `@if cond { ... } @else if cond { ... } @else { ... }` to a `rdl!`
+  /// object, every branch is declared as a `rdl!` object. The object is the
+  /// widget of the first branch whose condition is true, or nothing if no branch
+  /// is chosen and there isn't an `@else`.
+  ///
+  /// If any condition uses a `$` state, the object is a pipe that rebuilds the
+  /// widget when another branch is chosen.
+  fn if_children(at: Span, iter: &mut token_stream::IntoIter) -> Result<TokenStream> {
+    let mut conds = vec![];
+    let mut branches = vec![];
+    let mut otherwise = None;
+    loop {
+      let mut cond = TokenStream::new();
+      let branch = loop {
+        match iter.next() {
+          Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace && !cond.is_empty() => {
+            break g;
+          }
+          Some(t) => cond.extend([t]),
+          None => return Err(Error::RdlIfSyntax(at)),
+        }
+      };
+      conds.push(cond);
+      branches.push(branch);
+
+      let mut look = iter.clone();
+      let is_else = matches!(look.next(), Some(TokenTree::Punct(p)) if p.as_char() == '@')
+        && matches!(look.next(), Some(TokenTree::Ident(i)) if i == "else");
+      if !is_else {
+        break;
+      }
+      iter.next();
+      iter.next();
+      match iter.next() {
+        Some(TokenTree::Ident(i)) if i == "if" => {}
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+          otherwise = Some(g);
+          break;
+        }
+        t => return Err(Error::RdlIfSyntax(t.map_or(at, |t| t.span()))),
+      }
+    }
+
+    let reactive = conds.iter().any(|c| has_dollar(c.clone()));
+    let branch_idx = conds
+      .into_iter()
+      .enumerate()
+      .map(|(i, cond)| {
+        let i = Literal::usize_unsuffixed(i);
+        quote! { if #cond { #i } else }
+      })
+      .collect::<TokenStream>();
+    let none = Literal::usize_unsuffixed(branches.len());
+    let branch_idx = quote! { #branch_idx { #none } };
+
+    let idx = Ident::new("_ribir_branch", at);
+    let rdl = Ident::new(KW_RDL, at);
+    let has_else = otherwise.is_some();
+    let arms = branches.into_iter().enumerate().map(|(i, b)| {
+      let i = Literal::usize_unsuffixed(i);
+      if has_else {
+        quote! { #i => #rdl! #b.into_widget(), }
+      } else {
+        quote! { #i => Some(#rdl! #b.into_widget()), }
+      }
+    });
+    let otherwise = match otherwise {
+      Some(b) => quote! { _ => #rdl! #b.into_widget(), },
+      None => quote! { _ => None, },
+    };
+    let select = quote! { match #idx { #(#arms)* #otherwise } };
+    let obj = if reactive {
+      quote! { distinct_pipe!(#branch_idx).map(move |#idx: usize| #select) }
+    } else {
+      quote! {{ let #idx: usize = #branch_idx; #select }}
+    };
+
+    Ok(quote_spanned! { at => #rdl! { #obj } })
+  }
+
+  /// Convert `@for pat in iter { ... }` to a `rdl!` object, the body is
+  /// declared as a `rdl!` object for every item of the iterator, and the object
+  /// is the list of them.
+  ///
+  /// If the iterator uses any `$` state, the object is a pipe that regenerates
+  /// the list from a clone of the iterator when the state changes. Every child
+  /// is keyed by its item, unless the body is a `KeyWidget`, so the children
+  /// are matched by their keys when the list is regenerated.
+  fn for_children(at: Span, iter: &mut token_stream::IntoIter) -> Result<TokenStream> {
+    let mut pat = TokenStream::new();
+    loop {
+      match iter.next() {
+        Some(TokenTree::Ident(i)) if i == "in" && !pat.is_empty() => break,
+        Some(t) => pat.extend([t]),
+        None => return Err(Error::RdlForSyntax(at)),
+      }
+    }
+    let mut items = TokenStream::new();
+    let body = loop {
+      match iter.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace && !items.is_empty() => {
+          break g;
+        }
+        Some(t) => items.extend([t]),
+        None => return Err(Error::RdlForSyntax(at)),
+      }
+    };
+
+    let rdl = Ident::new(KW_RDL, at);
+    let obj = if has_dollar(items.clone()) {
+      // A `$` state is read by a reference, clone it to iterate its items.
+      quote! {
+        pipe!(IntoIterator::into_iter((#items).clone()).collect::<Vec<_>>())
+          .map(move |_ribir_items| {
+            let mut _ribir_keys = ForKeys::default();
+            _ribir_items
+              .into_iter()
+              .map(|_ribir_item| {
+                let _ribir_key = _ribir_keys.key_of(&_ribir_item);
+                let #pat = _ribir_item;
+                ForKeys::attach(_ribir_key, #rdl! #body.into_widget())
+              })
+              .collect::<Vec<_>>()
+          })
+      }
+    } else {
+      quote! {
+        (#items).into_iter().map(|#pat| #rdl! #body.into_widget()).collect::<Vec<_>>()
+      }
+    };
+
+    Ok(quote_spanned! { at => #rdl! { #obj } })
+  }
+
+  fn has_dollar(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|t| match t {
+      TokenTree::Punct(p) => p.as_char() == '$',
+      TokenTree::Group(g) => has_dollar(g.stream()),
+      _ => false,
+    })
+  }
+
   fn in_macro(tokens: &[TokenTree]) -> bool {
     let [.., TokenTree::Ident(_), TokenTree::Punct(p)] = tokens else {
       return false;
@@ -291,6 +453,9 @@ impl Fold for DollarRefsCtx {
     } else if mac.path.is_ident(KW_WATCH) {
       mac.tokens = crate::watch_macro::gen_code(mac.tokens, Some(self));
       mark_macro_expanded(&mut mac);
+    } else if mac.path.is_ident(KW_EFFECT) {
+      mac.tokens = crate::effect_macro::gen_code(mac.tokens, Some(self));
+      mark_macro_expanded(&mut mac);
     } else if mac.path.is_ident(KW_PART_WRITER) {
       mac.tokens = crate::part_state::gen_part_wrier(mac.tokens, self);
       mark_macro_expanded(&mut mac);
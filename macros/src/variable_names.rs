@@ -34,6 +34,34 @@ impl BuiltinMember {
   }
 }
 
+/// Return the builtin member with the most similar name to `name`, if there is
+/// one similar enough to be a misspelling of it.
+pub fn similar_builtin(name: &str) -> Option<&'static str> {
+  let max_dist = (name.chars().count() / 3).max(1);
+  BUILTIN_INFOS
+    .keys()
+    .map(|k| (edit_distance(name, k), *k))
+    .filter(|(dist, _)| *dist <= max_dist)
+    .min()
+    .map(|(_, k)| k)
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<_>>();
+  let mut row = (0..=b.len()).collect::<Vec<_>>();
+  for (i, ca) in a.chars().enumerate() {
+    let mut diagonal = row[0];
+    row[0] = i + 1;
+    for (j, cb) in b.iter().enumerate() {
+      let substitute = diagonal + (ca != *cb) as usize;
+      diagonal = row[j + 1];
+      row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+    }
+  }
+  row[b.len()]
+}
+
 use phf::phf_map;
 
 use self::BuiltinMemberType::*;
@@ -81,6 +109,8 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "on_pointer_cancel" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_pointer_enter" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_pointer_leave" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_hover_changed" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_hover_intent" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_tap" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_tap_capture" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_double_tap" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
@@ -150,6 +180,8 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "margin" => builtin_member!{"Margin", Field, "margin"},
   // ScrollableWidget
   "scrollable" => builtin_member!{"ScrollableWidget", Field, "scrollable"},
+  "scroll_direction_lock" => builtin_member!{"ScrollableWidget", Field, "scrollable"},
+  "scroll_sensitivity" => builtin_member!{"ScrollableWidget", Field, "scrollable"},
   "get_scroll_pos" => builtin_member!{"ScrollableWidget", Method, "scrollable"},
   "scroll_view_size" => builtin_member!{"ScrollableWidget", Method, "scrollable"},
   "scroll_content_size" => builtin_member!{"ScrollableWidget", Method, "scrollable"},
@@ -172,6 +204,10 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "keep_alive" => builtin_member!{"KeepAlive", Field, "keep_alive"},
   // Tooltips
   "tooltips" => builtin_member!{"Tooltips", Field, "tooltips"},
+  // SemanticsWidget
+  "semantics" => builtin_member!{"SemanticsWidget", Field, "semantics"},
+  // PixelSnap
+  "pixel_snap" => builtin_member!{"PixelSnap", Field, "pixel_snap"},
   // TrackWidgetId
   "track_id" => builtin_member!{"TrackWidgetId", Method, "track_id"},
   // ClipBoundary
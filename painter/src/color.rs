@@ -41,6 +41,48 @@ pub struct LinearGradient {
   pub spread_method: SpreadMethod,
 }
 
+/// A gradient runs along the length of a stroked path rather than the screen
+/// space, the offsets of the stops are the rates of the path length.
+///
+/// The `Pad` and `Reflect` spread methods extend the colors of the end stops
+/// to the range the stops don't cover, the `Repeat` spread method interpolates
+/// from the last stop to the first one, so the gradient wraps seamlessly on a
+/// closed path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PathGradient {
+  pub stops: Vec<GradientStop>,
+  pub spread_method: SpreadMethod,
+}
+
+impl PathGradient {
+  /// Returns the color at the `rate` of the path length.
+  pub fn color_at(&self, rate: f32) -> Color {
+    let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+      return Color::TRANSPARENT;
+    };
+    let lerp = |from: &GradientStop, from_offset: f32, to: &GradientStop, to_offset: f32| {
+      let dist = to_offset - from_offset;
+      let t = if dist > 0. { ((rate - from_offset) / dist).clamp(0., 1.) } else { 1. };
+      let from = from.color.into_f32_components();
+      let to = to.color.into_f32_components();
+      let c = |i: usize| from[i] + (to[i] - from[i]) * t;
+      Color::from_f32_rgba(c(0), c(1), c(2), c(3))
+    };
+    let wrap = self.spread_method == SpreadMethod::Repeat;
+
+    match self.stops.iter().position(|s| rate <= s.offset) {
+      Some(0) if wrap => lerp(last, last.offset - 1., first, first.offset),
+      Some(0) => first.color,
+      Some(i) => {
+        let (from, to) = (&self.stops[i - 1], &self.stops[i]);
+        lerp(from, from.offset, to, to.offset)
+      }
+      None if wrap => lerp(last, last.offset, first, first.offset + 1.),
+      None => last.color,
+    }
+  }
+}
+
 /// Describe the light tone of a color, should between [0, 1.0], 0.0 gives
 /// absolute black and 1.0 give the brightest white.
 #[derive(Clone, Debug, Copy)]
@@ -1,6 +1,7 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Deref, sync::OnceLock};
 
-use ribir_geom::DeviceSize;
+use ribir_algo::Resource;
+use ribir_geom::{DeviceRect, DeviceSize};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -19,6 +20,60 @@ impl ColorFormat {
   }
 }
 
+/// The encoded image formats that can be detected by their magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+  Png,
+  Jpeg,
+  Gif,
+  Bmp,
+  WebP,
+}
+
+/// The error of decoding an image from bytes.
+#[derive(Debug)]
+pub enum ImageDecodeError {
+  /// The bytes are not in any of the supported image formats.
+  UnknownFormat,
+  /// The format is detected but its decoder is not enabled, enable the feature
+  /// of the format to decode it.
+  Unsupported(ImageFormat),
+  /// The bytes are truncated or corrupted.
+  Corrupted { format: ImageFormat, reason: String },
+}
+
+impl ImageFormat {
+  /// Detect the format of the encoded image by its magic bytes.
+  pub fn detect(bytes: &[u8]) -> Option<Self> {
+    match bytes {
+      [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some(ImageFormat::Png),
+      [0xFF, 0xD8, 0xFF, ..] => Some(ImageFormat::Jpeg),
+      [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(ImageFormat::Gif),
+      [b'B', b'M', ..] => Some(ImageFormat::Bmp),
+      [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(ImageFormat::WebP),
+      _ => None,
+    }
+  }
+}
+
+impl std::fmt::Display for ImageDecodeError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ImageDecodeError::UnknownFormat => {
+        write!(f, "unknown image format, only PNG, JPEG, GIF, BMP and WebP are supported")
+      }
+      ImageDecodeError::Unsupported(format) => {
+        write!(f, "the decoder of {format:?} image is not enabled by the feature")
+      }
+      ImageDecodeError::Corrupted { format, reason } => {
+        write!(f, "failed to decode the {format:?} image: {reason}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for ImageDecodeError {}
+
 #[derive(Serialize, Deserialize, PartialEq, Eq)]
 pub struct PixelImage {
   data: Cow<'static, [u8]>,
@@ -43,6 +98,63 @@ impl PixelImage {
     PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8)
   }
 
+  /// Decode an image from the encoded bytes, the format is detected by the
+  /// magic bytes of the data. PNG, JPEG, GIF, BMP and WebP are supported if
+  /// the feature of the format is enabled. For an animated image, only the
+  /// first frame is decoded.
+  #[cfg(feature = "image")]
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageDecodeError> {
+    use ::image::{ImageError, ImageFormat as Fmt, error::ImageFormatHint};
+
+    let format = ImageFormat::detect(bytes).ok_or(ImageDecodeError::UnknownFormat)?;
+    let fmt = match format {
+      ImageFormat::Png => Fmt::Png,
+      ImageFormat::Jpeg => Fmt::Jpeg,
+      ImageFormat::Gif => Fmt::Gif,
+      ImageFormat::Bmp => Fmt::Bmp,
+      ImageFormat::WebP => Fmt::WebP,
+    };
+    let img = ::image::load_from_memory_with_format(bytes, fmt).map_err(|err| match err {
+      ImageError::Unsupported(e) if matches!(e.format_hint(), ImageFormatHint::Exact(_)) => {
+        ImageDecodeError::Unsupported(format)
+      }
+      err => ImageDecodeError::Corrupted { format, reason: err.to_string() },
+    })?;
+    let img = img.to_rgba8();
+    let (width, height) = img.dimensions();
+    Ok(PixelImage::new(img.into_raw().into(), width, height, ColorFormat::Rgba8))
+  }
+
+  /// Return a copy of the image scaled to `width` x `height`. It uses the
+  /// Lanczos filter, so the downscaled image keeps the details without
+  /// aliasing.
+  #[cfg(feature = "image")]
+  pub fn resize(&self, width: u32, height: u32) -> PixelImage {
+    use ::image::{GrayImage, RgbaImage, imageops::FilterType};
+
+    let data: Vec<u8> = match self.format {
+      ColorFormat::Rgba8 => {
+        let img = RgbaImage::from_raw(self.width, self.height, self.data.to_vec()).unwrap();
+        ::image::imageops::resize(&img, width, height, FilterType::Lanczos3).into_raw()
+      }
+      ColorFormat::Alpha8 => {
+        let img = GrayImage::from_raw(self.width, self.height, self.data.to_vec()).unwrap();
+        ::image::imageops::resize(&img, width, height, FilterType::Lanczos3).into_raw()
+      }
+    };
+    PixelImage::new(data.into(), width, height, self.format)
+  }
+
+  /// Generate the square variants of the image for every size in `sizes`,
+  /// it's useful to provide an icon of multiple resolutions to the platform.
+  #[cfg(feature = "image")]
+  pub fn icon_set(&self, sizes: &[u32]) -> Vec<PixelImage> {
+    sizes
+      .iter()
+      .map(|size| self.resize(*size, *size))
+      .collect()
+  }
+
   #[cfg(feature = "png")]
   pub fn write_as_png(
     &self, w: &mut impl std::io::Write,
@@ -76,3 +188,190 @@ impl std::fmt::Debug for PixelImage {
       .finish()
   }
 }
+
+/// An embedded image that is decoded at its first use.
+///
+/// The decoded image is cached as a `Resource`, so the bytes are decoded only
+/// once even if the image is first used by multiple threads at the same time,
+/// and every draw of the image shares one texture. It's usually created by the
+/// `include_crate_png!` macro as a static item.
+pub struct LazyImage {
+  bytes: &'static [u8],
+  decode: fn(&[u8]) -> PixelImage,
+  image: OnceLock<Resource<PixelImage>>,
+}
+
+impl LazyImage {
+  pub const fn new(bytes: &'static [u8], decode: fn(&[u8]) -> PixelImage) -> Self {
+    LazyImage { bytes, decode, image: OnceLock::new() }
+  }
+
+  /// Return the decoded image, decode it if it's the first use.
+  pub fn get(&self) -> &Resource<PixelImage> {
+    self
+      .image
+      .get_or_init(|| Resource::new((self.decode)(self.bytes)))
+  }
+
+  /// Return if the image is already decoded.
+  #[inline]
+  pub fn is_decoded(&self) -> bool { self.image.get().is_some() }
+
+  /// Return the encoded bytes of the image.
+  #[inline]
+  pub fn encoded_bytes(&self) -> &'static [u8] { self.bytes }
+}
+
+impl Deref for LazyImage {
+  type Target = PixelImage;
+
+  fn deref(&self) -> &PixelImage { self.get() }
+}
+
+impl From<&LazyImage> for Resource<PixelImage> {
+  #[inline]
+  fn from(img: &LazyImage) -> Self { img.get().clone() }
+}
+
+impl std::fmt::Debug for LazyImage {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("LazyImage")
+      .field("image", &self.image.get())
+      .finish()
+  }
+}
+
+/// A region of an atlas image, it's usually created by the
+/// `include_crate_sprites!` macro that packs many small images into one atlas.
+#[derive(Debug, Clone, Copy)]
+pub struct Sprite {
+  atlas: &'static LazyImage,
+  rect: DeviceRect,
+}
+
+impl Sprite {
+  #[inline]
+  pub fn new(atlas: &'static LazyImage, rect: DeviceRect) -> Self { Sprite { atlas, rect } }
+
+  /// The atlas image that the sprite is packed in.
+  #[inline]
+  pub fn atlas(&self) -> &'static LazyImage { self.atlas }
+
+  /// The region of the sprite in the atlas image.
+  #[inline]
+  pub fn rect(&self) -> DeviceRect { self.rect }
+
+  #[inline]
+  pub fn size(&self) -> DeviceSize { self.rect.size }
+}
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+  use ::image::{ImageOutputFormat, RgbaImage};
+
+  use super::*;
+
+  fn encode(format: ImageOutputFormat) -> Vec<u8> {
+    let img =
+      RgbaImage::from_fn(2, 3, |x, y| ::image::Rgba([x as u8 * 255, y as u8 * 100, 0, 255]));
+    let mut bytes = std::io::Cursor::new(vec![]);
+    img.write_to(&mut bytes, format).unwrap();
+    bytes.into_inner()
+  }
+
+  #[test]
+  fn decode_by_detected_format() {
+    let cases = [
+      (ImageFormat::Png, encode(ImageOutputFormat::Png)),
+      (ImageFormat::Jpeg, encode(ImageOutputFormat::Jpeg(90))),
+      (ImageFormat::Gif, encode(ImageOutputFormat::Gif)),
+      (ImageFormat::Bmp, encode(ImageOutputFormat::Bmp)),
+    ];
+    for (format, bytes) in cases {
+      assert_eq!(ImageFormat::detect(&bytes), Some(format));
+      let img = PixelImage::from_bytes(&bytes).unwrap();
+      assert_eq!((img.width(), img.height()), (2, 3), "{format:?}");
+      assert_eq!(img.color_format(), ColorFormat::Rgba8);
+    }
+
+    // A 1x1 lossless WebP image.
+    let webp = [
+      0x52, 0x49, 0x46, 0x46, 0x1a, 0x00, 0x00, 0x00, 0x57, 0x45, 0x42, 0x50, 0x56, 0x50, 0x38,
+      0x4c, 0x0d, 0x00, 0x00, 0x00, 0x2f, 0x00, 0x00, 0x00, 0x10, 0x07, 0x10, 0x11, 0x11, 0x88,
+      0x88, 0xfe, 0x07, 0x00,
+    ];
+    assert_eq!(ImageFormat::detect(&webp), Some(ImageFormat::WebP));
+    let img = PixelImage::from_bytes(&webp).unwrap();
+    assert_eq!((img.width(), img.height()), (1, 1));
+  }
+
+  #[test]
+  fn icon_set() {
+    let src = RgbaImage::from_fn(64, 64, |x, y| ::image::Rgba([x as u8 * 4, y as u8 * 4, 0, 255]));
+    let (width, height) = src.dimensions();
+    let src = PixelImage::new(src.into_raw().into(), width, height, ColorFormat::Rgba8);
+
+    let icons = src.icon_set(&[16, 32, 48]);
+    let sizes: Vec<_> = icons
+      .iter()
+      .map(|img| (img.width(), img.height()))
+      .collect();
+    assert_eq!(sizes, [(16, 16), (32, 32), (48, 48)]);
+    assert!(
+      icons
+        .iter()
+        .all(|img| img.pixel_bytes().len() == (img.width() * img.height() * 4) as usize)
+    );
+  }
+
+  #[test]
+  fn decode_error() {
+    let err = PixelImage::from_bytes(b"not an image").unwrap_err();
+    assert!(matches!(err, ImageDecodeError::UnknownFormat));
+    assert_eq!(
+      err.to_string(),
+      "unknown image format, only PNG, JPEG, GIF, BMP and WebP are supported"
+    );
+
+    let png = encode(ImageOutputFormat::Png);
+    let err = PixelImage::from_bytes(&png[..png.len() / 2]).unwrap_err();
+    assert!(matches!(err, ImageDecodeError::Corrupted { format: ImageFormat::Png, .. }));
+  }
+
+  #[test]
+  fn lazy_image_decode_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static DECODE_CNT: AtomicUsize = AtomicUsize::new(0);
+    fn counting_decode(bytes: &[u8]) -> PixelImage {
+      DECODE_CNT.fetch_add(1, Ordering::SeqCst);
+      PixelImage::from_bytes(bytes).unwrap()
+    }
+    static LEAVES: LazyImage =
+      LazyImage::new(include_bytes!("../../gpu/imgs/leaves.png"), counting_decode);
+
+    assert!(!LEAVES.is_decoded());
+    assert_eq!(DECODE_CNT.load(Ordering::SeqCst), 0);
+
+    let barrier = std::sync::Barrier::new(2);
+    let imgs: Vec<_> = std::thread::scope(|s| {
+      let handles: Vec<_> = (0..2)
+        .map(|_| {
+          s.spawn(|| {
+            barrier.wait();
+            LEAVES.get().clone()
+          })
+        })
+        .collect();
+      handles
+        .into_iter()
+        .map(|h| h.join().unwrap())
+        .collect()
+    });
+
+    // Both threads share the same decoded resource.
+    assert_eq!(imgs[0], imgs[1]);
+    assert!(LEAVES.is_decoded());
+    assert_eq!(DECODE_CNT.load(Ordering::SeqCst), 1);
+  }
+}
@@ -17,6 +17,6 @@ pub mod image;
 mod style;
 pub use style::*;
 
-pub use crate::image::PixelImage;
+pub use crate::image::{LazyImage, PixelImage, Sprite};
 mod svg;
-pub use svg::Svg;
+pub use svg::{Svg, SvgBinaryError, SvgFit};
@@ -5,8 +5,8 @@ use ribir_geom::{Angle, DeviceRect, Point, Rect, Size, Transform, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  Brush, Color, Glyph, PixelImage, Svg, VisualGlyphs,
-  color::{LinearGradient, RadialGradient},
+  Brush, Color, Glyph, PixelImage, Sprite, Svg, VisualGlyphs,
+  color::{LinearGradient, PathGradient, RadialGradient},
   font_db::FontDB,
   path::*,
   path_builder::PathBuilder,
@@ -87,6 +87,28 @@ pub enum CommandBrush {
   Image { img: Resource<PixelImage>, opacity: f32 },
   Radial(RadialGradient),
   Linear(LinearGradient),
+  /// A gradient along the length of the stroked path, only used by the stroke
+  /// commands.
+  PathGradient(PathGradient),
+}
+
+/// The mode to blend the colors of a group layer with the backdrop, the
+/// formulas follow the separable blend modes of the W3C compositing spec.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+  /// Paint the source over the backdrop with the normal alpha compositing.
+  #[default]
+  Normal,
+  /// Multiply the source and the backdrop colors, the result is always at
+  /// least as dark as either of them.
+  Multiply,
+  /// Multiply the complements of the source and the backdrop colors, the result
+  /// is always at least as light as either of them.
+  Screen,
+  /// Multiply or screen the colors depending on the backdrop color, the
+  /// highlights and shadows of the backdrop are preserved.
+  Overlay,
 }
 
 #[repr(u32)]
@@ -143,6 +165,9 @@ pub enum PaintCommand {
     /// the bounds of the bundle commands. This is the union of all paint
     /// command
     bounds: Rect,
+    /// How the bundle blends with the content painted before it.
+    #[serde(default)]
+    blend_mode: BlendMode,
     cmds: Resource<Box<[PaintCommand]>>,
   },
 }
@@ -160,6 +185,23 @@ struct PainterState {
   /// The visible boundary of the painter in visual axis, not care about the
   /// transform.
   bounds: Rect,
+  /// The group layer started in this state, all commands painted after it
+  /// will be composited as a whole when the state is restored.
+  layer: Option<GroupLayer>,
+}
+
+#[derive(Clone, Copy)]
+struct GroupLayer {
+  /// The index of the first command that belongs to this layer.
+  cmd_start: usize,
+  /// The clip count of the state when the layer started.
+  clip_cnt: usize,
+  /// The visible boundary of the painter when the layer started.
+  bounds: Rect,
+  /// The alpha to composite the layer with.
+  alpha: f32,
+  /// The mode to blend the layer with the backdrop.
+  blend_mode: BlendMode,
 }
 
 impl PainterState {
@@ -173,6 +215,7 @@ impl PainterState {
       clip_cnt: 0,
       opacity: 1.,
       style: PathStyle::Fill,
+      layer: None,
     }
   }
 }
@@ -234,19 +277,23 @@ impl Painter {
   /// Saves the entire state of the canvas by pushing the current drawing state
   /// onto a stack.
   pub fn save(&mut self) -> &mut Self {
-    let new_state = self.current_state().clone();
+    let mut new_state = self.current_state().clone();
+    new_state.layer = None;
     self.state_stack.push(new_state);
     self
   }
 
+  /// The number of the states in the stack, includes the current state.
+  pub fn state_depth(&self) -> usize { self.state_stack.len() }
+
   /// Restores the most recently saved canvas state by popping the top entry in
   /// the drawing state stack. If there is no saved state, this method does
   /// nothing.
   #[inline]
   pub fn restore(&mut self) {
-    let clip_cnt = self.current_state().clip_cnt;
-    self.state_stack.pop();
-    self.push_n_pop_cmd(clip_cnt - self.current_state().clip_cnt);
+    let PainterState { clip_cnt, layer, .. } = self.state_stack.pop().unwrap();
+    let parent_clip_cnt = self.current_state().clip_cnt;
+    self.close_state(clip_cnt, layer, parent_clip_cnt);
   }
 
   pub fn reset(&mut self) {
@@ -292,6 +339,37 @@ impl Painter {
     self
   }
 
+  /// Apply an alpha to all the subsequent paint commands of the current state
+  /// as a whole group. Unlike [`Painter::apply_alpha`], which applies the
+  /// alpha to every command individually, the commands are composited as a
+  /// single layer when the state is restored, so overlapping content does not
+  /// show through each other.
+  pub fn apply_group_alpha(&mut self, alpha: f32) -> &mut Self {
+    if self.alpha() * alpha <= 0. || alpha >= 1. {
+      return self.apply_alpha(alpha);
+    }
+
+    self.group_layer_mut().alpha *= alpha;
+    self
+  }
+
+  /// Blend all the subsequent paint commands of the current state as a group
+  /// with the content painted before them, the group is composited when the
+  /// state is restored.
+  pub fn set_blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+    if mode != BlendMode::Normal || self.current_state().layer.is_some() {
+      self.group_layer_mut().blend_mode = mode;
+    }
+    self
+  }
+
+  pub fn blend_mode(&self) -> BlendMode {
+    self
+      .current_state()
+      .layer
+      .map_or(BlendMode::Normal, |l| l.blend_mode)
+  }
+
   pub fn alpha(&self) -> f32 { self.current_state().opacity }
 
   pub fn set_alpha(&mut self, alpha: f32) -> &mut Self {
@@ -543,7 +621,8 @@ impl Painter {
     invisible_return!(self);
     let transform = *self.transform();
     let opacity = self.alpha();
-    let cmd = PaintCommand::Bundle { transform, opacity, bounds, cmds };
+    let blend_mode = BlendMode::Normal;
+    let cmd = PaintCommand::Bundle { transform, opacity, bounds, blend_mode, cmds };
     self.commands.push(cmd);
     self
   }
@@ -572,12 +651,15 @@ impl Painter {
             PaintCommand::Path(path)
           }
           PaintCommand::PopClip => PaintCommand::PopClip,
-          PaintCommand::Bundle { transform: b_ts, opacity, bounds, cmds } => PaintCommand::Bundle {
-            transform: transform.then(&b_ts),
-            opacity: alpha * opacity,
-            bounds,
-            cmds,
-          },
+          PaintCommand::Bundle { transform: b_ts, opacity, bounds, blend_mode, cmds } => {
+            PaintCommand::Bundle {
+              transform: transform.then(&b_ts),
+              opacity: alpha * opacity,
+              bounds,
+              blend_mode,
+              cmds,
+            }
+          }
         };
         self.commands.push(cmd);
       }
@@ -623,6 +705,13 @@ impl Painter {
     self
   }
 
+  /// Draw the sprite fitted into `dst_rect`. Only the region of the sprite in
+  /// its atlas is drawn, so the sprites of an atlas share one texture.
+  pub fn draw_sprite(&mut self, sprite: &Sprite, dst_rect: &Rect) -> &mut Self {
+    let src_rect = sprite.rect().to_f32().cast_unit();
+    self.draw_img(sprite.atlas().into(), dst_rect, &Some(src_rect))
+  }
+
   pub fn draw_glyph(&mut self, g: &Glyph, font_size: f32, font_db: &FontDB) -> &mut Self {
     let Some(face) = font_db.try_get_face_data(g.face_id) else { return self };
 
@@ -690,6 +779,22 @@ impl Painter {
     self
   }
 
+  /// Draw the glyphs of the first line along the `path`, see
+  /// [`VisualGlyphs::glyphs_on_path`] for how the glyphs are placed.
+  pub fn draw_glyphs_on_path(
+    &mut self, visual_glyphs: &VisualGlyphs, path: &Path, font_db: &FontDB,
+  ) -> &mut Self {
+    let matrix = *self.transform();
+    for (g, ts) in visual_glyphs.glyphs_on_path(path) {
+      self
+        .set_transform(ts.then(&matrix))
+        .draw_glyph(&g, visual_glyphs.font_size(), font_db);
+    }
+    self.set_transform(matrix);
+
+    self
+  }
+
   fn inner_draw_path(&mut self, path: PaintPath, path_style: PathStyle) -> &mut Self {
     invisible_return!(self);
     let line_width = matches!(path_style, PathStyle::Stroke).then(|| self.line_width());
@@ -706,6 +811,11 @@ impl Painter {
       PathStyle::Stroke => self.stroke_brush().clone(),
     };
 
+    if let (PathStyle::Fill, Brush::PathGradient(_)) = (path_style, &brush) {
+      log::warn!("The path gradient brush only works with stroking, the fill is ignored.");
+      return self;
+    }
+
     if brush.is_visible() {
       let mut brush = CommandBrush::from(brush);
       let painting_style = match path_style {
@@ -749,6 +859,24 @@ impl Painter {
 
   fn stroke_options(&self) -> &StrokeOptions { &self.current_state().stroke_options }
 
+  /// Return the group layer of the current state, start a new one if it does
+  /// not exist.
+  fn group_layer_mut(&mut self) -> &mut GroupLayer {
+    let cmd_start = self.commands.len();
+    let s = self.current_state_mut();
+    if s.layer.is_none() {
+      s.layer = Some(GroupLayer {
+        cmd_start,
+        clip_cnt: s.clip_cnt,
+        bounds: s.bounds,
+        alpha: s.opacity,
+        blend_mode: BlendMode::Normal,
+      });
+      s.opacity = 1.;
+    }
+    s.layer.as_mut().unwrap()
+  }
+
   fn push_n_pop_cmd(&mut self, n: usize) {
     for _ in 0..n {
       if matches!(
@@ -763,12 +891,94 @@ impl Painter {
   }
 
   fn fill_all_pop_clips(&mut self) {
-    let clip_cnt = self.current_state().clip_cnt;
+    // Close the states from the top, so every group layer only wraps its own
+    // clips.
+    for idx in (0..self.state_stack.len()).rev() {
+      let parent_clip_cnt = idx
+        .checked_sub(1)
+        .map_or(0, |p| self.state_stack[p].clip_cnt);
+      let s = &mut self.state_stack[idx];
+      let clip_cnt = s.clip_cnt;
+      let layer = s.layer.take();
+      if let Some(layer) = layer.as_ref() {
+        s.opacity = layer.alpha;
+      }
+      self.close_state(clip_cnt, layer, parent_clip_cnt);
+    }
     self
       .state_stack
       .iter_mut()
       .for_each(|s| s.clip_cnt = 0);
-    self.push_n_pop_cmd(clip_cnt);
+  }
+
+  fn close_state(&mut self, clip_cnt: usize, layer: Option<GroupLayer>, parent_clip_cnt: usize) {
+    if let Some(layer) = layer {
+      self.push_n_pop_cmd(clip_cnt - layer.clip_cnt);
+      self.pack_group_layer(layer);
+      self.push_n_pop_cmd(layer.clip_cnt - parent_clip_cnt);
+    } else {
+      self.push_n_pop_cmd(clip_cnt - parent_clip_cnt);
+    }
+  }
+
+  /// Pack the commands of the layer into a bundle command that composite with
+  /// the layer alpha.
+  fn pack_group_layer(&mut self, layer: GroupLayer) {
+    let GroupLayer { cmd_start, bounds, alpha, blend_mode, .. } = layer;
+    let normal_blend = blend_mode == BlendMode::Normal;
+    if self.commands.len() <= cmd_start || (normal_blend && alpha >= 1.) {
+      return;
+    }
+
+    // A single command does not overlap with anything, apply the alpha to it
+    // directly is equivalent and cheaper.
+    if self.commands.len() == cmd_start + 1 {
+      match self.commands.last_mut() {
+        Some(PaintCommand::Path(PathCommand {
+          action: PaintPathAction::Paint { brush, .. },
+          ..
+        }))
+          if normal_blend =>
+        {
+          brush.apply_alpha(alpha);
+          return;
+        }
+        Some(PaintCommand::Bundle { opacity, blend_mode: mode @ BlendMode::Normal, .. }) => {
+          *opacity *= alpha;
+          *mode = blend_mode;
+          return;
+        }
+        _ => {}
+      }
+    }
+
+    let cmds: Box<[PaintCommand]> = self.commands.drain(cmd_start..).collect();
+    let paint_bounds = cmds
+      .iter()
+      .filter_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand {
+          paint_bounds,
+          action: PaintPathAction::Paint { .. },
+          ..
+        }) => Some(*paint_bounds),
+        PaintCommand::Bundle { transform, bounds, .. } => {
+          Some(transform.outer_transformed_rect(bounds))
+        }
+        _ => None,
+      })
+      .reduce(|a, b| a.union(&b))
+      .and_then(|rect| rect.intersection(&bounds));
+
+    if let Some(bounds) = paint_bounds {
+      let bounds = bounds.round_out();
+      self.commands.push(PaintCommand::Bundle {
+        transform: Transform::identity(),
+        opacity: alpha,
+        bounds,
+        blend_mode,
+        cmds: Resource::new(cmds),
+      });
+    }
   }
 
   fn is_visible_canvas(&self) -> bool {
@@ -791,6 +1001,7 @@ impl From<Brush> for CommandBrush {
       Brush::Image(img) => CommandBrush::Image { img, opacity: 1. },
       Brush::RadialGradient(radial_gradient) => CommandBrush::Radial(radial_gradient),
       Brush::LinearGradient(linear_gradient) => CommandBrush::Linear(linear_gradient),
+      Brush::PathGradient(path_gradient) => CommandBrush::PathGradient(path_gradient),
     }
   }
 }
@@ -882,7 +1093,8 @@ impl CommandBrush {
       CommandBrush::Color(color) => *color = color.apply_alpha(alpha),
       CommandBrush::Image { opacity, .. } => *opacity *= alpha,
       CommandBrush::Radial(RadialGradient { stops, .. })
-      | CommandBrush::Linear(LinearGradient { stops, .. }) => stops
+      | CommandBrush::Linear(LinearGradient { stops, .. })
+      | CommandBrush::PathGradient(PathGradient { stops, .. }) => stops
         .iter_mut()
         .for_each(|s| s.color = s.color.apply_alpha(alpha)),
     }
@@ -919,6 +1131,7 @@ mod test {
   use ribir_geom::rect;
 
   use super::*;
+  use crate::GradientStop;
 
   fn painter() -> Painter { Painter::new(Rect::from_size(Size::new(512., 512.))) }
 
@@ -1022,4 +1235,107 @@ mod test {
       .rect(&rect(0., 0., 10., 10.))
       .fill();
   }
+
+  #[test]
+  fn group_alpha_single_command() {
+    let mut painter = painter();
+    painter
+      .save()
+      .apply_group_alpha(0.5)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+
+    assert_eq!(painter.commands.len(), 1);
+    assert!(matches!(
+      &painter.commands[0],
+      PaintCommand::Path(PathCommand {
+        action: PaintPathAction::Paint { brush: CommandBrush::Color(c), .. }, ..
+      }) if *c == Color::GRAY.apply_alpha(0.5)
+    ));
+  }
+
+  #[test]
+  fn group_alpha_pack_commands() {
+    let mut painter = painter();
+    painter
+      .save()
+      .apply_group_alpha(0.5)
+      .clip(Path::rect(&rect(0., 0., 100., 100.)).into())
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .rect(&rect(5., 5., 10., 10.))
+      .fill()
+      .restore();
+
+    assert_eq!(painter.commands.len(), 1);
+    let PaintCommand::Bundle { opacity, bounds, cmds, .. } = &painter.commands[0] else {
+      panic!("Expect a bundle command.");
+    };
+    assert_eq!(*opacity, 0.5);
+    assert_eq!(*bounds, rect(0., 0., 15., 15.));
+    // clip, two paths and the pop clip.
+    assert_eq!(cmds.len(), 4);
+    assert!(matches!(cmds[3], PaintCommand::PopClip));
+  }
+
+  #[test]
+  fn blend_mode_pack_commands() {
+    let mut painter = painter();
+    painter
+      .save()
+      .set_blend_mode(BlendMode::Multiply)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill();
+    assert_eq!(painter.blend_mode(), BlendMode::Multiply);
+    painter.restore();
+
+    assert_eq!(painter.blend_mode(), BlendMode::Normal);
+    assert_eq!(painter.commands.len(), 1);
+    assert!(matches!(
+      &painter.commands[0],
+      PaintCommand::Bundle { blend_mode: BlendMode::Multiply, cmds, .. } if cmds.len() == 1
+    ));
+  }
+
+  #[test]
+  fn path_gradient_wrap_closed_path() {
+    let stops = vec![GradientStop::new(Color::RED, 0.2), GradientStop::new(Color::BLUE, 0.8)];
+    let pad = PathGradient { stops: stops.clone(), spread_method: SpreadMethod::Pad };
+    assert_eq!(pad.color_at(0.), Color::RED);
+    assert_eq!(pad.color_at(1.), Color::BLUE);
+
+    let repeat = PathGradient { stops, spread_method: SpreadMethod::Repeat };
+    // The start and the end of a closed path meet at the same color.
+    let (start, end) = (repeat.color_at(0.), repeat.color_at(1.));
+    assert!(start.red.abs_diff(end.red) <= 1 && start.blue.abs_diff(end.blue) <= 1);
+    assert_eq!(repeat.color_at(0.2), Color::RED);
+    assert_eq!(repeat.color_at(0.8), Color::BLUE);
+
+    let mut painter = painter();
+    painter
+      .set_stroke_brush(Brush::PathGradient(repeat.clone()))
+      .circle(Point::new(100., 100.), 50.)
+      .stroke();
+    // The whole circle is stroked by one command, the backend samples the
+    // gradient along the path.
+    assert_eq!(painter.commands.len(), 1);
+    assert!(matches!(
+      &painter.commands[0],
+      PaintCommand::Path(PathCommand {
+        action: PaintPathAction::Paint {
+          brush: CommandBrush::PathGradient(_),
+          painting_style: PaintingStyle::Stroke(_)
+        },
+        ..
+      })
+    ));
+
+    // Filling with the path gradient is not supported.
+    painter
+      .set_fill_brush(Brush::PathGradient(repeat))
+      .circle(Point::new(100., 100.), 50.)
+      .fill();
+    assert_eq!(painter.commands.len(), 1);
+  }
 }
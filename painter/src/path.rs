@@ -1,11 +1,16 @@
 use std::ops::Range;
 
+use i_overlay::{
+  core::{fill_rule::FillRule as OverlayFillRule, overlay_rule::OverlayRule},
+  float::single::SingleFloatOverlay,
+};
 use lyon_algorithms::{
-  geom::euclid::SideOffsets2D,
+  geom::{LineSegment, euclid::SideOffsets2D},
+  hit_test::hit_test_path,
   measure::{PathMeasurements, SampleType},
-  path::{Event, Path as LyonPath},
+  path::{Event, Path as LyonPath, iterator::PathIterator},
 };
-use ribir_geom::{Point, Rect, Transform};
+use ribir_geom::{Point, Rect, Transform, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::path_builder::PathBuilder;
@@ -81,6 +86,31 @@ pub enum PathSegment {
   Close(bool),
 }
 
+/// The rule to decide which area is the inside of a path.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Default, Hash)]
+pub enum FillRule {
+  /// A point is inside if the sum of the winding numbers of the edges around
+  /// it is not zero.
+  #[default]
+  NonZero,
+  /// A point is inside if a ray from it crosses the edges an odd number of
+  /// times.
+  EvenOdd,
+}
+
+/// The boolean operation to combine two paths.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize, Hash)]
+pub enum PathOp {
+  /// The area inside either path.
+  Union,
+  /// The area inside both paths.
+  Intersection,
+  /// The area inside the first path but not the second.
+  Difference,
+  /// The area inside exactly one of the paths.
+  Xor,
+}
+
 /// The radius of each corner of a rounded rectangle.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default)]
 pub struct Radius {
@@ -153,6 +183,125 @@ impl Path {
     self.lyon_path.transformed(ts).into()
   }
 
+  /// Returns a path that approximates the curves by line segments, every
+  /// segment is within the `tolerance` of the original curve. The closed
+  /// subpaths are still closed.
+  pub fn flatten(&self, tolerance: f32) -> Self {
+    let mut builder = LyonPath::builder();
+    for e in self.lyon_path.iter().flattened(tolerance) {
+      match e {
+        Event::Begin { at } => {
+          builder.begin(at);
+        }
+        Event::Line { to, .. } => {
+          builder.line_to(to);
+        }
+        Event::End { close, .. } => builder.end(close),
+        Event::Quadratic { .. } | Event::Cubic { .. } => unreachable!(),
+      }
+    }
+    builder.build().into()
+  }
+
+  /// Returns the path of the area that combined this path and `other` by the
+  /// boolean operation `op`.
+  ///
+  /// Both paths are flattened first and the `fill_rule` decides the inside of
+  /// them, so self-intersecting paths are supported. The result only contains
+  /// closed subpaths of line segments, the holes are in the opposite direction
+  /// of the outlines, so it's the same area filled by either fill rule.
+  pub fn boolean_op(&self, other: &Path, op: PathOp, fill_rule: FillRule) -> Self {
+    const TOLERANCE: f32 = 0.01;
+    let subj = self.flatten(TOLERANCE).contours();
+    let clip = other.flatten(TOLERANCE).contours();
+    let rule = match op {
+      PathOp::Union => OverlayRule::Union,
+      PathOp::Intersection => OverlayRule::Intersect,
+      PathOp::Difference => OverlayRule::Difference,
+      PathOp::Xor => OverlayRule::Xor,
+    };
+    let fill_rule = match fill_rule {
+      FillRule::NonZero => OverlayFillRule::NonZero,
+      FillRule::EvenOdd => OverlayFillRule::EvenOdd,
+    };
+
+    let shapes = subj.overlay(&clip, rule, fill_rule);
+    let mut builder = LyonPath::builder();
+    for contour in shapes.iter().flatten() {
+      let mut pts = contour
+        .iter()
+        .map(|[x, y]| lyon_algorithms::geom::point(*x, *y));
+      if let Some(first) = pts.next() {
+        builder.begin(first);
+        pts.for_each(|p| {
+          builder.line_to(p);
+        });
+        builder.end(true);
+      }
+    }
+    builder.build().into()
+  }
+
+  /// The points of every subpath of a flattened path.
+  fn contours(&self) -> Vec<Vec<[f32; 2]>> {
+    let mut contours = vec![];
+    for e in self.lyon_path.iter() {
+      match e {
+        Event::Begin { at } => contours.push(vec![at.to_array()]),
+        Event::Line { to, .. } => {
+          if let Some(c) = contours.last_mut() {
+            c.push(to.to_array());
+          }
+        }
+        Event::End { .. } | Event::Quadratic { .. } | Event::Cubic { .. } => {}
+      }
+    }
+    contours
+  }
+
+  /// Returns whether the `point` is inside the filled area of this path
+  /// decided by `fill_rule`. The open subpaths are filled as if they were
+  /// closed, and the points exactly on the edges are considered inside.
+  pub fn contains(&self, point: Point, fill_rule: FillRule) -> bool {
+    const TOLERANCE: f32 = 0.01;
+    // Points within a thousandth of a pixel are treated as on the edges.
+    if self.outline_distance(point, TOLERANCE) <= 1e-3 {
+      return true;
+    }
+    let fill_rule = match fill_rule {
+      FillRule::NonZero => lyon_algorithms::path::FillRule::NonZero,
+      FillRule::EvenOdd => lyon_algorithms::path::FillRule::EvenOdd,
+    };
+    hit_test_path(&point.cast_unit(), self.lyon_path.iter(), fill_rule, TOLERANCE)
+  }
+
+  /// Returns whether the `point` is on the outline of this path stroked with
+  /// the line `width`.
+  ///
+  /// The stroke is approximated as the area within half of the `width` from
+  /// the outline, the extended corners of the miter joins and square caps are
+  /// not considered.
+  pub fn stroke_contains(&self, point: Point, width: f32) -> bool {
+    self.outline_distance(point, 0.01) <= width / 2.
+  }
+
+  /// The shortest distance from the `point` to the outline of this path.
+  fn outline_distance(&self, point: Point, tolerance: f32) -> f32 {
+    let pt = point.cast_unit();
+    let dist = |from, to| LineSegment { from, to }.distance_to_point(pt);
+    self
+      .lyon_path
+      .iter()
+      .flattened(tolerance)
+      .filter_map(|e| match e {
+        Event::Line { from, to } => Some(dist(from, to)),
+        Event::End { last, first, close: true } => Some(dist(last, first)),
+        Event::Begin { at } => Some(at.distance_to(pt)),
+        _ => None,
+      })
+      .fold(f32::MAX, f32::min)
+  }
+
   /// Create an sampler that can queries point at this path or usb-path of this
   /// path.
   pub fn sampler(&self) -> PathSampler {
@@ -199,6 +348,17 @@ impl Path {
   pub fn stroke_tessellate<Attr>(
     &self, tolerance: f32, options: StrokeOptions, buffer: &mut VertexBuffers<Attr>,
     vertex_ctor: impl Fn(Point) -> Vertex<Attr>,
+  ) {
+    self
+      .stroke_tessellate_with_distance(tolerance, options, buffer, move |pos, _| vertex_ctor(pos));
+  }
+
+  /// Same as `stroke_tessellate`, but the `vertex_ctor` also receives the
+  /// distance of the vertex along the path.
+  #[cfg(feature = "tessellation")]
+  pub fn stroke_tessellate_with_distance<Attr>(
+    &self, tolerance: f32, options: StrokeOptions, buffer: &mut VertexBuffers<Attr>,
+    vertex_ctor: impl Fn(Point, f32) -> Vertex<Attr>,
   ) {
     use lyon_tessellation::{
       BuffersBuilder, StrokeOptions as TessOptions, StrokeTessellator, StrokeVertex,
@@ -229,7 +389,7 @@ impl Path {
         &self.lyon_path,
         &options,
         &mut BuffersBuilder::new(buffer, move |v: StrokeVertex| {
-          vertex_ctor(v.position().cast_unit())
+          vertex_ctor(v.position().cast_unit(), v.advancement())
         }),
       )
       .unwrap();
@@ -245,6 +405,16 @@ impl PathSampler {
   #[inline]
   pub fn distance_sample(&self, dist: f32) -> Point { self.sample(dist, SampleType::Distance) }
 
+  /// Sample point and the unit tangent vector of the path at a given distance
+  /// along the path.
+  pub fn distance_sample_tangent(&self, dist: f32) -> (Point, Vector) {
+    let mut sampler = self
+      .measurements
+      .create_sampler(&self.path, SampleType::Distance);
+    let sample = sampler.sample(dist);
+    (sample.position().cast_unit(), sample.tangent().cast_unit())
+  }
+
   /// Construct a path for a specific rate range of the measured path.
   #[inline]
   pub fn normalized_sub_path(&self, rate_range: Range<f32>) -> Path {
@@ -352,3 +522,118 @@ impl<Attr> Vertex<Attr> {
   #[inline]
   pub fn new(pos: [f32; 2], attr: Attr) -> Self { Self { attr, pos } }
 }
+
+#[cfg(test)]
+mod tests {
+  use lyon_algorithms::geom::CubicBezierSegment;
+  use ribir_geom::Size;
+
+  use super::*;
+
+  #[test]
+  fn flatten_cubic() {
+    let (from, ctrl1, ctrl2, to) =
+      (Point::new(0., 0.), Point::new(10., 40.), Point::new(60., -20.), Point::new(80., 30.));
+    let mut builder = Path::builder();
+    builder
+      .begin_path(from)
+      .bezier_curve_to(ctrl1, ctrl2, to);
+    builder.end_path(true);
+    let path = builder.build();
+
+    let tolerance = 0.25;
+    let flat = path.flatten(tolerance);
+    let segments: Vec<_> = flat.segments().collect();
+    assert!(segments.len() > 3);
+    assert!(segments.iter().all(|s| matches!(
+      s,
+      PathSegment::MoveTo(_) | PathSegment::LineTo(_) | PathSegment::Close(_)
+    )));
+    // The closed subpath is still closed.
+    assert_eq!(segments.last(), Some(&PathSegment::Close(true)));
+
+    // Every point of the line segments is close to the curve.
+    let curve = CubicBezierSegment {
+      from: from.cast_unit(),
+      ctrl1: ctrl1.cast_unit(),
+      ctrl2: ctrl2.cast_unit(),
+      to: to.cast_unit(),
+    };
+    let samples: Vec<Point> = (0..=1000)
+      .map(|i| curve.sample(i as f32 / 1000.).cast_unit())
+      .collect();
+    let points: Vec<Point> = segments
+      .iter()
+      .filter_map(|s| match s {
+        PathSegment::MoveTo(p) | PathSegment::LineTo(p) => Some(*p),
+        _ => None,
+      })
+      .collect();
+    for w in points.windows(2) {
+      let mid = w[0].lerp(w[1], 0.5);
+      let dist = samples
+        .iter()
+        .map(|s| s.distance_to(mid))
+        .fold(f32::MAX, f32::min);
+      assert!(dist <= tolerance + 0.05, "{dist} is out of the tolerance");
+    }
+  }
+
+  #[test]
+  fn difference_of_rects() {
+    let a = Path::rect(&Rect::new(Point::new(0., 0.), Size::new(100., 100.)));
+    let b = Path::rect(&Rect::new(Point::new(50., 50.), Size::new(100., 100.)));
+    let diff = a.boolean_op(&b, PathOp::Difference, FillRule::NonZero);
+
+    let bounds = diff.bounds(None);
+    assert_eq!(bounds, Rect::new(Point::new(0., 0.), Size::new(100., 100.)));
+
+    let contains = |x: f32, y: f32| diff.contains(Point::new(x, y), FillRule::NonZero);
+    assert!(contains(25., 25.));
+    assert!(contains(75., 25.));
+    assert!(contains(25., 75.));
+    // The overlap region is excluded.
+    assert!(!contains(75., 75.));
+    assert!(!contains(120., 120.));
+  }
+
+  fn star() -> Path {
+    // A pentagram drawn by connecting every second vertex of a pentagon.
+    let vertex = |i: usize| {
+      let angle = std::f32::consts::PI * (i as f32 * 0.8 - 0.5);
+      Point::new(50. + 50. * angle.cos(), 50. + 50. * angle.sin())
+    };
+    let mut builder = Path::builder();
+    builder.begin_path(vertex(0));
+    (1..5).for_each(|i| {
+      builder.line_to(vertex(i));
+    });
+    builder.end_path(true);
+    builder.build()
+  }
+
+  #[test]
+  fn star_contains() {
+    let star = star();
+    // The top tip.
+    assert!(star.contains(Point::new(50., 10.), FillRule::EvenOdd));
+    // The concave notch between the top and the right tips.
+    assert!(!star.contains(Point::new(75., 25.), FillRule::EvenOdd));
+    assert!(!star.contains(Point::new(75., 25.), FillRule::NonZero));
+    // The center pentagon is only inside by the nonzero rule.
+    assert!(!star.contains(Point::new(50., 52.), FillRule::EvenOdd));
+    assert!(star.contains(Point::new(50., 52.), FillRule::NonZero));
+    // The points on the edges are inside.
+    assert!(star.contains(Point::new(50., 0.), FillRule::EvenOdd));
+  }
+
+  #[test]
+  fn rect_stroke_contains() {
+    let rect = Path::rect(&Rect::new(Point::new(0., 0.), Size::new(100., 100.)));
+    assert!(rect.stroke_contains(Point::new(50., 1.), 4.));
+    assert!(rect.stroke_contains(Point::new(-1.5, 50.), 4.));
+    assert!(!rect.stroke_contains(Point::new(50., 3.), 4.));
+    assert!(!rect.stroke_contains(Point::new(50., 50.), 4.));
+    assert!(!rect.stroke_contains(Point::new(103., 50.), 4.));
+  }
+}
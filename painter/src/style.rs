@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
   Color, PixelImage,
-  color::{LinearGradient, RadialGradient},
+  color::{LinearGradient, PathGradient, RadialGradient},
 };
 
 /// The brush is used to fill or stroke shapes with color, image, or gradient.
@@ -14,6 +14,9 @@ pub enum Brush {
   Image(Resource<PixelImage>),
   RadialGradient(RadialGradient),
   LinearGradient(LinearGradient),
+  /// A gradient runs along the length of the stroked path. It only works with
+  /// stroking, filling a path with it paints nothing.
+  PathGradient(PathGradient),
 }
 
 impl Brush {
@@ -30,7 +33,8 @@ impl Brush {
       Brush::Color(c) => c.alpha > 0,
       Brush::Image(_) => true,
       Brush::RadialGradient(RadialGradient { ref stops, .. })
-      | Brush::LinearGradient(LinearGradient { ref stops, .. }) => {
+      | Brush::LinearGradient(LinearGradient { ref stops, .. })
+      | Brush::PathGradient(PathGradient { ref stops, .. }) => {
         stops.iter().any(|s| s.color.alpha > 0)
       }
     }
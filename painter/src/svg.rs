@@ -26,6 +26,46 @@ pub struct Svg {
   last: RefCell<Option<StaticSvg>>,
 }
 
+/// How the SVG is fitted to a target size that has a different aspect ratio
+/// from the SVG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SvgFit {
+  /// Scale the SVG to fit inside the target size and keep its aspect ratio,
+  /// it's centered in the target.
+  #[default]
+  Contain,
+  /// Scale the SVG to cover the whole target size and keep its aspect ratio,
+  /// the overflowed part is cut off.
+  Cover,
+  /// Stretch the SVG to fill the target size.
+  Fill,
+}
+
+/// The error of loading an SVG from the binary format.
+#[derive(Debug)]
+pub enum SvgBinaryError {
+  /// The bytes are not in the binary format of `Svg`.
+  InvalidFormat,
+  /// The bytes are written by an incompatible version of the format.
+  IncompatibleVersion { found: u16, expected: u16 },
+  /// The content of the bytes is broken.
+  Corrupted(String),
+}
+
+impl std::fmt::Display for SvgBinaryError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SvgBinaryError::InvalidFormat => write!(f, "not a binary svg"),
+      SvgBinaryError::IncompatibleVersion { found, expected } => {
+        write!(f, "incompatible binary svg version {found}, expected {expected}")
+      }
+      SvgBinaryError::Corrupted(reason) => write!(f, "corrupted binary svg: {reason}"),
+    }
+  }
+}
+
+impl Error for SvgBinaryError {}
+
 #[derive(Clone)]
 struct StaticSvg {
   inherited_fill: Brush,
@@ -39,6 +79,11 @@ impl Svg {
   // and replace it with the actual color when rendering.
   const DYNAMIC_COLOR: Color = Color::from_u32(0x191B1901);
   const DYNAMIC_COLOR_STR: &'static str = "#191B1901";
+  /// The header of the binary format, followed by the version in little
+  /// endian.
+  const BINARY_MAGIC: &'static [u8; 4] = b"RSVG";
+  /// Bump it when the layout of the serialized `Svg` changes.
+  const BINARY_VERSION: u16 = 1;
 
   /// Parse SVG from bytes.
   ///
@@ -122,12 +167,142 @@ impl Svg {
 
   pub fn command_size(&self) -> usize { self.commands.len() }
 
+  /// Return the paint commands that draw the SVG scaled to `size` by the `fit`
+  /// mode. The inherited fill and stroke color use the `brush`.
+  pub fn fit_commands(&self, size: Size, fit: SvgFit, brush: &Brush) -> Box<[PaintCommand]> {
+    let (sx, sy) = (size.width / self.size.width, size.height / self.size.height);
+    let (sx, sy) = match fit {
+      SvgFit::Contain => (sx.min(sy), sx.min(sy)),
+      SvgFit::Cover => (sx.max(sy), sx.max(sy)),
+      SvgFit::Fill => (sx, sy),
+    };
+    let offset = (size - Size::new(self.size.width * sx, self.size.height * sy)) / 2.;
+
+    let mut painter = crate::Painter::new(Rect::from_size(size));
+    painter
+      .set_fill_brush(brush.clone())
+      .set_stroke_brush(brush.clone())
+      .translate(offset.width, offset.height)
+      .scale(sx, sy)
+      .draw_svg(self);
+    let commands = painter.finish().to_owned();
+    commands.into_boxed_slice()
+  }
+
   pub fn serialize(&self) -> Result<String, Box<dyn Error>> {
     // use json replace bincode, because https://github.com/Ogeon/palette/issues/130
     Ok(serde_json::to_string(self)?)
   }
 
   pub fn deserialize(str: &str) -> Result<Self, Box<dyn Error>> { Ok(serde_json::from_str(str)?) }
+
+  /// Serialize the SVG and compress it by gzip, the `level` is from 0 to 9,
+  /// the higher the level the smaller the data but the slower it compresses.
+  #[cfg(feature = "gzip")]
+  pub fn serialize_gzip(&self, level: u32) -> Result<Vec<u8>, Box<dyn Error>> {
+    use std::io::Write;
+
+    let mut encoder =
+      flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+    encoder.write_all(self.serialize()?.as_bytes())?;
+    Ok(encoder.finish()?)
+  }
+
+  /// Deserialize the SVG from the data compressed by [`Svg::serialize_gzip`].
+  #[cfg(feature = "gzip")]
+  pub fn deserialize_gzip(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+    let mut str = String::new();
+    flate2::read::GzDecoder::new(bytes).read_to_string(&mut str)?;
+    Self::deserialize(&str)
+  }
+
+  /// Serialize the SVG and compress it by zstd, the `level` is from 1 to 22.
+  /// It compresses better than gzip in the same speed.
+  #[cfg(feature = "zstd")]
+  pub fn serialize_zstd(&self, level: i32) -> Result<Vec<u8>, Box<dyn Error>> {
+    Ok(zstd::encode_all(self.serialize()?.as_bytes(), level)?)
+  }
+
+  /// Deserialize the SVG from the data compressed by [`Svg::serialize_zstd`].
+  #[cfg(feature = "zstd")]
+  pub fn deserialize_zstd(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+    let data = zstd::decode_all(bytes)?;
+    Self::deserialize(std::str::from_utf8(&data)?)
+  }
+
+  /// Return an SVG that approximates the curves of its paths by line
+  /// segments within the `tolerance` in pixels, it's cheaper to tessellate
+  /// when the SVG is redrawn frequently.
+  pub fn simplify(&self, tolerance: f32) -> Svg {
+    Svg {
+      size: self.size,
+      commands: Resource::new(flatten_commands(&self.commands, tolerance)),
+      inherited_fill: self.inherited_fill,
+      inherited_stroke: self.inherited_stroke,
+      last: RefCell::new(None),
+    }
+  }
+
+  /// Serialize the parsed SVG to a compact binary format, so it can be cached
+  /// and loaded by [`Svg::from_bytes`] without parsing the XML again.
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut bytes = Self::BINARY_MAGIC.to_vec();
+    bytes.extend_from_slice(&Self::BINARY_VERSION.to_le_bytes());
+    ciborium::into_writer(self, &mut bytes).expect("Serializing an svg to memory never fails.");
+    bytes
+  }
+
+  /// Load an SVG from the bytes written by [`Svg::to_bytes`].
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, SvgBinaryError> {
+    let (magic, rest) = bytes
+      .split_at_checked(Self::BINARY_MAGIC.len())
+      .ok_or(SvgBinaryError::InvalidFormat)?;
+    if magic != Self::BINARY_MAGIC {
+      return Err(SvgBinaryError::InvalidFormat);
+    }
+    let (version, content) = rest
+      .split_first_chunk::<2>()
+      .ok_or(SvgBinaryError::InvalidFormat)?;
+    let version = u16::from_le_bytes(*version);
+    if version != Self::BINARY_VERSION {
+      return Err(SvgBinaryError::IncompatibleVersion {
+        found: version,
+        expected: Self::BINARY_VERSION,
+      });
+    }
+    ciborium::from_reader(content).map_err(|e| SvgBinaryError::Corrupted(e.to_string()))
+  }
+}
+
+fn flatten_commands(commands: &[PaintCommand], tolerance: f32) -> Box<[PaintCommand]> {
+  // The tolerance is in pixels, convert it to the space of the path.
+  let local_tolerance = |t: &Transform| {
+    let scale = t.m11.hypot(t.m12).max(t.m21.hypot(t.m22));
+    if scale > 0. { tolerance / scale } else { tolerance }
+  };
+  commands
+    .iter()
+    .map(|cmd| match cmd {
+      PaintCommand::Path(cmd) => {
+        let mut cmd = cmd.clone();
+        cmd.path = cmd
+          .path
+          .flatten(local_tolerance(&cmd.transform))
+          .into();
+        PaintCommand::Path(cmd)
+      }
+      PaintCommand::Bundle { transform, opacity, bounds, blend_mode, cmds } => {
+        PaintCommand::Bundle {
+          transform: *transform,
+          opacity: *opacity,
+          bounds: *bounds,
+          blend_mode: *blend_mode,
+          cmds: Resource::new(flatten_commands(cmds, local_tolerance(transform))),
+        }
+      }
+      PaintCommand::PopClip => PaintCommand::PopClip,
+    })
+    .collect()
 }
 
 fn paint_group(g: &usvg::Group, painter: &mut crate::Painter) {
@@ -302,11 +477,17 @@ fn brush_replace(cmds: &[PaintCommand], fill: &Brush, stroke: &Brush) -> Box<[Pa
         PaintCommand::Path(p)
       }
       PaintCommand::PopClip => PaintCommand::PopClip,
-      PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
+      PaintCommand::Bundle { transform, opacity, bounds, blend_mode, cmds } => {
         let cmds = brush_replace(cmds, fill, stroke);
         let cmds = Resource::new(cmds);
 
-        PaintCommand::Bundle { transform: *transform, opacity: *opacity, bounds: *bounds, cmds }
+        PaintCommand::Bundle {
+          transform: *transform,
+          opacity: *opacity,
+          bounds: *bounds,
+          blend_mode: *blend_mode,
+          cmds,
+        }
       }
     })
     .collect()
@@ -373,3 +554,75 @@ impl Clone for Svg {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::PathSegment;
+
+  const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24">
+    <rect width="12" height="12" fill="red"/>
+    <circle cx="16" cy="16" r="6" stroke="blue" fill="none" stroke-width="2"/>
+  </svg>"#;
+
+  #[test]
+  fn binary_round_trip() {
+    let svg = Svg::parse_from_bytes(SVG.as_bytes(), false, false).unwrap();
+    let bytes = svg.to_bytes();
+    let loaded = Svg::from_bytes(&bytes).unwrap();
+
+    assert_eq!(loaded.size(), svg.size());
+    let brush = Brush::from(Color::BLACK);
+    let commands = |svg: &Svg| {
+      serde_json::to_string(&svg.fit_commands(Size::new(48., 48.), SvgFit::Fill, &brush)).unwrap()
+    };
+    assert_eq!(commands(&loaded), commands(&svg));
+    // The binary format is more compact than the JSON one.
+    assert!(bytes.len() < svg.serialize().unwrap().len());
+  }
+
+  #[test]
+  fn simplify_curves() {
+    let svg = Svg::parse_from_bytes(SVG.as_bytes(), false, false).unwrap();
+    let simple = svg.simplify(0.5);
+    assert_eq!(simple.size(), svg.size());
+    assert_eq!(simple.command_size(), svg.command_size());
+
+    let has_curve = |svg: &Svg| {
+      svg.commands.iter().any(|cmd| match cmd {
+        PaintCommand::Path(cmd) => cmd
+          .path
+          .segments()
+          .any(|s| matches!(s, PathSegment::QuadTo { .. } | PathSegment::CubicTo { .. })),
+        _ => false,
+      })
+    };
+    assert!(has_curve(&svg));
+    assert!(!has_curve(&simple));
+  }
+
+  #[test]
+  fn binary_version_mismatch() {
+    let svg = Svg::parse_from_bytes(SVG.as_bytes(), false, false).unwrap();
+    let mut bytes = svg.to_bytes();
+    bytes[4] = 0xFF;
+    let err = Svg::from_bytes(&bytes).err().unwrap();
+    assert!(matches!(err, SvgBinaryError::IncompatibleVersion { found: 255, expected: 1 }));
+    assert_eq!(err.to_string(), "incompatible binary svg version 255, expected 1");
+
+    assert!(matches!(Svg::from_bytes(b"<svg/>"), Err(SvgBinaryError::InvalidFormat)));
+  }
+
+  #[cfg(feature = "zstd")]
+  #[test]
+  fn zstd_round_trip() {
+    let svg = Svg::parse_from_bytes(SVG.as_bytes(), false, false).unwrap();
+    let json = svg.serialize().unwrap();
+    for level in [1, 3, 22] {
+      let bytes = svg.serialize_zstd(level).unwrap();
+      assert!(bytes.len() < json.len());
+      let loaded = Svg::deserialize_zstd(&bytes).unwrap();
+      assert_eq!(loaded.serialize().unwrap(), json);
+    }
+  }
+}
@@ -83,6 +83,20 @@ pub struct TextStyle {
   pub line_height: f32,
   /// How to handle the visual overflow.
   pub overflow: TextOverflow,
+  /// The indent of the first line of a paragraph, relative to the font size.
+  pub first_line_indent: Em,
+  /// The indent of the lines except the first line of a paragraph, relative to
+  /// the font size.
+  pub hanging_indent: Em,
+}
+
+/// A length relative to the font size, `Em(2.)` is twice the font size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Em(pub f32);
+
+impl Em {
+  /// The length in logical pixels under the `font_size`.
+  pub fn into_pixel(self, font_size: f32) -> f32 { self.0 * font_size }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
@@ -253,6 +267,8 @@ impl Default for TextStyle {
       letter_space: 0.,
       line_height: 16.,
       overflow: <_>::default(),
+      first_line_indent: Em(0.),
+      hanging_indent: Em(0.),
     }
   }
 }
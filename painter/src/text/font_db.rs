@@ -114,6 +114,26 @@ impl FontDB {
     self.static_generic_families();
   }
 
+  /// Maps every generic family to the bundled `Lato` font instead of loading
+  /// the system fonts.
+  ///
+  /// It's used by the tests, so the text is shaped and rendered the same on
+  /// every machine whatever fonts are installed. The fonts loaded after this
+  /// still work, so a test that needs a specific font can load it itself.
+  pub fn load_test_fonts(&mut self) {
+    let name = self
+      .default_fonts
+      .first()
+      .and_then(|id| self.data_base.face(*id))
+      .map(|info| info.families[0].0.clone())
+      .unwrap();
+    self.data_base.set_serif_family(name.clone());
+    self.data_base.set_sans_serif_family(name.clone());
+    self.data_base.set_cursive_family(name.clone());
+    self.data_base.set_fantasy_family(name.clone());
+    self.data_base.set_monospace_family(name);
+  }
+
   /// Performs a CSS-like query and returns the best matched font face id.
   pub fn select_best_match(&self, face: &FontFace) -> Option<ID> {
     let FontFace { families, stretch, style, weight } = face;
@@ -542,6 +562,33 @@ mod tests {
     assert!(face_id.is_some());
   }
 
+  #[test]
+  fn test_fonts_ignore_system_fonts() {
+    let mut db = FontDB::default();
+    db.load_test_fonts();
+    let lato = db.default_fonts()[0];
+    for family in [
+      FontFamily::Serif,
+      FontFamily::SansSerif,
+      FontFamily::Cursive,
+      FontFamily::Fantasy,
+      FontFamily::Monospace,
+    ] {
+      let face = FontFace { families: Box::new([family]), ..<_>::default() };
+      assert_eq!(db.select_best_match(&face), Some(lato));
+    }
+    assert_eq!(db.faces_info_iter().count(), 1);
+
+    // A test can still load the font it needs.
+    let bytes = include_bytes!("../../../fonts/GaramondNo8-Reg.ttf");
+    db.load_from_bytes(bytes.to_vec());
+    let face = FontFace {
+      families: Box::new([FontFamily::Name("GaramondNo8".into())]),
+      ..<_>::default()
+    };
+    assert_ne!(db.select_best_match(&face), Some(lato));
+  }
+
   #[test]
   fn load_sys_fonts() {
     let mut db = FontDB::default();
@@ -4,6 +4,8 @@ use ribir_algo::{FrameCache, Sc, Substr};
 use unicode_bidi::{BidiClass, BidiInfo, Level, LevelRun};
 
 pub struct Paragraph {
+  /// The base embedding level of the paragraph.
+  pub level: Level,
   pub levels: Vec<Level>,
   pub runs: Vec<LevelRun>,
   pub range: Range<usize>,
@@ -32,12 +34,13 @@ impl TextReorder {
         .iter()
         .map(|p| {
           let (levels, runs) = info.visual_runs(p, p.range.clone());
-          Paragraph { levels, runs, range: p.range.clone() }
+          Paragraph { level: p.level, levels, runs, range: p.range.clone() }
         })
         .collect();
 
       if paras.is_empty() || text.ends_with('\r') || text.ends_with('\n') {
         paras.push(Paragraph {
+          level: Level::ltr(),
           levels: vec![],
           runs: vec![Range { start: text.len(), end: text.len() }],
           range: Range { start: text.len(), end: text.len() },
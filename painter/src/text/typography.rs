@@ -58,6 +58,23 @@ pub struct VisualInfos {
   pub visual_height: GlyphUnit,
 }
 
+/// The indent of the lines of a paragraph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ParagraphIndent {
+  /// The indent of the first line of a paragraph.
+  pub first_line: GlyphUnit,
+  /// The indent of the lines except the first line of a paragraph.
+  pub hanging: GlyphUnit,
+}
+
+/// The runs of a paragraph to typography.
+pub struct InputParagraph {
+  pub runs: SmallVec<[InputRun; 1]>,
+  /// If the base direction of the paragraph is right-to-left, its lines are
+  /// indented from the right.
+  pub is_rtl: bool,
+}
+
 /// Typography the glyphs in a bounds.
 pub struct TypographyMan<Paras> {
   line_dir: PlaceLineDirection,
@@ -65,22 +82,29 @@ pub struct TypographyMan<Paras> {
   line_height: GlyphUnit,
   bounds: Size<GlyphUnit>,
   overflow: TextOverflow,
+  indent: ParagraphIndent,
   /// Not directly use text as inputs, but accept glyphs after text shape
   /// because both simple text and rich text can custom compose its glyph runs
   /// by text reorder result and its style .
   inputs: Paras,
   inline_cursor: GlyphUnit,
+  /// The indent of the current line.
+  line_indent: GlyphUnit,
+  /// If the current paragraph is right-to-left.
+  is_rtl_para: bool,
+  /// The advance of the whitespaces at the end of the current line.
+  trailing_whitespace: GlyphUnit,
   visual_lines: SmallVec<[VisualLine; 1]>,
   over_bounds: bool,
 }
 
 impl<Paras> TypographyMan<Paras>
 where
-  Paras: DoubleEndedIterator<Item = SmallVec<[InputRun; 1]>>,
+  Paras: DoubleEndedIterator<Item = InputParagraph>,
 {
   pub fn new(
     inputs: Paras, line_dir: PlaceLineDirection, text_align: TextAlign, line_height: GlyphUnit,
-    bounds: Size<GlyphUnit>, overflow: TextOverflow,
+    bounds: Size<GlyphUnit>, overflow: TextOverflow, indent: ParagraphIndent,
   ) -> Self {
     Self {
       line_dir,
@@ -88,8 +112,12 @@ where
       line_height,
       bounds,
       overflow,
+      indent,
       inputs,
       inline_cursor: GlyphUnit::ZERO,
+      line_indent: GlyphUnit::ZERO,
+      is_rtl_para: false,
+      trailing_whitespace: GlyphUnit::ZERO,
       visual_lines: smallvec![],
       over_bounds: false,
     }
@@ -175,8 +203,10 @@ where
   }
 
   /// consume paragraph and return if early break because over boundary.
-  fn consume_paragraph(&mut self, runs: SmallVec<[InputRun; 1]>) -> bool {
-    self.begin_line();
+  fn consume_paragraph(&mut self, para: InputParagraph) -> bool {
+    let InputParagraph { runs, is_rtl } = para;
+    self.is_rtl_para = is_rtl;
+    self.begin_line(self.indent.first_line);
 
     if self.line_dir.is_horizontal() {
       let mut cursor = VInlineCursor { pos: self.inline_cursor };
@@ -214,8 +244,14 @@ where
     let is_auto_wrap = self.overflow.is_auto_wrap();
 
     let new_line = |this: &mut Self, cursor: &mut dyn InlineCursor| {
+      // The whitespaces at the end of a wrapped line hang over the line end, they
+      // are not counted in the line width, so they don't affect the alignment.
+      // But keep the width of a line that only has whitespaces.
+      if this.trailing_whitespace < this.inline_cursor {
+        this.inline_cursor -= this.trailing_whitespace;
+      }
       this.end_line();
-      this.begin_line();
+      this.begin_line(this.indent.hanging);
       cursor.reset();
     };
 
@@ -223,10 +259,17 @@ where
       let width: GlyphUnit = word
         .clone()
         .fold(GlyphUnit::ZERO, |acc, g| acc + cursor.measure(&g, text));
+      let is_whitespace = word.clone().all(|g| {
+        text[g.cluster as usize..]
+          .chars()
+          .next()
+          .is_some_and(char::is_whitespace)
+      });
 
       if is_auto_wrap
+        && !is_whitespace
         && self.inline_cursor != GlyphUnit::ZERO
-        && self.is_over_line_bound(width + self.inline_cursor)
+        && self.is_over_wrap_bound(width + self.inline_cursor)
       {
         new_line(self, cursor);
       }
@@ -241,9 +284,15 @@ where
 
         if self.inline_cursor == GlyphUnit::ZERO
           || !is_auto_wrap
-          || !self.is_over_line_bound(cursor.position())
+          || is_whitespace
+          || !self.is_over_wrap_bound(cursor.position())
         {
           self.push_glyph(at);
+          if is_whitespace {
+            self.trailing_whitespace += cursor.position() - self.inline_cursor;
+          } else {
+            self.trailing_whitespace = GlyphUnit::ZERO;
+          }
           self.inline_cursor = cursor.position();
           word.next();
         } else {
@@ -258,7 +307,8 @@ where
     line.unwrap().glyphs.push(g)
   }
 
-  fn begin_line(&mut self) {
+  fn begin_line(&mut self, indent: GlyphUnit) {
+    self.line_indent = indent;
     let mut line = VisualLine::default();
     if self.line_dir.is_horizontal() {
       line.width = self.line_height;
@@ -270,15 +320,27 @@ where
 
   fn end_line(&mut self) {
     let line = self.visual_lines.last_mut().unwrap();
+    let indent = self.line_indent;
+    // The indent of a right-to-left paragraph is at the end of the line.
+    if !self.is_rtl_para && indent != GlyphUnit::ZERO {
+      line.glyphs.iter_mut().for_each(|g| {
+        if self.line_dir.is_horizontal() {
+          g.y_offset += indent;
+        } else {
+          g.x_offset += indent;
+        }
+      });
+    }
     // we will reorder the line after consumed all inputs.
     if self.line_dir.is_horizontal() {
-      line.height = self.inline_cursor;
+      line.height = self.inline_cursor + indent;
     } else {
-      line.width = self.inline_cursor;
+      line.width = self.inline_cursor + indent;
     }
     self.over_bounds |= self.is_over_line_bound(self.inline_cursor);
     self.over_bounds |= self.is_last_line_over();
     self.inline_cursor = GlyphUnit::ZERO;
+    self.trailing_whitespace = GlyphUnit::ZERO;
   }
 
   fn is_over_line_bound(&self, position: GlyphUnit) -> bool {
@@ -286,6 +348,13 @@ where
       return false;
     }
 
+    self.is_over_wrap_bound(position)
+  }
+
+  /// Whether the line should wrap before the `position`, the centered text
+  /// wraps too, though it's never over its line bound.
+  fn is_over_wrap_bound(&self, position: GlyphUnit) -> bool {
+    let position = position + self.line_indent;
     if self.line_dir.is_horizontal() {
       self.bounds.height <= position
     } else {
@@ -490,20 +559,100 @@ impl InputRun {
   #[inline]
   fn text(&self) -> &str { &self.shape_result.text }
 
+  /// Split the glyphs into the pieces that can't be broken by the auto wrap.
   fn word_glyphs(&self) -> impl Iterator<Item = impl Iterator<Item = Glyph> + Clone + '_> + '_ {
     let Self { reorder_text, font_size_factor, shape_result, .. } = self;
     let font_size = *font_size_factor * GlyphUnit::PIXELS_PER_EM as f32;
-    reorder_text
-      .split_word_bounds()
-      .scan(0, move |init, w| {
-        let base = *init;
-        *init += w.chars().count();
-        Some(
-          w.chars()
-            .enumerate()
-            .filter_map(move |(idx, _)| shape_result.glyphs.get(base + idx))
-            .map(move |g| g.clone().cast_to(font_size)),
-        )
+    let glyphs = &shape_result.glyphs;
+    // The glyphs of a right-to-left run are in the visual order.
+    let is_rtl = glyphs.len() > 1 && glyphs[0].cluster > glyphs[glyphs.len() - 1].cluster;
+    line_break_segments(reorder_text, is_rtl)
+      .into_iter()
+      .map(move |rg| {
+        rg.filter_map(move |idx| glyphs.get(idx))
+          .map(move |g| g.clone().cast_to(font_size))
       })
   }
 }
+
+/// Split the text into the char ranges between the line break opportunities.
+///
+/// The text is split by the word boundaries first, so the ideographs that have
+/// no space between them can be broken between every character. Then the
+/// segments are joined by the rules of UAX#14 that keep the punctuation with
+/// the characters around them: no break before a closing punctuation and no
+/// break after an opening punctuation.
+///
+/// If `reverse` is true, the text is in the reversed order of its logical
+/// order.
+fn line_break_segments(text: &str, reverse: bool) -> SmallVec<[Range<usize>; 8]> {
+  type BreakRule = fn(char) -> bool;
+  let (keep_with_prev, keep_with_next): (BreakRule, BreakRule) =
+    if reverse { (no_break_after, no_break_before) } else { (no_break_before, no_break_after) };
+
+  let mut segments: SmallVec<[Range<usize>; 8]> = SmallVec::new();
+  let mut last_char = None;
+  let mut start = 0;
+  for w in text.split_word_bounds() {
+    let end = start + w.chars().count();
+    let first = w.chars().next();
+    let join = last_char.is_some_and(keep_with_next) || first.is_some_and(keep_with_prev);
+    match segments.last_mut() {
+      Some(last) if join => last.end = end,
+      _ => segments.push(start..end),
+    }
+    last_char = w.chars().last();
+    start = end;
+  }
+  segments
+}
+
+/// If the char can't be placed at the start of a line. These are the CJK and
+/// ASCII characters of the UAX#14 classes CL (close punctuation), CP (close
+/// parenthesis), EX (exclamation/interrogation), IS (infix separator) and NS
+/// (nonstarter), and the small kana of the class CJ.
+fn no_break_before(c: char) -> bool {
+  matches!(
+    c,
+    // CL & CP
+    ')' | ']' | '}' | '、' | '。' | '，' | '．' | '）' | '］' | '｝' | '｣' | '｠'
+      | '〉' | '》' | '」' | '』' | '】' | '〕' | '〗' | '〙' | '〛' | '〞' | '〟'
+      // EX
+      | '!' | '?' | '！' | '？'
+      // IS
+      | ',' | '.' | ':' | ';' | '：' | '；'
+      // NS
+      | '‼' | '⁇' | '⁈' | '⁉' | '・' | '･' | '〜' | '～' | 'ー' | 'ｰ' | '々' | '〻'
+      | 'ゝ' | 'ゞ' | 'ヽ' | 'ヾ' | '゛' | '゜' | '゠'
+      // CJ
+      | 'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' | 'っ' | 'ゃ' | 'ゅ' | 'ょ' | 'ゎ' | 'ゕ' | 'ゖ'
+      | 'ァ' | 'ィ' | 'ゥ' | 'ェ' | 'ォ' | 'ッ' | 'ャ' | 'ュ' | 'ョ' | 'ヮ' | 'ヵ' | 'ヶ'
+      | 'ㇰ'..='ㇿ' | 'ｧ'..='ｯ'
+  )
+}
+
+/// If the char can't be placed at the end of a line, the CJK and ASCII
+/// characters of the UAX#14 class OP (open punctuation).
+fn no_break_after(c: char) -> bool {
+  matches!(
+    c,
+    '('
+      | '['
+      | '{'
+      | '（'
+      | '［'
+      | '｛'
+      | '｢'
+      | '｟'
+      | '〈'
+      | '《'
+      | '「'
+      | '『'
+      | '【'
+      | '〔'
+      | '〖'
+      | '〘'
+      | '〚'
+      | '〝'
+  )
+}
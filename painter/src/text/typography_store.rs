@@ -2,7 +2,7 @@ use std::{cell::RefCell, ops::Range};
 
 use font_db::GlyphBaseline;
 use ribir_algo::{FrameCache, Sc, Substr};
-use ribir_geom::{Point, Rect, Size};
+use ribir_geom::{Angle, Point, Rect, Size, Transform};
 
 use crate::{
   font_db::FontDB,
@@ -31,6 +31,7 @@ pub struct TypographyKey {
   baseline: GlyphBaseline,
   line_dir: PlaceLineDirection,
   overflow: TextOverflow,
+  indent: ParagraphIndent,
 }
 
 /// Do simple text typography and cache it.
@@ -93,7 +94,15 @@ impl TypographyStore {
     &mut self, text: Substr, style: &TextStyle, bounds: Size, text_align: TextAlign,
     baseline: GlyphBaseline, line_dir: PlaceLineDirection,
   ) -> VisualGlyphs {
-    let TextStyle { font_size, ref font_face, letter_space, line_height, overflow } = *style;
+    let TextStyle {
+      font_size,
+      ref font_face,
+      letter_space,
+      line_height,
+      overflow,
+      first_line_indent,
+      hanging_indent,
+    } = *style;
     // Since we cache the result of the standard font size, we must ensure that all
     // variables are cast relative to this standard font size.
     let scale = font_size / GlyphUnit::PIXELS_PER_EM as f32;
@@ -105,6 +114,10 @@ impl TypographyStore {
       GlyphUnit::from_pixel(letter_space / font_size * GlyphUnit::PIXELS_PER_EM as f32);
     let line_height =
       GlyphUnit::from_pixel(line_height / font_size * GlyphUnit::PIXELS_PER_EM as f32);
+    let indent = ParagraphIndent {
+      first_line: GlyphUnit::from_pixel(first_line_indent.into_pixel(font_size) / scale),
+      hanging: GlyphUnit::from_pixel(hanging_indent.into_pixel(font_size) / scale),
+    };
 
     let info = self.reorder.reorder_text(&text).clone();
     let ids = self
@@ -113,14 +126,15 @@ impl TypographyStore {
       .select_all_match(font_face)
       .into_boxed_slice();
     let runs = [RunKey { ids, line_height, letter_space, text }].into();
-    let key = TypographyKey::new(runs, bounds, text_align, line_dir, overflow, baseline);
+    let key = TypographyKey::new(runs, bounds, text_align, line_dir, overflow, baseline, indent);
     let infos = if let Some(infos) = self.cache.get(&key).cloned() {
       infos
     } else {
       let ids = &key.runs[0].ids;
       let text = &key.runs[0].text;
       let inputs = info.paras.iter().map(|p| {
-        p.runs
+        let runs = p
+          .runs
           .iter()
           .map(|r| {
             let dir = if r.is_empty() || p.levels[r.start].is_ltr() {
@@ -134,10 +148,12 @@ impl TypographyStore {
               .shape_text(&text.substr(r.clone()), ids, dir, baseline);
             InputRun::new(shape_result, 1., letter_space, r.clone())
           })
-          .collect()
+          .collect();
+        InputParagraph { runs, is_rtl: p.level.is_rtl() }
       });
 
-      let t_man = TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow);
+      let t_man =
+        TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow, indent);
       let visual_info = t_man.typography_all();
       let infos = Sc::new(visual_info);
       self.cache.put(key, infos.clone());
@@ -458,6 +474,65 @@ impl VisualGlyphs {
 
   pub fn glyph_row_count(&self) -> usize { self.visual_info.visual_lines.len() }
 
+  /// Return the baseline of the first line in pixel, relative to the top of
+  /// the bounds. It's `None` if there is no glyph or the lines are vertical.
+  pub fn first_baseline(&self) -> Option<f32> {
+    if !self.is_horizontal_line() {
+      return None;
+    }
+    let line = self.visual_info.visual_lines.first()?;
+    let glyph = line.glyphs.first()?;
+    // The glyph is painted in its em box with the baseline at the bottom (see
+    // `Painter::draw_glyph`), so its ascent is one em, not the ascender of its
+    // font. The em boxes of all the fonts share the bottom, so the fallback
+    // glyphs of the line sit on the same baseline.
+    let top = self.to_pixel_value(glyph.y_offset + line.y + self.y);
+    Some(top + self.font_size)
+  }
+
+  /// Layout the glyphs of the first line along the `path`, return every glyph
+  /// with the transform that moves it from its typography position to the
+  /// path.
+  ///
+  /// The glyph is placed at the arc length of its horizontal center, its
+  /// baseline sits on the path and it's rotated to be tangent to the path. The
+  /// glyphs are in visual order, so the right-to-left text reads from the end
+  /// of the path back to its start. The glyphs beyond the length of the path
+  /// are dropped. Vertical lines are not supported and return nothing.
+  pub fn glyphs_on_path(&self, path: &Path) -> Vec<(Glyph, Transform)> {
+    let Some(line) = self
+      .visual_info
+      .visual_lines
+      .first()
+      .filter(|_| self.is_horizontal_line())
+    else {
+      return vec![];
+    };
+
+    let sampler = path.sampler();
+    let length = sampler.length();
+    line
+      .glyphs_iter(true)
+      .map(|g| g.cast_to(self.font_size))
+      .filter(|g| g.glyph_id != NEWLINE_GLYPH_ID)
+      .filter_map(|g| {
+        let bounds = g.bounds();
+        let center = bounds.center().x;
+        if center < 0. || length < center {
+          return None;
+        }
+        // The glyph is drawn in its em box, and the baseline is at the bottom of
+        // the em box.
+        let baseline = bounds.min_y() + self.font_size;
+        let (pos, tangent) = sampler.distance_sample_tangent(center);
+        let ts = Transform::translation(-center, -baseline)
+          .then_rotate(Angle::radians(tangent.y.atan2(tangent.x)))
+          .then_translate(pos.to_vector());
+        Some((g, ts))
+      })
+      .collect()
+  }
+
   fn is_horizontal_line(&self) -> bool { !self.visual_info.line_dir.is_horizontal() }
 }
 
@@ -465,6 +540,7 @@ impl TypographyKey {
   fn new(
     runs: Box<[RunKey]>, bounds: Size<GlyphUnit>, text_align: TextAlign,
     line_dir: PlaceLineDirection, overflow: TextOverflow, baseline: GlyphBaseline,
+    indent: ParagraphIndent,
   ) -> Self {
     let line_width = match overflow {
       // line width is not so important in clip mode, the cache can be use even with difference line
@@ -480,7 +556,7 @@ impl TypographyKey {
       }
     };
 
-    Self { runs, line_width, text_align, line_dir, overflow, baseline }
+    Self { runs, line_width, text_align, line_dir, overflow, baseline, indent }
   }
 }
 
@@ -502,7 +578,15 @@ mod tests {
     FontFace { families: Box::new([FontFamily::Name("DejaVu Sans".into())]), ..<_>::default() }
   }
   fn text_style(font_size: f32, overflow: TextOverflow, letter_space: f32) -> TextStyle {
-    TextStyle { font_size, font_face: test_face(), letter_space, line_height: font_size, overflow }
+    TextStyle {
+      font_size,
+      font_face: test_face(),
+      letter_space,
+      line_height: font_size,
+      overflow,
+      first_line_indent: Em(0.),
+      hanging_indent: Em(0.),
+    }
   }
   fn zero_letter_space_style(font_size: f32, overflow: TextOverflow) -> TextStyle {
     text_style(font_size, overflow, 0.)
@@ -800,6 +884,133 @@ mod tests {
     );
   }
 
+  #[test]
+  fn cjk_auto_wrap() {
+    let style = zero_letter_space_style(16., TextOverflow::AutoWrap);
+    let char_width = typography_text(
+      "我".into(),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    )
+    .visual_rect()
+    .width();
+
+    let glyphs = typography_text(
+      "我喜欢写代码（真的吗）".into(),
+      &style,
+      Size::new(char_width * 5.5, f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+
+    // Breaks between the ideographs without spaces.
+    assert_eq!(glyphs.position_by_cluster("我喜欢写代".len()), (1, 0));
+    // "码（真的吗" fits the second line, but the closing bracket can't be at the
+    // start of a line, so it wraps with the character before it.
+    assert_eq!(glyphs.position_by_cluster("我喜欢写代码（真的".len()), (2, 0));
+    assert_eq!(glyphs.position_by_cluster("我喜欢写代码（真的吗".len()), (2, 1));
+  }
+
+  #[test]
+  fn center_wrap_ignore_trailing_spaces() {
+    let style = zero_letter_space_style(16., TextOverflow::AutoWrap);
+    let typography = |text: &'static str| {
+      typography_text(
+        text.into(),
+        &style,
+        Size::new(80., f32::MAX),
+        TextAlign::Center,
+        PlaceLineDirection::TopToBottom,
+      )
+    };
+    let first_x = |glyphs: &VisualGlyphs| {
+      glyphs.visual_rect().min_x()
+        + glyphs
+          .glyphs()
+          .next()
+          .unwrap()
+          .x_offset
+          .into_pixel()
+    };
+
+    let wrapped = typography("Hello          World");
+    assert_eq!(wrapped.glyph_row_count(), 2);
+    // The trailing spaces hang over the end of the first line.
+    assert_eq!(wrapped.glyph_count(0, false), "Hello          ".len());
+    assert_eq!(wrapped.position_by_cluster("Hello          ".len()), (1, 0));
+
+    let hello = typography("Hello");
+    assert_eq!(first_x(&wrapped), first_x(&hello));
+  }
+
+  #[test]
+  fn first_line_indent() {
+    let mut style = zero_letter_space_style(16., TextOverflow::AutoWrap);
+    style.first_line_indent = Em(2.);
+    let indent = Em(2.).into_pixel(style.font_size);
+    let line_start_x = |glyphs: &VisualGlyphs, row: usize| glyphs.glyph_rect(row, 0).min_x();
+
+    let glyphs = typography_text(
+      "Hello world, hello world, hello world.".into(),
+      &style,
+      Size::new(160., f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+    assert!(glyphs.glyph_row_count() > 2);
+    assert_eq!(line_start_x(&glyphs, 0), indent);
+    assert_eq!(line_start_x(&glyphs, 1), 0.);
+    assert_eq!(line_start_x(&glyphs, 2), 0.);
+
+    // The right-to-left paragraph indents from the right.
+    let text = "עברית";
+    let no_indent = typography_text(
+      text.into(),
+      &zero_letter_space_style(16., TextOverflow::AutoWrap),
+      Size::new(160., f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+    let rtl = typography_text(
+      text.into(),
+      &style,
+      Size::new(160., f32::MAX),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+    assert_eq!(line_start_x(&rtl, 0), 0.);
+    assert_eq!(rtl.visual_rect().width(), no_indent.visual_rect().width() + indent);
+  }
+
+  #[test]
+  fn first_baseline_on_painted_glyph() {
+    let mut store = test_store();
+    let mut style = zero_letter_space_style(16., TextOverflow::Overflow);
+    style.line_height = 24.;
+    for baseline in [GlyphBaseline::Alphabetic, GlyphBaseline::Middle] {
+      let glyphs = store.typography(
+        "H".into(),
+        &style,
+        Size::new(100., 100.),
+        TextAlign::Start,
+        baseline,
+        PlaceLineDirection::TopToBottom,
+      );
+      let rect = glyphs.visual_rect();
+      let mut painter = Painter::new(Rect::from_size(Size::new(100., 100.)));
+      painter.draw_glyphs_in_rect(&glyphs, rect, &store.font_db().borrow());
+      let Some(PaintCommand::Path(cmd)) = painter.finish().last().cloned() else {
+        panic!("the glyph is not painted");
+      };
+
+      // `H` sits on the baseline, so its painted bottom is the baseline.
+      let painted_baseline = cmd.paint_bounds.max_y() - rect.min_y();
+      assert!((glyphs.first_baseline().unwrap() - painted_baseline).abs() < 0.01);
+    }
+  }
+
   #[test]
   fn text_in_different_bounds() {
     let mut store = test_store();
@@ -831,4 +1042,70 @@ mod tests {
     );
     assert_eq!(1, store.cache.len());
   }
+
+  fn upper_arc(radius: f32) -> Path {
+    let center = Point::new(radius, radius);
+    let mut builder = Path::builder();
+    builder.begin_path(Point::new(0., radius));
+    builder.arc_to(center, radius, Angle::radians(-f32::consts::PI), Angle::zero());
+    builder.end_path(false);
+    builder.build()
+  }
+
+  fn glyphs_on_arc(text: &'static str, radius: f32) -> Vec<(Glyph, Transform)> {
+    let style = zero_letter_space_style(14., TextOverflow::Overflow);
+    let visual = typography_text(
+      text.into(),
+      &style,
+      (f32::MAX, f32::MAX).into(),
+      TextAlign::Start,
+      PlaceLineDirection::TopToBottom,
+    );
+    visual.glyphs_on_path(&upper_arc(radius))
+  }
+
+  fn baseline_center(g: &Glyph) -> Point {
+    let bounds = g.bounds();
+    Point::new(bounds.center().x, bounds.min_y() + 14.)
+  }
+
+  #[test]
+  fn glyphs_tangent_to_arc() {
+    let radius = 50.;
+    let center = Point::new(radius, radius);
+    let glyphs = glyphs_on_arc("Hello", radius);
+    assert_eq!(glyphs.len(), 5);
+
+    for (g, ts) in glyphs {
+      // The arc is approximated by quadratic curves, so the glyphs are allowed to
+      // be a little away from the circle. The baseline center of the glyph is on the arc.
+      let pos = ts.transform_point(baseline_center(&g));
+      assert!((pos.distance_to(center) - radius).abs() < 0.2);
+      // The baseline of the glyph is tangent to the arc, about one degree of
+      // error.
+      let dir = ts.transform_vector(ribir_geom::Vector::new(1., 0.));
+      let radial = (pos - center).normalize();
+      assert!(dir.normalize().dot(radial).abs() < 0.02);
+    }
+  }
+
+  #[test]
+  fn drop_glyphs_overflow_path() {
+    // The arc is shorter than the text.
+    let glyphs = glyphs_on_arc("Hello world, Hello world!", 10.);
+    assert!(!glyphs.is_empty());
+    assert!(glyphs.len() < 25);
+  }
+
+  #[test]
+  fn rtl_glyphs_on_path() {
+    let glyphs = glyphs_on_arc("שלום", 50.);
+    assert_eq!(glyphs.len(), 4);
+    let pos_x = |cluster: u32| {
+      let (g, ts) = glyphs.iter().find(|(g, _)| g.cluster == cluster).unwrap();
+      ts.transform_point(baseline_center(g)).x
+    };
+    // The first letter is placed after the last one along the path.
+    assert!(pos_x(0) > pos_x(6));
+  }
 }
@@ -1,6 +1,7 @@
 use std::{cell::RefCell, convert::Infallible, sync::LazyLock};
 
 use ribir_core::{local_sender::LocalSender, prelude::*, timer::Timer, window::WindowId};
+pub use ribir_gpu::{AntiAliasing, PresentMode};
 use winit::{
   event::{ElementState, Event, Ime, KeyEvent, StartCause, WindowEvent},
   event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget},
@@ -19,6 +20,8 @@ pub struct App {
   #[cfg(not(target_family = "wasm"))]
   active_wnd: std::cell::Cell<Option<WindowId>>,
   events_stream: MutRefItemSubject<'static, AppEvent, Infallible>,
+  /// The windows that their frame is delayed by the frame rate limit.
+  delayed_frames: RefCell<std::collections::HashSet<WindowId>>,
 }
 
 /// Attributes for creating a new window.
@@ -32,7 +35,38 @@ pub struct WindowAttributes {
   pub min_size: Option<Size>,
   pub max_size: Option<Size>,
   pub position: Option<Point>,
-  pub icon: Option<Resource<PixelImage>>,
+  pub icon: Option<WindowIcon>,
+  /// Whether the window background is transparent, so the content behind the
+  /// window shows through where the content is transparent.
+  pub transparent: bool,
+  /// The level of the window, for example, always on top of other windows.
+  pub window_level: WindowLevel,
+  /// The anti-aliasing level the window uses to render paths.
+  pub anti_aliasing: AntiAliasing,
+  /// The presentation mode of the window, controls the vsync of the frames.
+  pub present_mode: PresentMode,
+  /// The maximum frames per second the window draws, `None` means no limit.
+  pub max_fps: Option<u32>,
+}
+
+/// The icon of a window. An SVG icon is rasterized by the render backend of
+/// the window at the size the platform displays.
+#[derive(Clone)]
+pub enum WindowIcon {
+  Image(Resource<PixelImage>),
+  Svg(Svg),
+}
+
+impl From<Resource<PixelImage>> for WindowIcon {
+  fn from(img: Resource<PixelImage>) -> Self { WindowIcon::Image(img) }
+}
+
+impl From<PixelImage> for WindowIcon {
+  fn from(img: PixelImage) -> Self { WindowIcon::Image(Resource::new(img)) }
+}
+
+impl From<Svg> for WindowIcon {
+  fn from(svg: Svg) -> Self { WindowIcon::Svg(svg) }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -51,6 +85,14 @@ pub enum AppEvent {
   Hotkey(HotkeyEvent),
   /// The event is sent when the application window focus changed.
   WndFocusChanged(WindowId, bool),
+  /// The event is sent when the gamepads have new events, they're delivered to
+  /// the focused widgets before the event loop waits.
+  #[cfg(feature = "gamepad")]
+  Gamepad,
+  /// The event of a native tray icon, it's delivered to the tray before it's
+  /// emitted by the events stream.
+  #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+  Tray(crate::tray::TrayId, crate::tray::TrayEvent),
   /// The custom event, you can send any data with this event.
   Custom(Box<dyn Any + Send>),
 }
@@ -86,6 +128,55 @@ impl App {
     }
   }
 
+  /// Dispatch the window `event` and emit the widget events it generates.
+  ///
+  /// The pointer moves are emitted when the event loop is about to wait, see
+  /// [`App::emit_deferred_events`], so the moves the platform delivers in a
+  /// batch are coalesced into one event. Any other event emits the pending
+  /// moves before its own events, so the order of the events is kept.
+  fn process_window_event(wnd: &Window, event: WindowEvent) {
+    let defer_emit = matches!(event, WindowEvent::CursorMoved { .. });
+    match event {
+      WindowEvent::KeyboardInput { event, .. } if !wnd.is_pre_editing() => {
+        let KeyEvent { physical_key, logical_key, text, location, repeat, state, .. } = event;
+        wnd.processes_keyboard_event(physical_key, logical_key, repeat, location, state);
+        if state == ElementState::Pressed {
+          if let Some(txt) = text {
+            wnd.processes_receive_chars(txt.to_string());
+          }
+        }
+      }
+      WindowEvent::Ime(ime) => App::process_winit_ime_event(wnd, ime),
+      WindowEvent::MouseInput { state, button, device_id, .. } => {
+        if state == ElementState::Pressed {
+          wnd.force_exit_pre_edit()
+        }
+        wnd.process_mouse_input(device_id, state, button);
+      }
+      #[allow(deprecated)]
+      event => wnd.processes_native_event(event),
+    }
+    if !defer_emit {
+      wnd.emit_events();
+    }
+  }
+
+  /// Emit the events deferred by [`App::process_window_event`] of all windows.
+  fn emit_deferred_events() {
+    // The event handlers may create or close windows.
+    let windows: Vec<_> = AppCtx::windows()
+      .borrow()
+      .values()
+      .cloned()
+      .collect();
+    for wnd in windows {
+      wnd.emit_events();
+      if wnd.need_draw() {
+        request_redraw(&wnd);
+      }
+    }
+  }
+
   fn event_loop_handle(event: Event<AppEvent>, loop_handle: &EventLoopWindowTarget<AppEvent>) {
     match event {
       Event::WindowEvent { event, window_id } => {
@@ -104,8 +195,23 @@ impl App {
             AppCtx::frame_ticks().clone().next(Instant::now());
 
             if let Some(wnd) = AppCtx::get_window(wnd_id) {
-              // if the window is not visible, don't draw it./
-              if wnd.is_visible() != Some(false) {
+              if let Some(at) = wnd.next_frame_time(Instant::now()) {
+                // The frame rate is limited, delay the frame to the allowed time.
+                let delayed = &App::shared().delayed_frames;
+                if delayed.borrow_mut().insert(wnd_id) {
+                  let _ = AppCtx::spawn_local(async move {
+                    Timer::new(at).await;
+                    App::shared()
+                      .delayed_frames
+                      .borrow_mut()
+                      .remove(&wnd_id);
+                    if let Some(wnd) = AppCtx::get_window(wnd_id) {
+                      request_redraw(&wnd);
+                    }
+                  });
+                }
+              } else if wnd.is_visible() != Some(false) {
+                // if the window is not visible, don't draw it.
                 // if this frame is really draw, request another redraw. To make sure the draw
                 // always end with a empty draw and emit an extra tick cycle message.
                 if wnd.draw_frame() {
@@ -119,6 +225,12 @@ impl App {
             wnd.shell_wnd().borrow_mut().on_resize(size);
             request_redraw(&wnd)
           }
+          WindowEvent::ScaleFactorChanged { .. } => {
+            let size = wnd.shell_wnd().borrow().inner_size();
+            wnd.shell_wnd().borrow_mut().on_resize(size);
+            wnd.on_scale_factor_changed();
+            request_redraw(&wnd)
+          }
           WindowEvent::Focused(focused) => {
             let mut event = AppEvent::WndFocusChanged(wnd_id, focused);
 
@@ -127,33 +239,25 @@ impl App {
               .clone()
               .next(&mut event);
           }
-          WindowEvent::KeyboardInput { event, .. } if !wnd.is_pre_editing() => {
-            let KeyEvent { physical_key, logical_key, text, location, repeat, state, .. } = event;
-            wnd.processes_keyboard_event(physical_key, logical_key, repeat, location, state);
-            if state == ElementState::Pressed {
-              if let Some(txt) = text {
-                wnd.processes_receive_chars(txt.to_string());
-              }
-            }
-          }
-          WindowEvent::Ime(ime) => App::process_winit_ime_event(&wnd, ime),
-          WindowEvent::MouseInput { state, button, device_id, .. } => {
-            if state == ElementState::Pressed {
-              wnd.force_exit_pre_edit()
-            }
-            wnd.process_mouse_input(device_id, state, button);
-          }
-          #[allow(deprecated)]
-          event => wnd.processes_native_event(event),
+          event => App::process_window_event(&wnd, event),
         }
-        wnd.emit_events();
 
         if wnd.need_draw() {
           request_redraw(&wnd)
         }
       }
       Event::AboutToWait => {
+        #[cfg(feature = "gamepad")]
+        crate::gamepad::poll_gamepads();
+        App::emit_deferred_events();
+        let had_wnd = AppCtx::has_wnd();
         let run_count = AppCtx::run_until_stalled();
+        // The last window is closed by `Window::close`, don't exit before the
+        // first window is created.
+        if had_wnd && !AppCtx::has_wnd() {
+          loop_handle.exit();
+          return;
+        }
         if run_count > 0 {
           for wnd in AppCtx::windows().borrow().values() {
             request_redraw(wnd);
@@ -172,6 +276,10 @@ impl App {
         Timer::wake_timeout_futures()
       }
       Event::UserEvent(mut event) => {
+        #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+        if let AppEvent::Tray(id, e) = event {
+          crate::tray::dispatch_tray_event(id, e);
+        }
         AppCtx::spawn_local(async move {
           App::shared()
             .events_stream
@@ -219,8 +327,12 @@ impl App {
     let event_loop = event_loop.as_ref().expect(
       " Event loop consumed. You can't create window after `App::exec` called in Web platform.",
     );
+    let max_fps = attrs.max_fps;
+    let (min_size, max_size) = (attrs.min_size, attrs.max_size);
     let shell_wnd = WinitShellWnd::new_with_canvas(canvas, &event_loop, attrs).await;
     let wnd = AppCtx::new_window(Box::new(shell_wnd), root);
+    wnd.set_max_fps(max_fps);
+    init_size_limit(&wnd, min_size, max_size);
     wnd
   }
 
@@ -232,8 +344,12 @@ impl App {
     let event_loop = event_loop.as_ref().expect(
       " Event loop consumed. You can't create window after `App::exec` called in Web platform.",
     );
+    let max_fps = attrs.max_fps;
+    let (min_size, max_size) = (attrs.min_size, attrs.max_size);
     let shell_wnd = WinitShellWnd::new(event_loop, attrs).await;
     let wnd = AppCtx::new_window(Box::new(shell_wnd), root);
+    wnd.set_max_fps(max_fps);
+    init_size_limit(&wnd, min_size, max_size);
 
     #[cfg(not(target_family = "wasm"))]
     if app.active_wnd.get().is_none() {
@@ -275,6 +391,10 @@ impl App {
     #[cfg(not(target_family = "wasm"))]
     {
       use winit::platform::run_on_demand::EventLoopExtRunOnDemand;
+      #[cfg(feature = "gamepad")]
+      crate::gamepad::init_default_backend();
+      #[cfg(feature = "debug")]
+      start_debug_server();
       let mut event_loop = App::shared().event_loop.borrow_mut();
       let _ = event_loop
         .as_mut()
@@ -311,6 +431,7 @@ impl App {
         event_loop_proxy: event_loop.create_proxy(),
         event_loop: RefCell::new(Some(event_loop)),
         events_stream: <_>::default(),
+        delayed_frames: <_>::default(),
         #[cfg(not(target_family = "wasm"))]
         active_wnd: std::cell::Cell::new(None),
       };
@@ -394,8 +515,38 @@ impl AppRunGuard {
   }
 
   /// Sets the icon of the window.
-  pub fn with_icon(&mut self, icon: Resource<PixelImage>) -> &mut Self {
-    self.wnd_attr().icon = Some(icon);
+  pub fn with_icon(&mut self, icon: impl Into<WindowIcon>) -> &mut Self {
+    self.wnd_attr().icon = Some(icon.into());
+    self
+  }
+
+  /// Sets whether the background of the window should be transparent.
+  pub fn with_transparent(&mut self, transparent: bool) -> &mut Self {
+    self.wnd_attr().transparent = transparent;
+    self
+  }
+
+  /// Sets the level of the window.
+  pub fn with_window_level(&mut self, level: WindowLevel) -> &mut Self {
+    self.wnd_attr().window_level = level;
+    self
+  }
+
+  /// Sets the anti-aliasing level of the window.
+  pub fn with_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) -> &mut Self {
+    self.wnd_attr().anti_aliasing = anti_aliasing;
+    self
+  }
+
+  /// Sets the presentation mode of the window.
+  pub fn with_present_mode(&mut self, present_mode: PresentMode) -> &mut Self {
+    self.wnd_attr().present_mode = present_mode;
+    self
+  }
+
+  /// Sets the maximum frames per second of the window.
+  pub fn with_max_fps(&mut self, max_fps: u32) -> &mut Self {
+    self.wnd_attr().max_fps = Some(max_fps);
     self
   }
 
@@ -515,12 +666,70 @@ impl WindowAttributes {
   }
 
   /// Sets the icon of the window.
-  pub fn with_icon(&mut self, icon: Resource<PixelImage>) -> &mut Self {
-    self.icon = Some(icon);
+  pub fn with_icon(&mut self, icon: impl Into<WindowIcon>) -> &mut Self {
+    self.icon = Some(icon.into());
+    self
+  }
+
+  /// Sets whether the background of the window should be transparent. The
+  /// surface is cleared with the transparent color instead of the palette
+  /// surface color, and the platforms without compositing keep the window
+  /// opaque.
+  pub fn with_transparent(&mut self, transparent: bool) -> &mut Self {
+    self.transparent = transparent;
+    self
+  }
+
+  /// Sets the level of the window, use `WindowLevel::AlwaysOnTop` to keep the
+  /// window on top of the other windows. The default is `WindowLevel::Normal`.
+  pub fn with_window_level(&mut self, level: WindowLevel) -> &mut Self {
+    self.window_level = level;
+    self
+  }
+
+  /// Sets the anti-aliasing level the window uses to render paths. The
+  /// default is `AntiAliasing::Msaa6X`.
+  pub fn with_anti_aliasing(&mut self, anti_aliasing: AntiAliasing) -> &mut Self {
+    self.anti_aliasing = anti_aliasing;
+    self
+  }
+
+  /// Sets the presentation mode of the window, the default is
+  /// `PresentMode::AutoVsync`. If the mode is not supported by the platform, a
+  /// fallback mode is used.
+  pub fn with_present_mode(&mut self, present_mode: PresentMode) -> &mut Self {
+    self.present_mode = present_mode;
+    self
+  }
+
+  /// Sets the maximum frames per second the window draws, this caps the frame
+  /// rate of continuous rendering, such as animations, to save power. There is
+  /// no limit by default.
+  pub fn with_max_fps(&mut self, max_fps: u32) -> &mut Self {
+    self.max_fps = Some(max_fps);
     self
   }
 }
 
+fn init_size_limit(wnd: &Window, min_size: Option<Size>, max_size: Option<Size>) {
+  if let Some(min_size) = min_size {
+    wnd.set_min_inner_size(min_size);
+  }
+  if let Some(max_size) = max_size {
+    wnd.set_max_inner_size(max_size);
+  }
+}
+
+/// Start the debug server once, even if the application is executed again.
+#[cfg(all(feature = "debug", not(target_family = "wasm")))]
+fn start_debug_server() {
+  static ONCE: std::sync::Once = std::sync::Once::new();
+  ONCE.call_once(|| match ribir_core::debug_server::start_debug_server_from_env() {
+    Ok(addr) => log::info!("The debug server is listening on {addr}"),
+    Err(err) => log::warn!("Failed to start the debug server: {err}"),
+  });
+}
+
 impl Default for WindowAttributes {
   fn default() -> Self {
     Self {
@@ -534,6 +743,11 @@ impl Default for WindowAttributes {
       visible: true,
       decorations: true,
       icon: None,
+      transparent: false,
+      window_level: WindowLevel::Normal,
+      anti_aliasing: AntiAliasing::default(),
+      present_mode: PresentMode::default(),
+      max_fps: None,
     }
   }
 }
@@ -541,13 +755,18 @@ impl Default for WindowAttributes {
 #[cfg(test)]
 mod tests {
 
-  use ribir_core::{prelude::*, test_helper::*};
-  use winit::event::Ime;
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+  use winit::{
+    dpi::LogicalPosition,
+    event::{DeviceId, ElementState, Ime, MouseButton, WindowEvent},
+  };
 
   use super::App;
 
   #[test]
   fn ime_pre_edit() {
+    reset_test_env!();
+
     let log = Stateful::new(vec![]);
     let log2 = log.clone_writer();
 
@@ -617,4 +836,53 @@ mod tests {
       "on_tap",
     ]);
   }
+
+  #[test]
+  fn coalesce_moves_in_event_loop() {
+    reset_test_env!();
+
+    let (moves, w_moves) = split_value(vec![]);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_pointer_move: move |e| {
+            let samples = e.coalesced_samples().iter().map(|s| s.pos).collect::<Vec<_>>();
+            $w_moves.write().push(samples);
+          },
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    let device_id = unsafe { DeviceId::dummy() };
+    let cursor_moved = |x: f32| WindowEvent::CursorMoved {
+      device_id,
+      position: LogicalPosition::new(x, 10.).to_physical(1.),
+    };
+    // The platform delivers several moves in a batch of events.
+    for x in [10., 20., 30.] {
+      App::process_window_event(&wnd, cursor_moved(x));
+    }
+    assert!(moves.read().is_empty());
+    App::emit_deferred_events();
+    assert_eq!(
+      &*moves.read(),
+      &[vec![Point::new(10., 10.), Point::new(20., 10.), Point::new(30., 10.)]]
+    );
+
+    // Other events emit the pending moves first.
+    App::process_window_event(&wnd, cursor_moved(40.));
+    App::process_window_event(
+      &wnd,
+      WindowEvent::MouseInput {
+        device_id,
+        state: ElementState::Pressed,
+        button: MouseButton::Left,
+      },
+    );
+    assert_eq!(moves.read().len(), 2);
+    assert_eq!(moves.read().last().unwrap(), &[Point::new(40., 10.)]);
+  }
 }
@@ -1,7 +1,11 @@
-use ribir_core::prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, Transform};
+#[cfg(feature = "debug")]
+use ribir_core::window::CaptureFuture;
+use ribir_core::prelude::{
+  Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, PixelImage, Svg, SvgFit, Transform,
+};
 use ribir_gpu::Surface;
 
-use crate::winit_shell_wnd::WinitBackend;
+use crate::{prelude::WindowAttributes, winit_shell_wnd::WinitBackend};
 
 pub struct WgpuBackend<'a> {
   surface: Surface<'a>,
@@ -9,8 +13,11 @@ pub struct WgpuBackend<'a> {
 }
 
 impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
-  async fn new(window: &'a winit::window::Window) -> WgpuBackend<'a> {
-    let (wgpu, surface) = ribir_gpu::WgpuImpl::new(window).await;
+  async fn new(window: &'a winit::window::Window, attrs: &WindowAttributes) -> WgpuBackend<'a> {
+    let (mut wgpu, mut surface) = ribir_gpu::WgpuImpl::new(window).await;
+    wgpu.set_anti_aliasing(attrs.anti_aliasing);
+    surface.set_present_mode(attrs.present_mode, &wgpu);
+    surface.set_transparent(attrs.transparent, &wgpu);
     let size = window.inner_size();
     let size = DeviceSize::new(size.width as i32, size.height as i32);
 
@@ -26,7 +33,12 @@ impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
     }
   }
 
-  fn begin_frame(&mut self, surface_color: Color) { self.backend.begin_frame(surface_color); }
+  fn begin_frame(&mut self, surface_color: Color) {
+    // Let the content behind the window show through the transparent surface.
+    let surface_color =
+      if self.surface.is_transparent() { Color::TRANSPARENT } else { surface_color };
+    self.backend.begin_frame(surface_color);
+  }
 
   fn draw_commands(
     &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
@@ -43,4 +55,25 @@ impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
     self.backend.end_frame();
     self.surface.present();
   }
+
+  async fn rasterize_svg(&mut self, svg: &Svg, size: DeviceSize) -> Option<PixelImage> {
+    self
+      .backend
+      .rasterize_svg(svg, size, SvgFit::Contain, &Color::BLACK.into())
+      .await
+      .ok()
+  }
+
+  #[cfg(feature = "debug")]
+  fn capture(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface_color: Color,
+  ) -> CaptureFuture {
+    let surface_color =
+      if self.surface.is_transparent() { Color::TRANSPARENT } else { surface_color };
+    let img = self
+      .backend
+      .draw_offscreen(size, commands, global_matrix, surface_color);
+    Box::pin(async move { img.await.ok() })
+  }
 }
@@ -0,0 +1,191 @@
+//! The native dialogs to open and save files.
+//!
+//! The dialogs are shown by the [`DialogBackend`] of the application, set it
+//! by [`set_dialog_backend`]. If the `dialog` feature is enabled, the native
+//! dialogs of [rfd](https://docs.rs/rfd) are used by default. Every dialog
+//! returns a future that resolves when the user closes it, and `None` if the
+//! user cancels it.
+
+use std::{cell::RefCell, future::Future, path::PathBuf, pin::Pin, rc::Rc};
+
+use ribir_core::prelude::*;
+
+/// A filter of the file dialog, it only shows the files with the extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileFilter {
+  pub name: CowArc<str>,
+  /// The extensions without the dot, like `png`.
+  pub extensions: Vec<CowArc<str>>,
+}
+
+/// The filters of the file dialog.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Filters(pub Vec<FileFilter>);
+
+/// The options of the file dialog.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FileDialogOptions {
+  pub filters: Filters,
+  pub title: Option<CowArc<str>>,
+  /// The directory the dialog starts in.
+  pub directory: Option<PathBuf>,
+  /// The default file name of the save dialog.
+  pub file_name: Option<CowArc<str>>,
+}
+
+/// The result of a dialog, `None` if it's cancelled.
+pub type DialogFuture = Pin<Box<dyn Future<Output = Option<PathBuf>>>>;
+
+/// The platform integration of the dialogs.
+pub trait DialogBackend {
+  fn open_file(&self, options: FileDialogOptions) -> DialogFuture;
+  fn save_file(&self, options: FileDialogOptions) -> DialogFuture;
+  fn pick_folder(&self, options: FileDialogOptions) -> DialogFuture;
+}
+
+impl Filters {
+  /// Add a filter `name` to show the files with the `extensions`.
+  pub fn add(mut self, name: impl Into<CowArc<str>>, extensions: &[&'static str]) -> Self {
+    let extensions = extensions
+      .iter()
+      .map(|e| CowArc::from(*e))
+      .collect();
+    self
+      .0
+      .push(FileFilter { name: name.into(), extensions });
+    self
+  }
+}
+
+thread_local! {
+  static BACKEND: RefCell<Option<Rc<dyn DialogBackend>>> = const { RefCell::new(None) };
+}
+
+/// Set the backend to show the dialogs.
+pub fn set_dialog_backend(backend: impl DialogBackend + 'static) {
+  BACKEND.with(|b| *b.borrow_mut() = Some(Rc::new(backend)));
+}
+
+/// Show a dialog to choose a file to open.
+pub fn open_file(filters: Filters) -> DialogFuture {
+  open_file_with(FileDialogOptions { filters, ..<_>::default() })
+}
+
+/// Show a dialog to choose a file to open with the full `options`.
+pub fn open_file_with(options: FileDialogOptions) -> DialogFuture {
+  with_backend(|b| b.open_file(options))
+}
+
+/// Show a dialog to choose the path to save a file.
+pub fn save_file(filters: Filters) -> DialogFuture {
+  save_file_with(FileDialogOptions { filters, ..<_>::default() })
+}
+
+/// Show a dialog to choose the path to save a file with the full `options`.
+pub fn save_file_with(options: FileDialogOptions) -> DialogFuture {
+  with_backend(|b| b.save_file(options))
+}
+
+/// Show a dialog to choose a folder.
+pub fn pick_folder() -> DialogFuture { with_backend(|b| b.pick_folder(<_>::default())) }
+
+/// The dialog backend by the native dialogs of [rfd](https://docs.rs/rfd), it's
+/// the default backend if the `dialog` feature is enabled.
+#[cfg(all(feature = "dialog", not(target_family = "wasm")))]
+pub struct NativeDialog;
+
+#[cfg(all(feature = "dialog", not(target_family = "wasm")))]
+impl NativeDialog {
+  fn dialog(options: FileDialogOptions) -> rfd::AsyncFileDialog {
+    let FileDialogOptions { filters, title, directory, file_name } = options;
+    let mut dialog = rfd::AsyncFileDialog::new();
+    for FileFilter { name, extensions } in &filters.0 {
+      let extensions: Vec<&str> = extensions.iter().map(|e| &**e).collect();
+      dialog = dialog.add_filter(&**name, &extensions);
+    }
+    if let Some(title) = title {
+      dialog = dialog.set_title(&*title);
+    }
+    if let Some(directory) = directory {
+      dialog = dialog.set_directory(directory);
+    }
+    if let Some(file_name) = file_name {
+      dialog = dialog.set_file_name(&*file_name);
+    }
+    dialog
+  }
+}
+
+#[cfg(all(feature = "dialog", not(target_family = "wasm")))]
+impl DialogBackend for NativeDialog {
+  fn open_file(&self, options: FileDialogOptions) -> DialogFuture {
+    let dialog = Self::dialog(options);
+    Box::pin(async move { Some(dialog.pick_file().await?.path().to_path_buf()) })
+  }
+
+  fn save_file(&self, options: FileDialogOptions) -> DialogFuture {
+    let dialog = Self::dialog(options);
+    Box::pin(async move { Some(dialog.save_file().await?.path().to_path_buf()) })
+  }
+
+  fn pick_folder(&self, options: FileDialogOptions) -> DialogFuture {
+    let dialog = Self::dialog(options);
+    Box::pin(async move { Some(dialog.pick_folder().await?.path().to_path_buf()) })
+  }
+}
+
+fn with_backend(f: impl FnOnce(&dyn DialogBackend) -> DialogFuture) -> DialogFuture {
+  let backend = BACKEND.with(|b| b.borrow().clone());
+  #[cfg(all(feature = "dialog", not(target_family = "wasm")))]
+  let backend = backend.or_else(|| Some(Rc::new(NativeDialog)));
+  match backend {
+    Some(backend) => f(&*backend),
+    None => {
+      log::warn!("No dialog backend, the dialog is treated as cancelled.");
+      Box::pin(std::future::ready(None))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use futures::executor::block_on;
+
+  use super::*;
+
+  /// A backend that answers the dialogs with the preset path.
+  struct MockDialog(Option<PathBuf>);
+
+  impl DialogBackend for MockDialog {
+    fn open_file(&self, options: FileDialogOptions) -> DialogFuture {
+      let path = self.0.clone().filter(|p| {
+        options.filters.0.iter().any(|f| {
+          f.extensions
+            .iter()
+            .any(|e| p.extension().is_some_and(|ext| ext == &**e))
+        })
+      });
+      Box::pin(std::future::ready(path))
+    }
+
+    fn save_file(&self, _: FileDialogOptions) -> DialogFuture {
+      Box::pin(std::future::ready(self.0.clone()))
+    }
+
+    fn pick_folder(&self, _: FileDialogOptions) -> DialogFuture {
+      Box::pin(std::future::ready(self.0.clone()))
+    }
+  }
+
+  #[test]
+  fn open_file_dialog() {
+    let filters = Filters::default().add("Image", &["png", "jpg"]);
+
+    set_dialog_backend(MockDialog(None));
+    assert_eq!(block_on(open_file(filters.clone())), None);
+
+    set_dialog_backend(MockDialog(Some("/tmp/a.png".into())));
+    assert_eq!(block_on(open_file(filters)), Some(PathBuf::from("/tmp/a.png")));
+    assert_eq!(block_on(pick_folder()), Some(PathBuf::from("/tmp/a.png")));
+  }
+}
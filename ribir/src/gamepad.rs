@@ -0,0 +1,383 @@
+//! The gamepad input of the application.
+//!
+//! The platform integration is abstracted by the [`GamepadBackend`] trait, set
+//! it by [`set_gamepad_backend`]. The gamepads of the platform are read by
+//! [gilrs](https://docs.rs/gilrs) if no backend is set. The application polls
+//! the backend when its event loop wakes up, and bubbles every
+//! [`GamepadEvent`] as a custom event from the focused widget of each window,
+//! like the keyboard events.
+//!
+//! ```no_run
+//! use ribir::prelude::*;
+//!
+//! let _w = fn_widget! {
+//!   let w = @Text { text: "Press any button", auto_focus: true };
+//!   w.on_gamepad_button(|e| println!("{:?}", e.data()))
+//! };
+//! ```
+
+use std::cell::RefCell;
+
+use ribir_core::prelude::*;
+
+use crate::prelude::{App, AppEvent};
+
+/// The id of a connected gamepad, it's assigned by the backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GamepadId(pub usize);
+
+/// The buttons of a gamepad, named by their position in the standard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+  /// The bottom action button, `A` on the Xbox controller.
+  South,
+  /// The right action button, `B` on the Xbox controller.
+  East,
+  /// The top action button, `Y` on the Xbox controller.
+  North,
+  /// The left action button, `X` on the Xbox controller.
+  West,
+  LeftTrigger,
+  LeftTrigger2,
+  RightTrigger,
+  RightTrigger2,
+  Select,
+  Start,
+  Mode,
+  LeftThumb,
+  RightThumb,
+  DPadUp,
+  DPadDown,
+  DPadLeft,
+  DPadRight,
+  /// A button that's not in the standard layout, with the code of the backend.
+  Other(u32),
+}
+
+/// The axes of a gamepad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+  LeftStickX,
+  LeftStickY,
+  RightStickX,
+  RightStickY,
+  LeftZ,
+  RightZ,
+  /// An axis that's not in the standard layout, with the code of the backend.
+  Other(u32),
+}
+
+/// The events of the gamepads that the backend reports.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GamepadEvent {
+  Connected(GamepadId),
+  Disconnected(GamepadId),
+  ButtonDown {
+    gamepad: GamepadId,
+    button: GamepadButton,
+  },
+  ButtonUp {
+    gamepad: GamepadId,
+    button: GamepadButton,
+  },
+  /// An axis is moved, the `value` is in the range of -1 to 1.
+  AxisMove {
+    gamepad: GamepadId,
+    axis: GamepadAxis,
+    value: f32,
+  },
+}
+
+pub type GamepadCustomEvent = CustomEvent<GamepadEvent>;
+
+/// The platform integration of the gamepads.
+///
+/// The backend is polled only when the event loop wakes up, so it should send
+/// [`AppEvent::Gamepad`] by [`App::event_sender`] when it receives new events.
+pub trait GamepadBackend {
+  /// Return the next event of the gamepads, `None` if there is no more event
+  /// now.
+  fn next_event(&mut self) -> Option<GamepadEvent>;
+}
+
+/// The handlers of the gamepad events.
+pub trait GamepadListener: Sized {
+  /// Attaches a handler to all the gamepad events that are bubbled from the
+  /// focused widget.
+  fn on_gamepad_event(self, f: impl FnMut(&mut GamepadCustomEvent) + 'static) -> Self;
+
+  /// Attaches a handler to the gamepad button down and up events.
+  fn on_gamepad_button(self, mut f: impl FnMut(&mut GamepadCustomEvent) + 'static) -> Self {
+    self.on_gamepad_event(move |e| {
+      if matches!(e.data(), GamepadEvent::ButtonDown { .. } | GamepadEvent::ButtonUp { .. }) {
+        f(e)
+      }
+    })
+  }
+}
+
+impl<T> GamepadListener for FatObj<T> {
+  fn on_gamepad_event(self, f: impl FnMut(&mut GamepadCustomEvent) + 'static) -> Self {
+    self.on_custom_concrete_event(f)
+  }
+}
+
+impl GamepadEvent {
+  /// The gamepad that the event comes from.
+  pub fn gamepad(&self) -> GamepadId {
+    match self {
+      GamepadEvent::Connected(id) | GamepadEvent::Disconnected(id) => *id,
+      GamepadEvent::ButtonDown { gamepad, .. }
+      | GamepadEvent::ButtonUp { gamepad, .. }
+      | GamepadEvent::AxisMove { gamepad, .. } => *gamepad,
+    }
+  }
+}
+
+#[derive(Default)]
+struct Gamepads {
+  backend: Option<Box<dyn GamepadBackend>>,
+  connected: Vec<GamepadId>,
+}
+
+thread_local! {
+  static GAMEPADS: RefCell<Gamepads> = RefCell::default();
+}
+
+/// Set the backend to receive the gamepad events from.
+pub fn set_gamepad_backend(backend: impl GamepadBackend + 'static) {
+  GAMEPADS.with(|g| {
+    let mut g = g.borrow_mut();
+    g.backend = Some(Box::new(backend));
+    g.connected.clear();
+  });
+}
+
+/// Return the gamepads that are connected now.
+pub fn connected_gamepads() -> Vec<GamepadId> { GAMEPADS.with(|g| g.borrow().connected.clone()) }
+
+/// Set the gilrs backend if no backend is set.
+pub(crate) fn init_default_backend() {
+  #[cfg(not(target_family = "wasm"))]
+  if GAMEPADS.with(|g| g.borrow().backend.is_none()) {
+    set_gamepad_backend(gilrs_backend::GilrsGamepad::new());
+  }
+}
+
+/// Deliver the pending events of the backend to the windows.
+pub(crate) fn poll_gamepads() {
+  let mut events = vec![];
+  GAMEPADS.with(|g| {
+    let Gamepads { backend, connected } = &mut *g.borrow_mut();
+    let Some(backend) = backend.as_mut() else { return };
+    while let Some(e) = backend.next_event() {
+      let id = e.gamepad();
+      match e {
+        GamepadEvent::Connected(_) if connected.contains(&id) => continue,
+        GamepadEvent::Connected(_) => connected.push(id),
+        GamepadEvent::Disconnected(_) if !connected.contains(&id) => continue,
+        GamepadEvent::Disconnected(_) => connected.retain(|c| *c != id),
+        // The backend may report the gamepads connected before it's set.
+        _ if !connected.contains(&id) => connected.push(id),
+        _ => {}
+      }
+      events.push(e);
+    }
+  });
+
+  if !events.is_empty() {
+    let windows: Vec<_> = AppCtx::windows()
+      .borrow()
+      .values()
+      .cloned()
+      .collect();
+    for wnd in windows {
+      if let Some(focus) = wnd.focusing() {
+        for e in events.iter() {
+          wnd.bubble_custom_event(focus, *e);
+        }
+        wnd.emit_events();
+      }
+    }
+  }
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod gilrs_backend {
+  use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+    mpsc::{Receiver, channel},
+  };
+
+  use gilrs::{Axis, Button, EventType, Gilrs, ev::Code};
+
+  use super::*;
+
+  /// The backend reads the gamepads by gilrs in a background thread, and
+  /// wakes the event loop when there are new events.
+  pub(super) struct GilrsGamepad {
+    events: Receiver<GamepadEvent>,
+    /// If the event loop is woken up but the events are not polled yet.
+    woken: Arc<AtomicBool>,
+  }
+
+  impl GilrsGamepad {
+    pub(super) fn new() -> Self {
+      let (tx, events) = channel();
+      let woken = Arc::new(AtomicBool::new(false));
+      let c_woken = woken.clone();
+      let sender = App::event_sender();
+      let res = std::thread::Builder::new()
+        .name("ribir-gamepad".into())
+        .spawn(move || {
+          let mut gilrs = match Gilrs::new() {
+            Ok(gilrs) => gilrs,
+            Err(err) => {
+              log::warn!("The gamepads are not available: {err}");
+              return;
+            }
+          };
+          let connected = gilrs
+            .gamepads()
+            .map(|(id, _)| GamepadEvent::Connected(GamepadId(id.into())));
+          for e in connected.collect::<Vec<_>>() {
+            let _ = tx.send(e);
+          }
+          loop {
+            let Some(e) = gilrs.next_event_blocking(None).and_then(convert) else {
+              continue;
+            };
+            // The backend is dropped.
+            if tx.send(e).is_err() {
+              break;
+            }
+            if !c_woken.swap(true, Ordering::AcqRel) {
+              sender.send(AppEvent::Gamepad);
+            }
+          }
+        });
+      if let Err(err) = res {
+        log::warn!("Failed to start the gamepad thread: {err}");
+      }
+      Self { events, woken }
+    }
+  }
+
+  impl GamepadBackend for GilrsGamepad {
+    fn next_event(&mut self) -> Option<GamepadEvent> {
+      self.events.try_recv().ok().or_else(|| {
+        // Clear the flag before the last try, so an event sent after it wakes
+        // the event loop again.
+        self.woken.store(false, Ordering::Release);
+        self.events.try_recv().ok()
+      })
+    }
+  }
+
+  fn convert(e: gilrs::Event) -> Option<GamepadEvent> {
+    let gamepad = GamepadId(e.id.into());
+    let e = match e.event {
+      EventType::Connected => GamepadEvent::Connected(gamepad),
+      EventType::Disconnected => GamepadEvent::Disconnected(gamepad),
+      EventType::ButtonPressed(button, code) => {
+        GamepadEvent::ButtonDown { gamepad, button: convert_button(button, code) }
+      }
+      EventType::ButtonReleased(button, code) => {
+        GamepadEvent::ButtonUp { gamepad, button: convert_button(button, code) }
+      }
+      EventType::AxisChanged(axis, value, code) => {
+        GamepadEvent::AxisMove { gamepad, axis: convert_axis(axis, code), value }
+      }
+      _ => return None,
+    };
+    Some(e)
+  }
+
+  fn convert_button(button: Button, code: Code) -> GamepadButton {
+    match button {
+      Button::South => GamepadButton::South,
+      Button::East => GamepadButton::East,
+      Button::North => GamepadButton::North,
+      Button::West => GamepadButton::West,
+      Button::LeftTrigger => GamepadButton::LeftTrigger,
+      Button::LeftTrigger2 => GamepadButton::LeftTrigger2,
+      Button::RightTrigger => GamepadButton::RightTrigger,
+      Button::RightTrigger2 => GamepadButton::RightTrigger2,
+      Button::Select => GamepadButton::Select,
+      Button::Start => GamepadButton::Start,
+      Button::Mode => GamepadButton::Mode,
+      Button::LeftThumb => GamepadButton::LeftThumb,
+      Button::RightThumb => GamepadButton::RightThumb,
+      Button::DPadUp => GamepadButton::DPadUp,
+      Button::DPadDown => GamepadButton::DPadDown,
+      Button::DPadLeft => GamepadButton::DPadLeft,
+      Button::DPadRight => GamepadButton::DPadRight,
+      _ => GamepadButton::Other(code.into_u32()),
+    }
+  }
+
+  fn convert_axis(axis: Axis, code: Code) -> GamepadAxis {
+    match axis {
+      Axis::LeftStickX => GamepadAxis::LeftStickX,
+      Axis::LeftStickY => GamepadAxis::LeftStickY,
+      Axis::RightStickX => GamepadAxis::RightStickX,
+      Axis::RightStickY => GamepadAxis::RightStickY,
+      Axis::LeftZ => GamepadAxis::LeftZ,
+      Axis::RightZ => GamepadAxis::RightZ,
+      _ => GamepadAxis::Other(code.into_u32()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{collections::VecDeque, rc::Rc};
+
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  struct MockGamepad(Rc<RefCell<VecDeque<GamepadEvent>>>);
+
+  impl GamepadBackend for MockGamepad {
+    fn next_event(&mut self) -> Option<GamepadEvent> { self.0.borrow_mut().pop_front() }
+  }
+
+  #[test]
+  fn button_press_to_handler() {
+    reset_test_env!();
+
+    let queue = Rc::new(RefCell::new(VecDeque::new()));
+    set_gamepad_backend(MockGamepad(queue.clone()));
+
+    let (buttons, w_buttons) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      let w = @MockBox { size: Size::zero(), auto_focus: true };
+      w.on_gamepad_button(move |e| $w_buttons.write().push(*e.data()))
+    });
+    wnd.draw_frame();
+
+    let pad = GamepadId(1);
+    queue.borrow_mut().extend([
+      GamepadEvent::Connected(pad),
+      GamepadEvent::AxisMove { gamepad: pad, axis: GamepadAxis::LeftStickX, value: 0.5 },
+      GamepadEvent::ButtonDown { gamepad: pad, button: GamepadButton::South },
+    ]);
+    poll_gamepads();
+    wnd.run_frame_tasks();
+
+    assert_eq!(
+      *buttons.read(),
+      [GamepadEvent::ButtonDown { gamepad: pad, button: GamepadButton::South }]
+    );
+    assert_eq!(connected_gamepads(), [pad]);
+
+    // Unplugging the gamepad removes it, and a repeated disconnect is ignored.
+    queue
+      .borrow_mut()
+      .extend([GamepadEvent::Disconnected(pad), GamepadEvent::Disconnected(pad)]);
+    poll_gamepads();
+    assert!(connected_gamepads().is_empty());
+  }
+}
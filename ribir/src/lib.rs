@@ -3,6 +3,9 @@ pub use ribir_core as core;
 pub use ribir_widgets as widgets;
 pub mod app;
 mod backends;
+pub mod dialog;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod clipboard;
@@ -12,6 +15,8 @@ pub use ribir_material as material;
 
 mod platform;
 pub use platform::*;
+#[cfg(all(feature = "tray", not(target_family = "wasm")))]
+pub mod tray;
 pub mod prelude {
   pub use ribir_core::prelude::*;
 
@@ -20,4 +25,8 @@ pub mod prelude {
   #[cfg(feature = "widgets")]
   pub use super::widgets::prelude::*;
   pub use crate::app::*;
+  #[cfg(feature = "gamepad")]
+  pub use crate::gamepad::*;
+  #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+  pub use crate::tray::*;
 }
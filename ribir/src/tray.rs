@@ -0,0 +1,461 @@
+//! The system tray icon of the application, it shows an icon, a tooltip and a
+//! context menu in the notification area of the platform.
+//!
+//! The platform integration is abstracted by the [`TrayBackend`] trait. On
+//! Windows and macOS, [`Tray::new`] creates a native tray icon, its events are
+//! sent to the event loop as [`AppEvent::Tray`](crate::prelude::AppEvent) and
+//! delivered to the tray that they belong to. The events of a custom backend
+//! are delivered by [`Tray::dispatch`].
+
+use std::{
+  cell::{Cell, RefCell},
+  collections::HashMap,
+  rc::{Rc, Weak},
+};
+
+use ribir_core::prelude::*;
+
+/// The error of creating a tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayError {
+  /// The platform doesn't support the tray icon.
+  Unsupported,
+  /// The platform fails to create the tray icon.
+  Platform(String),
+}
+
+impl std::fmt::Display for TrayError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TrayError::Unsupported => write!(f, "the system tray is not supported on this platform"),
+      TrayError::Platform(reason) => write!(f, "failed to create the tray icon: {reason}"),
+    }
+  }
+}
+
+impl std::error::Error for TrayError {}
+
+/// The id of a tray icon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrayId(usize);
+
+/// The id of a menu item in the tray menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrayMenuItemId(usize);
+
+/// The description of a menu item that the backend displays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrayMenuEntry {
+  pub id: TrayMenuItemId,
+  pub label: CowArc<str>,
+  pub enabled: bool,
+}
+
+/// The events of the tray that the platform reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+  /// The tray icon is clicked.
+  Click,
+  /// A menu item of the tray menu is clicked.
+  MenuItemClick(TrayMenuItemId),
+}
+
+/// The platform integration of the tray icon.
+pub trait TrayBackend {
+  fn set_icon(&mut self, icon: &PixelImage);
+  fn set_tooltip(&mut self, tooltip: &str);
+  /// Replace the context menu of the tray with the `items`.
+  fn set_menu(&mut self, items: &[TrayMenuEntry]);
+}
+
+struct TrayMenuItem {
+  entry: TrayMenuEntry,
+  on_click: Box<dyn FnMut()>,
+}
+
+#[derive(Default)]
+struct TrayCallbacks {
+  items: Vec<TrayMenuItem>,
+  on_click: Option<Box<dyn FnMut()>>,
+}
+
+thread_local! {
+  static TRAYS: RefCell<HashMap<TrayId, Weak<RefCell<TrayCallbacks>>>> =
+    RefCell::new(HashMap::new());
+  static NEXT_TRAY_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A system tray icon.
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// if let Ok(mut tray) = Tray::new() {
+///   tray
+///     .set_tooltip("My App")
+///     .add_menu_item("Start", || println!("Start"));
+/// }
+/// ```
+pub struct Tray {
+  id: TrayId,
+  backend: Box<dyn TrayBackend>,
+  callbacks: Rc<RefCell<TrayCallbacks>>,
+}
+
+impl Tray {
+  /// Create a tray icon by the backend of the current platform.
+  ///
+  /// On macOS, the tray icon should be created after the application starts
+  /// running.
+  ///
+  /// Return [`TrayError::Unsupported`] if there is no tray backend for the
+  /// platform.
+  pub fn new() -> Result<Self, TrayError> {
+    #[cfg(any(windows, target_os = "macos"))]
+    {
+      let id = Self::alloc_id();
+      let backend = native::NativeTray::new(id)?;
+      Ok(Self::with_id(id, backend))
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    Err(TrayError::Unsupported)
+  }
+
+  /// Create a tray icon by a custom `backend`.
+  pub fn with_backend(backend: impl TrayBackend + 'static) -> Self {
+    Self::with_id(Self::alloc_id(), backend)
+  }
+
+  /// The id of the tray, it's carried by the events of the native tray.
+  pub fn id(&self) -> TrayId { self.id }
+
+  pub fn set_icon(&mut self, icon: &PixelImage) -> &mut Self {
+    self.backend.set_icon(icon);
+    self
+  }
+
+  pub fn set_tooltip(&mut self, tooltip: &str) -> &mut Self {
+    self.backend.set_tooltip(tooltip);
+    self
+  }
+
+  /// Set the callback when the tray icon is clicked.
+  pub fn on_click(&mut self, f: impl FnMut() + 'static) -> &mut Self {
+    self.callbacks.borrow_mut().on_click = Some(Box::new(f));
+    self
+  }
+
+  /// Append a menu item to the tray menu, `on_click` is called when it's
+  /// clicked.
+  pub fn add_menu_item(
+    &mut self, label: impl Into<CowArc<str>>, on_click: impl FnMut() + 'static,
+  ) -> TrayMenuItemId {
+    let mut callbacks = self.callbacks.borrow_mut();
+    let id = TrayMenuItemId(callbacks.items.len());
+    let entry = TrayMenuEntry { id, label: label.into(), enabled: true };
+    callbacks
+      .items
+      .push(TrayMenuItem { entry, on_click: Box::new(on_click) });
+    drop(callbacks);
+    self.update_menu();
+    id
+  }
+
+  /// Enable or disable a menu item, a disabled item can't be clicked.
+  pub fn set_menu_item_enabled(&mut self, id: TrayMenuItemId, enabled: bool) -> &mut Self {
+    let changed = self
+      .callbacks
+      .borrow_mut()
+      .items
+      .get_mut(id.0)
+      .is_some_and(|item| std::mem::replace(&mut item.entry.enabled, enabled) != enabled);
+    if changed {
+      self.update_menu();
+    }
+    self
+  }
+
+  /// Deliver an event of the platform to the tray, and call its callback.
+  pub fn dispatch(&mut self, event: TrayEvent) { TrayCallbacks::dispatch(&self.callbacks, event); }
+
+  fn alloc_id() -> TrayId {
+    NEXT_TRAY_ID.with(|next| {
+      let id = next.get();
+      next.set(id + 1);
+      TrayId(id)
+    })
+  }
+
+  fn with_id(id: TrayId, backend: impl TrayBackend + 'static) -> Self {
+    let callbacks = Rc::new(RefCell::new(TrayCallbacks::default()));
+    TRAYS.with(|trays| {
+      trays
+        .borrow_mut()
+        .insert(id, Rc::downgrade(&callbacks))
+    });
+    Self { id, backend: Box::new(backend), callbacks }
+  }
+
+  fn update_menu(&mut self) {
+    let entries: Vec<_> = self
+      .callbacks
+      .borrow()
+      .items
+      .iter()
+      .map(|item| item.entry.clone())
+      .collect();
+    self.backend.set_menu(&entries);
+  }
+}
+
+impl Drop for Tray {
+  fn drop(&mut self) { TRAYS.with(|trays| trays.borrow_mut().remove(&self.id)); }
+}
+
+impl TrayCallbacks {
+  /// Call the callback of the `event`. The callback is taken out during the
+  /// call, so it can modify the tray.
+  fn dispatch(this: &Rc<RefCell<Self>>, event: TrayEvent) {
+    let mut callbacks = this.borrow_mut();
+    let slot = match event {
+      TrayEvent::Click => callbacks.on_click.as_mut(),
+      TrayEvent::MenuItemClick(id) => callbacks
+        .items
+        .get_mut(id.0)
+        .filter(|item| item.entry.enabled)
+        .map(|item| &mut item.on_click),
+    };
+    let Some(slot) = slot else { return };
+    let mut f = std::mem::replace(slot, Box::new(|| {}));
+    drop(callbacks);
+    f();
+
+    let mut callbacks = this.borrow_mut();
+    let slot = match event {
+      TrayEvent::Click => callbacks.on_click.as_mut(),
+      TrayEvent::MenuItemClick(id) => callbacks
+        .items
+        .get_mut(id.0)
+        .map(|item| &mut item.on_click),
+    };
+    if let Some(slot) = slot {
+      *slot = f;
+    }
+  }
+}
+
+/// Deliver an event of the native tray to the tray of `id`, the event is
+/// ignored if the tray is dropped.
+pub(crate) fn dispatch_tray_event(id: TrayId, event: TrayEvent) {
+  let callbacks = TRAYS.with(|trays| trays.borrow().get(&id).and_then(Weak::upgrade));
+  if let Some(callbacks) = callbacks {
+    TrayCallbacks::dispatch(&callbacks, event);
+  }
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+mod native {
+  use std::sync::{Mutex, Once};
+
+  use tray_icon::{
+    Icon, MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+    menu::{Menu, MenuEvent, MenuId, MenuItem},
+  };
+
+  use super::*;
+  use crate::prelude::{App, AppEvent};
+
+  const ID_PREFIX: &str = "ribir-tray";
+
+  pub(super) struct NativeTray {
+    id: TrayId,
+    icon: TrayIcon,
+  }
+
+  impl NativeTray {
+    pub(super) fn new(id: TrayId) -> Result<Self, TrayError> {
+      forward_events();
+      let icon = TrayIconBuilder::new()
+        .with_id(format!("{ID_PREFIX}-{}", id.0))
+        .build()
+        .map_err(|err| TrayError::Platform(err.to_string()))?;
+      Ok(Self { id, icon })
+    }
+  }
+
+  impl TrayBackend for NativeTray {
+    fn set_icon(&mut self, icon: &PixelImage) {
+      let rgba = match icon.color_format() {
+        image::ColorFormat::Rgba8 => icon.pixel_bytes().to_vec(),
+        image::ColorFormat::Alpha8 => icon
+          .pixel_bytes()
+          .iter()
+          .flat_map(|a| [0, 0, 0, *a])
+          .collect(),
+      };
+      let res = Icon::from_rgba(rgba, icon.width(), icon.height())
+        .map_err(|err| err.to_string())
+        .and_then(|icon| {
+          self
+            .icon
+            .set_icon(Some(icon))
+            .map_err(|err| err.to_string())
+        });
+      if let Err(err) = res {
+        log::warn!("Failed to set the tray icon: {err}");
+      }
+    }
+
+    fn set_tooltip(&mut self, tooltip: &str) {
+      if let Err(err) = self.icon.set_tooltip(Some(tooltip)) {
+        log::warn!("Failed to set the tray tooltip: {err}");
+      }
+    }
+
+    fn set_menu(&mut self, items: &[TrayMenuEntry]) {
+      let menu = Menu::new();
+      for entry in items {
+        let id = MenuId::new(format!("{ID_PREFIX}-{}-{}", self.id.0, entry.id.0));
+        let item = MenuItem::with_id(id, &*entry.label, entry.enabled, None);
+        if let Err(err) = menu.append(&item) {
+          log::warn!("Failed to append the tray menu item: {err}");
+        }
+      }
+      self.icon.set_menu(Some(Box::new(menu)));
+    }
+  }
+
+  /// Forward the events of the native trays to the event loop, they are
+  /// delivered to their tray by the application.
+  fn forward_events() {
+    static FORWARD: Once = Once::new();
+    FORWARD.call_once(|| {
+      let sender = Mutex::new(App::event_sender());
+      let send = move |id: TrayId, event: TrayEvent| {
+        if let Ok(sender) = sender.lock() {
+          sender.send(AppEvent::Tray(id, event));
+        }
+      };
+      let send = std::sync::Arc::new(send);
+      let c_send = send.clone();
+      TrayIconEvent::set_event_handler(Some(move |event| {
+        if let TrayIconEvent::Click {
+          id,
+          button: MouseButton::Left,
+          button_state: MouseButtonState::Up,
+          ..
+        } = event
+        {
+          if let Some(id) = parse_id(&id.0) {
+            c_send(id, TrayEvent::Click);
+          }
+        }
+      }));
+      MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+        let Some((tray, item)) = event.id.0.rsplit_once('-') else { return };
+        if let (Some(tray), Ok(item)) = (parse_id(tray), item.parse()) {
+          send(tray, TrayEvent::MenuItemClick(TrayMenuItemId(item)));
+        }
+      }));
+    });
+  }
+
+  fn parse_id(id: &str) -> Option<TrayId> {
+    let id = id.strip_prefix(ID_PREFIX)?.strip_prefix('-')?;
+    id.parse().ok().map(TrayId)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  use super::*;
+
+  #[derive(Default)]
+  struct MockTray {
+    tooltip: String,
+    menu: Vec<TrayMenuEntry>,
+  }
+
+  impl TrayBackend for Rc<RefCell<MockTray>> {
+    fn set_icon(&mut self, _: &PixelImage) {}
+
+    fn set_tooltip(&mut self, tooltip: &str) { self.borrow_mut().tooltip = tooltip.to_string(); }
+
+    fn set_menu(&mut self, items: &[TrayMenuEntry]) { self.borrow_mut().menu = items.to_vec(); }
+  }
+
+  #[test]
+  fn menu_item_click() {
+    let mock = Rc::new(RefCell::new(MockTray::default()));
+    let mut tray = Tray::with_backend(mock.clone());
+    let clicks = Rc::new(RefCell::new(vec![]));
+
+    tray.set_tooltip("Pomodoro");
+    let c_clicks = clicks.clone();
+    let start = tray.add_menu_item("Start", move || c_clicks.borrow_mut().push("start"));
+    let c_clicks = clicks.clone();
+    let quit = tray.add_menu_item("Quit", move || c_clicks.borrow_mut().push("quit"));
+    let c_clicks = clicks.clone();
+    tray.on_click(move || c_clicks.borrow_mut().push("icon"));
+
+    assert_eq!(mock.borrow().tooltip, "Pomodoro");
+    let labels: Vec<_> = mock
+      .borrow()
+      .menu
+      .iter()
+      .map(|e| e.label.to_string())
+      .collect();
+    assert_eq!(labels, ["Start", "Quit"]);
+
+    tray.dispatch(TrayEvent::MenuItemClick(quit));
+    tray.dispatch(TrayEvent::Click);
+    assert_eq!(*clicks.borrow(), ["quit", "icon"]);
+
+    // A disabled item doesn't fire its callback.
+    tray.set_menu_item_enabled(start, false);
+    assert!(!mock.borrow().menu[0].enabled);
+    tray.dispatch(TrayEvent::MenuItemClick(start));
+    assert_eq!(*clicks.borrow(), ["quit", "icon"]);
+  }
+
+  #[test]
+  fn dispatch_by_id() {
+    let tray =
+      Rc::new(RefCell::new(Tray::with_backend(Rc::new(RefCell::new(MockTray::default())))));
+    let c_tray = Rc::downgrade(&tray);
+    let pause = Rc::new(Cell::new(None));
+    let c_pause = pause.clone();
+    // The callback disables its own item through the tray.
+    let item = tray.borrow_mut().add_menu_item("Pause", move || {
+      let id = c_pause.get().unwrap();
+      c_tray
+        .upgrade()
+        .unwrap()
+        .borrow_mut()
+        .set_menu_item_enabled(id, false);
+    });
+    pause.set(Some(item));
+
+    let id = tray.borrow().id();
+    dispatch_tray_event(id, TrayEvent::MenuItemClick(item));
+    assert!(
+      !tray.borrow().callbacks.borrow().items[0]
+        .entry
+        .enabled
+    );
+
+    // The event of a dropped tray is ignored.
+    drop(tray);
+    dispatch_tray_event(id, TrayEvent::MenuItemClick(item));
+  }
+
+  #[test]
+  #[cfg(not(any(windows, target_os = "macos")))]
+  fn unsupported_platform() {
+    let err = Tray::new().err().unwrap();
+    assert_eq!(err, TrayError::Unsupported);
+    assert_eq!(err.to_string(), "the system tray is not supported on this platform");
+  }
+}
@@ -1,5 +1,7 @@
 use std::future::Future;
 
+#[cfg(feature = "debug")]
+use ribir_core::window::CaptureFuture;
 use ribir_core::{
   prelude::{image::ColorFormat, *},
   window::{ShellWindow, WindowId},
@@ -11,10 +13,11 @@ use winit::{
 
 use crate::{
   backends::*,
-  prelude::{WindowAttributes, request_redraw},
+  prelude::{WindowAttributes, WindowIcon, request_redraw},
 };
 pub trait WinitBackend<'a>: Sized {
-  fn new(window: &'a winit::window::Window) -> impl Future<Output = Self>;
+  fn new(window: &'a winit::window::Window, attrs: &WindowAttributes)
+  -> impl Future<Output = Self>;
 
   fn on_resize(&mut self, size: DeviceSize);
 
@@ -25,12 +28,40 @@ pub trait WinitBackend<'a>: Sized {
   );
 
   fn end_frame(&mut self);
+
+  /// Rasterize the `svg` to an image of `size`, return `None` if the backend
+  /// can't draw offscreen.
+  fn rasterize_svg(
+    &mut self, svg: &Svg, size: DeviceSize,
+  ) -> impl Future<Output = Option<PixelImage>>;
+
+  /// Draw the `commands` to an offscreen image of `size`, return `None` if the
+  /// backend can't draw offscreen.
+  #[cfg(feature = "debug")]
+  fn capture(
+    &mut self, size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand],
+    surface_color: Color,
+  ) -> CaptureFuture;
+}
+
+/// The last drawn frame, kept to capture the window for debugging.
+#[cfg(feature = "debug")]
+#[derive(Default)]
+struct LastFrame {
+  surface: Color,
+  viewport: DeviceRect,
+  commands: Vec<PaintCommand>,
 }
 
 pub struct WinitShellWnd {
   pub(crate) winit_wnd: winit::window::Window,
   backend: Backend<'static>,
   cursor: CursorIcon,
+  level: WindowLevel,
+  /// The safe area insets, updated when the window is resized.
+  safe_area: EdgeInsets,
+  #[cfg(feature = "debug")]
+  last_frame: LastFrame,
 }
 
 impl ShellWindow for WinitShellWnd {
@@ -73,14 +104,33 @@ impl ShellWindow for WinitShellWnd {
       .to_i32()
       .cast_unit();
     self.backend.on_resize(size);
+    self.safe_area = platform_safe_area_insets();
   }
 
+  fn safe_area_insets(&self) -> EdgeInsets { self.safe_area }
+
+  fn keyboard_rect(&self) -> Option<Rect> { platform_keyboard_rect() }
+
   fn set_min_size(&mut self, size: Size) {
     self
       .winit_wnd
       .set_min_inner_size(Some(LogicalSize::new(size.width, size.height)))
   }
 
+  fn set_max_size(&mut self, size: Size) {
+    // An infinite size means no limit.
+    let size = size
+      .is_finite()
+      .then(|| LogicalSize::new(size.width, size.height));
+    self.winit_wnd.set_max_inner_size(size)
+  }
+
+  fn set_outer_position(&mut self, pos: Point) {
+    self
+      .winit_wnd
+      .set_outer_position(LogicalPosition::new(pos.x, pos.y))
+  }
+
   fn set_cursor(&mut self, cursor: CursorIcon) {
     self.cursor = cursor;
     self.winit_wnd.set_cursor_icon(cursor)
@@ -130,12 +180,32 @@ impl ShellWindow for WinitShellWnd {
     }
   }
 
+  #[inline]
+  fn is_maximized(&self) -> bool { self.winit_wnd.is_maximized() }
+
+  #[inline]
+  fn set_maximized(&mut self, maximized: bool) { self.winit_wnd.set_maximized(maximized) }
+
+  fn drag_window(&mut self) {
+    if let Err(err) = self.winit_wnd.drag_window() {
+      log::warn!("Failed to drag the window: {err}");
+    }
+  }
+
   #[inline]
   fn focus_window(&mut self) { self.winit_wnd.focus_window() }
 
   #[inline]
   fn set_decorations(&mut self, decorations: bool) { self.winit_wnd.set_decorations(decorations) }
 
+  #[inline]
+  fn window_level(&self) -> WindowLevel { self.level }
+
+  fn set_window_level(&mut self, level: WindowLevel) {
+    self.level = level;
+    self.winit_wnd.set_window_level(level);
+  }
+
   #[inline]
   fn set_ime_allowed(&mut self, allowed: bool) { self.winit_wnd.set_ime_allowed(allowed); }
 
@@ -146,7 +216,13 @@ impl ShellWindow for WinitShellWnd {
   fn as_any_mut(&mut self) -> &mut dyn Any { self }
 
   #[inline]
-  fn begin_frame(&mut self, surface: Color) { self.backend.begin_frame(surface) }
+  fn begin_frame(&mut self, surface: Color) {
+    #[cfg(feature = "debug")]
+    {
+      self.last_frame.surface = surface;
+    }
+    self.backend.begin_frame(surface)
+  }
 
   #[inline]
   fn draw_commands(&mut self, viewport: Rect, commands: &[PaintCommand]) {
@@ -161,12 +237,94 @@ impl ShellWindow for WinitShellWnd {
     self
       .backend
       .draw_commands(viewport, &Transform::scale(scale, scale), commands);
+    #[cfg(feature = "debug")]
+    {
+      self.last_frame.viewport = viewport;
+      self.last_frame.commands = commands.to_vec();
+    }
   }
 
   #[inline]
   fn end_frame(&mut self) { self.backend.end_frame() }
+
+  #[cfg(feature = "debug")]
+  fn capture(&mut self) -> Option<CaptureFuture> {
+    let LastFrame { surface, viewport, commands } = &self.last_frame;
+    if viewport.is_empty() {
+      return None;
+    }
+    let scale = self.winit_wnd.scale_factor() as f32;
+    Some(
+      self
+        .backend
+        .capture(viewport.size, &Transform::scale(scale, scale), commands, *surface),
+    )
+  }
 }
 
+/// Read the safe area insets from the CSS `env(safe-area-inset-*)` variables
+/// by a hidden probe element, the browser resolves them to zero on the devices
+/// without notches.
+#[cfg(target_family = "wasm")]
+fn platform_safe_area_insets() -> EdgeInsets {
+  use web_sys::{HtmlElement, wasm_bindgen::JsCast};
+
+  let probe = || {
+    let wnd = web_sys::window()?;
+    let document = wnd.document()?;
+    let body = document.body()?;
+    let probe: HtmlElement = document
+      .create_element("div")
+      .ok()?
+      .dyn_into()
+      .ok()?;
+    probe.style().set_css_text(
+      "position: fixed; visibility: hidden; pointer-events: none; padding: \
+       env(safe-area-inset-top) env(safe-area-inset-right) env(safe-area-inset-bottom) \
+       env(safe-area-inset-left);",
+    );
+    body.append_child(&probe).ok()?;
+    let style = wnd.get_computed_style(&probe).ok().flatten();
+    let px = |name: &str| {
+      style
+        .as_ref()
+        .and_then(|s| s.get_property_value(name).ok())
+        .and_then(|v| v.trim_end_matches("px").parse().ok())
+        .unwrap_or(0.)
+    };
+    let insets = EdgeInsets {
+      left: px("padding-left"),
+      right: px("padding-right"),
+      bottom: px("padding-bottom"),
+      top: px("padding-top"),
+    };
+    probe.remove();
+    Some(insets)
+  };
+  probe().unwrap_or_default()
+}
+
+/// The desktop platforms have no unsafe area.
+#[cfg(not(target_family = "wasm"))]
+fn platform_safe_area_insets() -> EdgeInsets { EdgeInsets::default() }
+
+/// The on-screen keyboard of the mobile browsers shrinks the visual viewport,
+/// so the part of the window below the visual viewport is covered by it.
+#[cfg(target_family = "wasm")]
+fn platform_keyboard_rect() -> Option<Rect> {
+  let wnd = web_sys::window()?;
+  let viewport = wnd.visual_viewport()?;
+  let width = wnd.inner_width().ok()?.as_f64()? as f32;
+  let height = wnd.inner_height().ok()?.as_f64()? as f32;
+  let top = (viewport.offset_top() + viewport.height()) as f32;
+  // Ignore the rounding error of the viewport.
+  (height - top >= 1.).then(|| Rect::new(Point::new(0., top), Size::new(width, height - top)))
+}
+
+/// The desktop platforms have no on-screen keyboard.
+#[cfg(not(target_family = "wasm"))]
+fn platform_keyboard_rect() -> Option<Rect> { None }
+
 pub(crate) fn new_id(id: winit::window::WindowId) -> WindowId {
   let id: u64 = id.into();
   id.into()
@@ -235,11 +393,13 @@ impl WinitShellWnd {
     attrs: WindowAttributes,
   ) -> Self {
     builder = builder
-      .with_title(attrs.title)
+      .with_title(&attrs.title)
       .with_maximized(attrs.maximized)
       .with_resizable(attrs.resizable)
       // hide the window until the render backend is ready
       .with_visible(false)
+      .with_transparent(attrs.transparent)
+      .with_window_level(attrs.window_level)
       .with_decorations(attrs.decorations);
 
     if let Some(size) = attrs.size {
@@ -254,24 +414,46 @@ impl WinitShellWnd {
     if let Some(pos) = attrs.position {
       builder = builder.with_position(LogicalPosition::new(pos.x, pos.y));
     }
-    if let Some(icon) = attrs.icon {
-      builder = builder.with_window_icon(Some(img_to_winit_icon(&icon)));
+    if let Some(WindowIcon::Image(icon)) = &attrs.icon {
+      builder = builder.with_window_icon(Some(img_to_winit_icon(icon)));
     }
 
     let winit_wnd: winit::window::Window = builder.build(window_target).unwrap();
     let ptr = &winit_wnd as *const winit::window::Window;
     // Safety: a reference to winit_wnd is valid as long as the WinitShellWnd is
     // alive.
-    let backend = Backend::new(unsafe { &*ptr }).await;
+    let mut backend = Backend::new(unsafe { &*ptr }, &attrs).await;
+    if let Some(WindowIcon::Svg(svg)) = &attrs.icon {
+      // The platforms display the window icon in the title bar and the taskbar,
+      // rasterize it large enough for both and let the platform scale it down.
+      let size = (SVG_ICON_SIZE * winit_wnd.scale_factor()).ceil() as i32;
+      if let Some(icon) = backend
+        .rasterize_svg(svg, DeviceSize::new(size, size))
+        .await
+      {
+        winit_wnd.set_window_icon(Some(img_to_winit_icon(&icon)));
+      }
+    }
 
     // show the window after the render backend is ready
     if attrs.visible {
       winit_wnd.set_visible(attrs.visible);
     }
-    WinitShellWnd { backend, winit_wnd, cursor: CursorIcon::Default }
+    WinitShellWnd {
+      backend,
+      winit_wnd,
+      cursor: CursorIcon::Default,
+      level: attrs.window_level,
+      safe_area: platform_safe_area_insets(),
+      #[cfg(feature = "debug")]
+      last_frame: LastFrame::default(),
+    }
   }
 }
 
+/// The logical size an SVG window icon is rasterized at.
+const SVG_ICON_SIZE: f64 = 48.;
+
 fn img_to_winit_icon(icon: &PixelImage) -> winit::window::Icon {
   assert!(icon.color_format() == ColorFormat::Rgba8);
   winit::window::Icon::from_rgba(icon.pixel_bytes().to_vec(), icon.width(), icon.height()).unwrap()
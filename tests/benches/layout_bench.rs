@@ -0,0 +1,38 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use ribir::core::{prelude::*, reset_test_env, test_helper::*};
+
+/// A tree of 1,000 leaves in 10 rows, the size of the root is changed before
+/// every layout, so all the nodes have to be laid out again.
+fn layout_1k_nodes(c: &mut Criterion) {
+  reset_test_env!();
+
+  let root_size = Stateful::new(Size::new(1000., 1000.));
+  let c_size = root_size.clone_writer();
+  let mut wnd = TestWindow::new(fn_widget! {
+    @MockBox {
+      size: pipe!(*$c_size),
+      @MockMulti {
+        @{
+          (0..10).map(|_| @MockMulti {
+            @{ (0..100).map(|_| @MockBox { size: Size::new(10., 10.) }) }
+          })
+        }
+      }
+    }
+  });
+  wnd.draw_frame();
+
+  c.bench_function("layout_1k_nodes", |b| {
+    b.iter(|| {
+      {
+        let mut size = root_size.write();
+        size.width = if size.width == 1000. { 999. } else { 1000. };
+      }
+      wnd.layout();
+    })
+  });
+  AppCtx::remove_wnd(wnd.id());
+}
+
+criterion_group!(layout_benches, layout_1k_nodes);
+criterion_main!(layout_benches);
@@ -26,5 +26,28 @@ fn shape_1k(c: &mut Criterion) {
   });
 }
 
-criterion_group!(text_benches, shape_1k);
+/// Shape a paragraph of 10,000 characters with the bundled font, so the
+/// result doesn't depend on the fonts installed on the machine.
+fn shape_10k_paragraph(c: &mut Criterion) {
+  let mut shaper = TextShaper::new(<_>::default());
+  shaper.font_db().borrow_mut().load_test_fonts();
+  let ids = shaper.font_db().borrow().default_fonts().to_vec();
+
+  let sentence = "The quick brown fox jumps over the lazy dog, and then runs away. ";
+  let mut text = sentence.repeat(10_000 / sentence.len() + 1);
+  text.truncate(10_000);
+  let text: Substr = text.into();
+
+  c.bench_function("shape_10k_paragraph", |b| {
+    b.iter(|| {
+      // clean cache
+      shaper.end_frame();
+      shaper.end_frame();
+
+      shaper.shape_text(&text, &ids, TextDirection::LeftToRight, GlyphBaseline::Alphabetic)
+    })
+  });
+}
+
+criterion_group!(text_benches, shape_1k, shape_10k_paragraph);
 criterion_main!(text_benches);
@@ -96,9 +96,57 @@ fn enum_template() {
     @P3 { @{ self::ChildA } }
   };
   let _b = fn_widget! {
-    @P { @{ self::ChildB } }
+    @P3 { @{ self::ChildB } }
   };
   let _c = fn_widget! {
-    @P { @{ self::ChildC } }
+    @P3 { @{ self::ChildC } }
   };
 }
+
+#[derive(Declare)]
+struct Card;
+
+#[derive(ChildOfCompose)]
+struct Header;
+
+#[derive(ChildOfCompose)]
+struct Body;
+
+#[derive(ChildOfCompose, Debug, PartialEq)]
+struct Footer(&'static str);
+
+#[derive(Template)]
+struct CardTml {
+  header: Option<Header>,
+  _body: Body,
+  #[template(default = Footer("default"))]
+  footer: Footer,
+}
+
+impl ComposeChild<'static> for Card {
+  type Child = CardTml;
+
+  fn compose_child(_: impl StateWriter<Value = Self>, _: Self::Child) -> Widget<'static> {
+    Void.into_widget()
+  }
+}
+
+#[test]
+fn template_default_slot() {
+  let _omit_default = fn_widget! {
+    @Card { @{ self::Body } }
+  };
+
+  let tml = CardTml::compose_child_from(CardTml::builder().with_child(Body));
+  assert!(tml.header.is_none());
+  assert_eq!(tml.footer, Footer("default"));
+
+  let tml = CardTml::compose_child_from(
+    CardTml::builder()
+      .with_child(Footer("custom"))
+      .with_child(Header)
+      .with_child(Body),
+  );
+  assert!(tml.header.is_some());
+  assert_eq!(tml.footer, Footer("custom"));
+}
@@ -0,0 +1,8 @@
+use ribir::prelude::*;
+
+fn main() {
+  let _ = fn_widget! {
+    let mut text = @Text { text: "misspelled" };
+    @ $text { marign: EdgeInsets::all(4.) }
+  };
+}
@@ -0,0 +1,7 @@
+use ribir::prelude::*;
+
+fn main() {
+  let _ = fn_widget! {
+    @Text { text: "misspelled", marign: EdgeInsets::all(4.) }
+  };
+}
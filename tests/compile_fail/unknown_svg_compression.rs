@@ -0,0 +1,5 @@
+use ribir::prelude::*;
+
+fn main() {
+  let _: Svg = include_crate_svg!("./assets/test1.svg", true, false, compress = "nonsense");
+}
@@ -1,4 +1,4 @@
-use ribir::prelude::{include_crate_svg, *};
+use ribir::prelude::{include_crate_png, include_crate_svg, include_crate_translations, *};
 use ribir_dev_helper::*;
 
 #[test]
@@ -7,6 +7,17 @@ fn include_svg() {
   assert_eq!(svg.command_size(), 2);
 }
 
+#[test]
+fn include_compressed_svg() {
+  let svg: Svg = include_crate_svg!("./assets/test1.svg", true, false);
+  let gzip: Svg =
+    include_crate_svg!("./assets/test1.svg", true, false, compress = "gzip", level = 9);
+  assert_eq!(gzip.serialize().unwrap(), svg.serialize().unwrap());
+  let zstd: Svg =
+    include_crate_svg!("./assets/test1.svg", true, false, compress = "zstd", level = 22);
+  assert_eq!(zstd.serialize().unwrap(), svg.serialize().unwrap());
+}
+
 fn fix_draw_svg_not_apply_alpha() -> Painter {
   let mut painter = Painter::new(Rect::from_size(Size::new(64., 64.)));
   let svg: Svg = include_crate_svg!("./assets/test1.svg", true, false);
@@ -15,3 +26,43 @@ fn fix_draw_svg_not_apply_alpha() -> Painter {
 }
 
 painter_backend_eq_image_test!(fix_draw_svg_not_apply_alpha, comparison = 0.002);
+
+#[test]
+fn include_png() {
+  let img: &'static LazyImage = include_crate_png!("../gpu/imgs/leaves.png");
+  assert!(!img.is_decoded());
+  let size = img.size();
+  assert!(img.is_decoded());
+  assert_eq!(size, include_crate_png!("../gpu/imgs/leaves.png").size());
+
+  // Every draw of the image shares the same decoded resource.
+  let mut painter = Painter::new(Rect::from_size(Size::new(64., 64.)));
+  let dst = Rect::from_size(Size::new(32., 32.));
+  painter
+    .draw_img(img.into(), &dst, &None)
+    .draw_img(img.into(), &dst, &None);
+  let imgs: Vec<_> = painter
+    .finish()
+    .iter()
+    .filter_map(|cmd| match cmd {
+      PaintCommand::Path(PathCommand {
+        action: PaintPathAction::Paint { brush: CommandBrush::Image { img, .. }, .. },
+        ..
+      }) => Some(img.clone()),
+      _ => None,
+    })
+    .collect();
+  assert_eq!(imgs.len(), 2);
+  assert!(imgs.iter().all(|i| i == img.get()));
+}
+
+#[test]
+fn include_translations() {
+  let en: Translations = include_crate_translations!("./assets/i18n/en.json");
+  let zh: Translations = include_crate_translations!("./assets/i18n/zh.json");
+  assert_eq!((en.lang(), zh.lang()), ("en", "zh"));
+
+  let locale = Locale::new(zh).with_fallback(en);
+  assert_eq!(locale.translate("greet", &[("name", &"Ribir")]), "你好，Ribir！");
+  assert_eq!(locale.lookup("bye"), Some("Goodbye!"));
+}
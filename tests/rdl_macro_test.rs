@@ -298,6 +298,135 @@ fn pipe_as_multi_child() {
   wnd.assert_root_size((300., 100.).into());
 }
 
+#[test]
+fn if_else_children() {
+  reset_test_env!();
+
+  let (wide, w_wide) = split_value(true);
+  let w = fn_widget! {
+    @Row {
+      @SizedBox { size: Size::new(10., 10.) }
+      @if *$wide {
+        SizedBox { size: Size::new(100., 50.) }
+      } @else {
+        SizedBox { size: Size::new(20., 50.) }
+      }
+    }
+  };
+
+  let mut wnd = TestWindow::new(w);
+  wnd.draw_frame();
+  wnd.assert_root_size((110., 50.).into());
+
+  *w_wide.write() = false;
+  wnd.draw_frame();
+  wnd.assert_root_size((30., 50.).into());
+
+  *w_wide.write() = true;
+  wnd.draw_frame();
+  wnd.assert_root_size((110., 50.).into());
+}
+
+#[test]
+fn if_without_else_children() {
+  reset_test_env!();
+
+  let (cnt, w_cnt) = split_value(0);
+  let w = fn_widget! {
+    let size = Size::new(100., 100.);
+    @Row {
+      @if *$cnt > 1 {
+        SizedBox { size }
+      } @else if *$cnt > 0 {
+        @SizedBox { size: Size::new(50., 50.) }
+      }
+    }
+  };
+
+  let mut wnd = TestWindow::new(w);
+  wnd.draw_frame();
+  wnd.assert_root_size(Size::zero());
+
+  *w_cnt.write() = 1;
+  wnd.draw_frame();
+  wnd.assert_root_size((50., 50.).into());
+
+  *w_cnt.write() = 2;
+  wnd.draw_frame();
+  wnd.assert_root_size((100., 100.).into());
+}
+
+widget_layout_test!(
+  static_if_children,
+  WidgetTester::new(fn_widget! {
+    let wide = false;
+    @Row {
+      @if wide {
+        SizedBox { size: Size::new(100., 100.) }
+      } @else {
+        SizedBox { size: Size::new(50., 50.) }
+      }
+    }
+  }),
+  LayoutCase::default().with_size(Size::new(50., 50.))
+);
+
+#[test]
+fn for_children() {
+  reset_test_env!();
+
+  let (list, w_list) = split_value(vec!['a', 'b', 'c']);
+  let (entered, w_entered) = split_value(vec![]);
+  let (left, w_left) = split_value(vec![]);
+  let w = fn_widget! {
+    @Row {
+      @for c in $list {
+        let key = @KeyWidget { key: c, value: c };
+        @ $key {
+          @SizedBox {
+            size: Size::new(10., 10.),
+            on_mounted: move |_| if $key.is_enter() {
+              $w_entered.write().push(c);
+            },
+            on_disposed: move |_| if $key.is_leave() {
+              $w_left.write().push(c);
+            },
+          }
+        }
+      }
+    }
+  };
+
+  let mut wnd = TestWindow::new(w);
+  wnd.draw_frame();
+  wnd.assert_root_size((30., 10.).into());
+  assert_eq!(*entered.read(), ['a', 'b', 'c']);
+
+  {
+    let mut list = w_list.write();
+    list.remove(1);
+    list.push('d');
+  }
+  wnd.draw_frame();
+  wnd.assert_root_size((30., 10.).into());
+  // Only the child of the new key enters, and only the one of the removed key
+  // leaves.
+  assert_eq!(*entered.read(), ['a', 'b', 'c', 'd']);
+  assert_eq!(*left.read(), ['b']);
+}
+
+widget_layout_test!(
+  static_for_children,
+  WidgetTester::new(fn_widget! {
+    @Row {
+      @for i in 1..=3 {
+        SizedBox { size: Size::new(10. * i as f32, 10.) }
+      }
+    }
+  }),
+  LayoutCase::default().with_size(Size::new(60., 10.))
+);
+
 widget_layout_test!(
   at_in_widget_macro,
   WidgetTester::new(fn_widget! {
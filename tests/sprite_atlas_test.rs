@@ -0,0 +1,57 @@
+use ribir::prelude::{include_crate_sprites, *};
+
+#[test]
+fn include_sprites() {
+  let sprites = include_crate_sprites!("./assets/sprites");
+  let rect = |x, y, w, h| DeviceRect::new(DevicePoint::new(x, y), DeviceSize::new(w, h));
+  assert_eq!(sprites.arrow.rect(), rect(0, 0, 4, 3));
+  assert_eq!(sprites.dot.rect(), rect(5, 0, 2, 2));
+  assert_eq!(sprites.line.rect(), rect(0, 4, 6, 1));
+
+  let atlas = sprites.arrow.atlas();
+  assert!(std::ptr::eq(atlas, sprites.dot.atlas()));
+  assert!(std::ptr::eq(atlas, sprites.line.atlas()));
+  assert_eq!(atlas.size(), DeviceSize::new(8, 5));
+
+  let pixel = |x: i32, y: i32| {
+    let start = ((y * atlas.width() as i32 + x) * 4) as usize;
+    &atlas.pixel_bytes()[start..start + 4]
+  };
+  let colors = [
+    (sprites.arrow, [255, 0, 0, 255]),
+    (sprites.dot, [0, 255, 0, 255]),
+    (sprites.line, [0, 0, 255, 255]),
+  ];
+  for (sprite, color) in colors {
+    let r = sprite.rect();
+    assert_eq!(pixel(r.min_x(), r.min_y()), color);
+    assert_eq!(pixel(r.max_x() - 1, r.max_y() - 1), color);
+  }
+
+  // Drawing a sprite only draws its region of the shared atlas.
+  let mut painter = Painter::new(Rect::from_size(Size::new(64., 64.)));
+  let dst = Rect::from_size(Size::new(8., 6.));
+  painter
+    .draw_sprite(&sprites.arrow, &dst)
+    .draw_sprite(&sprites.dot, &dst);
+  let imgs: Vec<_> = painter
+    .finish()
+    .iter()
+    .filter_map(|cmd| match cmd {
+      PaintCommand::Path(PathCommand {
+        transform,
+        action: PaintPathAction::Paint { brush: CommandBrush::Image { img, .. }, .. },
+        ..
+      }) => Some((img.clone(), *transform)),
+      _ => None,
+    })
+    .collect();
+  assert_eq!(imgs.len(), 2);
+  assert!(imgs.iter().all(|(img, _)| img == atlas.get()));
+  // The region of a sprite is scaled to the destination rect.
+  let (_, arrow_ts) = imgs[0];
+  assert_eq!(arrow_ts.transform_point(Point::new(4., 3.)), Point::new(8., 6.));
+  let (_, dot_ts) = imgs[1];
+  assert_eq!(dot_ts.transform_point(Point::new(5., 0.)), Point::zero());
+  assert_eq!(dot_ts.transform_point(Point::new(7., 2.)), Point::new(8., 6.));
+}
@@ -2,11 +2,17 @@ use ribir_core::prelude::Classes;
 
 mod buttons_cls;
 mod checkbox_cls;
+mod chip_cls;
+mod disabled_cls;
+mod empty_state_cls;
 mod input_cls;
+mod pagination_cls;
 mod progress_cls;
+mod pull_to_refresh_cls;
 mod radio_cls;
 mod scrollbar_cls;
 mod slider_cls;
+mod stepper_cls;
 mod tooltips_cls;
 pub fn initd_classes() -> Classes {
   let mut classes = Classes::default();
@@ -16,9 +22,15 @@ pub fn initd_classes() -> Classes {
   radio_cls::init(&mut classes);
   progress_cls::init(&mut classes);
   checkbox_cls::init(&mut classes);
+  chip_cls::init(&mut classes);
   tooltips_cls::init(&mut classes);
   slider_cls::init(&mut classes);
+  stepper_cls::init(&mut classes);
+  pagination_cls::init(&mut classes);
+  empty_state_cls::init(&mut classes);
+  pull_to_refresh_cls::init(&mut classes);
   input_cls::init(&mut classes);
+  disabled_cls::init(&mut classes);
 
   classes
 }
@@ -0,0 +1,47 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const CHIP_HEIGHT: f32 = 32.;
+const CHIP_ICON_SIZE: f32 = 18.;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(CHIP, |w| {
+    let outline = Palette::of(BuildCtx::get()).outline_variant();
+    let mut text_style = TypographyTheme::of(BuildCtx::get())
+      .label_large
+      .text
+      .clone();
+    text_style.line_height = CHIP_ICON_SIZE;
+    fat_obj! {
+      padding: md::EDGES_HOR_8,
+      radius: md::RADIUS_8,
+      border: Border::all(BorderSide { color: outline.into(), width: 1. }),
+      clamp: BoxClamp::fixed_height(CHIP_HEIGHT),
+      text_style,
+      @ { w }
+    }
+    .into_widget()
+  });
+  classes.insert(CHIP_ICON, style_class! {
+    text_line_height: CHIP_ICON_SIZE,
+    foreground: BuildCtx::color(),
+  });
+  classes.insert(CHIP_LABEL, style_class! { padding: md::EDGES_HOR_8 });
+  classes.insert(CHIP_DELETE, |w| {
+    let hover_layer = HoverLayer::tracked(LayerArea::WidgetCover(md::RADIUS_12));
+    let foreground = Palette::of(BuildCtx::get()).on_surface_variant();
+    ripple! {
+      center: true,
+      ripple_radius: 12.,
+      cursor: CursorIcon::Pointer,
+      @ $hover_layer {
+        text_line_height: CHIP_ICON_SIZE,
+        foreground,
+        @ { w }
+      }
+    }
+    .into_widget()
+  });
+}
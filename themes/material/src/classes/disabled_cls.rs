@@ -0,0 +1,6 @@
+use ribir_core::prelude::*;
+
+pub(super) fn init(classes: &mut Classes) {
+  // The opacity of the disabled content in Material Design.
+  classes.insert(DISABLED, style_class! { opacity: 0.38 });
+}
@@ -0,0 +1,25 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const ICON_SIZE: f32 = 48.;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(EMPTY_STATE, style_class! { padding: md::EDGES_16 });
+  classes.insert(EMPTY_STATE_ICON, style_class! {
+    text_line_height: ICON_SIZE,
+    foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+    margin: EdgeInsets::only_bottom(16.),
+  });
+  classes.insert(EMPTY_STATE_TITLE, style_class! {
+    text_style: TypographyTheme::of(BuildCtx::get()).title_large.text.clone(),
+    foreground: Palette::of(BuildCtx::get()).on_surface(),
+  });
+  classes.insert(EMPTY_STATE_DESCRIPTION, style_class! {
+    text_style: TypographyTheme::of(BuildCtx::get()).body_medium.text.clone(),
+    foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+    margin: EdgeInsets::only_top(8.),
+  });
+  classes.insert(EMPTY_STATE_ACTION, style_class! { margin: EdgeInsets::only_top(24.) });
+}
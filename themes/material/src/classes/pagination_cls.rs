@@ -0,0 +1,63 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const ITEM_SIZE: f32 = 32.;
+
+fn item(w: Widget, foreground: DeclareInit<Brush>) -> Widget {
+  let mut text_style = TypographyTheme::of(BuildCtx::get())
+    .label_large
+    .text
+    .clone();
+  text_style.line_height = ITEM_SIZE;
+  container! {
+    size: Size::splat(ITEM_SIZE),
+    radius: md::RADIUS_16,
+    foreground,
+    text_style,
+    @fat_obj! {
+      h_align: HAlign::Center,
+      v_align: VAlign::Center,
+      @ { w }
+    }
+  }
+  .into_widget()
+}
+
+fn interactive(w: Widget) -> Widget {
+  let hover_layer = HoverLayer::tracked(LayerArea::WidgetCover(md::RADIUS_16));
+  ripple! {
+    center: true,
+    ripple_radius: ITEM_SIZE / 2.,
+    @ $hover_layer { @ { w } }
+  }
+  .into_widget()
+}
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(PAGINATION_ITEM, |w| {
+    let foreground = Palette::of(BuildCtx::get()).on_surface_variant();
+    interactive(item(w, foreground.declare_into()))
+  });
+  classes.insert(PAGINATION_ITEM_ACTIVE, |w| {
+    let color = BuildCtx::color();
+    let on_color = color.clone().on_this_color(BuildCtx::get());
+    let w = item(w, on_color.declare_into());
+    fat_obj! { background: color, radius: md::RADIUS_16, @ { w } }.into_widget()
+  });
+  classes.insert(PAGINATION_ELLIPSIS, |w| {
+    let foreground = Palette::of(BuildCtx::get()).on_surface_variant();
+    item(w, foreground.declare_into())
+  });
+  classes.insert(PAGINATION_CONTROL, |w| {
+    let foreground = Palette::of(BuildCtx::get()).on_surface_variant();
+    interactive(item(w, foreground.declare_into()))
+  });
+  classes.insert(PAGINATION_CONTROL_DISABLED, |w| {
+    let foreground = Palette::of(BuildCtx::get())
+      .on_surface()
+      .with_alpha(0.38);
+    item(w, foreground.declare_into())
+  });
+}
@@ -0,0 +1,6 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(PULL_TO_REFRESH_INDICATOR, style_class! { margin: EdgeInsets::vertical(8.) });
+}
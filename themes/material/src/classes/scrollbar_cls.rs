@@ -29,6 +29,13 @@ pub(super) fn init(classes: &mut Classes) {
 
   classes.insert(H_SCROLL_TRACK, |w| style_track(w, true));
   classes.insert(V_SCROLL_TRACK, |w| style_track(w, false));
+
+  classes.insert(SCROLL_MINIMAP, style_class! {
+    background: Palette::of(BuildCtx::get()).surface_container_low(),
+  });
+  classes.insert(SCROLL_MINIMAP_VIEWPORT, style_class! {
+    background: Palette::of(BuildCtx::get()).on_surface().with_alpha(0.12),
+  });
 }
 
 fn track_color(w: Color, hovering: bool) -> Color { if hovering { w } else { w.with_alpha(0.) } }
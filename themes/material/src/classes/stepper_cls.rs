@@ -0,0 +1,66 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const INDICATOR_SIZE: f32 = 24.;
+
+fn indicator(w: Widget, background: DeclareInit<Brush>, foreground: DeclareInit<Brush>) -> Widget {
+  let mut text_style = TypographyTheme::of(BuildCtx::get())
+    .label_medium
+    .text
+    .clone();
+  text_style.line_height = INDICATOR_SIZE;
+  container! {
+    size: Size::splat(INDICATOR_SIZE),
+    radius: md::RADIUS_12,
+    background,
+    foreground,
+    text_style,
+    @fat_obj! {
+      h_align: HAlign::Center,
+      v_align: VAlign::Center,
+      @ { w }
+    }
+  }
+  .into_widget()
+}
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(STEPPER_HEADER, style_class! { padding: md::EDGES_HOR_16 });
+  classes.insert(STEP_INDICATOR, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    let background = palette.surface_container_highest();
+    let foreground = palette.on_surface_variant();
+    indicator(w, background.declare_into(), foreground.declare_into())
+  });
+  classes.insert(STEP_INDICATOR_ACTIVE, |w| {
+    let color = BuildCtx::color();
+    let on_color = color.clone().on_this_color(BuildCtx::get());
+    indicator(w, color.declare_into(), on_color.declare_into())
+  });
+  classes.insert(STEP_INDICATOR_COMPLETED, |w| {
+    let color = BuildCtx::color();
+    let on_color = color.clone().on_this_color(BuildCtx::get());
+    indicator(w, color.declare_into(), on_color.declare_into())
+  });
+  classes.insert(STEP_TITLE, |w| {
+    let text_style = TypographyTheme::of(BuildCtx::get())
+      .label_large
+      .text
+      .clone();
+    fat_obj! {
+      margin: md::EDGES_HOR_8,
+      text_style,
+      @ { w }
+    }
+    .into_widget()
+  });
+  classes.insert(STEP_CONNECTOR, |w| {
+    let outline = Palette::of(BuildCtx::get()).outline_variant();
+    fat_obj! { margin: md::EDGES_HOR_8, background: outline, @ { w } }.into_widget()
+  });
+  classes.insert(STEP_CONNECTOR_COMPLETED, |w| {
+    fat_obj! { margin: md::EDGES_HOR_8, background: BuildCtx::color(), @ { w } }.into_widget()
+  });
+}
@@ -247,6 +247,8 @@ pub fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        first_line_indent: Em(0.),
+        hanging_indent: Em(0.),
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
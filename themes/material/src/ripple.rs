@@ -21,6 +21,18 @@ pub struct Ripple {
   launcher: Option<Box<dyn Fn(Option<Point>)>>,
 }
 
+/// The config of the ripples in its descendants, provide it to customize the
+/// ripples of the themed widgets.
+#[derive(Debug, Clone, Default)]
+pub struct RippleConfig {
+  /// The color of the ripples, use the foreground if it's `None`.
+  pub color: Option<Color>,
+  /// The maximum radius in pixels of the ripples.
+  pub radius_cap: Option<f32>,
+  /// Whether the ripples always originate from the center of the host bound.
+  pub centered: bool,
+}
+
 /// Config how ripples show outside of the host widget box.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum RippleBound {
@@ -37,12 +49,16 @@ impl<'c> ComposeChild<'c> for Ripple {
 
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
+      let config = Provider::of::<RippleConfig>(BuildCtx::get())
+        .map(|c| c.clone())
+        .unwrap_or_default();
       let mut ripple_layer = PressedLayer::new(LayerArea::WidgetCover(Radius::all(0.)));
-      init_ripple_launcher(&this, &mut ripple_layer);
+      ripple_layer.write().color = config.color;
+      init_ripple_launcher(&this, &mut ripple_layer, config.radius_cap);
 
       @ $ripple_layer {
         on_pointer_down: move |e| {
-          let pos = (!$this.center).then(||e.position());
+          let pos = (!$this.center && !config.centered).then(||e.position());
           $this.launch(pos);
         },
         on_disposed: move |_| $this.write().launcher = None,
@@ -60,6 +76,7 @@ impl Ripple {
 
 fn init_ripple_launcher(
   this: &impl StateWriter<Value = Ripple>, layer: &mut FatObj<Stateful<PressedLayer>>,
+  radius_cap: Option<f32>,
 ) {
   rdl! {
     let ripple_grow = @Animate {
@@ -100,11 +117,14 @@ fn init_ripple_launcher(
       let center = pos.unwrap_or_else(|| {
         (size / 2.).to_vector().to_point()
       });
+      // The distance to the furthest corner, so the ripple covers the host even if
+      // it's not a square.
       let radius = $this.ripple_radius.unwrap_or_else(|| {
         let distance_x = f32::max(center.x , size.width - center.x);
         let distance_y = f32::max(center.y, size.height - center.y);
         (distance_x.powf(2.) + distance_y.powf(2.)).sqrt()
       });
+      let radius = radius_cap.map_or(radius, |cap| radius.min(cap));
       let clip = match $this.bounded {
         RippleBound::Unbounded => None,
         RippleBound::Bounded => Some(Radius::all(0.)),
@@ -121,3 +141,52 @@ fn init_ripple_launcher(
     $this.write().launcher = Some(Box::new(launcher));
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn centered_ripple_with_color() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let config = RippleConfig { color: Some(Color::RED), centered: true, ..<_>::default() };
+        @Providers {
+          providers: [Provider::new(config)],
+          @Ripple { @MockBox { size: Size::new(100., 40.) } }
+        }
+      },
+      Size::new(200., 200.),
+    );
+    wnd.draw_frame();
+
+    wnd.cursor_move(Point::new(10., 10.));
+    wnd.mouse_press();
+    std::thread::sleep(md::easing::duration::SHORT3);
+    wnd.draw_frame();
+
+    let frame = wnd.take_last_frame().unwrap();
+    let ripple = frame
+      .commands
+      .iter()
+      .find_map(|cmd| match cmd {
+        PaintCommand::Path(PathCommand {
+          paint_bounds,
+          action: PaintPathAction::Paint { brush: CommandBrush::Color(color), .. },
+          ..
+        }) => Some((*paint_bounds, *color)),
+        _ => None,
+      })
+      .unwrap();
+
+    let (bounds, color) = ripple;
+    assert_eq!(bounds.center(), Point::new(50., 20.));
+    // The ripple fully expands to cover the non-square widget.
+    assert!(bounds.width() >= Vector::new(100., 40.).length() - 0.5);
+    assert_eq!(color.with_alpha(1.), Color::RED);
+  }
+}
@@ -13,6 +13,9 @@ pub type PressedLayer = StateLayer<PRESSED_OPACITY>;
 pub struct StateLayer<const M: u8> {
   pub area: LayerArea,
   pub draw_opacity: f32,
+  /// The color to paint the layer, use the foreground of the context if it's
+  /// `None`.
+  pub color: Option<Color>,
 }
 
 impl PressedLayer {
@@ -20,7 +23,7 @@ impl PressedLayer {
   /// visual effect and not track the interactive state to control to show or
   /// hide.
   pub fn new(path: impl Into<LayerArea>) -> FatObj<Stateful<PressedLayer>> {
-    FatObj::new(Stateful::new(Self { area: path.into(), draw_opacity: 0. }))
+    FatObj::new(Stateful::new(Self { area: path.into(), draw_opacity: 0., color: None }))
   }
 }
 
@@ -28,7 +31,7 @@ impl HoverLayer {
   /// Create a hover state layer displaying only when the pointer is hovering
   /// this widget.
   pub fn tracked(path: impl Into<LayerArea>) -> FatObj<Stateful<HoverLayer>> {
-    let layer = Stateful::new(Self { area: path.into(), draw_opacity: 0. });
+    let layer = Stateful::new(Self { area: path.into(), draw_opacity: 0., color: None });
     part_writer!(&mut layer.draw_opacity).transition(EasingTransition {
       easing: md::easing::STANDARD,
       duration: md::easing::duration::SHORT1,
@@ -87,12 +90,16 @@ impl<const M: u8> Render for StateLayer<M> {
   fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size { clamp.min }
 
   fn paint(&self, ctx: &mut PaintingCtx) {
-    let StateLayer { area, draw_opacity } = self;
+    let StateLayer { area, draw_opacity, color } = self;
     if *draw_opacity > 0. {
       let p = ctx.parent().unwrap();
       let size = ctx.widget_box_size(p).unwrap();
       let rect = Rect::from_size(size);
-      let painter = ctx.painter().apply_alpha(*draw_opacity);
+      let painter = ctx.painter();
+      if let Some(color) = color {
+        painter.set_fill_brush(*color);
+      }
+      let painter = painter.apply_alpha(*draw_opacity);
       match area {
         LayerArea::Circle { center, radius, clip } => {
           if let Some(clip) = clip {
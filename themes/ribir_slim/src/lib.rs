@@ -82,6 +82,8 @@ fn typography_theme() -> TypographyTheme {
         letter_space,
         font_face,
         overflow: TextOverflow::Overflow,
+        first_line_indent: Em(0.),
+        hanging_indent: Em(0.),
       },
       decoration: TextDecorationStyle {
         decoration: TextDecoration::NONE,
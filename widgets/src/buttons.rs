@@ -281,7 +281,7 @@ impl<'c> ButtonChild<'c> {
     [btn, btn_leading_icon, btn_trialing_icon, btn_label, icon_only, label_only]: [ClassName; 6],
   ) -> Widget<'c> {
     let Self { label, icon } = self;
-    match (label, icon) {
+    let btn = match (label, icon) {
       (None, None) => void!( class: btn ).into_widget(),
       (None, Some(icon)) => fat_obj! {
         class: icon_only,
@@ -306,7 +306,10 @@ impl<'c> ButtonChild<'c> {
         }
       }
       .into_widget(),
-    }
+    };
+    DefaultSemantics::new(|| Semantics::new(Role::Button))
+      .with_child(btn)
+      .into_widget()
   }
 }
 
@@ -320,6 +323,35 @@ mod tests {
 
   fn miss_icon() -> Svg { named_svgs::get_or_default("default") }
 
+  #[test]
+  fn button_semantics() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Column {
+        @Button { @ { "Submit" } }
+        @FilledButton {
+          @Icon { @miss_icon() }
+          @ { "Save" }
+        }
+        @TextButton {
+          semantics: Semantics::new(Role::Link).with_label("Home"),
+          @ { "Back" }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.semantics_tree();
+    let roles: Vec<_> = tree
+      .children
+      .iter()
+      .map(|c| (c.semantics.role, c.semantics.label.as_deref().unwrap()))
+      .collect();
+    // The explicit semantics is merged over the default one.
+    assert_eq!(roles, [(Role::Button, "Submit"), (Role::Button, "Save"), (Role::Link, "Home")]);
+  }
+
   widget_image_tests!(
     button,
     WidgetTester::new(row! {
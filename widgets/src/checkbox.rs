@@ -83,7 +83,12 @@ impl ComposeChild<'static> for Checkbox {
   type Child = Option<PositionChild<TextInit>>;
 
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
-    rdl! {
+    let reader = this.clone_reader();
+    let semantics = DefaultSemantics::new(move || {
+      let this = reader.read();
+      Semantics::new(Role::Checkbox).with_checked(this.checked && !this.indeterminate)
+    });
+    let checkbox = rdl! {
       let icon = @Class {
         class: distinct_pipe!($this.state_class_name()),
         @Void { class: CHECKBOX }
@@ -95,8 +100,8 @@ impl ComposeChild<'static> for Checkbox {
         },
         @ icon_with_label(icon.into_widget(), child)
       }
-    }
-    .into_widget()
+    };
+    semantics.with_child(checkbox).into_widget()
   }
 }
 
@@ -108,6 +113,29 @@ mod tests {
   use super::*;
   use crate::prelude::*;
 
+  #[test]
+  fn checkbox_semantics() {
+    reset_test_env!();
+
+    let checkbox = Stateful::new(Checkbox { checked: false, indeterminate: false });
+    let c_checkbox = checkbox.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      let checkbox = c_checkbox.clone_writer();
+      @$checkbox { @ { "Remember me" } }
+    });
+    wnd.draw_frame();
+
+    let semantics = |wnd: &TestWindow| wnd.semantics_tree().children[0].semantics.clone();
+    let node = semantics(&wnd);
+    assert_eq!(node.role, Role::Checkbox);
+    assert_eq!(node.label.as_deref(), Some("Remember me"));
+    assert_eq!(node.checked, Some(false));
+
+    checkbox.write().switch_check();
+    wnd.draw_frame();
+    assert_eq!(semantics(&wnd).checked, Some(true));
+  }
+
   widget_image_tests!(
     checkbox,
     WidgetTester::new(self::column! {
@@ -0,0 +1,284 @@
+//! Chips are compact elements that represent a tag, a filter or an attribute.
+//!
+//! A chip has a label and an optional leading icon. A chip with an
+//! `on_delete` handler also shows a trailing delete button, tapping it calls
+//! the handler.
+//!
+//! The label never overflows the chip, it is truncated with an ellipsis if the
+//! chip is not wide enough.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   @Row {
+//!     @Chip { @ { "Label only" } }
+//!     @Chip {
+//!       @Icon { @ { svgs::STAR } }
+//!       @ { "With icon" }
+//!     }
+//!     @Chip {
+//!       on_delete: move || println!("delete the tag."),
+//!       @ { "Deletable" }
+//!     }
+//!   }
+//! };
+//! ```
+use std::cell::{Ref, RefCell};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::Icon;
+
+class_names! {
+  #[doc = "Class name for the whole chip"]
+  CHIP,
+  #[doc = "Class name for the leading icon of the chip"]
+  CHIP_ICON,
+  #[doc = "Class name for the label of the chip"]
+  CHIP_LABEL,
+  #[doc = "Class name for the delete button of the chip"]
+  CHIP_DELETE,
+}
+
+/// A compact element with a label, an optional leading icon, and an optional
+/// delete button.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct Chip {
+  /// The handler called when the delete button is tapped, the chip has a
+  /// delete button only if it has this handler when it's built.
+  #[declare(custom, default)]
+  pub on_delete: Option<Box<dyn FnMut()>>,
+}
+
+pub trait ChipDeclarerExtend {
+  /// Initialize the handler called when the delete button is tapped.
+  fn on_delete(self, f: impl FnMut() + 'static) -> Self;
+}
+
+impl ChipDeclarerExtend for FatObj<ChipDeclarer> {
+  fn on_delete(mut self, f: impl FnMut() + 'static) -> Self {
+    let f: Box<dyn FnMut()> = Box::new(f);
+    self.on_delete = Some(DeclareInit::Value(Some(f)));
+    self
+  }
+}
+
+/// The template child of the chip, a label and an optional leading icon.
+#[derive(Template)]
+pub struct ChipChild<'c> {
+  label: TextInit,
+  icon: Option<Widget<'c>>,
+}
+
+impl<'c> ComposeChild<'c> for Chip {
+  type Child = ChipChild<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let ChipChild { label, icon } = child;
+    let deletable = this.read().on_delete.is_some();
+    rdl! {
+      let mut children = vec![];
+      if let Some(icon) = icon {
+        children.push(@Class { class: CHIP_ICON, @ { icon } }.into_widget());
+      }
+      let label_idx = children.len();
+      children.push(@ChipLabel { class: CHIP_LABEL, text: label }.into_widget());
+      if deletable {
+        let delete = @Icon {
+          class: CHIP_DELETE,
+          cursor: CursorIcon::Pointer,
+          on_tap: move |e| {
+            e.stop_propagation();
+            // Take the handler out, so it can modify the chip in place.
+            let handler = $this.silent().on_delete.take();
+            if let Some(mut handler) = handler {
+              handler();
+              $this.silent().on_delete.get_or_insert(handler);
+            }
+          },
+          @ { svgs::CLOSE }
+        };
+        children.push(delete.into_widget());
+      }
+
+      @ChipLine { class: CHIP, label_idx, @ { children } }
+    }
+    .into_widget()
+  }
+}
+
+/// Lays out the parts of the chip in a horizontal line, the label takes the
+/// space left by the other parts.
+#[derive(Declare, MultiChild)]
+struct ChipLine {
+  label_idx: usize,
+}
+
+impl Render for ChipLine {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let (ctx, children) = ctx.split_children();
+    let children: Vec<WidgetId> = children.collect();
+    let child_clamp = clamp.with_min_size(ZERO_SIZE);
+
+    let mut size = ZERO_SIZE;
+    for (idx, c) in children.iter().enumerate() {
+      if idx != self.label_idx {
+        let child_size = ctx.perform_child_layout(*c, child_clamp);
+        size.width += child_size.width;
+        size.height = size.height.max(child_size.height);
+      }
+    }
+    if let Some(label) = children.get(self.label_idx) {
+      let max_width = (clamp.max.width - size.width).max(0.);
+      let label_clamp = child_clamp.with_max_width(max_width);
+      let label_size = ctx.perform_child_layout(*label, label_clamp);
+      size.width += label_size.width;
+      size.height = size.height.max(label_size.height);
+    }
+
+    let clamped_size = clamp.clamp(size);
+    let mut x = (clamped_size.width - size.width).max(0.) / 2.;
+    for c in children {
+      let c_size = ctx.widget_box_size(c).unwrap();
+      let y = (clamped_size.height - c_size.height) / 2.;
+      ctx.update_position(c, Point::new(x, y));
+      x += c_size.width;
+    }
+
+    clamped_size
+  }
+}
+
+/// A single line text, the tail of the text is replaced with an ellipsis if
+/// the text is wider than the max width.
+#[derive(Declare)]
+struct ChipLabel {
+  text: CowArc<str>,
+  #[declare(skip)]
+  glyphs: RefCell<Option<VisualGlyphs>>,
+}
+
+const ELLIPSIS: &str = "…";
+
+impl ChipLabel {
+  fn glyphs(&self) -> Option<Ref<'_, VisualGlyphs>> {
+    Ref::filter_map(self.glyphs.borrow(), |v| v.as_ref()).ok()
+  }
+}
+
+impl Render for ChipLabel {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let mut style = Provider::of::<TextStyle>(ctx).unwrap().clone();
+    style.overflow = TextOverflow::Overflow;
+    let bounds = Size::new(f32::INFINITY, clamp.max.height);
+    let layout = |text: Substr| text_glyph(text, &style, TextAlign::Start, bounds);
+
+    let mut glyphs = layout(self.text.substr(..));
+    let max_width = clamp.max.width;
+    if glyphs.visual_rect().width() > max_width {
+      // Find the longest prefix that fits the max width with the ellipsis.
+      let ends: Vec<usize> = self
+        .text
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .collect();
+      let truncated = |cnt: usize| {
+        let text: CowArc<str> = format!("{}{ELLIPSIS}", &self.text[..ends[cnt]]).into();
+        layout(text.substr(..))
+      };
+      let (mut lo, mut hi) = (0, ends.len() - 1);
+      while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if truncated(mid).visual_rect().width() <= max_width {
+          lo = mid;
+        } else {
+          hi = mid - 1;
+        }
+      }
+      glyphs = truncated(lo);
+    }
+
+    let size = glyphs.visual_rect().size;
+    *self.glyphs.borrow_mut() = Some(glyphs);
+    clamp.clamp(size)
+  }
+
+  fn semantics(&self) -> Option<Semantics> {
+    Some(Semantics::new(Role::Text).with_label(self.text.clone()))
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
+    let glyphs = self.glyphs().unwrap();
+    let rect = glyphs.visual_rect();
+    paint_text(ctx.painter(), &glyphs, style.unwrap_or(PaintingStyle::Fill), rect);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn delete_fires_once() {
+    reset_test_env!();
+
+    let (deleted, w_deleted) = split_value(0);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let w_deleted = w_deleted.clone_writer();
+        @Chip {
+          on_delete: move || *w_deleted.write() += 1,
+          @ { "Tag" }
+        }
+      },
+      Size::new(200., 100.),
+    );
+    wnd.draw_frame();
+    let chip = wnd.layout_info_by_path(&[0]).unwrap();
+    let size = chip.size.unwrap();
+    let pos = chip.pos;
+
+    // Tap the label.
+    wnd.tap_at(Point::new(pos.x + 2., pos.y + size.height / 2.));
+    wnd.draw_frame();
+    assert_eq!(*deleted.read(), 0);
+
+    // Tap the delete button.
+    wnd.tap_at(Point::new(pos.x + size.width - 2., pos.y + size.height / 2.));
+    wnd.draw_frame();
+    assert_eq!(*deleted.read(), 1);
+  }
+
+  #[test]
+  fn long_label_ellipsis() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Chip {
+          clamp: BoxClamp::fixed_width(60.),
+          on_delete: || {},
+          @ { "A very very long label of the chip" }
+        }
+      },
+      Size::new(200., 100.),
+    );
+    wnd.draw_frame();
+    let chip = wnd.layout_info_by_path(&[0]).unwrap();
+    assert_eq!(chip.size.unwrap().width, 60.);
+    let label = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    let delete = wnd.layout_info_by_path(&[0, 1]).unwrap();
+    let label_right = label.pos.x + label.size.unwrap().width;
+    assert!(label_right <= delete.pos.x + 0.01);
+    assert!(delete.pos.x + delete.size.unwrap().width <= 60.01);
+  }
+}
@@ -0,0 +1,150 @@
+//! A placeholder shown in place of the content that has nothing to show, such
+//! as a search without results or an empty inbox.
+//!
+//! An empty state has a title, and an optional icon above it, an optional
+//! description below it and an optional action at the bottom. The parts that
+//! are not given are omitted without leaving any space.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   @EmptyState {
+//!     @Icon { @ { svgs::SEARCH } }
+//!     @ { "No results" }
+//!     @EmptyDescription(Label::new("Try a different keyword."))
+//!     @EmptyAction(@FilledButton { @ { "Clear search" } }.into_widget())
+//!   }
+//! };
+//! ```
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the whole empty state"]
+  EMPTY_STATE,
+  #[doc = "Class name for the icon of the empty state"]
+  EMPTY_STATE_ICON,
+  #[doc = "Class name for the title of the empty state"]
+  EMPTY_STATE_TITLE,
+  #[doc = "Class name for the description of the empty state"]
+  EMPTY_STATE_DESCRIPTION,
+  #[doc = "Class name for the action of the empty state"]
+  EMPTY_STATE_ACTION,
+}
+
+/// A placeholder for the content that has nothing to show.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct EmptyState;
+
+/// The template child of the empty state.
+#[derive(Template)]
+pub struct EmptyStateChild<'c> {
+  icon: Option<Widget<'c>>,
+  title: TextInit,
+  description: Option<EmptyDescription>,
+  action: Option<EmptyAction<'c>>,
+}
+
+/// The description of the [`EmptyState`], explains why it is empty or what to
+/// do next.
+#[derive(ChildOfCompose)]
+pub struct EmptyDescription(pub Label);
+
+/// The action of the [`EmptyState`], usually a button to resolve the empty
+/// state, such as a retry or a create button.
+#[derive(ChildOfCompose)]
+pub struct EmptyAction<'c>(pub Widget<'c>);
+
+impl<'c> ComposeChild<'c> for EmptyState {
+  type Child = EmptyStateChild<'c>;
+
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let EmptyStateChild { icon, title, description, action } = child;
+    rdl! {
+      @Column {
+        class: EMPTY_STATE,
+        align_items: Align::Center,
+        @ { icon.map(|icon| @Class { class: EMPTY_STATE_ICON, @ { icon } }) }
+        @Text {
+          class: EMPTY_STATE_TITLE,
+          text: title,
+        }
+        @ {
+          description.map(|desc| @Text {
+            class: EMPTY_STATE_DESCRIPTION,
+            text: desc.0.0,
+            })
+        }
+        @ { action.map(|action| @Class { class: EMPTY_STATE_ACTION, @ { action.0 } }) }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use ribir_dev_helper::*;
+
+  use super::*;
+
+  widget_image_tests!(
+    empty_state,
+    WidgetTester::new(fn_widget! {
+      @EmptyState {
+        @Icon { @ { svgs::SEARCH } }
+        @ { "No results" }
+        @EmptyDescription(Label::new("Try a different keyword."))
+        @EmptyAction(@FilledButton { @ { "Clear search" } }.into_widget())
+      }
+    })
+    .with_wnd_size(Size::new(300., 240.))
+  );
+
+  fn labels(wnd: &TestWindow) -> Vec<String> {
+    wnd
+      .semantics_tree()
+      .children
+      .iter()
+      .map(|c| c.semantics.label.as_deref().unwrap().to_string())
+      .collect()
+  }
+
+  #[test]
+  fn action_only_when_supplied() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @EmptyState {
+        @ { "Nothing here" }
+        @EmptyDescription(Label::new("Pull to refresh."))
+      }
+    });
+    wnd.draw_frame();
+    assert!(wnd.semantics_tree().find(Role::Button).is_none());
+    assert_eq!(labels(&wnd), ["Nothing here", "Pull to refresh."]);
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      let retry = @Container {
+        size: Size::new(80., 30.),
+        semantics: Semantics::new(Role::Button).with_label("Retry"),
+      };
+      @EmptyState {
+        @ { "Nothing here" }
+        @EmptyDescription(Label::new("Pull to refresh."))
+        @EmptyAction(retry.into_widget())
+      }
+    });
+    wnd.draw_frame();
+    assert!(wnd.semantics_tree().find(Role::Button).is_some());
+    assert_eq!(labels(&wnd), ["Nothing here", "Pull to refresh.", "Retry"]);
+  }
+}
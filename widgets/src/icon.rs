@@ -1,6 +1,10 @@
 use std::cell::Cell;
 
-use ribir_core::{impl_compose_child_for_wrap_render, prelude::*, wrap_render::WrapRender};
+use ribir_core::{
+  impl_compose_child_for_wrap_render,
+  prelude::{log::warn, *},
+  wrap_render::WrapRender,
+};
 
 /// An widget represents an icon.
 ///
@@ -45,6 +49,9 @@ use ribir_core::{impl_compose_child_for_wrap_render, prelude::*, wrap_render::Wr
 /// };
 /// ```
 ///
+/// To use an icon of a font other than the theme icon font, use
+/// [`Icon::from_font`].
+///
 /// To specify the icon size, you can use the `text_line_height` property.
 ///
 /// ```
@@ -72,7 +79,9 @@ impl<'c> ComposeChild<'c> for Icon {
   type Child = IconChild<'c>;
   fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     let child = match child {
-      IconChild::FontIcon(text) => IconText.with_child(text! { text }).into_widget(),
+      IconChild::FontIcon(text) => IconText { face: None }
+        .with_child(text! { text })
+        .into_widget(),
       IconChild::Widget(child) => child,
     };
 
@@ -82,12 +91,58 @@ impl<'c> ComposeChild<'c> for Icon {
   }
 }
 
-struct IconText;
+impl Icon {
+  /// Create an icon that displays a glyph of the icon font `face`, the `icon`
+  /// is the codepoint or the ligature name of the glyph.
+  ///
+  /// The `icon` should be shaped to a single glyph, otherwise a warning is
+  /// logged, this usually means the font doesn't have a ligature for the name.
+  ///
+  /// ```no_run
+  /// use ribir_core::prelude::*;
+  /// use ribir_widgets::prelude::*;
+  ///
+  /// let face =
+  ///   FontFace { families: Box::new([FontFamily::Name("Material Icons".into())]), ..<_>::default() };
+  /// // By the codepoint of the icon.
+  /// let _search = Icon::from_font("\u{e8b6}", face.clone());
+  /// // By the ligature name of the icon.
+  /// let _search = Icon::from_font("search", face);
+  /// ```
+  pub fn from_font(icon: impl Into<CowArc<str>>, face: FontFace) -> Widget<'static> {
+    let icon = icon.into();
+    let glyphs = font_icon_glyph_count(&icon, &face);
+    if glyphs != 1 {
+      let icon: &str = &icon;
+      warn!("The font icon `{icon}` is shaped to {glyphs} glyphs rather than a single glyph.");
+    }
+
+    let text = IconText { face: Some(face) }.with_child(text! { text: icon });
+    IconRender { scale: Cell::new(0.) }
+      .with_child(text)
+      .into_widget()
+  }
+}
+
+fn font_icon_glyph_count(icon: &CowArc<str>, face: &FontFace) -> usize {
+  let style = TextStyle { font_face: face.clone(), ..<_>::default() };
+  text_glyph(icon.substr(..), &style, TextAlign::Start, Size::splat(f32::INFINITY))
+    .glyphs()
+    .count()
+}
+
+struct IconText {
+  /// The font of the icon, use the `IconFont` of the theme if it's `None`.
+  face: Option<FontFace>,
+}
 impl_compose_child_for_wrap_render!(IconText, DirtyPhase::Layout);
 
 impl WrapRender for IconText {
   fn perform_layout(&self, clamp: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
-    let font_face = Provider::of::<IconFont>(&ctx).unwrap().0.clone();
+    let font_face = match &self.face {
+      Some(face) => face.clone(),
+      None => Provider::of::<IconFont>(&ctx).unwrap().0.clone(),
+    };
     let mut style = Provider::of::<TextStyle>(ctx).unwrap().clone();
     style.font_face = font_face;
     style.font_size = style.line_height;
@@ -185,6 +240,34 @@ mod tests {
     .with_comparison(0.002)
   );
 
+  #[test]
+  fn font_icon_by_codepoint() {
+    reset_test_env!();
+
+    let path = env!("CARGO_MANIFEST_DIR").to_owned() + "/../fonts/DejaVuSans.ttf";
+    let _ = AppCtx::font_db()
+      .borrow_mut()
+      .load_font_file(path);
+    let face =
+      FontFace { families: Box::new([FontFamily::Name("DejaVu Sans".into())]), ..<_>::default() };
+
+    // The black star.
+    let star: CowArc<str> = "\u{2605}".into();
+    assert_eq!(font_icon_glyph_count(&star, &face), 1);
+    assert_eq!(font_icon_glyph_count(&"ab".into(), &face), 2);
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      let icon = FatObj::new(Icon::from_font(star.clone(), face.clone()));
+      @ $icon { text_line_height: 32. }
+    });
+    wnd.draw_frame();
+
+    wnd.assert_root_size(Size::splat(32.));
+    // The glyph is laid out with the font size of the icon size.
+    let text = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(text.size.unwrap().height, 32.);
+  }
+
   widget_image_tests!(
     keep_icon_visual,
     WidgetTester::new(container! {
@@ -0,0 +1,188 @@
+//! A vertical scroll view that asks for more content when it's scrolled near
+//! the end, to implement the infinite scrolling of a paged feed.
+//!
+//! The `on_reach_end` handler is called once the distance to the end is not
+//! greater than the `threshold`. It's not called again for the same content,
+//! only after the content size is changed, usually by appending the new items,
+//! or after the view is scrolled away from the end and back.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   let items = Stateful::new(20);
+//!   let writer = items.clone_writer();
+//!   @InfiniteScroll {
+//!     threshold: 200.,
+//!     on_reach_end: move || *writer.write() += 20,
+//!     @Column {
+//!       @ {
+//!         pipe!(*$items).map(|cnt| {
+//!           (0..cnt).map(|i| @Text { text: i.to_string() }).collect::<Vec<_>>()
+//!         })
+//!       }
+//!     }
+//!   }
+//! };
+//! ```
+use ribir_core::prelude::*;
+
+/// A vertical scroll view calls `on_reach_end` when it's scrolled near the
+/// end.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct InfiniteScroll {
+  /// The distance to the end that the `on_reach_end` handler is called within.
+  #[declare(default = 100.)]
+  pub threshold: f32,
+  /// The handler called when the view is scrolled near the end.
+  #[declare(custom, default)]
+  pub on_reach_end: Option<Box<dyn FnMut()>>,
+  /// The content height that the `on_reach_end` was called with, if the view
+  /// is still near the end.
+  #[declare(skip)]
+  reached: Option<f32>,
+}
+
+pub trait InfiniteScrollDeclarerExtend {
+  /// Initialize the handler called when the view is scrolled near the end.
+  fn on_reach_end(self, f: impl FnMut() + 'static) -> Self;
+}
+
+impl InfiniteScrollDeclarerExtend for FatObj<InfiniteScrollDeclarer> {
+  fn on_reach_end(mut self, f: impl FnMut() + 'static) -> Self {
+    let f: Box<dyn FnMut()> = Box::new(f);
+    self.on_reach_end = Some(DeclareInit::Value(Some(f)));
+    self
+  }
+}
+
+impl InfiniteScroll {
+  /// Update the state with the distance to the end and the content height,
+  /// return if the `on_reach_end` should be called.
+  fn reach_end(&mut self, distance: f32, content_height: f32) -> bool {
+    if distance > self.threshold {
+      self.reached = None;
+      false
+    } else if self.reached != Some(content_height) {
+      self.reached = Some(content_height);
+      true
+    } else {
+      false
+    }
+  }
+}
+
+impl<'c> ComposeChild<'c> for InfiniteScroll {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let u = watch! {
+      let scroll = $scroll;
+      (
+        scroll.get_scroll_pos().y,
+        scroll.max_scrollable().y,
+        scroll.scroll_view_size().height,
+        scroll.scroll_content_size().height,
+      )
+    }
+    .distinct_until_changed()
+    // The view is not laid out yet.
+    .filter(|(_, _, view_height, _)| *view_height > 0.)
+    .subscribe(move |(pos, max, _, content_height)| {
+      if !this.silent().reach_end(max - pos, content_height) {
+        return;
+      }
+      // Take the handler out, so it can access the scroll view.
+      let handler = this.silent().on_reach_end.take();
+      if let Some(mut handler) = handler {
+        handler();
+        this
+          .silent()
+          .on_reach_end
+          .get_or_insert(handler);
+      }
+    });
+
+    let scroll = FatObj::new(scroll);
+    rdl! {
+      @ $scroll {
+        on_disposed: move |_| u.unsubscribe(),
+        @ { child }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+  use crate::prelude::*;
+
+  #[test]
+  fn reach_end_once() {
+    reset_test_env!();
+
+    let (reached, w_reached) = split_value(0);
+    let (items, w_items) = split_value(10);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let w_reached = w_reached.clone_writer();
+        @InfiniteScroll {
+          threshold: 50.,
+          on_reach_end: move || *w_reached.write() += 1,
+          @Column {
+            @ {
+              pipe!(*$items).map(|cnt| {
+                (0..cnt).map(|_| @Container { size: Size::new(100., 100.) }).collect::<Vec<_>>()
+              })
+            }
+          }
+        }
+      },
+      Size::new(100., 200.),
+    );
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 0);
+
+    // The content is 1000 high and the view is 200, so the end is at 800.
+    wnd.cursor_move(Point::new(50., 50.));
+    wnd.wheel(Vector::new(0., -700.));
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 0);
+
+    wnd.wheel(Vector::new(0., -60.));
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 1);
+
+    // Keep scrolling near the end doesn't fire again.
+    wnd.wheel(Vector::new(0., -20.));
+    wnd.draw_frame();
+    wnd.wheel(Vector::new(0., -20.));
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 1);
+
+    // Scroll away and back.
+    wnd.wheel(Vector::new(0., 200.));
+    wnd.draw_frame();
+    wnd.wheel(Vector::new(0., -200.));
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 2);
+
+    // Fire again after the items are appended and scrolled to the new end.
+    *w_items.write() += 10;
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 2);
+    wnd.wheel(Vector::new(0., -1000.));
+    wnd.draw_frame();
+    assert_eq!(*reached.read(), 3);
+  }
+}
@@ -198,7 +198,10 @@ impl Compose for TextArea {
 #[cfg(test)]
 mod tests {
   use ribir_core::{prelude::*, reset_test_env, test_helper::*};
-  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+  use winit::{
+    event::{DeviceId, ElementState, MouseButton, WindowEvent},
+    keyboard::ModifiersState,
+  };
 
   use super::*;
 
@@ -253,4 +256,38 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*value.read(), "hello");
   }
+
+  #[test]
+  fn input_bidi_arrow_keys() {
+    reset_test_env!();
+    let (selection, w_selection) = split_value(0..0);
+    let w = fn_widget! {
+      let input = @Input { auto_focus: true };
+      $input.write().set_text("abcעברית");
+      $input.write().select(2, 2);
+      watch!($input.selection())
+        .subscribe(move |rg| *$w_selection.write() = rg);
+      input
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    let press = |wnd: &mut TestWindow, code: KeyCode, key: NamedKey| {
+      wnd.key_press(PhysicalKey::Code(code), VirtualKey::Named(key));
+      wnd.draw_frame();
+    };
+
+    // The arrow key moves the caret to the visual neighbor, the last hebrew
+    // letter.
+    press(&mut wnd, KeyCode::ArrowRight, NamedKey::ArrowRight);
+    assert_eq!(*selection.read(), 11..11);
+
+    press(&mut wnd, KeyCode::ArrowLeft, NamedKey::ArrowLeft);
+    assert_eq!(*selection.read(), 2..2);
+
+    // The selection extends to the logical neighbor, the first hebrew letter.
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(ModifiersState::SHIFT.into()));
+    press(&mut wnd, KeyCode::ArrowRight, NamedKey::ArrowRight);
+    assert_eq!(*selection.read(), 2..3);
+  }
 }
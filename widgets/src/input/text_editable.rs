@@ -73,7 +73,8 @@ impl<T: EditText + 'static> BasicEditor<T> {
             if let Some(mut scrollable) = Provider::write_of::<ScrollableWidget>(e) {
               let wnd = e.window();
               let lt = scrollable.map_to_content(Point::zero(), e.current_target(), &wnd).unwrap();
-              scrollable.visible_content_box(Rect::new(lt, caret_size), Anchor::default());
+              let base = MeasureBase::of(&*e, &wnd);
+              scrollable.visible_content_box(Rect::new(lt, caret_size), Anchor::default(), &base);
             }
           }
           let pos = e.map_to_global(Point::zero());
@@ -123,6 +124,14 @@ impl<T: EditText + 'static> BasicEditor<T> {
     if !deal {
       deal = self.edit_with_key(event);
     }
+    // The focus is on the editor, so the key events never reach its text, the
+    // editor moves the caret for it.
+    if !deal {
+      if let Some(selection) = self.host.select_with_key(event) {
+        self.host.selection = selection;
+        deal = true;
+      }
+    }
     deal
   }
 
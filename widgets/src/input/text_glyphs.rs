@@ -98,10 +98,22 @@ pub trait VisualGlyphsHelper {
 
   fn cluster_from_glyph_position(&self, row: usize, col: usize) -> usize;
 
+  /// Move the caret to its visual left neighbor, the caret moves across the
+  /// bidi runs by their visual order.
   fn prev(&self, caret: CaretPosition) -> CaretPosition;
 
+  /// Move the caret to its visual right neighbor, the caret moves across the
+  /// bidi runs by their visual order.
   fn next(&self, caret: CaretPosition) -> CaretPosition;
 
+  /// Move the caret to the previous grapheme in the logical order of the text,
+  /// regardless of the bidi runs.
+  fn logical_prev(&self, caret: CaretPosition, text: &impl BaseText) -> CaretPosition;
+
+  /// Move the caret to the next grapheme in the logical order of the text,
+  /// regardless of the bidi runs.
+  fn logical_next(&self, caret: CaretPosition, text: &impl BaseText) -> CaretPosition;
+
   fn up(&self, caret: CaretPosition) -> CaretPosition;
 
   fn down(&self, caret: CaretPosition) -> CaretPosition;
@@ -165,6 +177,16 @@ impl VisualGlyphsHelper for VisualGlyphs {
     CaretPosition { cluster, position: Some((row, col)) }
   }
 
+  fn logical_prev(&self, caret: CaretPosition, text: &impl BaseText) -> CaretPosition {
+    let cluster = caret.cluster - text.measure_bytes(caret.cluster, -1);
+    CaretPosition { cluster, position: None }
+  }
+
+  fn logical_next(&self, caret: CaretPosition, text: &impl BaseText) -> CaretPosition {
+    let cluster = caret.cluster + text.measure_bytes(caret.cluster, 1);
+    CaretPosition { cluster, position: None }
+  }
+
   fn up(&self, caret: CaretPosition) -> CaretPosition {
     let (mut row, mut col) = self.caret_position(caret);
 
@@ -244,6 +266,8 @@ mod tests {
       letter_space: 0.,
       line_height: 16.,
       overflow: TextOverflow::AutoWrap,
+      first_line_indent: Em(0.),
+      hanging_indent: Em(0.),
     };
     let text: CowArc<str> = "1 23 456 7890\n12345".into();
     let glyphs = store.typography(
@@ -275,4 +299,49 @@ mod tests {
     caret = glyphs.up(caret);
     assert!(caret == CaretPosition { cluster: 0, position: Some((0, 0)) });
   }
+
+  #[test]
+  fn bidi_caret_move() {
+    let mut store = test_store();
+
+    let style = TextStyle {
+      font_size: 16.,
+      font_face: FontFace {
+        families: Box::new([FontFamily::Name("DejaVu Sans".into())]),
+        ..<_>::default()
+      },
+      letter_space: 0.,
+      line_height: 16.,
+      overflow: TextOverflow::Overflow,
+      first_line_indent: Em(0.),
+      hanging_indent: Em(0.),
+    };
+    let text: CowArc<str> = "abcעברית".into();
+    let glyphs = store.typography(
+      text.substr(..),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      font_db::GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+    // The hebrew run is placed in the reverse order.
+    let clusters: Vec<_> = glyphs.glyphs().map(|g| g.cluster).collect();
+    assert_eq!(clusters, [0, 1, 2, 11, 9, 7, 5, 3]);
+
+    // Move across the LTR/RTL boundary.
+    let caret = CaretPosition { cluster: 2, position: None };
+    let visual = glyphs.next(caret);
+    let logical = glyphs.logical_next(caret, &text);
+    // The visual neighbor of `c` is the last hebrew letter.
+    assert_eq!(visual, CaretPosition { cluster: 11, position: Some((0, 3)) });
+    // The logical neighbor of `c` is the first hebrew letter.
+    assert_eq!(logical, CaretPosition { cluster: 3, position: None });
+
+    let visual = glyphs.next(visual);
+    assert_eq!(visual, CaretPosition { cluster: 9, position: Some((0, 4)) });
+    let logical = glyphs.logical_next(logical, &text);
+    assert_eq!(logical.cluster, 5);
+    assert_eq!(glyphs.logical_prev(logical, &text).cluster, 3);
+  }
 }
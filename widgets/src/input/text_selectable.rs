@@ -155,13 +155,15 @@ impl Selection {
 }
 
 impl<T: BaseText> TextSelectable<T> {
-  fn select_with_key(&self, event: &KeyboardEvent) -> Option<Selection> {
+  pub(crate) fn select_with_key(&self, event: &KeyboardEvent) -> Option<Selection> {
     if let Ok(selection) = self.deal_with_command(event) {
       return selection;
     }
     let glyphs = self.glyphs()?;
     let cur_sel = &self.selection;
     let text = &self.text;
+    // The caret moves by the visual order, but the selection extends by the
+    // logical order, so it grows one grapheme at a time across the bidi runs.
     let new_caret = match event.key() {
       VirtualKey::Named(NamedKey::ArrowLeft) => {
         if is_move_by_word(event) {
@@ -172,6 +174,8 @@ impl<T: BaseText> TextSelectable<T> {
           CaretPosition { cluster: rg.start, position: None }
         } else if event.with_command_key() {
           glyphs.line_begin(cur_sel.to)
+        } else if event.with_shift_key() {
+          glyphs.logical_prev(cur_sel.to, text.text())
         } else {
           glyphs.prev(cur_sel.to)
         }
@@ -185,6 +189,8 @@ impl<T: BaseText> TextSelectable<T> {
           CaretPosition { cluster: rg.end, position: None }
         } else if event.with_command_key() {
           glyphs.line_end(cur_sel.to)
+        } else if event.with_shift_key() {
+          glyphs.logical_next(cur_sel.to, text.text())
         } else {
           glyphs.next(cur_sel.to)
         }
@@ -210,6 +210,13 @@ impl FlexLayouter {
       self.flex_children_layout(flex_main, child_clamp, ctx);
     }
 
+    if self.is_baseline_align() {
+      self
+        .lines
+        .iter_mut()
+        .for_each(MainLineInfo::align_baseline);
+    }
+
     // cross direction need calculate cross_axis_gap but last line don't need.
     let cross = self
       .lines
@@ -265,7 +272,8 @@ impl FlexLayouter {
           self.current_line.has_flex = true;
           self.has_flex = true;
         });
-      let info = FlexLayoutInfo { flex, pos: <_>::default(), size };
+      let baseline = ctx.widget_baseline(c);
+      let info = FlexLayoutInfo { flex, pos: <_>::default(), size, baseline };
       self.current_line.items_info.push(info);
     }
 
@@ -289,6 +297,7 @@ impl FlexLayouter {
           };
           let size = ctx.perform_child_layout(child, clamp);
           info.size = FlexSize::from_size(size, dir);
+          info.baseline = ctx.widget_baseline(child);
           line.main_width += info.size.main;
           line.cross_line_height = line.cross_line_height.max(info.size.cross);
         }
@@ -297,6 +306,7 @@ impl FlexLayouter {
   }
 
   fn update_children_position(&mut self, bound: FlexSize, ctx: &mut LayoutCtx) {
+    let is_baseline_align = self.is_baseline_align();
     let Self { reverse, dir, align_items, justify_content, lines, .. } = self;
 
     let cross_size = lines.iter().map(|l| l.cross_line_height).sum();
@@ -311,8 +321,11 @@ impl FlexLayouter {
         lines.iter_mut()$(.$rev())?.for_each(|line| {
           let (mut main, step) = line.place_args(bound.main, *justify_content, self.main_axis_gap);
           line.items_info.iter_mut()$(.$rev())?.for_each(|item| {
-            let item_cross_offset =
-              align_items.align_value(item.size.cross, line.cross_line_height);
+            let item_cross_offset = if is_baseline_align {
+              item.baseline.map_or(0., |b| line.baseline - b)
+            } else {
+              align_items.align_value(item.size.cross, line.cross_line_height)
+            };
 
             item.pos.cross = cross + item_cross_offset + self.cross_axis_gap;
             item.pos.main = main;
@@ -338,6 +351,10 @@ impl FlexLayouter {
     });
   }
 
+  fn is_baseline_align(&self) -> bool {
+    self.align_items == Align::Baseline && self.dir == Direction::Horizontal
+  }
+
   fn place_line(&mut self) {
     if !self.current_line.is_empty() {
       self
@@ -352,6 +369,8 @@ struct MainLineInfo {
   main_width: f32,
   items_info: Vec<FlexLayoutInfo>,
   cross_line_height: f32,
+  /// The baseline of the line, only used when the items align by baseline.
+  baseline: f32,
   has_flex: bool,
 }
 
@@ -359,11 +378,26 @@ struct FlexLayoutInfo {
   pos: FlexSize,
   size: FlexSize,
   flex: Option<f32>,
+  baseline: Option<f32>,
 }
 
 impl MainLineInfo {
   fn is_empty(&self) -> bool { self.items_info.is_empty() }
 
+  /// Place the baselines of the items at the same position, and the items
+  /// without baseline are aligned to the start of the line.
+  fn align_baseline(&mut self) {
+    let items = &self.items_info;
+    self.baseline = items
+      .iter()
+      .filter_map(|i| i.baseline)
+      .fold(0., f32::max);
+    self.cross_line_height = items.iter().fold(0., |height, i| {
+      let offset = i.baseline.map_or(0., |b| self.baseline - b);
+      height.max(offset + i.size.cross)
+    });
+  }
+
   fn calc_flex_unit_and_remove_useless_flex(&mut self, max: f32) -> Option<f32> {
     if !self.has_flex || self.main_width >= max {
       return None;
@@ -623,6 +657,39 @@ mod tests {
     LayoutCase::new(&[0, 2]).with_rect(ribir_geom::rect(200., 0., 100., 40.))
   );
 
+  #[test]
+  fn baseline_cross_align() {
+    reset_test_env!();
+
+    let small = Stateful::new(Text::new::<0>("Small"));
+    let large = Stateful::new(Text::new::<0>("Large"));
+    let (c_small, c_large) = (small.clone_writer(), large.clone_writer());
+    let mut wnd = TestWindow::new(fn_widget! {
+      let small = FatObj::new(c_small.clone_writer());
+      let large = FatObj::new(c_large.clone_writer());
+      @Row {
+        align_items: Align::Baseline,
+        @ $small { font_size: 12., text_line_height: 16. }
+        @ $large { font_size: 24., text_line_height: 32. }
+      }
+    });
+    wnd.draw_frame();
+
+    let baseline = |path, text: &Stateful<Text>| {
+      let y = wnd.layout_info_by_path(path).unwrap().pos.y;
+      y + text
+        .read()
+        .glyphs()
+        .unwrap()
+        .first_baseline()
+        .unwrap()
+    };
+    let small_baseline = baseline(&[0, 0], &small);
+    let large_baseline = baseline(&[0, 1], &large);
+    assert!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos.y > 0.);
+    assert_eq!(small_baseline, large_baseline);
+  }
+
   fn main_align(justify_content: JustifyContent) -> WidgetTester {
     WidgetTester::new(fn_widget! {
       let item_size = Size::new(100., 20.);
@@ -1,29 +1,41 @@
 pub mod avatar;
 pub mod buttons;
 pub mod checkbox;
+pub mod chip;
 pub mod common_widget;
 pub mod divider;
+pub mod empty_state;
 pub mod grid_view;
 pub mod icon;
+pub mod infinite_scroll;
 pub mod input;
 pub mod label;
 pub mod layout;
 pub mod link;
 pub mod lists;
+pub mod marquee;
+pub mod page_cache;
+pub mod pagination;
 pub mod path;
 pub mod progress;
+pub mod pull_to_refresh;
 pub mod radio;
 pub mod scrollbar;
 pub mod select_region;
+pub mod skeleton;
 pub mod slider;
+pub mod stepper;
 pub mod tabs;
 pub mod text_field;
 
 pub mod transform_box;
+pub mod window_controls;
 pub mod prelude {
   pub use super::{
-    avatar::*, buttons::*, checkbox::*, common_widget::*, divider::*, grid_view::*, icon::*,
-    input::*, label::*, layout::*, link::*, lists::*, path::*, progress::*, radio::*, scrollbar::*,
-    select_region::*, slider::*, tabs::*, text_field::*, transform_box::*,
+    avatar::*, buttons::*, checkbox::*, chip::*, common_widget::*, divider::*, empty_state::*,
+    grid_view::*, icon::*, infinite_scroll::*, input::*, label::*, layout::*, link::*, lists::*,
+    marquee::*, page_cache::*, pagination::*, path::*, progress::*, pull_to_refresh::*, radio::*,
+    scrollbar::*, select_region::*, skeleton::*, slider::*, stepper::*, tabs::*, text_field::*,
+    transform_box::*, window_controls::*,
   };
 }
@@ -69,6 +69,7 @@ use crate::prelude::*;
 ///           )
 ///         )
 ///       )
+///       @HeadlineText(Label::new("headline text"))
 ///     }
 ///   }
 /// };
@@ -0,0 +1,205 @@
+//! A single line text that scrolls horizontally when it's wider than the space
+//! it's given, such as a ticker or a long title in a status bar.
+//!
+//! The text scrolls continuously at the `speed` and starts over after a `gap`,
+//! a text that fits the space is shown as it is and doesn't scroll.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   @Marquee {
+//!     speed: 60.,
+//!     pause_on_hover: true,
+//!     @ { "Breaking news: the marquee scrolls the text that doesn't fit." }
+//!   }
+//! };
+//! ```
+use ribir_core::{prelude::*, ticker::FrameMsg};
+
+use crate::prelude::*;
+
+/// A text scrolls horizontally when it's wider than the space.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct Marquee {
+  /// The scrolling speed in pixels per second.
+  #[declare(default = 30.)]
+  pub speed: f32,
+  /// The space between the end of the text and the start of the text that
+  /// scrolls in after it.
+  #[declare(default = 48.)]
+  pub gap: f32,
+  /// Pause the scrolling while the pointer hovers the marquee.
+  #[declare(default)]
+  pub pause_on_hover: bool,
+  /// If the text is scrolling now.
+  #[declare(skip)]
+  running: bool,
+  /// The time of the last frame the text scrolled at.
+  #[declare(skip)]
+  last_tick: Option<Instant>,
+}
+
+impl Marquee {
+  /// Return the new offset of the text after it scrolled for a frame at `at`,
+  /// wrap around after the text and the gap are scrolled out.
+  fn advance(&mut self, offset: f32, text_width: f32, at: Instant) -> f32 {
+    let Some(last) = self.last_tick.replace(at) else { return offset };
+    let step = self.speed * at.duration_since(last).as_secs_f32();
+    (offset + step) % (text_width + self.gap)
+  }
+}
+
+/// Return if the text is wider than the view, `false` before the view is laid
+/// out.
+fn overflow_of(text_width: f32, scroll: &ScrollableWidget) -> bool {
+  let view_width = scroll.scroll_view_size().width;
+  view_width > 0. && text_width > view_width
+}
+
+impl<'c> ComposeChild<'c> for Marquee {
+  type Child = TextInit;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, text: Self::Child) -> Widget<'c> {
+    let mut scroll = ScrollableWidget::default();
+    scroll.scrollable = Scrollable::X;
+    let scroll = Stateful::new(scroll);
+    let text_width = Stateful::new(0f32);
+    let reset = scroll.clone_writer();
+    let tick = scroll.clone_writer();
+    let mut view = FatObj::new(scroll.clone_writer());
+
+    rdl! {
+      let wnd = BuildCtx::get().window();
+      let c_wnd = wnd.clone();
+      let running = watch! {
+        let scroll_view = $scroll;
+        let overflow = overflow_of(*$text_width, &scroll_view);
+        (overflow, $this.pause_on_hover && $view.is_hover())
+      }
+      .distinct_until_changed()
+      .subscribe(move |(overflow, paused)| {
+        let run = overflow && !paused;
+        let mut this = $this.silent();
+        if this.running != run {
+          this.running = run;
+          this.last_tick = None;
+          if run { c_wnd.inc_running_animate() } else { c_wnd.dec_running_animate() }
+        }
+        if !overflow {
+          reset.write().jump_to(Point::zero());
+        }
+      });
+
+      let ticks = wnd
+        .frame_tick_stream()
+        .subscribe(move |msg| {
+          let FrameMsg::BeforeLayout(at) = msg else { return };
+          if !$this.running {
+            return;
+          }
+          let offset = tick.read().get_scroll_pos().x;
+          let offset = $this.silent().advance(offset, *$text_width, at);
+          tick.write().jump_to(Point::new(offset, 0.));
+        });
+
+      let text = @Text {
+        text,
+        on_performed_layout: move |e| {
+          let width = e.box_size().unwrap_or_default().width;
+          if *$text_width != width {
+            *$text_width.write() = width;
+          }
+        },
+      };
+      // The copy of the text scrolls in after the text, so it starts over
+      // seamlessly.
+      let copy = distinct_pipe!(overflow_of(*$text_width, &$scroll)).map(move |o| {
+        o.then(|| @Text {
+          text: pipe!($text.text.clone()),
+          margin: EdgeInsets::only_left($this.gap),
+        })
+      });
+
+      @ $view {
+        on_disposed: move |_| {
+          running.unsubscribe();
+          ticks.unsubscribe();
+          if $this.running {
+            wnd.dec_running_animate();
+          }
+        },
+        @Row {
+          @ { text }
+          @ { copy }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn text_x(wnd: &TestWindow) -> f32 {
+    let tree = wnd.semantics_tree();
+    let text = tree.find(Role::Text).unwrap();
+    wnd.map_to_global(Point::zero(), text.id).x
+  }
+
+  fn frames(wnd: &mut TestWindow, count: usize) {
+    for _ in 0..count {
+      std::thread::sleep(Duration::from_millis(10));
+      wnd.draw_frame();
+    }
+  }
+
+  #[test]
+  fn scroll_overflow_text() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Marquee {
+          speed: 100.,
+          @ { "A text too long to fit the narrow window." }
+        }
+      },
+      Size::new(60., 30.),
+    );
+    frames(&mut wnd, 3);
+    let x = text_x(&wnd);
+    frames(&mut wnd, 3);
+    let x2 = text_x(&wnd);
+    assert!(x2 < x, "{x2} should be less than {x}");
+    frames(&mut wnd, 3);
+    assert!(text_x(&wnd) < x2);
+  }
+
+  #[test]
+  fn fitting_text_not_scroll() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @Marquee {
+          speed: 100.,
+          @ { "Fit" }
+        }
+      },
+      Size::new(200., 30.),
+    );
+    frames(&mut wnd, 6);
+    assert_eq!(text_x(&wnd), 0.);
+    assert_eq!(wnd.semantics_tree().children.len(), 1);
+  }
+}
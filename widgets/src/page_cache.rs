@@ -0,0 +1,148 @@
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+/// A widget shows one of its pages, and keeps the recently shown pages alive
+/// but hidden, so switching back to them doesn't rebuild the page and keeps
+/// its state, like the scroll position.
+///
+/// At most `size` pages are kept alive, including the showing one. When the
+/// limit is exceeded, the least recently shown page is disposed, and it's built
+/// again if it shows later.
+///
+/// The hidden pages stay in the tree with `visible: false` rather than being
+/// kept by [`KeepAlive`], because `KeepAlive` only delays dropping a disposed
+/// widget, for a leave animation, and a disposed widget can't be put back in
+/// the tree.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let cache = @PageCache { size: 2usize };
+///   @Column {
+///     @FilledButton {
+///       on_tap: move |_| {
+///         let next = ($cache.cur + 1) % 3;
+///         $cache.write().cur = next;
+///       },
+///       @{ "Next" }
+///     }
+///     @ $cache {
+///       @ { GenWidget::new(|| @Text { text: "Page 1" }.into_widget()) }
+///       @ { GenWidget::new(|| @Text { text: "Page 2" }.into_widget()) }
+///       @ { GenWidget::new(|| @Text { text: "Page 3" }.into_widget()) }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare, Clone)]
+pub struct PageCache {
+  /// The index of the showing page.
+  #[declare(default)]
+  pub cur: usize,
+  /// The max count of the pages kept alive, including the showing one.
+  #[declare(default = 1usize)]
+  pub size: usize,
+}
+
+impl ComposeChild<'static> for PageCache {
+  type Child = Vec<GenWidget>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    fn_widget! {
+      // The alive pages, the most recently shown one is the last.
+      let cur = $this.cur;
+      let alive = Stateful::new(vec![cur]);
+      let u = watch!(($this.cur, $this.size)).subscribe(move |(cur, size)| {
+        let mut alive = $alive.write();
+        alive.retain(|idx| *idx != cur);
+        alive.push(cur);
+        let evicted = alive.len().saturating_sub(size.max(1));
+        alive.drain(..evicted);
+      });
+
+      let pages = child.into_iter().enumerate().map(move |(idx, page)| {
+        Memo::new(pipe!($alive.contains(&idx)), move |is_alive: &bool| {
+          if *is_alive {
+            FatObj::new(page.gen_widget())
+              .visible(pipe!($this.cur == idx))
+              .into_widget()
+          } else {
+            Void.into_widget()
+          }
+        })
+      });
+
+      @Stack {
+        fit: StackFit::Passthrough,
+        on_disposed: move |_| u.unsubscribe(),
+        @ { pages }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn evict_least_recently_used() {
+    reset_test_env!();
+
+    let (built, w_built) = split_value(vec![]);
+    let (disposed, w_disposed) = split_value(vec![]);
+    let cache = Stateful::new(PageCache { cur: 0, size: 2 });
+    let c_cache = cache.clone_writer();
+    let page = move |idx: usize| {
+      let w_built = w_built.clone_writer();
+      let w_disposed = w_disposed.clone_writer();
+      GenWidget::new(move || {
+        w_built.write().push(idx);
+        let w_disposed = w_disposed.clone_writer();
+        FatObj::new(MockBox { size: Size::new(10., 10.) })
+          .on_disposed(move |_| w_disposed.write().push(idx))
+          .into_widget()
+      })
+    };
+    let mut wnd = TestWindow::new(fn_widget! {
+      let cache = c_cache.clone_writer();
+      @ $cache {
+        @ { page(0) }
+        @ { page(1) }
+        @ { page(2) }
+      }
+    });
+    wnd.draw_frame();
+    assert_eq!(&*built.read(), &[0]);
+
+    cache.write().cur = 1;
+    wnd.draw_frame();
+    assert_eq!(&*built.read(), &[0, 1]);
+    assert!(disposed.read().is_empty());
+
+    // The third page evicts the least recently used one.
+    cache.write().cur = 2;
+    wnd.draw_frame();
+    assert_eq!(&*built.read(), &[0, 1, 2]);
+    assert_eq!(&*disposed.read(), &[0]);
+
+    // Returning to a cached page reuses it.
+    cache.write().cur = 1;
+    wnd.draw_frame();
+    assert_eq!(&*built.read(), &[0, 1, 2]);
+    assert_eq!(&*disposed.read(), &[0]);
+
+    cache.write().cur = 0;
+    wnd.draw_frame();
+    assert_eq!(&*built.read(), &[0, 1, 2, 0]);
+    assert_eq!(&*disposed.read(), &[0, 2]);
+  }
+}
@@ -0,0 +1,332 @@
+//! A pagination control to navigate the pages of the paged data.
+//!
+//! It shows the buttons to the first, the previous, the next and the last page,
+//! and the buttons of the pages around the current page. The pages far from the
+//! current page are collapsed to an ellipsis, for example, `1 … 9 10 11 … 20`.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   @Pagination {
+//!     total: 20usize,
+//!     on_page_change: move |page| println!("load the page {page}."),
+//!   }
+//! };
+//! ```
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the whole pagination"]
+  PAGINATION,
+  #[doc = "Class name for the button of a page"]
+  PAGINATION_ITEM,
+  #[doc = "Class name for the button of the current page"]
+  PAGINATION_ITEM_ACTIVE,
+  #[doc = "Class name for the ellipsis of the collapsed pages"]
+  PAGINATION_ELLIPSIS,
+  #[doc = "Class name for the first, previous, next and last buttons"]
+  PAGINATION_CONTROL,
+  #[doc = "Class name for the control button that can't be used"]
+  PAGINATION_CONTROL_DISABLED,
+}
+
+/// A control to navigate the pages.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct Pagination {
+  /// The current page, starts from 1.
+  #[declare(default = 1usize)]
+  pub page: usize,
+  /// The count of the pages.
+  pub total: usize,
+  /// The handler called with the new page when the page is changed by the
+  /// control.
+  #[declare(custom, default)]
+  pub on_page_change: Option<Box<dyn FnMut(usize)>>,
+}
+
+pub trait PaginationDeclarerExtend {
+  /// Initialize the handler called when the page is changed by the control.
+  fn on_page_change(self, f: impl FnMut(usize) + 'static) -> Self;
+}
+
+impl PaginationDeclarerExtend for FatObj<PaginationDeclarer> {
+  fn on_page_change(mut self, f: impl FnMut(usize) + 'static) -> Self {
+    let f: Box<dyn FnMut(usize)> = Box::new(f);
+    self.on_page_change = Some(DeclareInit::Value(Some(f)));
+    self
+  }
+}
+
+/// An item shown between the previous and the next buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageItem {
+  Page(usize),
+  /// The collapsed pages.
+  Ellipsis,
+}
+
+impl Pagination {
+  /// Return the items to show, the first page, the last page and the pages
+  /// next to the current page are always shown, a gap of more than one page
+  /// between them is collapsed to an ellipsis.
+  pub fn items(&self) -> Vec<PageItem> {
+    let total = self.total;
+    if total == 0 {
+      return vec![];
+    }
+    let page = self.page.clamp(1, total);
+    let mut pages = vec![1, page.saturating_sub(1).max(1), page, (page + 1).min(total), total];
+    pages.sort_unstable();
+    pages.dedup();
+
+    let mut items = vec![];
+    let mut last = 0;
+    for p in pages {
+      match p - last {
+        1 => {}
+        2 => items.push(PageItem::Page(p - 1)),
+        _ if last > 0 => items.push(PageItem::Ellipsis),
+        _ => {}
+      }
+      items.push(PageItem::Page(p));
+      last = p;
+    }
+    items
+  }
+
+  /// Return if there is a previous page.
+  pub fn has_prev(&self) -> bool { self.page > 1 && self.total > 1 }
+
+  /// Return if there is a next page.
+  pub fn has_next(&self) -> bool { self.page < self.total }
+}
+
+/// Change the page and call the `on_page_change` handler, do nothing if the
+/// page is out of range or not changed.
+fn change_page(this: &impl StateWriter<Value = Pagination>, page: usize) {
+  let Pagination { page: current, total, .. } = *this.read();
+  if page == current || page == 0 || page > total {
+    return;
+  }
+  this.write().page = page;
+
+  // Take the handler out, so it can access the pagination.
+  let handler = this.silent().on_page_change.take();
+  if let Some(mut handler) = handler {
+    handler(page);
+    this
+      .silent()
+      .on_page_change
+      .get_or_insert(handler);
+  }
+}
+
+fn control(
+  this: impl StateWriter<Value = Pagination>, icon: &'static str, label: &'static str,
+  enabled: fn(&Pagination) -> bool, target: fn(&Pagination) -> usize,
+) -> Widget<'static> {
+  let writer = this.clone_writer();
+  text! {
+    text: icon,
+    cursor: CursorIcon::Pointer,
+    class: distinct_pipe! {
+      if enabled(&$this) { PAGINATION_CONTROL } else { PAGINATION_CONTROL_DISABLED }
+    },
+    semantics: distinct_pipe!(enabled(&$this)).map(move |enabled| {
+      Semantics::new(Role::Button)
+        .with_label(label)
+        .with_disabled(!enabled)
+    }),
+    on_tap: move |_| {
+      let page = enabled(&writer.read()).then(|| target(&writer.read()));
+      if let Some(page) = page {
+        change_page(&writer, page);
+      }
+    },
+  }
+  .into_widget()
+}
+
+fn page_item(this: impl StateWriter<Value = Pagination>, item: PageItem) -> Widget<'static> {
+  match item {
+    PageItem::Page(page) => {
+      let label: CowArc<str> = page.to_string().into();
+      text! {
+        text: label.clone(),
+        cursor: CursorIcon::Pointer,
+        class: distinct_pipe! {
+          if $this.page == page { PAGINATION_ITEM_ACTIVE } else { PAGINATION_ITEM }
+        },
+        semantics: Semantics::new(Role::Button).with_label(label),
+        on_tap: move |_| change_page(&this, page),
+      }
+      .into_widget()
+    }
+    PageItem::Ellipsis => text! { text: "…", class: PAGINATION_ELLIPSIS }.into_widget(),
+  }
+}
+
+impl Compose for Pagination {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let first = control(this.clone_writer(), "«", "First page", Pagination::has_prev, |_| 1);
+      let prev =
+        control(this.clone_writer(), "‹", "Previous page", Pagination::has_prev, |p| p.page - 1);
+      let next =
+        control(this.clone_writer(), "›", "Next page", Pagination::has_next, |p| p.page + 1);
+      let last = control(this.clone_writer(), "»", "Last page", Pagination::has_next, |p| p.total);
+      let items = pipe!($this.items()).map(move |items| {
+        let this = this.clone_writer();
+        items
+          .into_iter()
+          .map(move |item| page_item(this.clone_writer(), item))
+          .collect::<Vec<_>>()
+      });
+
+      @Row {
+        class: PAGINATION,
+        align_items: Align::Center,
+        @ { first }
+        @ { prev }
+        @ { items }
+        @ { next }
+        @ { last }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use ribir_dev_helper::*;
+
+  use super::*;
+
+  widget_image_tests!(
+    pagination,
+    WidgetTester::new(fn_widget! {
+      @Pagination { page: 10usize, total: 20usize }
+    })
+    .with_wnd_size(Size::new(400., 48.))
+  );
+
+  /// Collect the labels of the items, the texts inside the buttons are
+  /// skipped.
+  fn item_labels(node: &SemanticsNode, labels: &mut Vec<(String, WidgetId)>) {
+    match node.semantics.role {
+      Role::Button | Role::Text => {
+        let label = node
+          .semantics
+          .label
+          .as_deref()
+          .unwrap_or_default();
+        labels.push((label.to_string(), node.id));
+      }
+      _ => node
+        .children
+        .iter()
+        .for_each(|c| item_labels(c, labels)),
+    }
+  }
+
+  fn labels(wnd: &TestWindow) -> Vec<(String, WidgetId)> {
+    let mut labels = vec![];
+    item_labels(&wnd.semantics_tree(), &mut labels);
+    labels
+  }
+
+  #[test]
+  fn items() {
+    let pages = |page, total| Pagination { page, total, on_page_change: None }.items();
+    let p = PageItem::Page;
+    let e = PageItem::Ellipsis;
+
+    assert_eq!(pages(10, 20), vec![p(1), e, p(9), p(10), p(11), e, p(20)]);
+    // The ellipsis only on the far side.
+    assert_eq!(pages(1, 20), vec![p(1), p(2), e, p(20)]);
+    assert_eq!(pages(3, 20), vec![p(1), p(2), p(3), p(4), e, p(20)]);
+    assert_eq!(pages(20, 20), vec![p(1), e, p(19), p(20)]);
+    assert_eq!(pages(1, 1), vec![p(1)]);
+    assert_eq!(pages(1, 0), vec![]);
+  }
+
+  #[test]
+  fn next_page() {
+    reset_test_env!();
+
+    let (changes, w_changes) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      let w_changes = w_changes.clone_writer();
+      @Pagination {
+        page: 10usize,
+        total: 20usize,
+        on_page_change: move |page| w_changes.write().push(page),
+      }
+    });
+    wnd.draw_frame();
+
+    let items = labels(&wnd);
+    let texts: Vec<_> = items.iter().map(|(l, _)| l.as_str()).collect();
+    assert_eq!(texts.join(" "), "First page Previous page 1 … 9 10 11 … 20 Next page Last page");
+
+    let (_, next) = items
+      .iter()
+      .find(|(l, _)| l == "Next page")
+      .unwrap();
+    let size = wnd.widget_size(*next).unwrap();
+    let pos = wnd.map_to_global(Point::new(size.width / 2., size.height / 2.), *next);
+    wnd.tap_at(pos);
+    wnd.draw_frame();
+
+    assert_eq!(*changes.read(), vec![11]);
+    let items = labels(&wnd);
+    let texts: Vec<_> = items.iter().map(|(l, _)| l.as_str()).collect();
+    assert_eq!(texts.join(" "), "First page Previous page 1 … 10 11 12 … 20 Next page Last page");
+  }
+
+  #[test]
+  fn single_page_disabled() {
+    reset_test_env!();
+
+    let (changes, w_changes) = split_value(vec![]);
+    let mut wnd = TestWindow::new(fn_widget! {
+      let w_changes = w_changes.clone_writer();
+      @Pagination {
+        total: 1usize,
+        on_page_change: move |page| w_changes.write().push(page),
+      }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.semantics_tree();
+    let mut buttons = vec![];
+    item_labels(&tree, &mut buttons);
+    assert_eq!(buttons.len(), 5);
+
+    fn find<'a>(node: &'a SemanticsNode, label: &str) -> Option<&'a SemanticsNode> {
+      if node.semantics.label.as_deref() == Some(label) {
+        return Some(node);
+      }
+      node.children.iter().find_map(|c| find(c, label))
+    }
+    for label in ["First page", "Previous page", "Next page", "Last page"] {
+      let node = find(&tree, label).unwrap();
+      assert!(node.semantics.disabled, "{label} should be disabled");
+      let size = wnd.widget_size(node.id).unwrap();
+      let pos = wnd.map_to_global(Point::new(size.width / 2., size.height / 2.), node.id);
+      wnd.tap_at(pos);
+      wnd.draw_frame();
+    }
+    assert!(changes.read().is_empty());
+  }
+}
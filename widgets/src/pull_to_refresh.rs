@@ -0,0 +1,338 @@
+//! A vertical scroll view that can be refreshed by pulling it down from the
+//! top.
+//!
+//! When the view is scrolled to the top, dragging it down pulls the content
+//! down and reveals a spinner above it. Releasing after the pull distance
+//! passes the `threshold` starts a refresh, the `on_refresh` handler is called,
+//! and the spinner keeps spinning until the refresh is completed by
+//! [`PullToRefresh::finish_refresh`]. Releasing before the threshold cancels
+//! the pull without refreshing.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   let loaded = Stateful::new(0);
+//!   let mut refresh = @PullToRefresh {
+//!     // Start to load the new data.
+//!     on_refresh: move || *$loaded.write() += 1,
+//!   };
+//!   // Complete the refresh once the new data is loaded.
+//!   watch!(*$loaded).subscribe(move |_| $refresh.write().finish_refresh());
+//!   @ $refresh {
+//!     @Text { text: pipe!($loaded.to_string()) }
+//!   }
+//! };
+//! ```
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the indicator shown above the pulled content"]
+  PULL_TO_REFRESH_INDICATOR,
+}
+
+/// A vertical scroll view that calls `on_refresh` when it's pulled down from
+/// the top.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct PullToRefresh {
+  /// The pull distance to start a refresh when released.
+  #[declare(default = 64.)]
+  pub threshold: f32,
+  /// The handler called when a refresh starts.
+  #[declare(custom, default)]
+  pub on_refresh: Option<Box<dyn FnMut()>>,
+  #[declare(skip)]
+  pull: f32,
+  #[declare(skip)]
+  refreshing: bool,
+}
+
+pub trait PullToRefreshDeclarerExtend {
+  /// Initialize the handler called when a refresh starts.
+  fn on_refresh(self, f: impl FnMut() + 'static) -> Self;
+}
+
+impl PullToRefreshDeclarerExtend for FatObj<PullToRefreshDeclarer> {
+  fn on_refresh(mut self, f: impl FnMut() + 'static) -> Self {
+    let f: Box<dyn FnMut()> = Box::new(f);
+    self.on_refresh = Some(DeclareInit::Value(Some(f)));
+    self
+  }
+}
+
+impl PullToRefresh {
+  /// Return if a refresh is in progress.
+  pub fn is_refreshing(&self) -> bool { self.refreshing }
+
+  /// The distance of the current pull, zero if it's not pulled.
+  pub fn pull_distance(&self) -> f32 { self.pull }
+
+  /// Complete the refresh in progress, the spinner is hidden and the content
+  /// moves back to the top.
+  pub fn finish_refresh(&mut self) {
+    self.refreshing = false;
+    self.pull = 0.;
+  }
+
+  /// The distance the content is moved down. The content is held at the
+  /// threshold during the refresh, and it moves slower after the pull passes
+  /// the threshold.
+  fn offset(&self) -> f32 {
+    if self.refreshing {
+      self.threshold
+    } else if self.pull > self.threshold {
+      self.threshold + (self.pull - self.threshold) / 2.
+    } else {
+      self.pull
+    }
+  }
+
+  /// The progress of the pull to the threshold, `None` during the refresh.
+  fn progress(&self) -> Option<f32> {
+    (!self.refreshing).then(|| (self.pull / self.threshold).min(1.))
+  }
+}
+
+/// Release the pull, start a refresh if the pull passes the threshold.
+fn release(this: &impl StateWriter<Value = PullToRefresh>) {
+  let refresh = {
+    let mut this = this.write();
+    let refresh = this.pull >= this.threshold;
+    this.pull = 0.;
+    this.refreshing = refresh;
+    refresh
+  };
+  if !refresh {
+    return;
+  }
+
+  // Take the handler out, so it can complete the refresh in place.
+  let handler = this.silent().on_refresh.take();
+  if let Some(mut handler) = handler {
+    handler();
+    this.silent().on_refresh.get_or_insert(handler);
+  }
+}
+
+impl<'c> ComposeChild<'c> for PullToRefresh {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let scroll = Stateful::new(ScrollableWidget::default());
+    // The pointer position where the pull starts.
+    let drag: Stateful<Option<f32>> = Stateful::new(None);
+    let c_scroll = scroll.clone_writer();
+    let writer = this.clone_writer();
+    rdl! {
+      let scroll = FatObj::new(scroll);
+      let indicator = distinct_pipe!($this.pull > 0. || $this.refreshing).map(move |show| {
+        show.then(|| {
+          let mut indicator = @SpinnerProgress {
+            value: distinct_pipe!($this.progress()),
+          };
+          @ $indicator {
+            class: PULL_TO_REFRESH_INDICATOR,
+            h_align: HAlign::Center,
+            semantics: Semantics::new(Role::ProgressBar).with_label("Refresh"),
+            anchor: distinct_pipe!(Anchor::top($this.offset() - $indicator.layout_height())),
+          }
+        })
+      });
+
+      @Stack {
+        fit: StackFit::Passthrough,
+        clip_boundary: true,
+        // Join the gesture arena before the scroll view, so the pull wins the
+        // drag at the top. The pointer is not taken over until it moves
+        // farther than the drag slop, so the content still receives its taps.
+        on_pointer_down_capture: move |e| {
+          let scroll = $c_scroll;
+          let at_top = scroll.get_scroll_pos().y <= 0.;
+          if at_top && !$this.refreshing && e.join_drag_gesture() {
+            *$drag.write() = Some(e.global_pos().y);
+          }
+        },
+        on_pointer_move: move |e| if let Some(start) = *$drag {
+          if e.is_gesture_winner() {
+            let offset = start - e.global_pos().y;
+            $this.write().pull = (-offset).max(0.);
+            // Dragging up from the top scrolls the content as the scroll view.
+            let mut scroll = $c_scroll.write();
+            scroll.jump_to(Point::zero());
+            scroll.scroll(0., offset.max(0.));
+          }
+        },
+        on_pointer_up: move |_| if $drag.write().take().is_some() {
+          release(&writer);
+        },
+        @ $scroll {
+          anchor: distinct_pipe!(Anchor::top($this.offset())),
+          @ { child }
+        }
+        @ { indicator }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn has_spinner(wnd: &TestWindow) -> bool {
+    wnd
+      .semantics_tree()
+      .find(Role::ProgressBar)
+      .is_some()
+  }
+
+  #[test]
+  fn refresh_until_finished() {
+    reset_test_env!();
+    let (refreshed, w_refreshed) = split_value(0);
+    let (refreshing, w_refreshing) = split_value(false);
+    let finished = Stateful::new(false);
+    let c_finished = finished.clone_watcher();
+    let w = fn_widget! {
+      let refresh = @PullToRefresh {
+        threshold: 60.,
+        on_refresh: move || *$w_refreshed.write() += 1,
+      };
+      watch!($refresh.is_refreshing())
+        .subscribe(move |v| *$w_refreshing.write() = v);
+      watch!(*$c_finished)
+        .filter(|finished| *finished)
+        .subscribe(move |_| $refresh.write().finish_refresh());
+      @ $refresh {
+        @Container { size: Size::new(100., 400.) }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 200.));
+    wnd.draw_frame();
+    assert!(!has_spinner(&wnd));
+
+    wnd.drag(Point::new(50., 20.), Point::new(50., 100.));
+    wnd.draw_frame();
+    assert_eq!(*refreshed.read(), 1);
+    assert!(*refreshing.read());
+    assert!(has_spinner(&wnd));
+    // The content is held below the spinner during the refresh.
+    let content = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(content.pos.y, 60.);
+
+    // Can't pull again during the refresh.
+    wnd.drag(Point::new(50., 80.), Point::new(50., 180.));
+    wnd.draw_frame();
+    assert_eq!(*refreshed.read(), 1);
+    assert!(has_spinner(&wnd));
+
+    *finished.write() = true;
+    wnd.draw_frame();
+    assert!(!*refreshing.read());
+    assert!(!has_spinner(&wnd));
+    let content = wnd.layout_info_by_path(&[0, 0]).unwrap();
+    assert_eq!(content.pos.y, 0.);
+  }
+
+  #[test]
+  fn tap_content() {
+    reset_test_env!();
+    let (taps, w_taps) = split_value(0);
+    let (refreshed, w_refreshed) = split_value(0);
+    let w = fn_widget! {
+      @PullToRefresh {
+        on_refresh: move || *$w_refreshed.write() += 1,
+        @Container {
+          size: Size::new(100., 400.),
+          on_tap: move |_| *$w_taps.write() += 1,
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 200.));
+    wnd.draw_frame();
+    wnd
+      .cursor_move(Point::new(50., 20.))
+      .mouse_press()
+      .mouse_release();
+    wnd.draw_frame();
+    assert_eq!(*taps.read(), 1);
+
+    // A small movement within the drag slop is still a tap.
+    wnd.drag(Point::new(50., 20.), Point::new(50., 25.));
+    wnd.draw_frame();
+    assert_eq!(*taps.read(), 2);
+    assert!(!has_spinner(&wnd));
+
+    // A pull cancels the tap.
+    wnd.drag(Point::new(50., 20.), Point::new(50., 100.));
+    wnd.draw_frame();
+    assert_eq!(*taps.read(), 2);
+    assert_eq!(*refreshed.read(), 1);
+  }
+
+  #[test]
+  fn drag_up_scrolls() {
+    reset_test_env!();
+    let (refreshed, w_refreshed) = split_value(0);
+    let w = fn_widget! {
+      @PullToRefresh {
+        on_refresh: move || *$w_refreshed.write() += 1,
+        @Container { size: Size::new(100., 400.) }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 200.));
+    wnd.draw_frame();
+    wnd.drag(Point::new(50., 100.), Point::new(50., 50.));
+    wnd.draw_frame();
+    assert_eq!(*refreshed.read(), 0);
+    assert!(!has_spinner(&wnd));
+    let content = wnd.layout_info_by_path(&[0, 0, 0]).unwrap();
+    assert_eq!(content.pos.y, -50.);
+  }
+
+  #[test]
+  fn release_before_threshold_cancel() {
+    reset_test_env!();
+    let (refreshed, w_refreshed) = split_value(0);
+    let (pull, w_pull) = split_value(0.);
+    let w = fn_widget! {
+      let refresh = @PullToRefresh {
+        threshold: 60.,
+        on_refresh: move || *$w_refreshed.write() += 1,
+      };
+      watch!($refresh.pull_distance()).subscribe(move |v| *$w_pull.write() = v);
+      @ $refresh {
+        @Container { size: Size::new(100., 400.) }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 200.));
+    wnd.draw_frame();
+    wnd
+      .cursor_move(Point::new(50., 20.))
+      .mouse_press()
+      .cursor_move(Point::new(50., 50.));
+    wnd.draw_frame();
+    assert_eq!(*pull.read(), 30.);
+    assert!(has_spinner(&wnd));
+
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(*refreshed.read(), 0);
+    assert_eq!(*pull.read(), 0.);
+    assert!(!has_spinner(&wnd));
+  }
+}
@@ -1,3 +1,5 @@
+use std::{cell::Cell, rc::Rc};
+
 use ribir_core::prelude::*;
 
 use crate::layout::{Stack, StackDeclareExtend, StackFit};
@@ -23,10 +25,54 @@ use crate::layout::{Stack, StackDeclareExtend, StackFit};
 /// when implementing the class name, you can utilize
 /// `Provider::of::<ScrollableWidget>` to retrieve the scroll status and
 /// determine the scrollbar's appearance.
+///
+/// # Minimap
+///
+/// For long documents, the vertical scrollbar can be a minimap instead: a
+/// thin overview of the content with the viewport highlighted, tapping it
+/// jumps to the tapped position. The overview is a widget you provide, it's
+/// laid out at the size of the content scaled down to the width of the
+/// minimap. Its cost grows with what you build, so keep it a downsampled
+/// picture of the content, like a bar for each paragraph, rather than a copy
+/// of the content.
+///
+/// The overview is scaled by at most [`MINIMAP_MAX_SCALE`], if the content is
+/// so long that the scaled overview exceeds the minimap, the overview scrolls
+/// along with the content, so the minimap keeps a readable scale.
+///
+/// ```
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   let overview = GenWidget::new(|| @Void { background: Color::GRAY }.into_widget());
+///   @Scrollbar {
+///     minimap: overview,
+///     @Container { size: Size::new(200., 5000.) }
+///   }
+/// };
+/// ```
+///
+/// # Auto hide
+///
+/// The scrollbars are always overlaid on the content, they don't occupy the
+/// layout space. Use [`Scrollbar::with_auto_hide`] or the `auto_hide` field
+/// to show the scrollbars only when scrolling or hovering them, and fade them
+/// out after the given duration of inactivity.
 pub struct Scrollbar {
   scroll: Stateful<ScrollableWidget>,
+  minimap: Option<GenWidget>,
+  auto_hide: Option<AutoHide>,
 }
 
+/// The duration of the scrollbars fading in or out when they auto hide.
+const FADE_DURATION: Duration = Duration::from_millis(150);
+
+/// The width of the minimap of the scrollbar.
+pub const MINIMAP_WIDTH: f32 = 64.;
+/// The max scale of the overview in the minimap.
+pub const MINIMAP_MAX_SCALE: f32 = 0.2;
+
 class_names! {
   #[doc = "Class name for the thumb of the horizontal scrollbar"]
   H_SCROLL_THUMB,
@@ -37,7 +83,11 @@ class_names! {
   #[doc = "Class name for the track of the vertical scrollbar"]
   V_SCROLL_TRACK,
   #[doc = "Class name for the scrollable widget of the scrollbar"]
-  SCROLL_CLIENT_AREA
+  SCROLL_CLIENT_AREA,
+  #[doc = "Class name for the minimap of the scrollbar"]
+  SCROLL_MINIMAP,
+  #[doc = "Class name for the viewport highlighted in the minimap"]
+  SCROLL_MINIMAP_VIEWPORT
 }
 
 /// Macro used to generate a function widget using `Scrollbar` as the root
@@ -52,7 +102,30 @@ impl Scrollbar {
   pub fn new(scrollable: Scrollable) -> Self {
     let mut inner = ScrollableWidget::default();
     inner.scrollable = scrollable;
-    Self { scroll: Stateful::new(inner) }
+    Self { scroll: Stateful::new(inner), minimap: None, auto_hide: None }
+  }
+
+  /// Hide the scrollbars after the `delay` of inactivity, they show again when
+  /// scrolling or hovering them.
+  pub fn with_auto_hide(mut self, delay: Duration) -> Self {
+    self.auto_hide = Some(AutoHide::new(delay));
+    self
+  }
+
+  /// Return if the scrollbars are visible, it's always `true` if they don't
+  /// auto hide.
+  pub fn is_bar_visible(&self) -> bool {
+    self
+      .auto_hide
+      .as_ref()
+      .is_none_or(|hide| *hide.visible.read())
+  }
+
+  /// Show a minimap with the `overview` of the content as the vertical
+  /// scrollbar.
+  pub fn with_minimap(mut self, overview: impl Into<GenWidget>) -> Self {
+    self.minimap = Some(overview.into());
+    self
   }
 
   /// Return the `ScrollableWidget` of the scrollbar. You can utilize it to
@@ -60,12 +133,37 @@ impl Scrollbar {
   pub fn inner_scrollable_widget(&self) -> &Stateful<ScrollableWidget> { &self.scroll }
 }
 
-pub struct ScrollbarDeclarer;
+#[derive(Default)]
+pub struct ScrollbarDeclarer {
+  minimap: Option<GenWidget>,
+  auto_hide: Option<Duration>,
+}
+
+pub trait ScrollbarDeclareExtend {
+  /// Show a minimap with the `overview` of the content as the vertical
+  /// scrollbar.
+  fn minimap(self, overview: impl Into<GenWidget>) -> Self;
+
+  /// Hide the scrollbars after the `delay` of inactivity.
+  fn auto_hide(self, delay: Duration) -> Self;
+}
+
+impl ScrollbarDeclareExtend for FatObj<ScrollbarDeclarer> {
+  fn minimap(mut self, overview: impl Into<GenWidget>) -> Self {
+    self.minimap = Some(overview.into());
+    self
+  }
+
+  fn auto_hide(mut self, delay: Duration) -> Self {
+    self.auto_hide = Some(delay);
+    self
+  }
+}
 
 impl Declare for Scrollbar {
   type Builder = FatObj<ScrollbarDeclarer>;
 
-  fn declarer() -> Self::Builder { FatObj::new(ScrollbarDeclarer) }
+  fn declarer() -> Self::Builder { FatObj::new(ScrollbarDeclarer::default()) }
 }
 
 impl FatDeclarerExtend for ScrollbarDeclarer {
@@ -77,7 +175,9 @@ impl FatDeclarerExtend for ScrollbarDeclarer {
     } else {
       Stateful::new(ScrollableWidget::default())
     };
-    this.map(|_| Scrollbar { scroll })
+    let minimap = this.minimap.take();
+    let auto_hide = this.auto_hide.take().map(AutoHide::new);
+    this.map(|_| Scrollbar { scroll, minimap, auto_hide })
   }
 }
 
@@ -85,12 +185,15 @@ impl<'c> ComposeChild<'c> for Scrollbar {
   type Child = Widget<'c>;
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     let scroll = this.read().scroll.clone_writer();
+    let minimap = this.read().minimap.clone();
+    let auto_hide = this.read().auto_hide.clone();
     // Here we provide the `ScrollableWidget`, which allows the theme to access
     // scroll states or enables descendants to trigger scrolling to a different
     // position.
     providers! {
       providers: [Provider::value_of_writer(scroll.clone_writer(), None)],
       @ {
+        let c_auto_hide = auto_hide.clone();
         let h_scrollbar = distinct_pipe!($scroll.is_x_scrollable())
           .map(move |need_bar| need_bar.then(||{
             let mut h_track = @Stack {
@@ -122,10 +225,16 @@ impl<'c> ComposeChild<'c> for Scrollbar {
                 }
               }
             }
-          }));
+            .into_widget()
+          })
+          .map(|bar| auto_hide_bar(bar, c_auto_hide.clone())));
 
+        let c_auto_hide = auto_hide.clone();
         let v_scrollbar = distinct_pipe!($scroll.is_y_scrollable())
           .map(move |need_bar| need_bar.then(|| {
+            if let Some(overview) = minimap.clone() {
+              return scroll_minimap(scroll.clone_writer(), overview);
+            }
             let mut v_track = @Stack {
               class: V_SCROLL_TRACK,
               v_align: VAlign::Stretch,
@@ -156,11 +265,25 @@ impl<'c> ComposeChild<'c> for Scrollbar {
                 }
               }
             }
-          }));
+            .into_widget()
+          })
+          .map(|bar| auto_hide_bar(bar, c_auto_hide.clone())));
+
+        // Show the auto hiding scrollbars when scrolling.
+        let u = auto_hide.map(|hide| {
+          watch!({
+            let scroll = $scroll;
+            scroll.get_scroll_pos()
+          })
+          .distinct_until_changed()
+          .skip(1)
+          .subscribe(move |_| hide.show())
+        });
 
         let scroll = FatObj::new(scroll);
         @Stack {
           fit: StackFit::Passthrough,
+          on_disposed: move |_| if let Some(u) = u { u.unsubscribe() },
           @ $scroll {
             class: SCROLL_CLIENT_AREA,
             @{ child }
@@ -188,6 +311,140 @@ impl<'c> ComposeChild<'c> for Scrollbar {
   }
 }
 
+/// The state of the auto hiding scrollbars.
+struct AutoHide {
+  delay: Duration,
+  visible: Stateful<bool>,
+  hovered: Rc<Cell<bool>>,
+  timer: Rc<Cell<Option<BoxSubscription<'static>>>>,
+}
+
+impl Clone for AutoHide {
+  fn clone(&self) -> Self {
+    Self {
+      delay: self.delay,
+      visible: self.visible.clone_writer(),
+      hovered: self.hovered.clone(),
+      timer: self.timer.clone(),
+    }
+  }
+}
+
+impl AutoHide {
+  fn new(delay: Duration) -> Self {
+    Self { delay, visible: Stateful::new(false), hovered: <_>::default(), timer: <_>::default() }
+  }
+
+  /// Show the scrollbars, and hide them after the delay if they are not
+  /// hovered.
+  fn show(&self) {
+    if !*self.visible.read() {
+      *self.visible.write() = true;
+    }
+    if let Some(timer) = self.timer.take() {
+      timer.unsubscribe();
+    }
+    let visible = self.visible.clone_writer();
+    let hovered = self.hovered.clone();
+    let timer = observable::timer((), self.delay, AppCtx::scheduler())
+      .filter(move |_| !hovered.get())
+      .subscribe(move |_| *visible.write() = false);
+    self.timer.set(Some(BoxSubscription::new(timer)));
+  }
+}
+
+fn auto_hide_bar(bar: Widget<'static>, auto_hide: Option<AutoHide>) -> Widget<'static> {
+  let Some(hide) = auto_hide else { return bar };
+  fn_widget! {
+    let visible = hide.visible.clone_watcher();
+    let c_hide = hide.clone();
+    let bar = FatObj::new(bar);
+    let mut bar = @ $bar {
+      opacity: pipe!(if *$visible { 1. } else { 0. }),
+      on_pointer_enter: move |_| {
+        hide.hovered.set(true);
+        hide.show();
+      },
+      on_pointer_leave: move |_| {
+        c_hide.hovered.set(false);
+        c_hide.show();
+      },
+    };
+    part_writer!(&mut bar.opacity)
+      .transition(EasingTransition { easing: easing::LINEAR, duration: FADE_DURATION });
+    bar
+  }
+  .into_widget()
+}
+
+fn scroll_minimap(scroll: Stateful<ScrollableWidget>, overview: GenWidget) -> Widget<'static> {
+  fn_widget! {
+    let mut map = @Stack {
+      class: SCROLL_MINIMAP,
+      h_align: HAlign::Right,
+      v_align: VAlign::Stretch,
+      clamp: BoxClamp::fixed_width(MINIMAP_WIDTH),
+      clip_boundary: true,
+      on_wheel: move |e| $scroll.write().scroll(-e.delta_x, -e.delta_y),
+    };
+    let overview = FatObj::new(overview.gen_widget());
+    let viewport = @Container {
+      class: SCROLL_MINIMAP_VIEWPORT,
+      size: pipe!{
+        let scroll = $scroll;
+        let (scale, _) = minimap_geometry(&scroll, $map.layout_size());
+        scroll.scroll_view_size() * scale
+      },
+      anchor: pipe!{
+        let scroll = $scroll;
+        let (scale, offset) = minimap_geometry(&scroll, $map.layout_size());
+        Anchor::top(scroll.get_scroll_pos().y * scale - offset)
+      },
+    };
+
+    @ $map {
+      on_tap: move |e| if e.is_primary {
+        let (scale, offset) = minimap_geometry(&$scroll, $map.layout_size());
+        if scale > 0. {
+          let mut scroll = $scroll.write();
+          // Center the viewport at the tapped position.
+          let y = (e.position().y + offset) / scale - scroll.scroll_view_size().height / 2.;
+          let x = scroll.get_scroll_pos().x;
+          scroll.jump_to(Point::new(x, y));
+        }
+      },
+      @UnconstrainedBox {
+        dir: UnconstrainedDir::Both,
+        @ $overview {
+          clamp: pipe!{
+            let scroll = $scroll;
+            let (scale, _) = minimap_geometry(&scroll, $map.layout_size());
+            BoxClamp::fixed_size(scroll.scroll_content_size() * scale)
+          },
+          anchor: pipe!{
+            let (_, offset) = minimap_geometry(&$scroll, $map.layout_size());
+            Anchor::top(-offset)
+          },
+        }
+      }
+      @ { viewport }
+    }
+  }
+  .into_widget()
+}
+
+/// Return the scale of the overview in the minimap of `size`, and the offset
+/// the overview scrolls when it's longer than the minimap.
+fn minimap_geometry(s: &ScrollableWidget, size: Size) -> (f32, f32) {
+  let content = s.scroll_content_size();
+  if content.width <= 0. || content.height <= 0. {
+    return (0., 0.);
+  }
+  let scale = (size.width / content.width).min(MINIMAP_MAX_SCALE);
+  let overflow = (content.height * scale - size.height).max(0.);
+  (scale, overflow * s.get_y_scroll_rate())
+}
+
 fn h_thumb_rate(s: &ScrollableWidget) -> f32 {
   s.scroll_view_size().width / s.scroll_content_size().width
 }
@@ -197,7 +454,7 @@ fn v_thumb_rate(s: &ScrollableWidget) -> f32 {
 
 #[cfg(test)]
 mod test {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use super::*;
@@ -223,7 +480,7 @@ mod test {
       let inner2 = inner.clone_writer();
 
       WidgetTester::new(fn_widget! {
-        let scrollbar = Scrollbar { scroll : inner.clone_writer() };
+        let scrollbar = Scrollbar { scroll : inner.clone_writer(), minimap: None, auto_hide: None };
         @ $scrollbar {
           @Container { size: Size::new(500., 500.) }
         }
@@ -238,4 +495,107 @@ mod test {
     },
     LayoutCase::default().with_size(Size::new(100., 100.))
   );
+
+  #[test]
+  fn tap_minimap_to_jump() {
+    reset_test_env!();
+
+    let scroll = Stateful::new(ScrollableWidget::default());
+    let c_scroll = scroll.clone_writer();
+    let content_height = Stateful::new(1000.);
+    let c_content_height = content_height.clone_writer();
+    let overview_size = Rc::new(Cell::new(Size::zero()));
+    let c_overview_size = overview_size.clone();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let c_overview_size = c_overview_size.clone();
+        let overview = GenWidget::new(move || {
+          let c_overview_size = c_overview_size.clone();
+          @Void {
+            on_performed_layout: move |e| c_overview_size.set(e.box_size().unwrap()),
+          }
+          .into_widget()
+        });
+        let scrollbar = Scrollbar {
+          scroll: c_scroll.clone_writer(),
+          minimap: Some(overview),
+          auto_hide: None
+        };
+        @ $scrollbar {
+          @Container { size: pipe!(Size::new(100., *$c_content_height)) }
+        }
+      },
+      Size::new(200., 100.),
+    );
+    // The minimap is generated by the pipe after the content is laid out.
+    wnd.draw_frame();
+    wnd.draw_frame();
+    // The overview is laid out at the downsampled size, not the content size.
+    assert_eq!(overview_size.get(), Size::new(20., 200.));
+
+    // The overview is scaled to 0.2, tapping at 50 centers the viewport at 250.
+    wnd.tap_at(Point::new(190., 50.));
+    wnd.draw_frame();
+    assert_eq!(scroll.read().get_scroll_pos().y, 200.);
+
+    // The overview longer than the minimap scrolls along with the content.
+    wnd.tap_at(Point::new(190., 90.));
+    wnd.draw_frame();
+    let offset = 100. * 200. / 900.;
+    let expect = (90. + offset) / 0.2 - 50.;
+    assert!((scroll.read().get_scroll_pos().y - expect).abs() < 0.01);
+
+    // An extremely long content keeps the scale of the overview.
+    *content_height.write() = 100_000.;
+    scroll.write().jump_to(Point::zero());
+    wnd.draw_frame();
+    wnd.tap_at(Point::new(190., 50.));
+    wnd.draw_frame();
+    assert_eq!(scroll.read().get_scroll_pos().y, 200.);
+  }
+
+  #[test]
+  fn auto_hide() {
+    reset_test_env!();
+
+    let delay = Duration::from_millis(100);
+    let scrollbar = Stateful::new(Scrollbar::new(Scrollable::Y).with_auto_hide(delay));
+    let c_scrollbar = scrollbar.clone_writer();
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        let scrollbar = c_scrollbar.clone_writer();
+        @ $scrollbar {
+          @Container { size: Size::new(100., 1000.) }
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(!scrollbar.read().is_bar_visible());
+
+    // Visible on scroll.
+    wnd.cursor_move(Point::new(50., 50.));
+    wnd.wheel(Vector::new(0., -50.));
+    wnd.draw_frame();
+    assert!(scrollbar.read().is_bar_visible());
+
+    // Fade after the inactivity.
+    std::thread::sleep(delay);
+    wnd.draw_frame();
+    assert!(!scrollbar.read().is_bar_visible());
+
+    // Hovering the scrollbar keeps it visible.
+    wnd.cursor_move(Point::new(2., 50.));
+    wnd.draw_frame();
+    assert!(scrollbar.read().is_bar_visible());
+    std::thread::sleep(delay);
+    wnd.draw_frame();
+    assert!(scrollbar.read().is_bar_visible());
+
+    wnd.cursor_move(Point::new(50., 50.));
+    std::thread::sleep(delay);
+    wnd.draw_frame();
+    assert!(!scrollbar.read().is_bar_visible());
+  }
 }
@@ -0,0 +1,204 @@
+use ribir_core::prelude::{color::LinearGradient, *};
+
+/// A placeholder block to mock the content while it is loading, a highlight
+/// band shimmers across it repeatedly.
+///
+/// The shimmer starts when the skeleton is mounted and stops when it is
+/// disposed, so a skeleton removed from the tree costs nothing.
+///
+/// # Example
+///
+/// ```
+/// # use ribir_core::prelude::*;
+/// # use ribir_widgets::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Row {
+///     @Skeleton { shape: SkeletonShape::Circle }
+///     @Column {
+///       @Skeleton { shape: SkeletonShape::Line }
+///       @Skeleton {
+///         shape: SkeletonShape::Rect,
+///         clamp: BoxClamp::fixed_size(Size::new(120., 60.)),
+///       }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct Skeleton {
+  /// The shape of the placeholder block.
+  #[declare(default)]
+  pub shape: SkeletonShape,
+  /// The base color of the placeholder block.
+  #[declare(default = Palette::of(BuildCtx::get()).surface_container_highest())]
+  pub color: Color,
+  /// The color of the highlight band moving across the block.
+  #[declare(default = Palette::of(BuildCtx::get()).surface_container_low())]
+  pub highlight: Color,
+  /// The time the highlight band takes to move across the block once.
+  #[declare(default = Duration::from_millis(1500))]
+  pub duration: Duration,
+}
+
+/// The shapes of the [`Skeleton`] to mock the different content.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SkeletonShape {
+  /// A rounded bar with the height of a text line, fills the width.
+  #[default]
+  Line,
+  /// A circle, for the avatars and icons.
+  Circle,
+  /// A rounded rectangle, fills the space for the images and cards.
+  Rect,
+}
+
+/// The block paints the skeleton shape, the `shimmer` is the progress of the
+/// highlight band across the block, from 0. to 1.
+#[derive(Declare)]
+struct SkeletonBlock {
+  shape: SkeletonShape,
+  color: Color,
+  highlight: Color,
+  #[declare(default)]
+  shimmer: f32,
+}
+
+const LINE_HEIGHT: f32 = 16.;
+const CIRCLE_SIZE: f32 = 40.;
+const RECT_HEIGHT: f32 = 80.;
+const CORNER_RADIUS: f32 = 4.;
+
+impl Compose for Skeleton {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let block = @SkeletonBlock {
+        shape: pipe!($this.shape),
+        color: pipe!($this.color),
+        highlight: pipe!($this.highlight),
+        shimmer: 1.,
+      };
+      let duration = $this.duration;
+      let animate = @Animate {
+        state: part_writer!(&mut block.shimmer),
+        transition: EasingTransition { easing: easing::LINEAR, duration }
+          .repeat(f32::INFINITY)
+          .box_it(),
+        from: 0.,
+      };
+
+      let animate2 = animate.clone_writer();
+      block
+        .on_mounted(move |_| animate.run())
+        .on_disposed(move |_| animate2.stop())
+    }
+    .into_widget()
+  }
+}
+
+impl Render for SkeletonBlock {
+  fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size {
+    let fill = |max: f32, min: f32| if max.is_finite() { max } else { min };
+    let size = match self.shape {
+      SkeletonShape::Line => Size::new(fill(clamp.max.width, clamp.min.width), LINE_HEIGHT),
+      SkeletonShape::Circle => Size::splat(CIRCLE_SIZE),
+      SkeletonShape::Rect => {
+        Size::new(fill(clamp.max.width, clamp.min.width), fill(clamp.max.height, RECT_HEIGHT))
+      }
+    };
+    clamp.clamp(size)
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    if size.is_empty() {
+      return;
+    }
+
+    // The band starts outside the left edge and ends outside the right edge,
+    // so the block looks the same at the start and the end of each round.
+    let band = size.width / 2.;
+    let x = -band + (size.width + band * 2.) * self.shimmer;
+    let gradient = LinearGradient {
+      start: Point::new(x - band, 0.),
+      end: Point::new(x + band, 0.),
+      stops: vec![
+        GradientStop::new(self.color, 0.),
+        GradientStop::new(self.highlight, 0.5),
+        GradientStop::new(self.color, 1.),
+      ],
+      spread_method: SpreadMethod::Pad,
+    };
+
+    let rect = Rect::from_size(size);
+    let painter = ctx.painter();
+    painter.set_fill_brush(Brush::LinearGradient(gradient));
+    match self.shape {
+      SkeletonShape::Circle => {
+        let radius = size.width.min(size.height) / 2.;
+        painter.circle(rect.center(), radius);
+      }
+      SkeletonShape::Line => {
+        painter.rect_round(&rect, &Radius::all(size.height / 2.));
+      }
+      SkeletonShape::Rect => {
+        painter.rect_round(&rect, &Radius::all(CORNER_RADIUS));
+      }
+    }
+    painter.fill();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use ribir_core::{reset_test_env, test_helper::*, window::WindowFlags};
+
+  use super::*;
+
+  fn gradient_start(wnd: &mut TestWindow) -> Option<Point> {
+    wnd.draw_frame();
+    let frame = wnd.take_last_frame()?;
+    frame.commands.iter().find_map(|cmd| match cmd {
+      PaintCommand::Path(PathCommand {
+        action: PaintPathAction::Paint { brush: CommandBrush::Linear(gradient), .. },
+        ..
+      }) => Some(gradient.start),
+      _ => None,
+    })
+  }
+
+  #[test]
+  fn shimmer_until_disposed() {
+    reset_test_env!();
+
+    let (show, w_show) = split_value(true);
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @pipe! {
+          $show.then(|| @Skeleton {
+            shape: SkeletonShape::Rect,
+            duration: Duration::from_secs(10),
+          })
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    // Rebuild the skeleton to start the shimmer with the animations enabled.
+    *w_show.write() = false;
+    wnd.draw_frame();
+    *w_show.write() = true;
+
+    let first = gradient_start(&mut wnd).unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    let second = gradient_start(&mut wnd).unwrap();
+    assert!(second.x > first.x, "{first:?} -> {second:?}");
+    assert!(wnd.need_draw());
+
+    *w_show.write() = false;
+    wnd.draw_frame();
+    assert!(!wnd.need_draw());
+  }
+}
@@ -0,0 +1,368 @@
+//! A stepper shows the progress through a flow of numbered steps, such as an
+//! onboarding or a checkout.
+//!
+//! The header shows an indicator for every step, connected by lines, and the
+//! content of the active step is shown below it. Tapping an indicator moves to
+//! that step, if the navigation allows it.
+//!
+//! In the linear mode, the steps must be completed in order, you can't skip
+//! ahead of the first uncompleted step, and a step that fails its validation
+//! blocks advancing. In the non-linear mode, you can move to any step.
+//!
+//! ## Usage
+//!
+//! ```
+//! # use ribir_core::prelude::*;
+//! # use ribir_widgets::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   let stepper = @Stepper { linear: true };
+//!   let handle = stepper.clone_writer();
+//!   @Column {
+//!     @ $stepper {
+//!       @Step {
+//!         @ { "Account" }
+//!         @StepContent(fn_widget!{ @Text { text: "Create the account" } }.into())
+//!       }
+//!       @Step {
+//!         @ { "Address" }
+//!         @StepContent(fn_widget!{ @Text { text: "Fill the address" } }.into())
+//!       }
+//!       @Step { @ { "Done" } }
+//!     }
+//!     @TextButton {
+//!       on_tap: move |_| { $handle.write().next_step(); },
+//!       @ { "Next" }
+//!     }
+//!   }
+//! };
+//! ```
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "Class name for the whole stepper"]
+  STEPPER,
+  #[doc = "Class name for the header of the stepper, which contains the indicators"]
+  STEPPER_HEADER,
+  #[doc = "Class name for the indicator of the step that is not reached yet"]
+  STEP_INDICATOR,
+  #[doc = "Class name for the indicator of the active step"]
+  STEP_INDICATOR_ACTIVE,
+  #[doc = "Class name for the indicator of the completed step"]
+  STEP_INDICATOR_COMPLETED,
+  #[doc = "Class name for the title of the step"]
+  STEP_TITLE,
+  #[doc = "Class name for the line connects two steps"]
+  STEP_CONNECTOR,
+  #[doc = "Class name for the line after a completed step"]
+  STEP_CONNECTOR_COMPLETED,
+}
+
+/// A widget that guides through a flow of steps.
+///
+/// See the [module-level documentation](self) for more.
+#[derive(Declare)]
+pub struct Stepper {
+  /// The index of the active step.
+  #[declare(default)]
+  pub active: usize,
+  /// If true, the steps must be completed in order.
+  #[declare(default = true)]
+  pub linear: bool,
+  #[declare(skip)]
+  steps: Vec<StepState>,
+}
+
+/// The status of a step in the [`Stepper`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+  /// The step is not active and not completed.
+  Inactive,
+  /// The step is the active step.
+  Active,
+  /// The step is completed and not active.
+  Completed,
+}
+
+#[derive(Clone, Copy)]
+struct StepState {
+  completed: bool,
+  valid: bool,
+}
+
+impl Default for StepState {
+  fn default() -> Self { Self { completed: false, valid: true } }
+}
+
+/// A step of the [`Stepper`], it has a title and an optional content.
+#[derive(Template)]
+pub struct Step {
+  title: TextInit,
+  content: Option<StepContent>,
+}
+
+/// The content of a step, it's built when the step is active.
+#[derive(ChildOfCompose)]
+pub struct StepContent(pub GenWidget);
+
+impl Stepper {
+  /// The number of the steps.
+  pub fn step_count(&self) -> usize { self.steps.len() }
+
+  /// Return the status of the step at `idx`.
+  pub fn status(&self, idx: usize) -> StepStatus {
+    if idx == self.active {
+      StepStatus::Active
+    } else if self.is_completed(idx) {
+      StepStatus::Completed
+    } else {
+      StepStatus::Inactive
+    }
+  }
+
+  /// Return if the step at `idx` is completed.
+  pub fn is_completed(&self, idx: usize) -> bool {
+    self.steps.get(idx).is_some_and(|s| s.completed)
+  }
+
+  /// Return if the step at `idx` passes its validation, a step is valid by
+  /// default.
+  pub fn is_valid(&self, idx: usize) -> bool { self.steps.get(idx).is_none_or(|s| s.valid) }
+
+  /// Mark if the step at `idx` passes its validation. An invalid step is not
+  /// completed, so it blocks advancing in the linear mode.
+  pub fn set_valid(&mut self, idx: usize, valid: bool) {
+    if let Some(step) = self.steps.get_mut(idx) {
+      step.valid = valid;
+      if !valid {
+        step.completed = false;
+      }
+    }
+  }
+
+  /// Complete the active step and move to the next step. Return false if the
+  /// active step can't be left.
+  ///
+  /// An invalid step isn't completed, and in the linear mode it blocks
+  /// advancing. Completing the last step doesn't move the active index.
+  pub fn next_step(&mut self) -> bool {
+    let active = self.active;
+    let valid = self.is_valid(active);
+    if !valid && self.linear {
+      return false;
+    }
+    if let Some(step) = self.steps.get_mut(active) {
+      step.completed = valid;
+    }
+    if active + 1 < self.step_count() {
+      self.active = active + 1;
+    }
+    true
+  }
+
+  /// Move to the previous step. Return false if the active step is the first
+  /// one.
+  pub fn prev_step(&mut self) -> bool {
+    if self.active == 0 {
+      return false;
+    }
+    self.active -= 1;
+    true
+  }
+
+  /// Move to the step at `idx`. Return false if the step doesn't exist or, in
+  /// the linear mode, any step before it is not completed.
+  pub fn go_to(&mut self, idx: usize) -> bool {
+    if idx >= self.step_count() {
+      return false;
+    }
+    if self.linear && !(0..idx).all(|i| self.is_completed(i)) {
+      return false;
+    }
+    self.active = idx;
+    true
+  }
+
+  fn indicator_class(&self, idx: usize) -> ClassName {
+    match self.status(idx) {
+      StepStatus::Inactive => STEP_INDICATOR,
+      StepStatus::Active => STEP_INDICATOR_ACTIVE,
+      StepStatus::Completed => STEP_INDICATOR_COMPLETED,
+    }
+  }
+
+  fn connector_class(&self, idx: usize) -> ClassName {
+    if self.is_completed(idx) { STEP_CONNECTOR_COMPLETED } else { STEP_CONNECTOR }
+  }
+}
+
+impl ComposeChild<'static> for Stepper {
+  type Child = Vec<Step>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    {
+      let mut this = this.silent();
+      this
+        .steps
+        .resize(child.len(), StepState::default());
+      this.active = this.active.min(child.len().saturating_sub(1));
+    }
+
+    let mut titles = vec![];
+    let mut contents = vec![];
+    for Step { title, content } in child {
+      titles.push(title);
+      contents.push(content.map(|c| c.0));
+    }
+    let cnt = titles.len();
+
+    fn_widget! {
+      let mut header = vec![];
+      for (idx, title) in titles.into_iter().enumerate() {
+        let indicator = @Row {
+          align_items: Align::Center,
+          cursor: CursorIcon::Pointer,
+          on_tap: move |_| { $this.write().go_to(idx); },
+          @Text {
+            class: distinct_pipe!($this.indicator_class(idx)),
+            text: distinct_pipe!($this.status(idx)).map(move |status| {
+              if status == StepStatus::Completed {
+                CowArc::from("✓")
+              } else {
+                CowArc::from((idx + 1).to_string())
+              }
+            }),
+          }
+          @Text { class: STEP_TITLE, text: title }
+        };
+        header.push(indicator.into_widget());
+        if idx + 1 < cnt {
+          let connector = @Expanded {
+            @Container {
+              class: distinct_pipe!($this.connector_class(idx)),
+              size: Size::new(0., 1.),
+            }
+          };
+          header.push(connector.into_widget());
+        }
+      }
+
+      @Column {
+        class: STEPPER,
+        align_items: Align::Stretch,
+        @Row {
+          class: STEPPER_HEADER,
+          align_items: Align::Center,
+          @ { header }
+        }
+        @ {
+          pipe!($this.active).map(move |idx| {
+            contents.get(idx).and_then(|c| c.as_ref()).map(|c| c.gen_widget())
+          })
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use ribir_dev_helper::*;
+
+  use super::*;
+
+  widget_image_tests!(
+    stepper,
+    WidgetTester::new(fn_widget! {
+      @Stepper {
+        active: 1usize,
+        @Step { @ { "Account" } }
+        @Step { @ { "Address" } }
+        @Step { @ { "Done" } }
+      }
+    })
+    .with_wnd_size(Size::new(400., 48.))
+  );
+
+  fn stepper(linear: bool) -> (TestWindow, Stateful<Stepper>) {
+    let stepper = Stateful::new(Stepper { active: 0, linear, steps: vec![] });
+    let s2 = stepper.clone_writer();
+    let wnd = TestWindow::new(fn_widget! {
+      let s2 = s2.clone_writer();
+      @ $s2 {
+        @Step { @ { "One" } }
+        @Step { @ { "Two" } }
+        @Step { @ { "Three" } }
+      }
+    });
+    (wnd, stepper)
+  }
+
+  #[test]
+  fn advance_steps() {
+    reset_test_env!();
+
+    let (mut wnd, stepper) = stepper(true);
+    wnd.draw_frame();
+    assert_eq!(stepper.read().step_count(), 3);
+    assert_eq!(stepper.read().status(0), StepStatus::Active);
+
+    assert!(stepper.write().next_step());
+    wnd.draw_frame();
+    assert_eq!(stepper.read().active, 1);
+    assert_eq!(stepper.read().status(0), StepStatus::Completed);
+    assert_eq!(stepper.read().status(2), StepStatus::Inactive);
+
+    assert!(stepper.write().next_step());
+    assert!(stepper.write().next_step());
+    assert_eq!(stepper.read().active, 2);
+    assert!((0..3).all(|i| stepper.read().is_completed(i)));
+
+    assert!(stepper.write().prev_step());
+    assert_eq!(stepper.read().active, 1);
+  }
+
+  #[test]
+  fn linear_blocks_skip() {
+    reset_test_env!();
+
+    let (mut wnd, stepper) = stepper(true);
+    wnd.draw_frame();
+    // Can't skip ahead of the uncompleted step.
+    assert!(!stepper.write().go_to(2));
+    assert_eq!(stepper.read().active, 0);
+
+    // An invalid step blocks advancing.
+    stepper.write().set_valid(0, false);
+    assert!(!stepper.write().next_step());
+    assert_eq!(stepper.read().active, 0);
+    assert!(!stepper.read().is_completed(0));
+
+    stepper.write().set_valid(0, true);
+    assert!(stepper.write().next_step());
+    assert!(stepper.write().go_to(0));
+    assert!(!stepper.write().go_to(2));
+    assert!(stepper.write().go_to(1));
+  }
+
+  #[test]
+  fn non_linear_skip() {
+    reset_test_env!();
+
+    let (mut wnd, stepper) = stepper(false);
+    wnd.draw_frame();
+    assert!(stepper.write().go_to(2));
+    assert_eq!(stepper.read().active, 2);
+    assert!(!stepper.read().is_completed(0));
+
+    stepper.write().set_valid(2, false);
+    assert!(stepper.write().go_to(0));
+    assert!(stepper.write().next_step());
+    assert_eq!(stepper.read().active, 1);
+    assert!(stepper.read().is_completed(0));
+  }
+}
@@ -55,6 +55,10 @@ pub struct Tabs {
   pub pos: Position,
   #[declare(default)]
   pub cur_idx: usize,
+  /// The max count of the panes kept alive, the inactive panes are hidden
+  /// instead of disposed, see [`PageCache`].
+  #[declare(default = 1usize)]
+  pub cache_size: usize,
 }
 
 #[derive(Clone)]
@@ -320,7 +324,11 @@ impl ComposeChild<'static> for Tabs {
           },
           @ { header }
           @Expanded {
-            @ { pipe!($this.cur_idx).map(move |idx| panes[idx].gen_widget()) }
+            @PageCache {
+              cur: pipe!($this.cur_idx),
+              size: pipe!($this.cache_size),
+              @ { panes }
+            }
           }
         }
       }
@@ -0,0 +1,175 @@
+use std::{cell::Cell, rc::Rc};
+
+use ribir_core::prelude::*;
+
+/// A region to drag the window by the primary button, it's used to build the
+/// custom title bar of a window without decorations. Double tapping it toggles
+/// the window between maximized and normal.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let _title_bar = fn_widget! {
+///   @WindowDragArea {
+///     @Row {
+///       @Text { text: "My App" }
+///       @WindowControlButton { control: WindowControl::Minimize, @Text { text: "-" } }
+///       @WindowControlButton { control: WindowControl::Maximize, @Text { text: "□" } }
+///       @WindowControlButton { control: WindowControl::Close, @Text { text: "×" } }
+///     }
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct WindowDragArea {}
+
+/// The actions of the window control buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowControl {
+  Minimize,
+  /// Toggle the window between maximized and normal.
+  Maximize,
+  Close,
+}
+
+/// A widget that controls the window by the `control` action when its child is
+/// tapped.
+#[derive(Declare)]
+pub struct WindowControlButton {
+  pub control: WindowControl,
+}
+
+impl<'c> ComposeChild<'c> for WindowDragArea {
+  type Child = Widget<'c>;
+  fn compose_child(_: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    // Start to drag the window when the pointer moves with the primary button
+    // pressed, not just pressed, so the double tap can be detected.
+    let pressed = Rc::new(Cell::new(false));
+    let c_pressed = pressed.clone();
+    let c_pressed2 = pressed.clone();
+    FatObj::new(child)
+      .on_pointer_down(move |e| {
+        pressed.set(e.mouse_buttons() == MouseButtons::PRIMARY);
+      })
+      .on_pointer_move(move |e| {
+        if c_pressed.get() && e.mouse_buttons() == MouseButtons::PRIMARY {
+          c_pressed.set(false);
+          e.window().drag_window();
+        }
+      })
+      .on_pointer_up(move |_| c_pressed2.set(false))
+      .on_double_tap(|e| {
+        let wnd = e.window();
+        wnd.set_maximized(!wnd.is_maximized());
+      })
+      .into_widget()
+  }
+}
+
+impl<'c> ComposeChild<'c> for WindowControlButton {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    FatObj::new(child)
+      .on_tap(move |e| {
+        let wnd = e.window();
+        match this.read().control {
+          WindowControl::Minimize => {
+            wnd.set_minimized(true);
+          }
+          WindowControl::Maximize => {
+            wnd.set_maximized(!wnd.is_maximized());
+          }
+          WindowControl::Close => wnd.close(),
+        }
+      })
+      .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn shell_wnd<R>(wnd: &TestWindow, f: impl FnOnce(&TestShellWindow) -> R) -> R {
+    let shell_wnd = wnd.shell_wnd().borrow();
+    f(shell_wnd
+      .as_any()
+      .downcast_ref::<TestShellWindow>()
+      .unwrap())
+  }
+
+  #[test]
+  fn drag_window_by_area() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @WindowDragArea {
+        @MockBox { size: Size::new(100., 30.) }
+      }
+    });
+    wnd.draw_frame();
+
+    wnd.drag(Point::new(10., 10.), Point::new(50., 10.));
+    assert_eq!(shell_wnd(&wnd, |w| w.drag_times), 1);
+
+    // A tap without moving doesn't drag the window.
+    wnd.tap_at(Point::new(10., 10.));
+    assert_eq!(shell_wnd(&wnd, |w| w.drag_times), 1);
+
+    // The released press doesn't drag the window when moving in with a press
+    // out of the area.
+    wnd
+      .cursor_move(Point::new(10., 10.))
+      .mouse_press()
+      .mouse_release();
+    wnd.drag(Point::new(200., 10.), Point::new(50., 10.));
+    assert_eq!(shell_wnd(&wnd, |w| w.drag_times), 1);
+
+    // Moving outside the area doesn't drag the window.
+    wnd.cursor_move(Point::new(200., 200.));
+    assert_eq!(shell_wnd(&wnd, |w| w.drag_times), 1);
+  }
+
+  #[test]
+  fn double_tap_toggle_maximize() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @WindowDragArea {
+        @MockBox { size: Size::new(100., 30.) }
+      }
+    });
+    wnd.draw_frame();
+
+    wnd
+      .tap_at(Point::new(10., 10.))
+      .tap_at(Point::new(10., 10.));
+    assert!(wnd.is_maximized());
+    wnd
+      .tap_at(Point::new(10., 10.))
+      .tap_at(Point::new(10., 10.));
+    assert!(!wnd.is_maximized());
+  }
+
+  #[test]
+  fn maximize_button() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @WindowControlButton {
+        control: WindowControl::Maximize,
+        @MockBox { size: Size::new(30., 30.) }
+      }
+    });
+    wnd.draw_frame();
+
+    wnd.tap_at(Point::new(10., 10.));
+    assert!(wnd.is_maximized());
+    wnd.tap_at(Point::new(10., 10.));
+    assert!(!wnd.is_maximized());
+  }
+}